@@ -0,0 +1,132 @@
+//! Benchmarks of the hot paths on a synthetic repository: tag scan, revwalk
+//! and commit analysis.
+//!
+//! The repository has 5,000 commits and 500 tags by default. For a stress run
+//! on a large repository, raise the sizes:
+//!
+//! ```sh
+//! GIT_PUBLISH_BENCH_COMMITS=100000 GIT_PUBLISH_BENCH_TAGS=10000 cargo bench
+//! ```
+
+use std::env;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use git2::{Oid, Repository, Signature};
+use tempfile::TempDir;
+
+use git_publish::config::ConventionalCommitsConfig;
+use git_publish::domain::commit::analyze_version_bump;
+use git_publish::domain::Changelog;
+use git_publish::git_ops::GitRepo;
+
+const MESSAGES: [&str; 5] = [
+    "fix(io): close files on error",
+    "feat(api): add search endpoint (#42)",
+    "chore: bump dependencies",
+    "docs: describe the tag pattern",
+    "refactor(core)!: rename the publish entry point",
+];
+
+fn size(var: &str, default: usize) -> usize {
+    env::var(var)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// A `main` branch of `commits` empty commits, with a `v0.<n>.0` tag on every
+/// `commits / tags`-th one
+fn synthetic_repo(commits: usize, tags: usize) -> TempDir {
+    let dir = TempDir::new().expect("Could not create temp dir");
+    let repo = Repository::init(dir.path()).expect("Could not init git repo");
+    let sig = Signature::now("Bench", "bench@example.com").unwrap();
+    let tree_id = repo.treebuilder(None).unwrap().write().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+
+    let step = (commits / tags.max(1)).max(1);
+    let mut parent: Option<Oid> = None;
+    for i in 0..commits {
+        let parents: Vec<git2::Commit> = parent
+            .map(|oid| repo.find_commit(oid).unwrap())
+            .into_iter()
+            .collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        let message = MESSAGES[i % MESSAGES.len()];
+        let oid = repo
+            .commit(None, &sig, &sig, message, &tree, &parents)
+            .expect("Could not create commit");
+        if i % step == 0 {
+            let object = repo.find_object(oid, None).unwrap();
+            repo.tag_lightweight(&format!("v0.{}.0", i / step + 1), &object, false)
+                .expect("Could not create tag");
+        }
+        parent = Some(oid);
+    }
+
+    repo.reference("refs/heads/main", parent.unwrap(), true, "bench")
+        .unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+    dir
+}
+
+fn hot_paths(c: &mut Criterion) {
+    let commits = size("GIT_PUBLISH_BENCH_COMMITS", 5_000);
+    let tags = size("GIT_PUBLISH_BENCH_TAGS", 500);
+    let dir = synthetic_repo(commits, tags);
+    env::set_current_dir(dir.path()).unwrap();
+    let git_repo = GitRepo::new().unwrap();
+
+    c.bench_function("tag scan", |b| {
+        b.iter(|| {
+            git_repo
+                .get_latest_tag_on_branch("main", Some("v{version}"))
+                .unwrap()
+        })
+    });
+
+    let latest = git_repo
+        .get_latest_tag_on_branch("main", Some("v{version}"))
+        .unwrap();
+    c.bench_function("revwalk since latest tag", |b| {
+        b.iter(|| {
+            git_repo
+                .get_commits_since_tag("main", latest.as_deref())
+                .unwrap()
+        })
+    });
+    c.bench_function("revwalk since first tag", |b| {
+        b.iter(|| {
+            git_repo
+                .get_commits_since_tag("main", Some("v0.1.0"))
+                .unwrap()
+        })
+    });
+    c.bench_function("tag reachability", |b| {
+        b.iter(|| git_repo.is_tag_reachable("main", "v0.1.0").unwrap())
+    });
+
+    let messages: Vec<String> = git_repo
+        .get_commits_since_tag("main", None)
+        .unwrap()
+        .iter()
+        .filter_map(|commit| commit.message().map(str::to_string))
+        .collect();
+    let config = ConventionalCommitsConfig::default();
+    c.bench_function("analysis", |b| {
+        b.iter(|| analyze_version_bump(&messages, &config))
+    });
+    c.bench_function("changelog", |b| {
+        b.iter_batched(
+            || messages.clone(),
+            |messages| Changelog::from_commits(&messages),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20);
+    targets = hot_paths
+}
+criterion_main!(benches);
@@ -0,0 +1,120 @@
+//! Analysis of commit messages given as text, for `git-publish analyze --stdin`.
+//!
+//! Other tools, or tests of a team's commit convention, can feed messages to
+//! the same analyzer a release uses, without a repository.
+
+use serde::Serialize;
+
+use crate::config::ConventionalCommitsConfig;
+use crate::domain::commit::{analyze_version_bump, breaking_commits, explain_commit};
+use crate::domain::VersionBump;
+
+/// Commit messages in `input`: separated by NUL bytes when there is any (so
+/// messages can span several lines, as with `git log -z --format=%B`), one
+/// per line otherwise. Blank entries are dropped.
+pub fn split_messages(input: &str) -> Vec<String> {
+    let separator = if input.contains('\0') { '\0' } else { '\n' };
+    input
+        .split(separator)
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// How one message is classified on its own
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AnalyzedCommit {
+    pub subject: String,
+    pub r#type: String,
+    pub scope: Option<String>,
+    /// Whether the subject has a `type:` or `type(scope):` header
+    pub conventional: bool,
+    pub breaking: bool,
+    /// `None` when the commit doesn't affect the version on its own
+    pub bump: Option<VersionBump>,
+    /// The rule that decided `bump`
+    pub rule: Option<String>,
+}
+
+/// The bump a release of the analyzed messages would get, and why
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MessageAnalysis {
+    /// `None` without any message
+    pub bump: Option<VersionBump>,
+    pub commit_count: usize,
+    /// Subject lines of the breaking commits
+    pub breaking_changes: Vec<String>,
+    pub commits: Vec<AnalyzedCommit>,
+}
+
+impl MessageAnalysis {
+    /// Analyze `messages` as the commits of one release
+    pub fn new(messages: &[String], config: &ConventionalCommitsConfig) -> Self {
+        let commits = messages
+            .iter()
+            .map(|message| {
+                let explanation = explain_commit(message, config);
+                AnalyzedCommit {
+                    subject: message.lines().next().unwrap_or_default().to_string(),
+                    r#type: explanation.parsed.r#type,
+                    scope: explanation.parsed.scope,
+                    conventional: explanation.conventional,
+                    breaking: explanation.parsed.is_breaking_change,
+                    bump: explanation.bump.as_ref().map(|(bump, _)| *bump),
+                    rule: explanation.bump.map(|(_, rule)| rule.to_string()),
+                }
+            })
+            .collect();
+
+        MessageAnalysis {
+            bump: (!messages.is_empty()).then(|| analyze_version_bump(messages, config)),
+            commit_count: messages.len(),
+            breaking_changes: breaking_commits(messages, &config.breaking_change_indicators),
+            commits,
+        }
+    }
+
+    /// Pretty-printed JSON of the analysis
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_messages() {
+        assert_eq!(
+            split_messages("feat: add export\n\nfix: close files\n"),
+            vec!["feat: add export", "fix: close files"]
+        );
+        // NUL-separated messages keep their bodies
+        assert_eq!(
+            split_messages("feat: add export\n\nBREAKING CHANGE: new format\n\0fix: x\n\0"),
+            vec!["feat: add export\n\nBREAKING CHANGE: new format", "fix: x"]
+        );
+    }
+
+    #[test]
+    fn test_analysis_of_messages() {
+        let messages = split_messages("feat(io): add export\0fix: close files\0docs: typo");
+        let analysis = MessageAnalysis::new(&messages, &ConventionalCommitsConfig::default());
+
+        assert_eq!(analysis.bump, Some(VersionBump::Minor));
+        assert_eq!(analysis.commit_count, 3);
+        assert!(analysis.breaking_changes.is_empty());
+        assert_eq!(analysis.commits[0].scope.as_deref(), Some("io"));
+        assert_eq!(analysis.commits[1].bump, Some(VersionBump::Patch));
+        assert_eq!(analysis.commits[2].bump, None);
+
+        let json: serde_json::Value = serde_json::from_str(&analysis.to_json().unwrap()).unwrap();
+        assert_eq!(json["bump"], "minor");
+        assert_eq!(json["commits"][0]["type"], "feat");
+
+        let empty = MessageAnalysis::new(&[], &ConventionalCommitsConfig::default());
+        assert_eq!(empty.bump, None);
+    }
+}
@@ -1,5 +1,7 @@
 //! Analysis engine for determining version bumps from commits
 
+pub mod message_analysis;
 pub mod version_analyzer;
 
+pub use message_analysis::MessageAnalysis;
 pub use version_analyzer::VersionAnalyzer;
@@ -1,5 +1,6 @@
 use crate::config::ConventionalCommitsConfig;
-use crate::domain::{ParsedCommit, VersionBump};
+use crate::domain::commit::analyze_version_bump;
+use crate::domain::VersionBump;
 
 /// Analyzes commits to determine version bump type
 pub struct VersionAnalyzer {
@@ -14,53 +15,7 @@ impl VersionAnalyzer {
 
     /// Analyze commit messages and determine version bump
     pub fn analyze_messages(&self, messages: &[String]) -> VersionBump {
-        let mut has_breaking = false;
-        let mut has_features = false;
-        let mut has_fixes = false;
-
-        for message in messages {
-            let parsed = ParsedCommit::parse(message);
-
-            // Check for breaking changes (highest priority)
-            if parsed.is_breaking_change {
-                has_breaking = true;
-            }
-
-            // Check for major version indicators
-            for keyword in &self.config.major_keywords {
-                if message.to_lowercase().contains(keyword) {
-                    has_features = true;
-                }
-            }
-
-            // Check for minor version indicators
-            for keyword in &self.config.minor_keywords {
-                if message.to_lowercase().contains(keyword) {
-                    has_features = true;
-                }
-            }
-
-            // Check for commit types that might indicate features or fixes
-            match parsed.r#type.as_str() {
-                "feat" | "feature" => has_features = true,
-                "fix" | "perf" | "refactor" => has_fixes = true,
-                _ => {}
-            }
-
-            // If we found a breaking change, we can return early
-            if has_breaking {
-                return VersionBump::Major;
-            }
-        }
-
-        if has_features {
-            VersionBump::Minor
-        } else if has_fixes {
-            VersionBump::Patch
-        } else {
-            // If no conventional commits detected, default to patch
-            VersionBump::Patch
-        }
+        analyze_version_bump(messages, &self.config)
     }
 }
 
@@ -291,4 +246,35 @@ mod tests {
         ];
         assert_eq!(analyzer.analyze_messages(&messages), VersionBump::Patch);
     }
+
+    #[test]
+    fn test_analyze_scope_rules() {
+        let mut config = ConventionalCommitsConfig::default();
+        config.scopes.ignore = vec!["ci".to_string(), "deps".to_string()];
+        config.scopes.major = vec!["api".to_string()];
+        let analyzer = VersionAnalyzer::new(config);
+
+        let messages = vec![
+            "feat(ci): cache the build".to_string(),
+            "fix: close files".to_string(),
+        ];
+        assert_eq!(analyzer.analyze_messages(&messages), VersionBump::Patch);
+
+        let messages = vec!["feat(deps)!: require tokio 2".to_string()];
+        assert_eq!(analyzer.analyze_messages(&messages), VersionBump::Major);
+
+        let messages = vec!["fix(api): rename a field".to_string()];
+        assert_eq!(analyzer.analyze_messages(&messages), VersionBump::Major);
+    }
+
+    #[test]
+    fn test_analyze_semver_trailer_override() {
+        let analyzer = VersionAnalyzer::new(ConventionalCommitsConfig::default());
+
+        let messages = vec![
+            "fix: small fix".to_string(),
+            "feat: internal refactor helper\n\nSemver: patch".to_string(),
+        ];
+        assert_eq!(analyzer.analyze_messages(&messages), VersionBump::Patch);
+    }
 }
@@ -1,5 +1,8 @@
 use std::fmt;
 
+use crate::error::RemoteFailureKind;
+use crate::i18n::{text, tr, Msg};
+
 /// Warnings that occur when processing git tags near repository boundaries.
 /// These are non-fatal issues that should be reported to the user.
 #[derive(Debug, Clone, PartialEq)]
@@ -14,6 +17,128 @@ pub enum BoundaryWarning {
 
     /// Fetch operation failed due to authentication issues
     FetchAuthenticationFailed { remote: String },
+
+    /// Fetch operation failed because the remote host could not be reached
+    FetchNetworkUnreachable { remote: String },
+
+    /// Fetch operation failed because the remote host key could not be verified
+    FetchHostKeyVerificationFailed { remote: String },
+
+    /// The up-front connection probe to the remote failed
+    RemoteConnectionFailed {
+        remote: String,
+        kind: RemoteFailureKind,
+        detail: String,
+    },
+
+    /// The manifest declares a different version than the latest tag
+    ManifestVersionMismatch {
+        manifest: String,
+        manifest_version: String,
+        tag: String,
+        tag_version: String,
+    },
+
+    /// The message of the latest (annotated) tag records a different version
+    /// than its name
+    TagMetadataMismatch {
+        tag: String,
+        tag_version: String,
+        recorded_version: String,
+    },
+
+    /// The latest tag is not in the history of the branch, which has been
+    /// rewritten since the tag was created
+    TagUnreachable { tag: String, branch: String },
+
+    /// More commits since the latest tag than `max_commits_analyzed`; only
+    /// the most recent `limit` are analyzed
+    CommitLimitExceeded { count: usize, limit: usize },
+
+    /// The chosen tag's version is lower than or equal to the current
+    /// version, e.g. a custom `v1.2.3` after `v1.10.0`
+    VersionNotIncreased {
+        tag: String,
+        version: String,
+        current_version: String,
+    },
+
+    /// Commits since the latest tag without an author name or email, as
+    /// left by some bots and squash tools; `commits` holds short hashes
+    AnonymousCommits { count: usize, commits: Vec<String> },
+
+    /// `user.name` or `user.email` is unset, so annotated tags can't record
+    /// a tagger
+    MissingGitIdentity,
+}
+
+impl BoundaryWarning {
+    /// Build the warning matching a classified fetch failure.
+    ///
+    /// Returns `None` for unclassified failures, which are reported as plain status lines.
+    pub fn from_fetch_failure(remote: &str, kind: RemoteFailureKind) -> Option<Self> {
+        let remote = remote.to_string();
+        match kind {
+            RemoteFailureKind::Authentication => {
+                Some(BoundaryWarning::FetchAuthenticationFailed { remote })
+            }
+            RemoteFailureKind::NetworkUnreachable => {
+                Some(BoundaryWarning::FetchNetworkUnreachable { remote })
+            }
+            RemoteFailureKind::HostKeyVerification => {
+                Some(BoundaryWarning::FetchHostKeyVerificationFailed { remote })
+            }
+            RemoteFailureKind::Other => None,
+        }
+    }
+
+    /// Key of the warning in the `[warnings]` configuration table
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            BoundaryWarning::NoNewCommits { .. } => "no_new_commits",
+            BoundaryWarning::UnparsableTag { .. } => "unparsable_tag",
+            BoundaryWarning::FetchAuthenticationFailed { .. } => "fetch_authentication_failed",
+            BoundaryWarning::FetchNetworkUnreachable { .. } => "fetch_network_unreachable",
+            BoundaryWarning::FetchHostKeyVerificationFailed { .. } => {
+                "fetch_host_key_verification_failed"
+            }
+            BoundaryWarning::RemoteConnectionFailed { .. } => "remote_connection_failed",
+            BoundaryWarning::ManifestVersionMismatch { .. } => "manifest_version_mismatch",
+            BoundaryWarning::TagMetadataMismatch { .. } => "tag_metadata_mismatch",
+            BoundaryWarning::TagUnreachable { .. } => "tag_unreachable",
+            BoundaryWarning::CommitLimitExceeded { .. } => "commit_limit_exceeded",
+            BoundaryWarning::VersionNotIncreased { .. } => "version_not_increased",
+            BoundaryWarning::AnonymousCommits { .. } => "anonymous_commits",
+            BoundaryWarning::MissingGitIdentity => "missing_git_identity",
+        }
+    }
+
+    /// Suggested next step for resolving the warning, if any.
+    pub fn remediation_hint(&self) -> Option<&'static str> {
+        match self {
+            BoundaryWarning::FetchAuthenticationFailed { .. } => {
+                Some(text(Msg::HintAuthentication))
+            }
+            BoundaryWarning::FetchNetworkUnreachable { .. } => Some(text(Msg::HintNetwork)),
+            BoundaryWarning::FetchHostKeyVerificationFailed { .. } => Some(text(Msg::HintHostKey)),
+            BoundaryWarning::RemoteConnectionFailed { kind, .. } => match kind {
+                RemoteFailureKind::Authentication => Some(text(Msg::HintAuthentication)),
+                RemoteFailureKind::NetworkUnreachable => Some(text(Msg::HintNetwork)),
+                RemoteFailureKind::HostKeyVerification => Some(text(Msg::HintHostKey)),
+                RemoteFailureKind::Other => None,
+            },
+            BoundaryWarning::ManifestVersionMismatch { .. } => {
+                Some(text(Msg::HintManifestMismatch))
+            }
+            BoundaryWarning::TagMetadataMismatch { .. } => Some(text(Msg::HintTagMetadataMismatch)),
+            BoundaryWarning::TagUnreachable { .. } => Some(text(Msg::HintTagUnreachable)),
+            BoundaryWarning::CommitLimitExceeded { .. } => Some(text(Msg::HintCommitLimit)),
+            BoundaryWarning::VersionNotIncreased { .. } => Some(text(Msg::HintVersionNotIncreased)),
+            BoundaryWarning::AnonymousCommits { .. } => Some(text(Msg::HintAnonymousCommits)),
+            BoundaryWarning::MissingGitIdentity => Some(text(Msg::HintMissingGitIdentity)),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for BoundaryWarning {
@@ -28,21 +153,102 @@ impl fmt::Display for BoundaryWarning {
                 } else {
                     current_commit_hash.as_str()
                 };
-                write!(
-                    f,
-                    "No new commits since tag '{}' (current: {})",
-                    latest_tag, short_hash
-                )
+                let message = tr(
+                    Msg::NoNewCommits,
+                    &[("tag", latest_tag), ("commit", &short_hash)],
+                );
+                write!(f, "{}", message)
             }
             BoundaryWarning::UnparsableTag { tag, reason } => {
-                write!(f, "Cannot parse tag '{}': {}", tag, reason)
+                let message = tr(Msg::UnparsableTag, &[("tag", tag), ("reason", reason)]);
+                write!(f, "{}", message)
             }
             BoundaryWarning::FetchAuthenticationFailed { remote } => {
-                write!(
-                    f,
-                    "Authentication failed when fetching from remote '{}'",
-                    remote
-                )
+                let message = tr(Msg::FetchAuthenticationFailed, &[("remote", remote)]);
+                write!(f, "{}", message)
+            }
+            BoundaryWarning::FetchNetworkUnreachable { remote } => {
+                let message = tr(Msg::FetchNetworkUnreachable, &[("remote", remote)]);
+                write!(f, "{}", message)
+            }
+            BoundaryWarning::FetchHostKeyVerificationFailed { remote } => {
+                let message = tr(Msg::FetchHostKeyVerificationFailed, &[("remote", remote)]);
+                write!(f, "{}", message)
+            }
+            BoundaryWarning::RemoteConnectionFailed { remote, detail, .. } => {
+                let message = tr(
+                    Msg::RemoteConnectionFailed,
+                    &[("remote", remote), ("error", detail)],
+                );
+                write!(f, "{}", message)
+            }
+            BoundaryWarning::ManifestVersionMismatch {
+                manifest,
+                manifest_version,
+                tag,
+                tag_version,
+            } => {
+                let message = tr(
+                    Msg::ManifestVersionMismatch,
+                    &[
+                        ("manifest", manifest),
+                        ("manifest_version", manifest_version),
+                        ("tag", tag),
+                        ("tag_version", tag_version),
+                    ],
+                );
+                write!(f, "{}", message)
+            }
+            BoundaryWarning::TagMetadataMismatch {
+                tag,
+                tag_version,
+                recorded_version,
+            } => {
+                let message = tr(
+                    Msg::TagMetadataMismatch,
+                    &[
+                        ("tag", tag),
+                        ("tag_version", tag_version),
+                        ("recorded_version", recorded_version),
+                    ],
+                );
+                write!(f, "{}", message)
+            }
+            BoundaryWarning::TagUnreachable { tag, branch } => {
+                let message = tr(Msg::TagUnreachable, &[("tag", tag), ("branch", branch)]);
+                write!(f, "{}", message)
+            }
+            BoundaryWarning::CommitLimitExceeded { count, limit } => {
+                let message = tr(
+                    Msg::CommitLimitExceeded,
+                    &[("count", count), ("limit", limit)],
+                );
+                write!(f, "{}", message)
+            }
+            BoundaryWarning::VersionNotIncreased {
+                tag,
+                version,
+                current_version,
+            } => {
+                let message = tr(
+                    Msg::VersionNotIncreased,
+                    &[
+                        ("tag", tag),
+                        ("version", version),
+                        ("current", current_version),
+                    ],
+                );
+                write!(f, "{}", message)
+            }
+            BoundaryWarning::AnonymousCommits { count, commits } => {
+                let message = tr(
+                    Msg::AnonymousCommits,
+                    &[("count", count), ("commits", &commits.join(", "))],
+                );
+                write!(f, "{}", message)
+            }
+            BoundaryWarning::MissingGitIdentity => {
+                write!(f, "{}", text(Msg::MissingGitIdentity))
             }
         }
     }
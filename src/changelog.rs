@@ -0,0 +1,105 @@
+//! The repository's changelog file, updated on every release.
+//!
+//! With `[changelog] file` set (or `--changelog`), the release notes built by
+//! [`crate::domain::Changelog`] are added as a new section at the top of the
+//! file and committed before the tag is created, so the tag includes them.
+//!
+//! Earlier releases are left untouched: the new section goes right above the
+//! first `## ` heading, below any title or introduction.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+
+/// Changelog file written with `--changelog` when `[changelog] file` is unset
+pub const DEFAULT_FILE: &str = "CHANGELOG.md";
+
+/// Title of a newly created changelog file
+const TITLE: &str = "# Changelog";
+
+/// A Markdown changelog file in the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogFile {
+    pub path: PathBuf,
+}
+
+impl ChangelogFile {
+    /// The changelog at `file`, relative to the repository root
+    pub fn in_root(root: &Path, file: &str) -> Self {
+        ChangelogFile {
+            path: root.join(file),
+        }
+    }
+
+    /// Add a release above the previous ones, creating the file if needed
+    pub fn add_release(&self, header: &str, notes: &str) -> Result<()> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        fs::write(&self.path, with_release(&content, header, notes))?;
+        Ok(())
+    }
+}
+
+/// `content` with a release section inserted before the first release
+/// heading, or appended when there is none
+fn with_release(content: &str, header: &str, notes: &str) -> String {
+    let notes = notes.trim_end();
+    let section = if notes.is_empty() {
+        format!("{}\n", header)
+    } else {
+        format!("{}\n\n{}\n", header, notes)
+    };
+
+    if content.trim().is_empty() {
+        return format!("{}\n\n{}", TITLE, section);
+    }
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            return format!("{}{}\n{}", &content[..offset], section, &content[offset..]);
+        }
+        offset += line.len();
+    }
+    format!("{}\n\n{}", content.trim_end(), section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTES: &str = "### Features\n\n- add export\n\n";
+
+    #[test]
+    fn test_release_goes_above_previous_releases() {
+        let content = "# Changelog\n\nAll notable changes.\n\n## v1.2.0 (2024-01-02)\n\n- first\n";
+        assert_eq!(
+            with_release(content, "## v1.3.0 (2024-02-03)", NOTES),
+            "# Changelog\n\nAll notable changes.\n\n\
+             ## v1.3.0 (2024-02-03)\n\n### Features\n\n- add export\n\n\
+             ## v1.2.0 (2024-01-02)\n\n- first\n"
+        );
+        assert_eq!(
+            with_release("# Notes\n", "## v1.3.0", ""),
+            "# Notes\n\n## v1.3.0\n"
+        );
+    }
+
+    #[test]
+    fn test_add_release_creates_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = ChangelogFile::in_root(dir.path(), DEFAULT_FILE);
+        file.add_release("## v1.0.0", NOTES).unwrap();
+        file.add_release("## v1.1.0", "### Bug Fixes\n\n- close files\n\n")
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&file.path).unwrap(),
+            "# Changelog\n\n## v1.1.0\n\n### Bug Fixes\n\n- close files\n\n\
+             ## v1.0.0\n\n### Features\n\n- add export\n"
+        );
+    }
+}
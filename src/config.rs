@@ -1,15 +1,30 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::ops::Deref;
+use std::path::{Component, Path, PathBuf};
+
+use crate::boundary::BoundaryWarning;
+use crate::config_diagnostics::ConfigWarning;
+use crate::domain::{TagPattern, VersionBump};
+use crate::hooks::{HookEnv, HookFailurePolicy, HookType};
+use crate::i18n::Locale;
+use crate::manifest::ManifestKind;
 
 /// Represents the complete configuration for git-publish.
 ///
 /// Contains branch mappings, conventional commit settings, version formatting patterns, and behavior options.
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct Config {
+    /// Format version the configuration was written for (0 when unset);
+    /// older versions are migrated when loaded
+    #[serde(default)]
+    pub config_version: u32,
+
+    /// Branch name → tag pattern, or a table with `pattern` and `hooks`
     #[serde(default)]
-    pub branches: HashMap<String, String>,
+    pub branches: BranchesConfig,
 
     #[serde(default)]
     pub conventional_commits: ConventionalCommitsConfig,
@@ -22,6 +37,159 @@ pub struct Config {
 
     #[serde(default)]
     pub prerelease: PreReleaseConfig,
+
+    /// Push overrides keyed by remote name
+    #[serde(default)]
+    pub remote_overrides: HashMap<String, RemoteOverride>,
+
+    #[serde(default)]
+    pub auth: AuthConfig,
+
+    #[serde(default)]
+    pub hooks: HooksConfig,
+
+    #[serde(default)]
+    pub changelog: ChangelogConfig,
+
+    #[serde(default)]
+    pub tags: TagsConfig,
+
+    /// Commit scope → pre-release identifier (e.g. `experimental = "beta"`);
+    /// commits in these scopes only feed pre-releases
+    #[serde(default)]
+    pub channels: HashMap<String, String>,
+
+    /// Package name → paths and tag pattern, for releasing parts of a
+    /// monorepo on their own with `--package`
+    #[serde(default)]
+    pub packages: HashMap<String, PackageConfig>,
+
+    #[serde(default)]
+    pub warnings: WarningsConfig,
+}
+
+/// Branch-to-tag-pattern mappings with optional per-branch hook overrides.
+///
+/// Each branch is either a pattern string (`main = "v{version}"`) or a table
+/// with a `pattern` and a `hooks` table. Patterns are validated when loaded and
+/// kept in compiled form, see [`BranchesConfig::tag_pattern`]. Derefs to the
+/// branch → pattern string map.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BranchesConfig {
+    patterns: HashMap<String, String>,
+    compiled: HashMap<String, TagPattern>,
+    hooks: HashMap<String, HooksConfig>,
+}
+
+/// A single `[branches]` entry as written in the config file
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+enum BranchEntry {
+    /// Tag pattern with exactly one `{version}` placeholder
+    Pattern(String),
+    Table {
+        /// Tag pattern with exactly one `{version}` placeholder
+        pattern: String,
+        /// Hooks overriding `[hooks]` for this branch
+        #[serde(default)]
+        hooks: HooksConfig,
+    },
+}
+
+impl BranchesConfig {
+    /// Tag `branch` with `pattern`, replacing any previous pattern
+    pub fn insert(&mut self, branch: impl Into<String>, pattern: TagPattern) {
+        let branch = branch.into();
+        self.patterns.insert(branch.clone(), pattern.to_string());
+        self.compiled.insert(branch, pattern);
+    }
+
+    /// Returns the compiled tag pattern of a branch, if it is configured
+    pub fn tag_pattern(&self, branch: &str) -> Option<&TagPattern> {
+        self.compiled.get(branch)
+    }
+
+    /// Returns the hook overrides configured for a branch, if any
+    pub fn hooks(&self, branch: &str) -> Option<&HooksConfig> {
+        self.hooks.get(branch)
+    }
+
+    /// Branches with hook overrides and their hooks
+    pub fn branch_hooks(&self) -> impl Iterator<Item = (&String, &HooksConfig)> {
+        self.hooks.iter()
+    }
+}
+
+impl Deref for BranchesConfig {
+    type Target = HashMap<String, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.patterns
+    }
+}
+
+impl<'de> Deserialize<'de> for BranchesConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut entries: Vec<(String, BranchEntry)> =
+            HashMap::<String, BranchEntry>::deserialize(deserializer)?
+                .into_iter()
+                .collect();
+        // Report the first invalid pattern deterministically
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut branches = BranchesConfig::default();
+        for (branch, entry) in entries {
+            let pattern = match entry {
+                BranchEntry::Pattern(pattern) => pattern,
+                BranchEntry::Table { pattern, hooks } => {
+                    branches.hooks.insert(branch.clone(), hooks);
+                    pattern
+                }
+            };
+            let pattern = TagPattern::parse(&pattern)
+                .map_err(|e| serde::de::Error::custom(invalid_pattern(&branch, e)))?;
+            branches.insert(branch, pattern);
+        }
+        Ok(branches)
+    }
+}
+
+/// Error message for a branch whose pattern doesn't parse
+fn invalid_pattern(branch: &str, error: crate::error::GitPublishError) -> String {
+    match error {
+        crate::error::GitPublishError::Tag(message) => format!("branches.{}: {}", branch, message),
+        error => format!("branches.{}: {}", branch, error),
+    }
+}
+
+impl JsonSchema for BranchesConfig {
+    fn schema_name() -> String {
+        "BranchesConfig".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        HashMap::<String, BranchEntry>::json_schema(gen)
+    }
+}
+
+impl Serialize for BranchesConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let entries: HashMap<&String, BranchEntry> = self
+            .patterns
+            .iter()
+            .map(|(branch, pattern)| {
+                let entry = match self.hooks.get(branch) {
+                    Some(hooks) => BranchEntry::Table {
+                        pattern: pattern.clone(),
+                        hooks: hooks.clone(),
+                    },
+                    None => BranchEntry::Pattern(pattern.clone()),
+                };
+                (branch, entry)
+            })
+            .collect();
+        entries.serialize(serializer)
+    }
 }
 
 /// Returns the default list of conventional commit types.
@@ -40,12 +208,15 @@ fn default_commit_types() -> Vec<String> {
     ]
 }
 
+/// Breaking change indicators used when none are configured
+pub const DEFAULT_BREAKING_CHANGE_INDICATORS: [&str; 2] = ["BREAKING CHANGE:", "BREAKING-CHANGE:"];
+
 /// Returns the default list of breaking change indicators.
 fn default_breaking_change_indicators() -> Vec<String> {
-    vec![
-        "BREAKING CHANGE:".to_string(),
-        "BREAKING-CHANGE:".to_string(),
-    ]
+    DEFAULT_BREAKING_CHANGE_INDICATORS
+        .iter()
+        .map(|indicator| indicator.to_string())
+        .collect()
 }
 
 /// Returns the default list of keywords that trigger major version bumps.
@@ -66,19 +237,123 @@ fn default_minor_keywords() -> Vec<String> {
 ///
 /// Defines the types, breaking change indicators, and keywords used to analyze commits
 /// and determine version bumping strategy.
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct ConventionalCommitsConfig {
+    /// Recognized conventional commit types
     #[serde(default = "default_commit_types")]
     pub types: Vec<String>,
 
+    /// Footers that mark a commit as breaking
     #[serde(default = "default_breaking_change_indicators")]
     pub breaking_change_indicators: Vec<String>,
 
+    /// Keywords in a commit message that trigger a major bump
     #[serde(default = "default_major_keywords")]
     pub major_keywords: Vec<String>,
 
+    /// Keywords in a commit message that trigger a minor bump
     #[serde(default = "default_minor_keywords")]
     pub minor_keywords: Vec<String>,
+
+    /// Flag commits whose subject line is longer than this many characters
+    #[serde(default)]
+    pub max_subject_length: Option<usize>,
+
+    /// Flag commits without a conventional type or with a type not in `types`
+    #[serde(default)]
+    pub flag_missing_type: bool,
+
+    /// Bump called for by a `deprecate:` commit or a `Deprecated:` trailer
+    #[serde(default = "default_deprecation_bump")]
+    pub deprecation_bump: VersionBump,
+
+    /// Match keywords anywhere in the message, also inside other words,
+    /// instead of as whole words in the subject and footer
+    #[serde(default)]
+    pub legacy_keyword_matching: bool,
+
+    /// Bumps called for by commit scopes, such as `feat(ci):` not counting
+    #[serde(default)]
+    pub scopes: ScopeRules,
+}
+
+/// How the scope of a commit affects the bump, under
+/// `[conventional_commits] scopes`
+///
+/// Scopes are compared exactly; a commit with several comma-separated scopes
+/// (`fix(api,cli):`) follows the rules of each.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq, Eq, JsonSchema)]
+pub struct ScopeRules {
+    /// Scopes whose commits don't bump the version, unless breaking or
+    /// overridden with a `Semver:` trailer
+    #[serde(default)]
+    pub ignore: Vec<String>,
+
+    /// Scopes whose commits call for at least a major bump
+    #[serde(default)]
+    pub major: Vec<String>,
+
+    /// Scopes whose commits call for at least a minor bump
+    #[serde(default)]
+    pub minor: Vec<String>,
+
+    /// Scopes whose commits call for at least a patch bump
+    #[serde(default)]
+    pub patch: Vec<String>,
+}
+
+/// What [`ScopeRules`] make of a commit's scope
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScopeRule {
+    /// The commit doesn't bump the version
+    Ignore(String),
+    /// The commit calls for at least this bump
+    Bump(VersionBump, String),
+}
+
+impl ScopeRules {
+    /// The rule for a commit with `scope`, the largest bump when its scopes
+    /// have several; a scope with a bump wins over an ignored one
+    pub fn rule_for(&self, scope: Option<&str>) -> Option<ScopeRule> {
+        let scopes: Vec<&str> = scope?.split(',').map(str::trim).collect();
+        let find = |list: &[String]| {
+            scopes
+                .iter()
+                .find(|scope| list.iter().any(|listed| listed == *scope))
+                .map(|scope| scope.to_string())
+        };
+        [
+            (VersionBump::Major, &self.major),
+            (VersionBump::Minor, &self.minor),
+            (VersionBump::Patch, &self.patch),
+        ]
+        .into_iter()
+        .find_map(|(bump, list)| find(list).map(|scope| ScopeRule::Bump(bump, scope)))
+        .or_else(|| find(&self.ignore).map(ScopeRule::Ignore))
+    }
+
+    /// The first scope listed under more than one rule, with the two rules
+    fn conflict(&self) -> Option<(&str, &'static str, &'static str)> {
+        let lists = [
+            ("ignore", &self.ignore),
+            ("major", &self.major),
+            ("minor", &self.minor),
+            ("patch", &self.patch),
+        ];
+        lists.iter().enumerate().find_map(|(index, (name, list))| {
+            list.iter().find_map(|scope| {
+                lists[index + 1..]
+                    .iter()
+                    .find(|(_, other)| other.contains(scope))
+                    .map(|(other, _)| (scope.as_str(), *name, *other))
+            })
+        })
+    }
+}
+
+/// Returns the default bump for deprecations
+fn default_deprecation_bump() -> VersionBump {
+    VersionBump::Minor
 }
 
 impl Default for ConventionalCommitsConfig {
@@ -88,6 +363,11 @@ impl Default for ConventionalCommitsConfig {
             breaking_change_indicators: default_breaking_change_indicators(),
             major_keywords: default_major_keywords(),
             minor_keywords: default_minor_keywords(),
+            max_subject_length: None,
+            flag_missing_type: false,
+            deprecation_bump: default_deprecation_bump(),
+            legacy_keyword_matching: false,
+            scopes: ScopeRules::default(),
         }
     }
 }
@@ -95,8 +375,9 @@ impl Default for ConventionalCommitsConfig {
 /// Configuration for version formatting patterns.
 ///
 /// Allows customization of how versions are formatted for different bump types.
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct PatternsConfig {
+    /// Version format per bump type (`major`, `minor`, `patch`)
     #[serde(default = "default_version_format")]
     pub version_format: HashMap<String, String>,
 }
@@ -121,16 +402,407 @@ impl Default for PatternsConfig {
 /// Configuration for behavior customization.
 ///
 /// Controls runtime behavior of git-publish without affecting version analysis.
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, JsonSchema)]
 pub struct BehaviorConfig {
+    /// Pick the only remote without prompting
     #[serde(default)]
     pub skip_remote_selection: bool,
+
+    /// How SSH host keys are verified against `~/.ssh/known_hosts`
+    #[serde(default)]
+    pub host_key_checking: HostKeyChecking,
+
+    /// Display language for prompts and messages (e.g. "en", "zh-CN")
+    #[serde(default)]
+    pub language: Option<Locale>,
+
+    /// Print the command line of every hook script before running it
+    #[serde(default)]
+    pub hook_echo: bool,
+
+    /// Keep a tag created by this run when interrupted before it is pushed
+    #[serde(default)]
+    pub keep_tag_on_interrupt: bool,
+
+    /// Give up on a fetch or push after this many seconds without progress
+    #[serde(default)]
+    pub network_timeout_secs: Option<u64>,
+
+    /// Where the current version is read from
+    #[serde(default)]
+    pub version_source: VersionSource,
+
+    /// What to do when the tag to create already exists
+    #[serde(default)]
+    pub on_existing_tag: ExistingTagPolicy,
+
+    /// Command suggested for pushing a tag that was only created locally;
+    /// `{remote}` and `{tag}` are replaced (default: `git push {remote} {tag}`)
+    #[serde(default)]
+    pub manual_push_command: Option<String>,
+
+    /// Reject unknown keys in the configuration instead of warning about them
+    #[serde(default)]
+    pub strict_config: bool,
+
+    /// Read the version and notes recorded in the message of the previous
+    /// release's annotated tag
+    #[serde(default)]
+    pub read_tag_metadata: bool,
+
+    /// Analyze only this many of the most recent commits since the latest
+    /// tag; `0` analyzes all of them
+    #[serde(default = "default_max_commits_analyzed")]
+    pub max_commits_analyzed: usize,
+
+    /// Regular expression every new tag must match on top of the branch's
+    /// tag pattern, e.g. to forbid uppercase or limit the length
+    #[serde(default)]
+    pub tag_validation_regex: Option<String>,
+
+    /// What to do when an edited tag is not newer than the current version
+    #[serde(default)]
+    pub on_version_downgrade: DowngradePolicy,
+
+    /// Paths whose changes never count toward a release, in
+    /// `.gitpublishignore` syntax (e.g. `docs/**`, `*.md`); the file's
+    /// patterns come after these
+    #[serde(default)]
+    pub ignore_paths: Vec<String>,
+}
+
+/// Returns the default cap on analyzed commits
+fn default_max_commits_analyzed() -> usize {
+    5000
+}
+
+impl Default for BehaviorConfig {
+    fn default() -> Self {
+        BehaviorConfig {
+            skip_remote_selection: false,
+            host_key_checking: HostKeyChecking::default(),
+            language: None,
+            hook_echo: false,
+            keep_tag_on_interrupt: false,
+            network_timeout_secs: None,
+            version_source: VersionSource::default(),
+            on_existing_tag: ExistingTagPolicy::default(),
+            manual_push_command: None,
+            strict_config: false,
+            read_tag_metadata: false,
+            max_commits_analyzed: default_max_commits_analyzed(),
+            tag_validation_regex: None,
+            on_version_downgrade: DowngradePolicy::default(),
+            ignore_paths: Vec::new(),
+        }
+    }
+}
+
+/// Default template for [`BehaviorConfig::manual_push_command`]
+pub const DEFAULT_MANUAL_PUSH_COMMAND: &str = "git push {remote} {tag}";
+
+impl BehaviorConfig {
+    /// Render the manual push command for a tag and remote
+    pub fn manual_push_command_for(&self, tag: &str, remote: &str) -> String {
+        self.manual_push_command
+            .as_deref()
+            .unwrap_or(DEFAULT_MANUAL_PUSH_COMMAND)
+            .replace("{remote}", remote)
+            .replace("{tag}", tag)
+    }
+}
+
+/// SSH host key verification mode.
+///
+/// - `ask`: trust known hosts, prompt to accept and save unknown hosts (default)
+/// - `strict`: trust known hosts only, reject unknown hosts (for CI)
+/// - `off`: defer to libgit2's built-in checks
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HostKeyChecking {
+    #[default]
+    Ask,
+    Strict,
+    Off,
+}
+
+/// Source of truth for the current version.
+///
+/// - `tag`: the latest tag on the branch (default)
+/// - `manifest`: the version in `Cargo.toml` or `package.json`, which is bumped
+///   and committed along with the new tag
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionSource {
+    #[default]
+    Tag,
+    Manifest,
+}
+
+/// What to do when the tag to create already exists.
+///
+/// - `abort`: stop with an error (default)
+/// - `skip`: leave the existing tag alone and stop without error
+/// - `increment`: bump the patch version until the tag is free
+/// - `replace`: move the tag to the branch head and force-push it
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExistingTagPolicy {
+    #[default]
+    Abort,
+    Skip,
+    Increment,
+    Replace,
+}
+
+/// What to do when an edited tag's version is lower than or equal to the
+/// current version, e.g. `v1.2.3` after `v1.10.0`.
+///
+/// - `warn`: show a warning and ask before going on (default)
+/// - `block`: stop with an error
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DowngradePolicy {
+    #[default]
+    Warn,
+    Block,
+}
+
+/// Configuration for the release changelog.
+///
+/// Controls how pull request references such as `(#123)` in squash-merge
+/// subjects are linked, and whether the notes are kept in a changelog file.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, JsonSchema)]
+pub struct ChangelogConfig {
+    /// Link pull request references when the remote's web URL is known
+    #[serde(default = "default_pull_request_links")]
+    pub pull_request_links: bool,
+
+    /// Link target; `{repo_url}` is the repository's web URL and `{number}`
+    /// the pull request number
+    #[serde(default = "default_pull_request_url")]
+    pub pull_request_url: String,
+
+    /// How entries are arranged within each section
+    #[serde(default)]
+    pub layout: ChangelogLayout,
+
+    /// What to do with commits whose change was already released from
+    /// another configured branch
+    #[serde(default)]
+    pub cherry_picks: CherryPicks,
+
+    /// Markdown file, relative to the repository root, that gets the release
+    /// notes before tagging; not written when unset
+    #[serde(default)]
+    pub file: Option<String>,
+
+    /// Heading of a release in `file`; `{tag}`, `{version}` and `{date}`
+    /// (YYYY-MM-DD) are replaced
+    #[serde(default = "default_release_header")]
+    pub release_header: String,
+}
+
+/// Arrangement of changelog entries within a section.
+///
+/// - `flat`: one list, scopes shown as a bold prefix (default)
+/// - `scope`: unscoped entries first, then a sub-heading per scope
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangelogLayout {
+    #[default]
+    Flat,
+    Scope,
+}
+
+/// Changelog treatment of cherry-picks: commits with the same patch-id as one
+/// already released by the latest tag of another configured branch.
+///
+/// - `keep`: list them like any other commit, without looking for them (default)
+/// - `annotate`: list them with the tag that released them
+/// - `exclude`: leave them out
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CherryPicks {
+    #[default]
+    Keep,
+    Annotate,
+    Exclude,
+}
+
+/// Returns the default pull request link setting
+fn default_pull_request_links() -> bool {
+    true
+}
+
+/// Returns the default pull request link template
+fn default_pull_request_url() -> String {
+    "{repo_url}/pull/{number}".to_string()
+}
+
+/// Returns the default release heading of the changelog file
+fn default_release_header() -> String {
+    "## {tag} ({date})".to_string()
+}
+
+impl Default for ChangelogConfig {
+    fn default() -> Self {
+        ChangelogConfig {
+            pull_request_links: default_pull_request_links(),
+            pull_request_url: default_pull_request_url(),
+            layout: ChangelogLayout::default(),
+            cherry_picks: CherryPicks::default(),
+            file: None,
+            release_header: default_release_header(),
+        }
+    }
+}
+
+impl ChangelogConfig {
+    /// Link template for pull requests of the repository at `repo_url`, with
+    /// `{number}` left in place; `None` when links are disabled
+    pub fn pull_request_link_template(&self, repo_url: &str) -> Option<String> {
+        self.pull_request_links
+            .then(|| self.pull_request_url.replace("{repo_url}", repo_url))
+    }
+
+    /// Heading of a release in the changelog file
+    pub fn release_header(&self, tag: &str, version: &str, date: &str) -> String {
+        self.release_header
+            .replace("{tag}", tag)
+            .replace("{version}", version)
+            .replace("{date}", date)
+    }
+}
+
+/// How release tags are written.
+///
+/// Tags are lightweight unless `annotated` is set; annotated tags carry a
+/// message summarizing the release, which `read_tag_metadata` reads back.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+pub struct TagsConfig {
+    /// Create annotated tags
+    #[serde(default)]
+    pub annotated: bool,
+
+    /// Sign the tags with GPG as configured for git (`user.signingkey`,
+    /// `gpg.program`); implies `annotated`
+    #[serde(default)]
+    pub signed: bool,
+}
+
+/// A part of a monorepo released on its own with `--package`.
+///
+/// Only commits changing files under `path` count toward its releases, which
+/// are tagged with `tag` instead of the branch's pattern.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, JsonSchema)]
+pub struct PackageConfig {
+    /// Files of the package, in `.gitpublishignore` syntax (e.g. `services/api/**`)
+    pub path: String,
+
+    /// Tag pattern with exactly one `{version}` placeholder (e.g. `api-v{version}`)
+    pub tag: String,
+}
+
+/// What to do about a boundary warning.
+///
+/// - `prompt`: show it and, where there is a question, ask before going on
+///   (default; `--force` and `--dry-run` go on without asking)
+/// - `ignore`: show it and go on without asking
+/// - `fail`: stop with an error, even with `--force`
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WarningPolicy {
+    #[default]
+    Prompt,
+    Ignore,
+    Fail,
+}
+
+/// Policy for each boundary warning, keyed like
+/// [`BoundaryWarning::config_key`].
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+pub struct WarningsConfig {
+    /// No new commits since the latest tag
+    #[serde(default)]
+    pub no_new_commits: WarningPolicy,
+
+    /// The latest tag is not a semantic version
+    #[serde(default)]
+    pub unparsable_tag: WarningPolicy,
+
+    /// Fetching was refused for lack of valid credentials
+    #[serde(default)]
+    pub fetch_authentication_failed: WarningPolicy,
+
+    /// Fetching failed because the remote could not be reached
+    #[serde(default)]
+    pub fetch_network_unreachable: WarningPolicy,
+
+    /// Fetching failed because the remote host key could not be verified
+    #[serde(default)]
+    pub fetch_host_key_verification_failed: WarningPolicy,
+
+    /// The connection check to the remote failed
+    #[serde(default)]
+    pub remote_connection_failed: WarningPolicy,
+
+    /// The manifest's version differs from the latest tag
+    #[serde(default)]
+    pub manifest_version_mismatch: WarningPolicy,
+
+    /// The latest tag's message records another version than its name
+    #[serde(default)]
+    pub tag_metadata_mismatch: WarningPolicy,
+
+    /// The latest tag is not in the branch history
+    #[serde(default)]
+    pub tag_unreachable: WarningPolicy,
+
+    /// More commits than `max_commits_analyzed`
+    #[serde(default)]
+    pub commit_limit_exceeded: WarningPolicy,
+
+    /// The chosen version is not above the current one
+    #[serde(default)]
+    pub version_not_increased: WarningPolicy,
+
+    /// Commits without an author name or email
+    #[serde(default)]
+    pub anonymous_commits: WarningPolicy,
+
+    /// No git identity for the tagger of annotated tags
+    #[serde(default)]
+    pub missing_git_identity: WarningPolicy,
+}
+
+impl WarningsConfig {
+    /// Policy for `warning`
+    pub fn policy(&self, warning: &BoundaryWarning) -> WarningPolicy {
+        match warning {
+            BoundaryWarning::NoNewCommits { .. } => self.no_new_commits,
+            BoundaryWarning::UnparsableTag { .. } => self.unparsable_tag,
+            BoundaryWarning::FetchAuthenticationFailed { .. } => self.fetch_authentication_failed,
+            BoundaryWarning::FetchNetworkUnreachable { .. } => self.fetch_network_unreachable,
+            BoundaryWarning::FetchHostKeyVerificationFailed { .. } => {
+                self.fetch_host_key_verification_failed
+            }
+            BoundaryWarning::RemoteConnectionFailed { .. } => self.remote_connection_failed,
+            BoundaryWarning::ManifestVersionMismatch { .. } => self.manifest_version_mismatch,
+            BoundaryWarning::TagMetadataMismatch { .. } => self.tag_metadata_mismatch,
+            BoundaryWarning::TagUnreachable { .. } => self.tag_unreachable,
+            BoundaryWarning::CommitLimitExceeded { .. } => self.commit_limit_exceeded,
+            BoundaryWarning::VersionNotIncreased { .. } => self.version_not_increased,
+            BoundaryWarning::AnonymousCommits { .. } => self.anonymous_commits,
+            BoundaryWarning::MissingGitIdentity => self.missing_git_identity,
+        }
+    }
 }
 
 /// Configuration for pre-release version handling.
 ///
 /// Controls how pre-release versions (alpha, beta, rc, custom) are managed.
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, JsonSchema)]
 pub struct PreReleaseConfig {
     /// Enable pre-release version support
     #[serde(default)]
@@ -163,151 +835,1222 @@ impl Default for PreReleaseConfig {
             auto_increment: default_prerelease_auto_increment(),
         }
     }
-}
+}
+
+/// Returns the default refspec template used when pushing tags.
+fn default_tag_refspec() -> String {
+    "refs/tags/{tag}:refs/tags/{tag}".to_string()
+}
+
+/// Push overrides for a single remote.
+///
+/// Lets a remote push to a different URL than it fetches from, and push tags
+/// into a custom ref namespace (e.g. `refs/releases/*`) via a refspec template.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, JsonSchema)]
+pub struct RemoteOverride {
+    /// URL to push to instead of the remote's configured URL
+    #[serde(default)]
+    pub push_url: Option<String>,
+
+    /// Refspec template for pushing tags; `{tag}` is replaced with the tag name
+    #[serde(default = "default_tag_refspec")]
+    pub tag_refspec: String,
+}
+
+impl RemoteOverride {
+    /// Render the tag refspec for the given tag name
+    pub fn tag_refspec_for(&self, tag_name: &str) -> String {
+        self.tag_refspec.replace("{tag}", tag_name)
+    }
+}
+
+impl Default for RemoteOverride {
+    fn default() -> Self {
+        RemoteOverride {
+            push_url: None,
+            tag_refspec: default_tag_refspec(),
+        }
+    }
+}
+
+/// Authentication settings for fetch and push.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, JsonSchema)]
+pub struct AuthConfig {
+    /// Per-remote credentials, keyed by remote name
+    #[serde(default)]
+    pub remotes: HashMap<String, RemoteAuth>,
+    /// Hosts besides github.com that are sent `GITHUB_TOKEN`, such as a
+    /// GitHub Enterprise Server
+    #[serde(default)]
+    pub github_hosts: Vec<String>,
+}
+
+/// Credentials used for a single remote.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default, JsonSchema)]
+pub struct RemoteAuth {
+    /// SSH private key to offer first (a leading `~` expands to the home directory)
+    #[serde(default)]
+    pub ssh_key: Option<String>,
+}
+
+impl RemoteAuth {
+    /// Returns the SSH key path with `~` expanded
+    pub fn ssh_key_path(&self) -> Option<PathBuf> {
+        self.ssh_key.as_deref().map(expand_home)
+    }
+}
+
+/// Configuration for git hooks.
+///
+/// Specifies paths to optional hook scripts that will be executed at
+/// key points in the git-publish workflow. Each hook takes a script path, a
+/// `{ script, on_error }` table, or an array of either; scripts run in order.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Default, JsonSchema)]
+pub struct HooksConfig {
+    /// Scripts for the pre-tag-create hook
+    ///
+    /// Executed before the tag is created. Can inspect commits and abort
+    /// tag creation if needed (exit code non-zero).
+    #[serde(
+        default,
+        rename = "pre-tag-create",
+        alias = "pre_tag_create",
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    #[schemars(with = "OneOrMany")]
+    pub pre_tag_create: Vec<HookCommand>,
+
+    /// Scripts for the post-tag-create hook
+    ///
+    /// Executed after tag is created locally but before push. Can validate
+    /// tag format or prepare for push (exit code non-zero aborts push).
+    #[serde(
+        default,
+        rename = "post-tag-create",
+        alias = "post_tag_create",
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    #[schemars(with = "OneOrMany")]
+    pub post_tag_create: Vec<HookCommand>,
+
+    /// Scripts for the post-push hook
+    ///
+    /// Executed after successful push to remote. Can trigger deployments
+    /// or update documentation. Failures logged but don't fail overall
+    /// operation (push already succeeded) unless `on_error = "abort"`.
+    #[serde(
+        default,
+        rename = "post-push",
+        alias = "post_push",
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    #[schemars(with = "OneOrMany")]
+    pub post_push: Vec<HookCommand>,
+
+    /// Extra static environment variables for every hook (`[hooks.env]`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+
+    /// Whether hooks inherit git-publish's environment (default: true)
+    ///
+    /// When false, hooks only get `PATH`, `HOME` (and the Windows essentials),
+    /// the variables listed in `pass_env`, `[hooks.env]` and `GITPUBLISH_*`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inherit_env: Option<bool>,
+
+    /// Variables passed through from git-publish's environment when
+    /// `inherit_env = false`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pass_env: Vec<String>,
+
+    /// Hooks directory, relative to the repository root (like `core.hooksPath`)
+    ///
+    /// Relative script paths are resolved against it, and discovered hooks are
+    /// looked up in it instead of `.gitpublish/hooks`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+/// A single hook script and what to do when it fails
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(from = "HookCommandEntry")]
+pub struct HookCommand {
+    /// Path to the script
+    pub script: String,
+
+    /// Failure policy; defaults to the hook type's policy when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_error: Option<HookFailurePolicy>,
+
+    /// Interpreter command line (e.g. `"pwsh -File"`); detected from the
+    /// shebang or extension when unset and the script isn't executable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interpreter: Option<String>,
+}
+
+/// A hook script as written in the config file: a bare path or a table
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum HookCommandEntry {
+    /// Path to the script
+    Script(String),
+    Table {
+        /// Path to the script
+        script: String,
+        /// `abort` or `continue`; defaults to the hook type's policy
+        #[serde(default)]
+        on_error: Option<HookFailurePolicy>,
+        /// Interpreter command line (e.g. `"pwsh -File"`)
+        #[serde(default)]
+        interpreter: Option<String>,
+    },
+}
+
+impl JsonSchema for HookCommand {
+    fn schema_name() -> String {
+        "HookCommand".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        HookCommandEntry::json_schema(gen)
+    }
+}
+
+impl From<HookCommandEntry> for HookCommand {
+    fn from(entry: HookCommandEntry) -> Self {
+        match entry {
+            HookCommandEntry::Script(script) => HookCommand::new(script),
+            HookCommandEntry::Table {
+                script,
+                on_error,
+                interpreter,
+            } => HookCommand {
+                script,
+                on_error,
+                interpreter,
+            },
+        }
+    }
+}
+
+impl HookCommand {
+    /// Create a hook command using the hook type's default failure policy
+    pub fn new(script: impl Into<String>) -> Self {
+        HookCommand {
+            script: script.into(),
+            on_error: None,
+            interpreter: None,
+        }
+    }
+
+    /// The failure policy for this script when run as `hook_type`
+    pub fn failure_policy(&self, hook_type: HookType) -> HookFailurePolicy {
+        self.on_error
+            .unwrap_or_else(|| hook_type.default_failure_policy())
+    }
+
+    /// This command with a relative script path resolved against `root`
+    ///
+    /// Hooks run from the repository root, so `./scripts/notify.sh` means the
+    /// same file wherever git-publish is invoked from.
+    pub fn relative_to(&self, root: &Path) -> HookCommand {
+        let script = Path::new(&self.script);
+        if script.is_absolute() {
+            return self.clone();
+        }
+        let resolved: PathBuf = root
+            .components()
+            .chain(script.components().filter(|c| *c != Component::CurDir))
+            .collect();
+        HookCommand {
+            script: resolved.to_string_lossy().into_owned(),
+            ..self.clone()
+        }
+    }
+}
+
+/// A single hook command or an array of them
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(HookCommand),
+    Many(Vec<HookCommand>),
+}
+
+/// Accepts a single hook command or an array of them
+fn one_or_many<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<HookCommand>, D::Error> {
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(command) => vec![command],
+        OneOrMany::Many(commands) => commands,
+    })
+}
+
+impl HooksConfig {
+    /// Returns these hooks with each hook set in `overrides` replacing the one here
+    pub fn with_overrides(&self, overrides: &HooksConfig) -> HooksConfig {
+        let pick = |own: &Vec<HookCommand>, other: &Vec<HookCommand>| {
+            if other.is_empty() {
+                own.clone()
+            } else {
+                other.clone()
+            }
+        };
+        let mut env = self.env.clone();
+        env.extend(overrides.env.clone());
+        HooksConfig {
+            pre_tag_create: pick(&self.pre_tag_create, &overrides.pre_tag_create),
+            post_tag_create: pick(&self.post_tag_create, &overrides.post_tag_create),
+            post_push: pick(&self.post_push, &overrides.post_push),
+            env,
+            inherit_env: overrides.inherit_env.or(self.inherit_env),
+            pass_env: if overrides.pass_env.is_empty() {
+                self.pass_env.clone()
+            } else {
+                overrides.pass_env.clone()
+            },
+            path: overrides.path.clone().or_else(|| self.path.clone()),
+        }
+    }
+
+    /// Directory relative script paths are resolved against: `path` under the
+    /// repository root if set, else the root itself
+    pub fn scripts_dir(&self, repo_root: &Path) -> PathBuf {
+        match &self.path {
+            Some(path) => repo_root.join(path),
+            None => repo_root.to_path_buf(),
+        }
+    }
+
+    /// Environment settings for hook processes
+    pub fn hook_env(&self) -> HookEnv {
+        HookEnv {
+            vars: self.env.clone(),
+            inherit: self.inherit_env.unwrap_or(true),
+            pass: self.pass_env.clone(),
+        }
+    }
+
+    /// Returns the scripts configured for the given hook, in execution order
+    pub fn scripts(&self, hook_type: HookType) -> &[HookCommand] {
+        match hook_type {
+            HookType::PreTagCreate => &self.pre_tag_create,
+            HookType::PostTagCreate => &self.post_tag_create,
+            HookType::PostPush => &self.post_push,
+        }
+    }
+}
+
+/// Expands a leading `~` in a path to the user's home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    if path == "~" {
+        if let Some(home) = dirs::home_dir() {
+            return home;
+        }
+    } else if let Some(rest) = path.strip_prefix("~/") {
+        if let Some(home) = dirs::home_dir() {
+            return home.join(rest);
+        }
+    }
+    PathBuf::from(path)
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        let mut branches = BranchesConfig::default();
+        branches.insert("main", TagPattern::new("v{version}"));
+        branches.insert("develop", TagPattern::new("d{version}"));
+        branches.insert("gray", TagPattern::new("g{version}"));
+
+        Config {
+            config_version: crate::config_migrate::CURRENT_CONFIG_VERSION,
+            branches,
+            conventional_commits: ConventionalCommitsConfig::default(),
+            patterns: PatternsConfig::default(),
+            behavior: BehaviorConfig::default(),
+            prerelease: PreReleaseConfig::default(),
+            remote_overrides: HashMap::new(),
+            auth: AuthConfig::default(),
+            hooks: HooksConfig::default(),
+            changelog: ChangelogConfig::default(),
+            tags: TagsConfig::default(),
+            channels: HashMap::new(),
+            packages: HashMap::new(),
+            warnings: WarningsConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Start building a configuration in code, see [`ConfigBuilder`]
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    /// JSON Schema of the configuration file, for editor validation and completion
+    pub fn schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(Config)
+    }
+
+    /// Hooks to run when tagging `branch`: the global `[hooks]` with the
+    /// branch's `[branches.<branch>.hooks]` overrides applied
+    pub fn hooks_for(&self, branch: &str) -> HooksConfig {
+        match self.branches.hooks(branch) {
+            Some(overrides) => self.hooks.with_overrides(overrides),
+            None => self.hooks.clone(),
+        }
+    }
+}
+
+/// Builds a [`Config`] without writing TOML.
+///
+/// Starts from the defaults with no branches configured. [`ConfigBuilder::build`]
+/// checks the result the same way [`load_config`] does, and also requires at
+/// least one branch.
+///
+/// ```
+/// use git_publish::config::Config;
+///
+/// let config = Config::builder()
+///     .branch("main", "v{version}")
+///     .branch("develop", "d{version}")
+///     .build()
+///     .unwrap();
+/// assert_eq!(config.branches.get("main"), Some(&"v{version}".to_string()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfigBuilder {
+    config: Config,
+    /// Branch patterns as given, compiled by [`ConfigBuilder::build`]
+    branches: Vec<(String, String)>,
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        ConfigBuilder {
+            config: Config {
+                branches: BranchesConfig::default(),
+                ..Config::default()
+            },
+            branches: Vec::new(),
+        }
+    }
+}
+
+impl ConfigBuilder {
+    /// Tag `branch` with `pattern` (e.g. `"v{version}"`)
+    pub fn branch(mut self, branch: impl Into<String>, pattern: impl Into<String>) -> Self {
+        self.branches.push((branch.into(), pattern.into()));
+        self
+    }
+
+    /// Tag `branch` with `pattern`, overriding the global hooks for it
+    pub fn branch_with_hooks(
+        mut self,
+        branch: impl Into<String>,
+        pattern: impl Into<String>,
+        hooks: HooksConfig,
+    ) -> Self {
+        let branch = branch.into();
+        self.config.branches.hooks.insert(branch.clone(), hooks);
+        self.branch(branch, pattern)
+    }
+
+    /// Commit types, breaking change indicators and bump keywords
+    pub fn conventional_commits(mut self, conventional_commits: ConventionalCommitsConfig) -> Self {
+        self.config.conventional_commits = conventional_commits;
+        self
+    }
+
+    /// Version format patterns
+    pub fn patterns(mut self, patterns: PatternsConfig) -> Self {
+        self.config.patterns = patterns;
+        self
+    }
+
+    /// Runtime behavior options
+    pub fn behavior(mut self, behavior: BehaviorConfig) -> Self {
+        self.config.behavior = behavior;
+        self
+    }
+
+    /// Pre-release handling
+    pub fn prerelease(mut self, prerelease: PreReleaseConfig) -> Self {
+        self.config.prerelease = prerelease;
+        self
+    }
+
+    /// Push overrides for `remote`
+    pub fn remote_override(
+        mut self,
+        remote: impl Into<String>,
+        remote_override: RemoteOverride,
+    ) -> Self {
+        self.config
+            .remote_overrides
+            .insert(remote.into(), remote_override);
+        self
+    }
+
+    /// Credentials for `remote`
+    pub fn remote_auth(mut self, remote: impl Into<String>, auth: RemoteAuth) -> Self {
+        self.config.auth.remotes.insert(remote.into(), auth);
+        self
+    }
+
+    /// Global hooks
+    pub fn hooks(mut self, hooks: HooksConfig) -> Self {
+        self.config.hooks = hooks;
+        self
+    }
+
+    /// Changelog options
+    pub fn changelog(mut self, changelog: ChangelogConfig) -> Self {
+        self.config.changelog = changelog;
+        self
+    }
+
+    /// Validate and return the configuration
+    pub fn build(mut self) -> crate::error::Result<Config> {
+        if self.branches.is_empty() {
+            return Err(crate::error::GitPublishError::config(
+                "at least one branch must be configured",
+            ));
+        }
+        for (branch, pattern) in self.branches {
+            let pattern = TagPattern::parse(&pattern)
+                .map_err(|e| crate::error::GitPublishError::config(invalid_pattern(&branch, e)))?;
+            self.config.branches.insert(branch, pattern);
+        }
+        validate_config(&self.config)?;
+        Ok(self.config)
+    }
+}
+
+/// File names of the configuration, in lookup order
+///
+/// In the user config directory they are prefixed with a dot.
+pub const CONFIG_FILE_NAMES: [&str; 4] = [
+    "gitpublish.toml",
+    "gitpublish.json",
+    "gitpublish.yaml",
+    "gitpublish.yml",
+];
+
+/// Format of a configuration file, detected from its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConfigFormat {
+    #[default]
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// `.json` is JSON, `.yaml`/`.yml` is YAML and anything else is TOML
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => ConfigFormat::Json,
+            Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+                ConfigFormat::Yaml
+            }
+            _ => ConfigFormat::Toml,
+        }
+    }
+}
+
+/// Loads configuration from file or returns defaults.
+///
+/// Attempts to load configuration in the following order:
+/// 1. Custom path provided as parameter
+/// 2. `gitpublish.toml`, `.json`, `.yaml` or `.yml` in the git repository root
+/// 3. `[package.metadata.gitpublish]` (or `[workspace.metadata.gitpublish]`) in
+///    the repository's `Cargo.toml`, or the `gitpublish` key of its `package.json`
+/// 4. `.gitpublish.toml`, `.json`, `.yaml` or `.yml` in the user config directory
+/// 5. Default configuration if no file found
+///
+/// The format is detected from the file extension, see [`ConfigFormat`].
+///
+/// # Arguments
+/// * `config_path` - Optional path to custom configuration file
+///
+/// # Returns
+/// * `Ok(Config)` - Loaded or default configuration
+/// * `Err` - If file exists but cannot be read or parsed
+pub fn load_config(config_path: Option<&str>) -> Result<Config, Box<dyn std::error::Error>> {
+    load_config_with_warnings(config_path).map(|(config, _)| config)
+}
+
+/// Like [`load_config`], also returning the warnings about the file.
+///
+/// Unknown keys are otherwise ignored, unless `[behavior] strict_config` turns
+/// them into an error. Deprecated keys of older `config_version`s are migrated.
+pub fn load_config_with_warnings(
+    config_path: Option<&str>,
+) -> Result<LoadedConfig, Box<dyn std::error::Error>> {
+    let Some(source) = find_config_source(config_path)? else {
+        return Ok((Config::default(), Vec::new()));
+    };
+    let content = fs::read_to_string(source.path())?;
+    let (config, warnings) = match &source {
+        ConfigSource::File(_) => crate::config_diagnostics::parse(&content, source.format())?,
+        ConfigSource::Manifest { table_path, .. } => {
+            crate::config_diagnostics::parse_embedded(&content, source.format(), table_path)?
+                .ok_or_else(|| {
+                    crate::error::GitPublishError::config(format!(
+                        "{} not found in {}",
+                        table_path.join("."),
+                        source.path().display()
+                    ))
+                })?
+        }
+    };
+    validate_config(&config)?;
+    Ok((config, warnings))
+}
+
+/// A configuration with the warnings found while loading it
+pub type LoadedConfig = (Config, Vec<ConfigWarning>);
+
+/// Where a configuration is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// A standalone configuration file
+    File(PathBuf),
+    /// A table inside a package manifest
+    Manifest {
+        path: PathBuf,
+        kind: ManifestKind,
+        /// Keys leading to the configuration (e.g. `package.metadata.gitpublish`)
+        table_path: &'static [&'static str],
+    },
+}
+
+impl ConfigSource {
+    /// File holding the configuration
+    pub fn path(&self) -> &Path {
+        match self {
+            ConfigSource::File(path) | ConfigSource::Manifest { path, .. } => path,
+        }
+    }
+
+    /// Format of the file
+    pub fn format(&self) -> ConfigFormat {
+        match self {
+            ConfigSource::File(path) => ConfigFormat::from_path(path),
+            ConfigSource::Manifest {
+                kind: ManifestKind::Cargo,
+                ..
+            } => ConfigFormat::Toml,
+            ConfigSource::Manifest {
+                kind: ManifestKind::Npm,
+                ..
+            } => ConfigFormat::Json,
+        }
+    }
+
+    /// Keys leading to the configuration in the file, empty for standalone files
+    pub fn table_path(&self) -> &'static [&'static str] {
+        match self {
+            ConfigSource::File(_) => &[],
+            ConfigSource::Manifest { table_path, .. } => table_path,
+        }
+    }
+}
+
+/// Find the configuration [`load_config`] would read, `None` meaning defaults
+pub fn find_config_source(
+    config_path: Option<&str>,
+) -> Result<Option<ConfigSource>, Box<dyn std::error::Error>> {
+    if let Some(path) = config_path {
+        return Ok(Some(ConfigSource::File(PathBuf::from(path))));
+    }
+
+    let repo_root = find_repo_root();
+    if let Some(path) = repo_root
+        .as_deref()
+        .and_then(|root| find_config_file(root, ""))
+    {
+        return Ok(Some(ConfigSource::File(path)));
+    }
+    if let Some(root) = repo_root.as_deref() {
+        if let Some(source) = find_manifest_config(root)? {
+            return Ok(Some(source));
+        }
+    }
+    Ok(dirs::config_dir()
+        .and_then(|dir| find_config_file(&dir, "."))
+        .map(ConfigSource::File))
+}
+
+/// Tables that can hold the configuration in a manifest, in lookup order
+fn manifest_table_paths(kind: ManifestKind) -> &'static [&'static [&'static str]] {
+    match kind {
+        ManifestKind::Cargo => &[
+            &["package", "metadata", "gitpublish"],
+            &["workspace", "metadata", "gitpublish"],
+        ],
+        ManifestKind::Npm => &[&["gitpublish"]],
+    }
+}
+
+/// The manifest table holding the configuration in a repository root, if any
+fn find_manifest_config(root: &Path) -> Result<Option<ConfigSource>, Box<dyn std::error::Error>> {
+    for kind in ManifestKind::ALL {
+        let path = root.join(kind.file_name());
+        if !path.is_file() {
+            continue;
+        }
+        let content = fs::read_to_string(&path)?;
+        let document: serde_json::Value = match kind {
+            ManifestKind::Cargo => serde_json::to_value(toml::from_str::<toml::Table>(&content)?)?,
+            ManifestKind::Npm => serde_json::from_str(&content)?,
+        };
+        let found = manifest_table_paths(kind).iter().find(|table_path| {
+            table_path
+                .iter()
+                .try_fold(&document, |value, key| value.get(*key))
+                .is_some()
+        });
+        if let Some(table_path) = found {
+            return Ok(Some(ConfigSource::Manifest {
+                path,
+                kind,
+                table_path,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// First of [`CONFIG_FILE_NAMES`] (with `prefix`) that exists in `dir`
+fn find_config_file(dir: &Path, prefix: &str) -> Option<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(format!("{}{}", prefix, name)))
+        .find(|path| path.exists())
+}
+
+/// Validates cross-field constraints that serde cannot express.
+fn validate_config(config: &Config) -> crate::error::Result<()> {
+    for (remote, remote_override) in &config.remote_overrides {
+        if !remote_override.tag_refspec.contains("{tag}") {
+            return Err(crate::error::GitPublishError::config(format!(
+                "remote_overrides.{}.tag_refspec must contain a {{tag}} placeholder",
+                remote
+            )));
+        }
+    }
+    if !config.changelog.pull_request_url.contains("{number}") {
+        return Err(crate::error::GitPublishError::config(
+            "changelog.pull_request_url must contain a {number} placeholder",
+        ));
+    }
+    let header = &config.changelog.release_header;
+    if !header.contains("{tag}") && !header.contains("{version}") {
+        return Err(crate::error::GitPublishError::config(
+            "changelog.release_header must contain a {tag} or {version} placeholder",
+        ));
+    }
+    for (scope, identifier) in &config.channels {
+        if identifier.is_empty() || identifier.parse::<crate::domain::PreReleaseType>().is_err() {
+            return Err(crate::error::GitPublishError::config(format!(
+                "channels.{} must be a pre-release identifier such as \"beta\", got \"{}\"",
+                scope, identifier
+            )));
+        }
+    }
+    if let Some((scope, rule, other)) = config.conventional_commits.scopes.conflict() {
+        return Err(crate::error::GitPublishError::config(format!(
+            "conventional_commits.scopes: scope '{}' is listed under both {} and {}",
+            scope, rule, other
+        )));
+    }
+    let mut packages: Vec<_> = config.packages.iter().collect();
+    packages.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, package) in packages {
+        if package.path.trim().is_empty() {
+            return Err(crate::error::GitPublishError::config(format!(
+                "packages.{}.path must not be empty",
+                name
+            )));
+        }
+        if let Err(e) = TagPattern::parse(&package.tag) {
+            let message = match e {
+                crate::error::GitPublishError::Tag(message) => message,
+                e => e.to_string(),
+            };
+            return Err(crate::error::GitPublishError::config(format!(
+                "packages.{}.tag: {}",
+                name, message
+            )));
+        }
+    }
+    if let Some(pattern) = &config.behavior.tag_validation_regex {
+        if let Err(e) = regex::Regex::new(pattern) {
+            return Err(crate::error::GitPublishError::config(format!(
+                "behavior.tag_validation_regex is not a valid regular expression: {}",
+                e
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Where `git-publish init` writes the configuration: `gitpublish.toml` in
+/// the repository root
+pub fn init_config_path() -> Option<PathBuf> {
+    find_repo_root().map(|root| root.join(CONFIG_FILE_NAMES[0]))
+}
+
+/// The release branches among `branches` with their tag patterns, as
+/// `git-publish init` suggests them
+///
+/// `main`, `master` and `develop` get the patterns of the default
+/// configuration; without any of them the first branch is tagged `v{version}`.
+pub fn default_release_branches(branches: &[String]) -> Vec<(String, String)> {
+    let mut release_branches: Vec<(String, String)> = branches
+        .iter()
+        .filter_map(|branch| match branch.as_str() {
+            "main" | "master" => Some((branch.clone(), "v{version}".to_string())),
+            "develop" => Some((branch.clone(), "d{version}".to_string())),
+            _ => None,
+        })
+        .collect();
+    if release_branches.is_empty() {
+        release_branches.extend(
+            branches
+                .first()
+                .map(|branch| (branch.clone(), "v{version}".to_string())),
+        );
+    }
+    release_branches
+}
+
+/// A starter TOML configuration tagging `release_branches` (branch and tag
+/// pattern)
+pub fn scaffold_config(release_branches: &[(String, String)]) -> String {
+    let mut content = format!(
+        "# git-publish configuration, see the README for every option\nconfig_version = {}\n\n[branches]\n",
+        crate::config_migrate::CURRENT_CONFIG_VERSION
+    );
+    for (branch, pattern) in release_branches {
+        content.push_str(&format!("{} = {:?}\n", toml_key(branch), pattern));
+    }
+    content
+}
+
+/// `key` quoted when it isn't a bare TOML key (e.g. `release/2.x`)
+fn toml_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        key.to_string()
+    } else {
+        format!("{:?}", key)
+    }
+}
+
+fn find_repo_root() -> Option<PathBuf> {
+    let current_dir = std::env::current_dir().ok()?;
+    let repo = git2::Repository::discover(current_dir).ok()?;
+
+    if let Some(workdir) = repo.workdir() {
+        return Some(workdir.to_path_buf());
+    }
+
+    Some(repo.path().to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    // Integration tests: configuration scenarios
+    #[test]
+    fn test_config_default() {
+        let config = Config::default();
+
+        assert!(config.branches.contains_key("main"));
+        assert!(config.branches.contains_key("develop"));
+        assert_eq!(config.branches.get("main"), Some(&"v{version}".to_string()));
+    }
+
+    #[test]
+    fn test_config_conventional_commits_defaults() {
+        let config = ConventionalCommitsConfig::default();
+
+        assert!(config.types.contains(&"feat".to_string()));
+        assert!(config.types.contains(&"fix".to_string()));
+        assert!(config.types.contains(&"docs".to_string()));
+        assert_eq!(config.types.len(), 10);
+    }
+
+    #[test]
+    fn test_config_breaking_change_indicators() {
+        let config = ConventionalCommitsConfig::default();
+
+        assert!(config
+            .breaking_change_indicators
+            .contains(&"BREAKING CHANGE:".to_string()));
+        assert!(config
+            .breaking_change_indicators
+            .contains(&"BREAKING-CHANGE:".to_string()));
+    }
+
+    #[test]
+    fn test_config_major_keywords() {
+        let config = ConventionalCommitsConfig::default();
+
+        assert!(config.major_keywords.contains(&"breaking".to_string()));
+        assert!(config.major_keywords.contains(&"deprecate".to_string()));
+    }
+
+    #[test]
+    fn test_config_minor_keywords() {
+        let config = ConventionalCommitsConfig::default();
+
+        assert!(config.minor_keywords.contains(&"feat".to_string()));
+        assert!(config.minor_keywords.contains(&"feature".to_string()));
+        assert!(config.minor_keywords.contains(&"enhancement".to_string()));
+    }
+
+    #[test]
+    fn test_config_patterns_default() {
+        let config = PatternsConfig::default();
+
+        assert!(config.version_format.contains_key("major"));
+        assert!(config.version_format.contains_key("minor"));
+        assert!(config.version_format.contains_key("patch"));
+    }
+
+    #[test]
+    fn test_config_behavior_default() {
+        let config = BehaviorConfig::default();
+
+        assert!(!config.skip_remote_selection);
+        assert_eq!(config.max_commits_analyzed, 5000);
+        assert_eq!(config.on_version_downgrade, DowngradePolicy::Warn);
+
+        assert!(config.ignore_paths.is_empty());
+
+        let config: Config = toml::from_str(
+            "[behavior]\nmax_commits_analyzed = 0\nignore_paths = [\"docs/**\", \"*.md\"]\n",
+        )
+        .unwrap();
+        assert_eq!(config.behavior.max_commits_analyzed, 0);
+        assert_eq!(config.behavior.ignore_paths, ["docs/**", "*.md"]);
+    }
+
+    #[test]
+    fn test_config_commit_conformance_rules() {
+        let defaults = ConventionalCommitsConfig::default();
+        assert_eq!(defaults.max_subject_length, None);
+        assert!(!defaults.flag_missing_type);
+
+        let toml_str = r#"
+[conventional_commits]
+max_subject_length = 72
+flag_missing_type = true
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.conventional_commits.max_subject_length, Some(72));
+        assert!(config.conventional_commits.flag_missing_type);
+        assert_eq!(config.conventional_commits.types, default_commit_types());
+    }
+
+    #[test]
+    fn test_hooks_config_default() {
+        let hooks = HooksConfig::default();
+        assert!(hooks.pre_tag_create.is_empty());
+        assert!(hooks.post_tag_create.is_empty());
+        assert!(hooks.post_push.is_empty());
+    }
+
+    #[test]
+    fn test_hooks_config_accepts_snake_case_keys() {
+        let toml_str = r#"
+[hooks]
+pre_tag_create = "./hooks/check.sh"
+post-push = "./hooks/notify.sh"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.hooks.scripts(HookType::PreTagCreate),
+            [HookCommand::new("./hooks/check.sh")]
+        );
+        assert!(config.hooks.scripts(HookType::PostTagCreate).is_empty());
+        assert_eq!(
+            config.hooks.scripts(HookType::PostPush),
+            [HookCommand::new("./hooks/notify.sh")]
+        );
+    }
+
+    #[test]
+    fn test_hooks_config_arrays_with_failure_policy() {
+        let toml_str = r#"
+[hooks]
+post_push = ["./notify.sh", { script = "./deploy.ps1", on_error = "abort", interpreter = "pwsh -File" }]
+pre-tag-create = { script = "./lint.sh", on_error = "continue" }
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let post_push = config.hooks.scripts(HookType::PostPush);
+        assert_eq!(post_push.len(), 2);
+        assert_eq!(post_push[0].script, "./notify.sh");
+        assert_eq!(
+            post_push[0].failure_policy(HookType::PostPush),
+            HookFailurePolicy::Continue
+        );
+        assert_eq!(post_push[1].script, "./deploy.ps1");
+        assert_eq!(post_push[1].interpreter.as_deref(), Some("pwsh -File"));
+        assert_eq!(
+            post_push[1].failure_policy(HookType::PostPush),
+            HookFailurePolicy::Abort
+        );
+
+        let pre_tag = config.hooks.scripts(HookType::PreTagCreate);
+        assert_eq!(
+            pre_tag[0].failure_policy(HookType::PreTagCreate),
+            HookFailurePolicy::Continue
+        );
+
+        let restored: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(restored.hooks, config.hooks);
+    }
+
+    #[test]
+    fn test_hook_command_relative_to() {
+        let root = Path::new("/repo");
+        let command = HookCommand {
+            on_error: Some(HookFailurePolicy::Continue),
+            ..HookCommand::new("./scripts/notify.sh")
+        };
+        let resolved = command.relative_to(root);
+        assert_eq!(
+            Path::new(&resolved.script),
+            Path::new("/repo/scripts/notify.sh")
+        );
+        assert_eq!(resolved.on_error, Some(HookFailurePolicy::Continue));
+
+        let absolute = HookCommand::new(root.join("hook.sh").to_string_lossy());
+        assert_eq!(absolute.relative_to(Path::new("/elsewhere")), absolute);
+    }
+
+    #[test]
+    fn test_hooks_path() {
+        let root = Path::new("/repo");
+        assert_eq!(HooksConfig::default().scripts_dir(root), root);
+
+        let config: Config = toml::from_str(
+            r#"
+[hooks]
+path = "ci/release-hooks"
+post-push = "notify.sh"
+
+[branches.main]
+pattern = "v{version}"
+
+[branches.main.hooks]
+path = "ci/prod-hooks"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.hooks_for("develop").scripts_dir(root),
+            root.join("ci/release-hooks")
+        );
+        assert_eq!(
+            config.hooks_for("main").scripts_dir(root),
+            root.join("ci/prod-hooks")
+        );
+    }
 
-impl Default for Config {
-    fn default() -> Self {
-        let mut branches = HashMap::new();
-        branches.insert("main".to_string(), "v{version}".to_string());
-        branches.insert("develop".to_string(), "d{version}".to_string());
-        branches.insert("gray".to_string(), "g{version}".to_string());
+    #[test]
+    fn test_hooks_env_settings() {
+        assert!(HooksConfig::default().hook_env().inherit);
 
-        Config {
-            branches,
-            conventional_commits: ConventionalCommitsConfig::default(),
-            patterns: PatternsConfig::default(),
-            behavior: BehaviorConfig::default(),
-            prerelease: PreReleaseConfig::default(),
-        }
+        let toml_str = r#"
+[hooks]
+inherit_env = false
+pass_env = ["SSH_AUTH_SOCK"]
+
+[hooks.env]
+DEPLOY_ENV = "staging"
+REGION = "eu"
+
+[branches.main]
+pattern = "v{version}"
+
+[branches.main.hooks.env]
+DEPLOY_ENV = "prod"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let develop = config.hooks_for("develop").hook_env();
+        assert!(!develop.inherit);
+        assert_eq!(develop.pass, vec!["SSH_AUTH_SOCK".to_string()]);
+        assert_eq!(develop.vars.get("DEPLOY_ENV").unwrap(), "staging");
+
+        let main = config.hooks_for("main").hook_env();
+        assert!(!main.inherit);
+        assert_eq!(main.vars.get("DEPLOY_ENV").unwrap(), "prod");
+        assert_eq!(main.vars.get("REGION").unwrap(), "eu");
     }
-}
 
-/// Loads configuration from file or returns defaults.
-///
-/// Attempts to load configuration in the following order:
-/// 1. Custom path provided as parameter
-/// 2. `gitpublish.toml` in the git repository root
-/// 3. `~/.config/.gitpublish.toml` in user config directory
-/// 4. Default configuration if no file found
-///
-/// # Arguments
-/// * `config_path` - Optional path to custom configuration file
-///
-/// # Returns
-/// * `Ok(Config)` - Loaded or default configuration
-/// * `Err` - If file exists but cannot be read or parsed
-pub fn load_config(config_path: Option<&str>) -> Result<Config, Box<dyn std::error::Error>> {
-    let config_str = if let Some(path) = config_path {
-        fs::read_to_string(path)?
-    } else if let Some(repo_root) = find_repo_root() {
-        let repo_config_path = repo_root.join("gitpublish.toml");
-        if repo_config_path.exists() {
-            fs::read_to_string(repo_config_path)?
-        } else if let Some(config_dir) = dirs::config_dir() {
-            let config_path = config_dir.join(".gitpublish.toml");
-            if config_path.exists() {
-                fs::read_to_string(config_path)?
-            } else {
-                return Ok(Config::default());
-            }
-        } else {
-            return Ok(Config::default());
-        }
-    } else if let Some(config_dir) = dirs::config_dir() {
-        let config_path = config_dir.join(".gitpublish.toml");
-        if config_path.exists() {
-            fs::read_to_string(config_path)?
-        } else {
-            return Ok(Config::default());
-        }
-    } else {
-        return Ok(Config::default());
-    };
+    #[test]
+    fn test_branch_hook_overrides() {
+        let toml_str = r#"
+[branches]
+develop = "d{version}"
 
-    let config: Config = toml::from_str(&config_str)?;
-    Ok(config)
-}
+[branches.main]
+pattern = "v{version}"
 
-fn find_repo_root() -> Option<PathBuf> {
-    let current_dir = std::env::current_dir().ok()?;
-    let repo = git2::Repository::discover(current_dir).ok()?;
+[branches.main.hooks]
+post_push = "./deploy-prod.sh"
 
-    if let Some(workdir) = repo.workdir() {
-        return Some(workdir.to_path_buf());
+[hooks]
+pre-tag-create = "./check.sh"
+post-push = "./deploy-staging.sh"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.branches.get("main"), Some(&"v{version}".to_string()));
+        assert_eq!(
+            config.branches.get("develop"),
+            Some(&"d{version}".to_string())
+        );
+
+        let main_hooks = config.hooks_for("main");
+        assert_eq!(main_hooks.pre_tag_create, [HookCommand::new("./check.sh")]);
+        assert_eq!(main_hooks.post_push, [HookCommand::new("./deploy-prod.sh")]);
+        assert_eq!(
+            config.hooks_for("develop").post_push,
+            [HookCommand::new("./deploy-staging.sh")]
+        );
+
+        let restored: Config = toml::from_str(&toml::to_string(&config).unwrap()).unwrap();
+        assert_eq!(restored.branches, config.branches);
     }
 
-    Some(repo.path().to_path_buf())
-}
+    #[test]
+    fn test_config_behavior_host_key_checking() {
+        assert_eq!(
+            BehaviorConfig::default().host_key_checking,
+            HostKeyChecking::Ask
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serial_test::serial;
-    use tempfile::TempDir;
+        let toml_str = r#"
+[behavior]
+host_key_checking = "strict"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.behavior.host_key_checking, HostKeyChecking::Strict);
+    }
 
-    // Integration tests: configuration scenarios
     #[test]
-    fn test_config_default() {
-        let config = Config::default();
+    fn test_config_behavior_hook_echo() {
+        assert!(!BehaviorConfig::default().hook_echo);
 
-        assert!(config.branches.contains_key("main"));
-        assert!(config.branches.contains_key("develop"));
-        assert_eq!(config.branches.get("main"), Some(&"v{version}".to_string()));
+        let config: Config = toml::from_str("[behavior]\nhook_echo = true\n").unwrap();
+        assert!(config.behavior.hook_echo);
     }
 
     #[test]
-    fn test_config_conventional_commits_defaults() {
-        let config = ConventionalCommitsConfig::default();
+    fn test_config_behavior_keep_tag_on_interrupt() {
+        assert!(!BehaviorConfig::default().keep_tag_on_interrupt);
 
-        assert!(config.types.contains(&"feat".to_string()));
-        assert!(config.types.contains(&"fix".to_string()));
-        assert!(config.types.contains(&"docs".to_string()));
-        assert_eq!(config.types.len(), 10);
+        let config: Config = toml::from_str("[behavior]\nkeep_tag_on_interrupt = true\n").unwrap();
+        assert!(config.behavior.keep_tag_on_interrupt);
     }
 
     #[test]
-    fn test_config_breaking_change_indicators() {
-        let config = ConventionalCommitsConfig::default();
+    fn test_config_behavior_manual_push_command() {
+        assert_eq!(
+            BehaviorConfig::default().manual_push_command_for("v1.2.3", "origin"),
+            "git push origin v1.2.3"
+        );
 
-        assert!(config
-            .breaking_change_indicators
-            .contains(&"BREAKING CHANGE:".to_string()));
-        assert!(config
-            .breaking_change_indicators
-            .contains(&"BREAKING-CHANGE:".to_string()));
+        let config: Config = toml::from_str(
+            "[behavior]\nmanual_push_command = \"release-tool push --follow-tags {remote} {tag}\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config
+                .behavior
+                .manual_push_command_for("v1.2.3", "upstream"),
+            "release-tool push --follow-tags upstream v1.2.3"
+        );
     }
 
     #[test]
-    fn test_config_major_keywords() {
-        let config = ConventionalCommitsConfig::default();
+    fn test_config_behavior_on_existing_tag() {
+        assert_eq!(
+            BehaviorConfig::default().on_existing_tag,
+            ExistingTagPolicy::Abort
+        );
 
-        assert!(config.major_keywords.contains(&"breaking".to_string()));
-        assert!(config.major_keywords.contains(&"deprecate".to_string()));
+        let config: Config =
+            toml::from_str("[behavior]\non_existing_tag = \"increment\"\n").unwrap();
+        assert_eq!(
+            config.behavior.on_existing_tag,
+            ExistingTagPolicy::Increment
+        );
+        assert!(toml::from_str::<Config>("[behavior]\non_existing_tag = \"merge\"\n").is_err());
     }
 
     #[test]
-    fn test_config_minor_keywords() {
-        let config = ConventionalCommitsConfig::default();
+    fn test_config_behavior_version_source() {
+        assert_eq!(BehaviorConfig::default().version_source, VersionSource::Tag);
 
-        assert!(config.minor_keywords.contains(&"feat".to_string()));
-        assert!(config.minor_keywords.contains(&"feature".to_string()));
-        assert!(config.minor_keywords.contains(&"enhancement".to_string()));
+        let config: Config = toml::from_str("[behavior]\nversion_source = \"manifest\"\n").unwrap();
+        assert_eq!(config.behavior.version_source, VersionSource::Manifest);
     }
 
     #[test]
-    fn test_config_patterns_default() {
-        let config = PatternsConfig::default();
+    fn test_config_behavior_network_timeout() {
+        assert_eq!(BehaviorConfig::default().network_timeout_secs, None);
 
-        assert!(config.version_format.contains_key("major"));
-        assert!(config.version_format.contains_key("minor"));
-        assert!(config.version_format.contains_key("patch"));
+        let config: Config = toml::from_str("[behavior]\nnetwork_timeout_secs = 30\n").unwrap();
+        assert_eq!(config.behavior.network_timeout_secs, Some(30));
     }
 
     #[test]
-    fn test_config_behavior_default() {
-        let config = BehaviorConfig::default();
+    fn test_config_behavior_language() {
+        assert_eq!(BehaviorConfig::default().language, None);
 
-        assert!(!config.skip_remote_selection);
+        let config: Config = toml::from_str("[behavior]\nlanguage = \"zh-CN\"\n").unwrap();
+        assert_eq!(config.behavior.language, Some(Locale::ZhCn));
+
+        assert!(toml::from_str::<Config>("[behavior]\nlanguage = \"xx\"\n").is_err());
+    }
+
+    #[test]
+    fn test_config_auth_remote_ssh_key() {
+        let toml_str = r#"
+[auth.remotes.origin]
+ssh_key = "~/.ssh/work_ed25519"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let auth = config.auth.remotes.get("origin").unwrap();
+        assert_eq!(auth.ssh_key.as_deref(), Some("~/.ssh/work_ed25519"));
+
+        let path = auth.ssh_key_path().unwrap();
+        assert!(path.ends_with(".ssh/work_ed25519"));
+        if dirs::home_dir().is_some() {
+            assert!(!path.starts_with("~"));
+        }
+        assert!(Config::default().auth.remotes.is_empty());
+
+        let config: Config =
+            toml::from_str("[auth]\ngithub_hosts = [\"github.example.com\"]\n").unwrap();
+        assert_eq!(config.auth.github_hosts, ["github.example.com"]);
     }
 
     #[test]
@@ -463,6 +2206,133 @@ release = "release/{version}"
         );
     }
 
+    #[test]
+    fn test_config_remote_overrides_parsing() {
+        let toml_str = r#"
+[remote_overrides.deploy]
+push_url = "git://deploy.example.com/app.git"
+tag_refspec = "refs/tags/{tag}:refs/releases/{tag}"
+
+[remote_overrides.origin]
+push_url = "ssh://git@example.com/app.git"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        let deploy = config.remote_overrides.get("deploy").unwrap();
+        assert_eq!(
+            deploy.push_url.as_deref(),
+            Some("git://deploy.example.com/app.git")
+        );
+        assert_eq!(
+            deploy.tag_refspec_for("v1.2.3"),
+            "refs/tags/v1.2.3:refs/releases/v1.2.3"
+        );
+
+        let origin = config.remote_overrides.get("origin").unwrap();
+        assert_eq!(
+            origin.tag_refspec_for("v1.2.3"),
+            "refs/tags/v1.2.3:refs/tags/v1.2.3"
+        );
+    }
+
+    #[test]
+    fn test_validate_config_rejects_refspec_without_placeholder() {
+        let toml_str = r#"
+[remote_overrides.deploy]
+tag_refspec = "refs/tags/latest:refs/releases/latest"
+"#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_config_rejects_invalid_tag_validation_regex() {
+        let config: Config =
+            toml::from_str("[behavior]\ntag_validation_regex = \"^v[0-9\"\n").unwrap();
+        assert!(validate_config(&config).is_err());
+
+        let config: Config =
+            toml::from_str("[behavior]\ntag_validation_regex = \"^[a-z0-9.-]{1,20}$\"\n").unwrap();
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_config_rejects_invalid_channel() {
+        let config: Config = toml::from_str("[channels]\nexperimental = \"beta\"\n").unwrap();
+        assert!(validate_config(&config).is_ok());
+
+        let config: Config = toml::from_str("[channels]\nexperimental = \"be ta\"\n").unwrap();
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_config_packages() {
+        let config: Config = toml::from_str(
+            "[packages]\napi = { path = \"services/api/**\", tag = \"api-v{version}\" }\n",
+        )
+        .unwrap();
+        assert!(validate_config(&config).is_ok());
+        assert_eq!(config.packages["api"].path, "services/api/**");
+
+        let config: Config =
+            toml::from_str("[packages]\napi = { path = \"services/api/**\", tag = \"api\" }\n")
+                .unwrap();
+        let error = validate_config(&config).unwrap_err().to_string();
+        assert!(error.contains("packages.api.tag"), "{}", error);
+    }
+
+    #[test]
+    fn test_scope_rules() {
+        let config: Config = toml::from_str(
+            "[conventional_commits]\nscopes.ignore = [\"ci\", \"deps\"]\nscopes.major = [\"api\"]\n",
+        )
+        .unwrap();
+        assert!(validate_config(&config).is_ok());
+        let scopes = &config.conventional_commits.scopes;
+        assert_eq!(
+            scopes.rule_for(Some("ci")),
+            Some(ScopeRule::Ignore("ci".to_string()))
+        );
+        assert_eq!(
+            scopes.rule_for(Some("deps, api")),
+            Some(ScopeRule::Bump(VersionBump::Major, "api".to_string()))
+        );
+        assert_eq!(scopes.rule_for(Some("cli")), None);
+        assert_eq!(scopes.rule_for(None), None);
+
+        let config: Config =
+            toml::from_str("[conventional_commits.scopes]\nignore = [\"ci\"]\npatch = [\"ci\"]\n")
+                .unwrap();
+        let error = validate_config(&config).unwrap_err().to_string();
+        assert!(
+            error.contains("'ci' is listed under both ignore and patch"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_warning_policies() {
+        let config: Config =
+            toml::from_str("[warnings]\nunparsable_tag = \"fail\"\nno_new_commits = \"ignore\"\n")
+                .unwrap();
+        let unparsable = BoundaryWarning::UnparsableTag {
+            tag: "release".to_string(),
+            reason: String::new(),
+        };
+        let unreachable = BoundaryWarning::TagUnreachable {
+            tag: "v1.0.0".to_string(),
+            branch: "main".to_string(),
+        };
+        assert_eq!(config.warnings.policy(&unparsable), WarningPolicy::Fail);
+        assert_eq!(config.warnings.no_new_commits, WarningPolicy::Ignore);
+        assert_eq!(config.warnings.policy(&unreachable), WarningPolicy::Prompt);
+        assert_eq!(unparsable.config_key(), "unparsable_tag");
+
+        assert!(toml::from_str::<Config>("[warnings]\nunparsable_tag = \"abort\"\n").is_err());
+    }
+
     #[test]
     #[serial]
     fn test_load_config_from_repo_root_when_in_subdir() {
@@ -493,6 +2363,112 @@ main = "root-{version}"
         );
     }
 
+    #[test]
+    fn test_scaffold_config() {
+        let branches: Vec<String> = ["feature/x", "develop", "main"]
+            .iter()
+            .map(|branch| branch.to_string())
+            .collect();
+        let config: Config =
+            toml::from_str(&scaffold_config(&default_release_branches(&branches))).unwrap();
+        assert!(validate_config(&config).is_ok());
+        assert_eq!(config.branches.len(), 2);
+        assert_eq!(config.branches.get("main"), Some(&"v{version}".to_string()));
+        assert_eq!(
+            config.branches.get("develop"),
+            Some(&"d{version}".to_string())
+        );
+
+        let config: Config = toml::from_str(&scaffold_config(&default_release_branches(&[
+            "release/2.x".to_string(),
+        ])))
+        .unwrap();
+        assert_eq!(
+            config.branches.get("release/2.x"),
+            Some(&"v{version}".to_string())
+        );
+    }
+
+    #[test]
+    fn test_config_format_from_path() {
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("gitpublish.json")),
+            ConfigFormat::Json
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("ci/gitpublish.YML")),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("gitpublish.yaml")),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("release.conf")),
+            ConfigFormat::Toml
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_config_finds_yaml_in_repo_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = temp_dir.path();
+        git2::Repository::init(repo_root).unwrap();
+        fs::write(
+            repo_root.join("gitpublish.yaml"),
+            "branches:\n  main: yaml-{version}\n",
+        )
+        .unwrap();
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(repo_root).unwrap();
+        let config = load_config(None);
+        std::env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(
+            config.unwrap().branches.get("main"),
+            Some(&"yaml-{version}".to_string())
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_load_config_from_manifest_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_root = temp_dir.path();
+        git2::Repository::init(repo_root).unwrap();
+        fs::write(
+            repo_root.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[package.metadata.gitpublish.branches]\nmain = \"cargo-{version}\"\n",
+        )
+        .unwrap();
+        fs::write(
+            repo_root.join("package.json"),
+            r#"{ "name": "demo", "gitpublish": { "branches": { "main": "npm-{version}" } } }"#,
+        )
+        .unwrap();
+
+        let original_dir = std::env::current_dir().unwrap();
+        std::env::set_current_dir(repo_root).unwrap();
+        let from_cargo = load_config(None).map_err(|e| e.to_string());
+        fs::remove_file(repo_root.join("Cargo.toml")).unwrap();
+        let from_npm = load_config(None).map_err(|e| e.to_string());
+        fs::write(
+            repo_root.join("gitpublish.toml"),
+            "[branches]\nmain = \"file-{version}\"\n",
+        )
+        .unwrap();
+        let from_file = load_config(None).map_err(|e| e.to_string());
+        std::env::set_current_dir(original_dir).unwrap();
+
+        let main_pattern =
+            |config: Result<Config, String>| config.unwrap().branches["main"].clone();
+        assert_eq!(main_pattern(from_cargo), "cargo-{version}");
+        assert_eq!(main_pattern(from_npm), "npm-{version}");
+        assert_eq!(main_pattern(from_file), "file-{version}");
+    }
+
     #[test]
     #[serial]
     fn test_load_config_without_repo_falls_back_to_default() {
@@ -519,4 +2495,148 @@ main = "root-{version}"
 
         assert_eq!(config.branches.get("main"), Some(&"v{version}".to_string()));
     }
+
+    #[test]
+    fn test_builder_sets_sections() {
+        let config = Config::builder()
+            .branch("main", "v{version}")
+            .branch_with_hooks(
+                "release",
+                "r{version}",
+                HooksConfig {
+                    post_push: vec![HookCommand::new("./notify.sh")],
+                    ..HooksConfig::default()
+                },
+            )
+            .prerelease(PreReleaseConfig {
+                enabled: true,
+                ..PreReleaseConfig::default()
+            })
+            .behavior(BehaviorConfig {
+                skip_remote_selection: true,
+                ..BehaviorConfig::default()
+            })
+            .remote_override("origin", RemoteOverride::default())
+            .build()
+            .unwrap();
+
+        assert_eq!(config.branches.len(), 2);
+        assert!(!config.branches.contains_key("develop"));
+        assert_eq!(
+            config.hooks_for("release").scripts(HookType::PostPush),
+            [HookCommand::new("./notify.sh")]
+        );
+        assert!(config.hooks_for("main").post_push.is_empty());
+        assert!(config.prerelease.enabled);
+        assert_eq!(config.prerelease.default_identifier, "alpha");
+        assert!(config.behavior.skip_remote_selection);
+        assert!(config.remote_overrides.contains_key("origin"));
+    }
+
+    #[test]
+    fn test_builder_validates() {
+        let err = Config::builder().build().unwrap_err();
+        assert!(err.to_string().contains("at least one branch"));
+
+        let err = Config::builder()
+            .branch("main", "v{version}")
+            .branch("develop", "develop")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Configuration error: branches.develop: pattern 'develop' must contain exactly one {version} placeholder"
+        );
+
+        let err = Config::builder()
+            .branch("main", "v{version}")
+            .remote_override(
+                "origin",
+                RemoteOverride {
+                    push_url: None,
+                    tag_refspec: "refs/tags/latest".to_string(),
+                },
+            )
+            .build()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("remote_overrides.origin.tag_refspec"));
+
+        let err = Config::builder()
+            .branch("main", "v{version}")
+            .changelog(ChangelogConfig {
+                pull_request_url: "{repo_url}/pulls".to_string(),
+                ..ChangelogConfig::default()
+            })
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("changelog.pull_request_url"));
+
+        let err = Config::builder()
+            .branch("main", "v{version}")
+            .changelog(ChangelogConfig {
+                release_header: "## Release ({date})".to_string(),
+                ..ChangelogConfig::default()
+            })
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("changelog.release_header"));
+    }
+
+    #[test]
+    fn test_schema_uses_config_file_keys() {
+        let schema = serde_json::to_value(Config::schema()).unwrap();
+        let definitions = &schema["definitions"];
+
+        assert!(schema["properties"]["behavior"].is_object());
+        assert_eq!(
+            schema["properties"]["branches"]["allOf"][0]["$ref"],
+            "#/definitions/BranchesConfig"
+        );
+        assert_eq!(
+            definitions["BranchesConfig"]["additionalProperties"]["$ref"],
+            "#/definitions/BranchEntry"
+        );
+        // Hooks use their kebab-case names and accept one command or a list
+        let hooks = &definitions["HooksConfig"]["properties"];
+        assert!(hooks["post-push"].is_object());
+        assert!(hooks["post_push"].is_null());
+        assert_eq!(
+            definitions["OneOrMany"]["anyOf"].as_array().unwrap().len(),
+            2
+        );
+        assert_eq!(
+            definitions["HostKeyChecking"]["enum"],
+            serde_json::json!(["ask", "strict", "off"])
+        );
+    }
+
+    #[test]
+    fn test_branch_patterns_are_compiled_on_load() {
+        let config: Config = toml::from_str(
+            r#"
+[branches]
+main = "v{version}"
+release = { pattern = "release-{version}" }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.branches.tag_pattern("release"),
+            Some(&TagPattern::new("release-{version}"))
+        );
+        assert_eq!(config.branches.get("main"), Some(&"v{version}".to_string()));
+
+        let err = toml::from_str::<Config>("[branches]\nmain = \"v{major}.{version}\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("branches.main"), "{}", err);
+        assert!(err.contains("unknown placeholder {major}"), "{}", err);
+
+        let err = toml::from_str::<Config>("[branches]\nmain = \"latest\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("exactly one {version}"), "{}", err);
+    }
 }
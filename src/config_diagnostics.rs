@@ -0,0 +1,637 @@
+//! Readable configuration errors.
+//!
+//! Unknown keys are collected while parsing instead of being silently ignored,
+//! located in the file (TOML only) and paired with a "did you mean"
+//! suggestion. With `[behavior] strict_config = true` they are errors rather
+//! than warnings.
+
+use std::fmt;
+use std::ops::Range;
+
+use serde::de::{self, Visitor};
+use serde::Deserialize;
+
+use crate::config::{
+    AuthConfig, BehaviorConfig, ChangelogConfig, Config, ConfigFormat, ConventionalCommitsConfig,
+    HooksConfig, PatternsConfig, PreReleaseConfig, RemoteAuth, RemoteOverride,
+};
+use crate::config_migrate::{migrate_value, Deprecation, CURRENT_CONFIG_VERSION};
+use crate::error::{GitPublishError, Result};
+use crate::i18n::{tr, Msg};
+
+/// A key in the configuration file that git-publish doesn't know
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownKey {
+    /// Dotted path of the key (e.g. `behavior.skip_remote_selectoin`)
+    pub path: String,
+
+    /// 1-based line and column of the key, when it could be located
+    pub position: Option<(usize, usize)>,
+
+    /// Text of the offending line
+    pub line_text: Option<String>,
+
+    /// Closest known key at the same level
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for UnknownKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown key `{}`", self.path)?;
+        if let Some((line, column)) = self.position {
+            write!(f, " at line {}, column {}", line, column)?;
+            if let Some(text) = &self.line_text {
+                let gutter = " ".repeat(line.to_string().len());
+                let key_len = self.path.rsplit('.').next().unwrap_or_default().len();
+                write!(
+                    f,
+                    "\n{} |\n{} | {}\n{} | {}{}",
+                    gutter,
+                    line,
+                    text,
+                    gutter,
+                    " ".repeat(column - 1),
+                    "^".repeat(key_len.max(1))
+                )?;
+            }
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "\ndid you mean `{}`?", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Something worth fixing in a configuration that still loaded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigWarning {
+    /// A key git-publish doesn't know, ignored
+    UnknownKey(UnknownKey),
+    /// A key of an older `config_version`, migrated in memory
+    Deprecated(Deprecation),
+}
+
+impl ConfigWarning {
+    /// The warning as shown to the user, in the display language
+    pub fn message(&self) -> String {
+        match self {
+            ConfigWarning::UnknownKey(key) => tr(Msg::IgnoredConfigKey, &[("key", key)]),
+            ConfigWarning::Deprecated(deprecation) => tr(
+                Msg::DeprecatedConfigKey,
+                &[
+                    ("key", &deprecation.key),
+                    ("replacement", &deprecation.replacement),
+                ],
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigWarning::UnknownKey(unknown_key) => unknown_key.fmt(f),
+            ConfigWarning::Deprecated(deprecation) => deprecation.fmt(f),
+        }
+    }
+}
+
+/// Parse a configuration file's content, collecting the keys it doesn't know
+///
+/// Syntax and type errors keep the line and column reported by the parser.
+/// Unknown keys are returned, or turned into an error when the configuration
+/// enables `strict_config`. Only TOML keys are located in the file.
+///
+/// Configurations of an older `config_version` are migrated first; their
+/// deprecated keys are returned as warnings too.
+pub fn parse(content: &str, format: ConfigFormat) -> Result<(Config, Vec<ConfigWarning>)> {
+    let mut ignored = Vec::new();
+    let record = |path: serde_ignored::Path| ignored.push(segments(&path));
+    let mut config: Config = match format {
+        ConfigFormat::Toml => serde_ignored::deserialize(toml::Deserializer::new(content), record)
+            .map_err(|e| GitPublishError::config(e.to_string()))?,
+        ConfigFormat::Json => {
+            let mut deserializer = serde_json::Deserializer::from_str(content);
+            serde_ignored::deserialize(&mut deserializer, record)
+                .and_then(|config| deserializer.end().map(|()| config))
+                .map_err(|e| GitPublishError::config(e.to_string()))?
+        }
+        ConfigFormat::Yaml => {
+            serde_ignored::deserialize(serde_norway::Deserializer::from_str(content), record)
+                .map_err(|e| GitPublishError::config(e.to_string()))?
+        }
+    };
+
+    let mut deprecations = Vec::new();
+    if config.config_version != CURRENT_CONFIG_VERSION {
+        let mut value = to_value(content, format)?;
+        deprecations = migrate_value(&mut value)?;
+        if !deprecations.is_empty() {
+            ignored.clear();
+            config = from_value(value, &mut ignored, "")?;
+        }
+    }
+    check_unknown_keys(config, &ignored, deprecations, content, format, &[])
+}
+
+/// Parse a configuration embedded in another file, such as the
+/// `[package.metadata.gitpublish]` table of `Cargo.toml`
+///
+/// `table_path` leads to the configuration; `Ok(None)` means the file doesn't
+/// have it. Unknown keys are reported with their full path in the file.
+pub fn parse_embedded(
+    content: &str,
+    format: ConfigFormat,
+    table_path: &[&str],
+) -> Result<Option<(Config, Vec<ConfigWarning>)>> {
+    let location = table_path.join(".");
+    let mut value = to_value(content, format)?;
+    for key in table_path {
+        match value.as_object_mut().and_then(|object| object.remove(*key)) {
+            Some(inner) => value = inner,
+            None => return Ok(None),
+        }
+    }
+
+    let deprecations = migrate_value(&mut value)
+        .map_err(|e| GitPublishError::config(format!("{}: {}", location, e)))?;
+    let mut ignored = Vec::new();
+    let config = from_value(value, &mut ignored, &location)?;
+
+    let prefix: Vec<Segment> = table_path
+        .iter()
+        .map(|key| Segment::Key(key.to_string()))
+        .collect();
+    check_unknown_keys(config, &ignored, deprecations, content, format, &prefix).map(Some)
+}
+
+/// Parse a whole document into a generic value
+fn to_value(content: &str, format: ConfigFormat) -> Result<serde_json::Value> {
+    let value = match format {
+        ConfigFormat::Toml => toml::from_str::<toml::Table>(content)
+            .map_err(|e| GitPublishError::config(e.to_string()))
+            .and_then(|table| {
+                serde_json::to_value(table).map_err(|e| GitPublishError::config(e.to_string()))
+            })?,
+        ConfigFormat::Json => {
+            serde_json::from_str(content).map_err(|e| GitPublishError::config(e.to_string()))?
+        }
+        ConfigFormat::Yaml => {
+            serde_norway::from_str(content).map_err(|e| GitPublishError::config(e.to_string()))?
+        }
+    };
+    Ok(value)
+}
+
+/// Deserialize a configuration from a generic value, collecting ignored keys
+///
+/// Errors are prefixed with `location` as the value has no line numbers.
+fn from_value(
+    value: serde_json::Value,
+    ignored: &mut Vec<Vec<Segment>>,
+    location: &str,
+) -> Result<Config> {
+    serde_ignored::deserialize(value, |path| ignored.push(segments(&path))).map_err(|e| {
+        match location {
+            "" => GitPublishError::config(e.to_string()),
+            location => GitPublishError::config(format!("{}: {}", location, e)),
+        }
+    })
+}
+
+/// Describe the ignored keys, failing when `strict_config` is enabled
+fn check_unknown_keys(
+    config: Config,
+    ignored: &[Vec<Segment>],
+    deprecations: Vec<Deprecation>,
+    content: &str,
+    format: ConfigFormat,
+    prefix: &[Segment],
+) -> Result<(Config, Vec<ConfigWarning>)> {
+    let unknown_keys: Vec<UnknownKey> = ignored
+        .iter()
+        .map(|path| unknown_key(content, format, prefix, path))
+        .collect();
+
+    if config.behavior.strict_config && !unknown_keys.is_empty() {
+        let details: Vec<String> = unknown_keys.iter().map(UnknownKey::to_string).collect();
+        return Err(GitPublishError::config(format!(
+            "strict_config is enabled and the configuration has unknown keys:\n{}",
+            details.join("\n")
+        )));
+    }
+
+    let warnings = deprecations
+        .into_iter()
+        .map(ConfigWarning::Deprecated)
+        .chain(unknown_keys.into_iter().map(ConfigWarning::UnknownKey))
+        .collect();
+    Ok((config, warnings))
+}
+
+/// A segment of a key path: a table key or an array index
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Flatten a serde_ignored path into its key and index segments
+fn segments(path: &serde_ignored::Path) -> Vec<Segment> {
+    use serde_ignored::Path;
+
+    match path {
+        Path::Root => Vec::new(),
+        Path::Seq { parent, index } => {
+            let mut segments = segments(parent);
+            segments.push(Segment::Index(*index));
+            segments
+        }
+        Path::Map { parent, key } => {
+            let mut segments = segments(parent);
+            segments.push(Segment::Key(key.clone()));
+            segments
+        }
+        Path::Some { parent }
+        | Path::NewtypeStruct { parent }
+        | Path::NewtypeVariant { parent } => segments(parent),
+    }
+}
+
+fn unknown_key(
+    content: &str,
+    format: ConfigFormat,
+    prefix: &[Segment],
+    path: &[Segment],
+) -> UnknownKey {
+    let suggestion = match path.split_last() {
+        Some((Segment::Key(key), parent)) => suggest(key, known_keys(parent)),
+        _ => None,
+    };
+
+    let path = [prefix, path].concat();
+    let dotted: Vec<String> = path
+        .iter()
+        .map(|segment| match segment {
+            Segment::Key(key) => key.clone(),
+            Segment::Index(index) => index.to_string(),
+        })
+        .collect();
+
+    let position = match format {
+        ConfigFormat::Toml => {
+            key_span(content, &path).map(|span| line_and_column(content, span.start))
+        }
+        ConfigFormat::Json | ConfigFormat::Yaml => None,
+    };
+    let line_text =
+        position.and_then(|(line, _)| content.lines().nth(line - 1).map(str::to_string));
+
+    UnknownKey {
+        path: dotted.join("."),
+        position,
+        line_text,
+        suggestion,
+    }
+}
+
+/// Byte range of the last key of `path` in the document
+fn key_span(content: &str, path: &[Segment]) -> Option<Range<usize>> {
+    let document = toml_edit::ImDocument::parse(content).ok()?;
+    let mut table: &dyn toml_edit::TableLike = document.as_table();
+    let mut item = document.as_item();
+    let mut span = None;
+    for segment in path {
+        match segment {
+            Segment::Key(key) => {
+                let (found_key, found_item) = table.get_key_value(key)?;
+                span = found_key.span();
+                item = found_item;
+                if let Some(found_table) = found_item.as_table_like() {
+                    table = found_table;
+                }
+            }
+            Segment::Index(index) => match item {
+                toml_edit::Item::ArrayOfTables(tables) => table = tables.get(*index)?,
+                _ => return span,
+            },
+        }
+    }
+    span
+}
+
+/// 1-based line and column (in characters) of a byte offset
+fn line_and_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Closest candidate to a misspelled key, if any is close enough
+fn suggest(key: &str, candidates: &[&str]) -> Option<String> {
+    let max_distance = (key.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|candidate| (strsim::damerau_levenshtein(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Keys accepted in the table at `parent`
+fn known_keys(parent: &[Segment]) -> &'static [&'static str] {
+    let keys: Vec<&str> = parent
+        .iter()
+        .map(|segment| match segment {
+            Segment::Key(key) => key.as_str(),
+            Segment::Index(_) => "",
+        })
+        .collect();
+    match keys.as_slice() {
+        [] => field_names::<Config>(),
+        ["conventional_commits"] => field_names::<ConventionalCommitsConfig>(),
+        ["patterns"] => field_names::<PatternsConfig>(),
+        ["behavior"] => field_names::<BehaviorConfig>(),
+        ["prerelease"] => field_names::<PreReleaseConfig>(),
+        ["remote_overrides", _] => field_names::<RemoteOverride>(),
+        ["auth"] => field_names::<AuthConfig>(),
+        ["auth", "remotes", _] => field_names::<RemoteAuth>(),
+        ["hooks"] => field_names::<HooksConfig>(),
+        ["changelog"] => field_names::<ChangelogConfig>(),
+        _ => &[],
+    }
+}
+
+/// Field names of a struct deriving `Deserialize`
+fn field_names<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+    let mut fields = None;
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields.unwrap_or_default()
+}
+
+/// Deserializer that only records the fields a struct asks for
+struct FieldNames<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> de::Deserializer<'de> for FieldNames<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        _visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> std::result::Result<V::Value, Self::Error> {
+        *self.0 = Some(fields);
+        Err(de::Error::custom("field names recorded"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::HookCommand;
+
+    fn unknown_keys(warnings: Vec<ConfigWarning>) -> Vec<UnknownKey> {
+        warnings
+            .into_iter()
+            .filter_map(|warning| match warning {
+                ConfigWarning::UnknownKey(key) => Some(key),
+                ConfigWarning::Deprecated(_) => None,
+            })
+            .collect()
+    }
+
+    fn parse_toml(content: &str) -> Result<(Config, Vec<UnknownKey>)> {
+        parse(content, ConfigFormat::Toml)
+            .map(|(config, warnings)| (config, unknown_keys(warnings)))
+    }
+
+    #[test]
+    fn test_misspelled_section_is_located_and_suggested() {
+        let content =
+            "[branches]\nmain = \"v{version}\"\n\n[behaviour]\nskip_remote_selection = true\n";
+        let (config, unknown) = parse_toml(content).unwrap();
+
+        assert!(!config.behavior.skip_remote_selection);
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].path, "behaviour");
+        assert_eq!(unknown[0].position, Some((4, 2)));
+        assert_eq!(unknown[0].line_text.as_deref(), Some("[behaviour]"));
+        assert_eq!(unknown[0].suggestion.as_deref(), Some("behavior"));
+        assert_eq!(
+            unknown[0].to_string(),
+            "unknown key `behaviour` at line 4, column 2\n  |\n4 | [behaviour]\n  |  ^^^^^^^^^\ndid you mean `behavior`?"
+        );
+    }
+
+    #[test]
+    fn test_misspelled_nested_keys() {
+        let content = r#"
+[behavior]
+skip_remote_selectoin = true
+
+[remote_overrides.origin]
+push_ur = "git@example.com:repo.git"
+
+[hooks]
+post_pus = "./notify.sh"
+unrelated = 1
+"#;
+        let (_, unknown) = parse_toml(content).unwrap();
+        let find = |path: &str| {
+            let key = unknown.iter().find(|key| key.path == path).unwrap();
+            (key.position, key.suggestion.as_deref())
+        };
+
+        assert_eq!(unknown.len(), 4);
+        assert_eq!(
+            find("behavior.skip_remote_selectoin"),
+            (Some((3, 1)), Some("skip_remote_selection"))
+        );
+        assert_eq!(
+            find("remote_overrides.origin.push_ur"),
+            (Some((6, 1)), Some("push_url"))
+        );
+        assert_eq!(find("hooks.post_pus"), (Some((9, 1)), Some("post_push")));
+        assert_eq!(find("hooks.unrelated"), (Some((10, 1)), None));
+    }
+
+    #[test]
+    fn test_strict_config_rejects_unknown_keys() {
+        let content = "[behavior]\nstrict_config = true\n\n[prerelase]\nenabled = true\n";
+        let err = parse_toml(content).unwrap_err().to_string();
+
+        assert!(err.contains("strict_config"), "{}", err);
+        assert!(
+            err.contains("unknown key `prerelase` at line 4, column 2"),
+            "{}",
+            err
+        );
+        assert!(err.contains("did you mean `prerelease`?"), "{}", err);
+
+        let content = "[behavior]\nstrict_config = true\n\n[prerelease]\nenabled = true\n";
+        assert!(parse_toml(content).is_ok());
+    }
+
+    #[test]
+    fn test_syntax_error_reports_line_and_column() {
+        let err = parse_toml("[behavior]\nhost_key_checking = \"sometimes\"\n")
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("line 2, column 21"), "{}", err);
+        assert!(err.contains("sometimes"), "{}", err);
+    }
+
+    #[test]
+    fn test_suggest_only_close_matches() {
+        assert_eq!(
+            suggest("prerelase", &["prerelease", "patterns"]),
+            Some("prerelease".to_string())
+        );
+        assert_eq!(suggest("zzz", &["prerelease", "patterns"]), None);
+    }
+
+    #[test]
+    fn test_json_and_yaml_share_the_config_model() {
+        let json = r#"{
+  "branches": { "main": "v{version}", "release": { "pattern": "r{version}" } },
+  "behavior": { "skip_remote_selection": true, "on_existing_tag": "skip" },
+  "hooks": { "post-push": ["./notify.sh"] },
+  "behaviour": {}
+}"#;
+        let yaml = r#"
+branches:
+  main: v{version}
+  release:
+    pattern: r{version}
+behavior:
+  skip_remote_selection: true
+  on_existing_tag: skip
+hooks:
+  post-push:
+    - ./notify.sh
+behaviour: {}
+"#;
+        for (content, format) in [(json, ConfigFormat::Json), (yaml, ConfigFormat::Yaml)] {
+            let (config, warnings) = parse(content, format).unwrap();
+            let unknown = unknown_keys(warnings);
+
+            assert_eq!(
+                config.branches.tag_pattern("release").unwrap().as_str(),
+                "r{version}"
+            );
+            assert!(config.behavior.skip_remote_selection);
+            assert_eq!(
+                config.behavior.on_existing_tag,
+                crate::config::ExistingTagPolicy::Skip
+            );
+            assert_eq!(config.hooks.post_push.len(), 1);
+            assert_eq!(unknown.len(), 1);
+            assert_eq!(unknown[0].path, "behaviour");
+            assert_eq!(unknown[0].position, None);
+            assert_eq!(unknown[0].suggestion.as_deref(), Some("behavior"));
+        }
+    }
+
+    #[test]
+    fn test_json_and_yaml_errors_report_location() {
+        let err = parse(
+            "{\n  \"behavior\": { \"hook_echo\": \"yes\" }\n}",
+            ConfigFormat::Json,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("line 2"), "{}", err);
+
+        let err = parse("behavior:\n  hook_echo: [1\n", ConfigFormat::Yaml)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("line"), "{}", err);
+    }
+
+    #[test]
+    fn test_embedded_config() {
+        let cargo = r#"[package]
+name = "demo"
+version = "0.1.0"
+
+[package.metadata.gitpublish.branches]
+main = "v{version}"
+
+[package.metadata.gitpublish.behavor]
+hook_echo = true
+"#;
+        let (config, warnings) = parse_embedded(
+            cargo,
+            ConfigFormat::Toml,
+            &["package", "metadata", "gitpublish"],
+        )
+        .unwrap()
+        .unwrap();
+
+        let unknown = unknown_keys(warnings);
+        assert_eq!(config.branches["main"], "v{version}");
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].path, "package.metadata.gitpublish.behavor");
+        assert_eq!(unknown[0].position, Some((8, 30)));
+        assert_eq!(unknown[0].suggestion.as_deref(), Some("behavior"));
+
+        let npm = r#"{ "name": "demo", "gitpublish": { "branches": { "main": 1 } } }"#;
+        let err = parse_embedded(npm, ConfigFormat::Json, &["gitpublish"])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("gitpublish:"), "{}", err);
+
+        let without = r#"{ "name": "demo" }"#;
+        assert!(parse_embedded(without, ConfigFormat::Json, &["gitpublish"])
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_deprecated_keys_are_migrated() {
+        let content = "[hooks]\npost_push = \"./notify.sh\"\n\n[branches.main]\npattern = \"v{version}\"\nhooks = { pre_tag_create = \"./check.sh\" }\n";
+        let (config, warnings) = parse(content, ConfigFormat::Toml).unwrap();
+
+        assert_eq!(config.hooks.post_push, [HookCommand::new("./notify.sh")]);
+        assert_eq!(
+            config.hooks_for("main").pre_tag_create,
+            [HookCommand::new("./check.sh")]
+        );
+        let deprecated: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(deprecated.len(), 2, "{:?}", deprecated);
+        assert!(deprecated[0].starts_with("`hooks.post_push` is deprecated, use `hooks.post-push`"));
+        assert!(deprecated[1]
+            .starts_with("`branches.main.hooks.pre_tag_create` is deprecated, use `branches.main.hooks.pre-tag-create`"));
+
+        // Current configurations aren't migrated
+        let current = format!("config_version = {}\n{}", CURRENT_CONFIG_VERSION, content);
+        assert!(parse(&current, ConfigFormat::Toml).unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn test_newer_config_version_is_rejected() {
+        let content = format!("config_version = {}\n", CURRENT_CONFIG_VERSION + 1);
+        let err = parse(&content, ConfigFormat::Toml).unwrap_err().to_string();
+        assert!(
+            err.contains("newer than this git-publish supports"),
+            "{}",
+            err
+        );
+    }
+}
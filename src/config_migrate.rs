@@ -0,0 +1,444 @@
+//! Configuration versioning.
+//!
+//! `config_version` records which schema a configuration was written for.
+//! Older configurations are migrated in memory when loaded, with a warning
+//! for every deprecated key, and `git-publish config migrate` rewrites the
+//! file so the warnings go away.
+
+use std::fmt;
+use std::fs;
+
+use serde_json::Value;
+
+use crate::config::{ConfigFormat, ConfigSource};
+use crate::error::{GitPublishError, Result};
+
+/// Schema version written by this release of git-publish
+pub const CURRENT_CONFIG_VERSION: u32 = 1;
+
+/// A key renamed between two schema versions
+struct Rename {
+    /// Table holding the key; `*` matches any key (e.g. a branch name)
+    table: &'static [&'static str],
+    from: &'static str,
+    to: &'static str,
+}
+
+/// Changes made to reach `version` from the version before it
+struct Migration {
+    version: u32,
+    renames: &'static [Rename],
+}
+
+/// Every migration, oldest first
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    // Hook names are spelled like the hook files, the snake_case
+    // spellings were only ever accepted as aliases
+    renames: &[
+        Rename {
+            table: &["hooks"],
+            from: "pre_tag_create",
+            to: "pre-tag-create",
+        },
+        Rename {
+            table: &["hooks"],
+            from: "post_tag_create",
+            to: "post-tag-create",
+        },
+        Rename {
+            table: &["hooks"],
+            from: "post_push",
+            to: "post-push",
+        },
+        Rename {
+            table: &["branches", "*", "hooks"],
+            from: "pre_tag_create",
+            to: "pre-tag-create",
+        },
+        Rename {
+            table: &["branches", "*", "hooks"],
+            from: "post_tag_create",
+            to: "post-tag-create",
+        },
+        Rename {
+            table: &["branches", "*", "hooks"],
+            from: "post_push",
+            to: "post-push",
+        },
+    ],
+}];
+
+/// A deprecated key found in the configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Dotted path of the deprecated key
+    pub key: String,
+    /// Dotted path of the key replacing it
+    pub replacement: String,
+}
+
+impl fmt::Display for Deprecation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is deprecated, use `{}` (run `git-publish config migrate` to update the file)",
+            self.key, self.replacement
+        )
+    }
+}
+
+/// Schema version declared by a configuration; unversioned ones are version 0
+fn declared_version(config_version: Option<&Value>) -> Result<u32> {
+    let version = match config_version {
+        None => 0,
+        Some(value) => value
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| {
+                GitPublishError::config("config_version must be a non-negative integer")
+            })?,
+    };
+    if version > CURRENT_CONFIG_VERSION {
+        return Err(GitPublishError::config(format!(
+            "config_version {} is newer than this git-publish supports ({}); please upgrade git-publish",
+            version, CURRENT_CONFIG_VERSION
+        )));
+    }
+    Ok(version)
+}
+
+/// Bring a parsed configuration up to [`CURRENT_CONFIG_VERSION`]
+///
+/// Returns the deprecated keys that were renamed; `config_version` itself is
+/// left alone.
+pub fn migrate_value(config: &mut Value) -> Result<Vec<Deprecation>> {
+    let version = declared_version(config.get("config_version"))?;
+    let mut deprecations = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version > version) {
+        for rename in migration.renames {
+            rename_in_value(
+                config,
+                rename.table,
+                rename,
+                &mut Vec::new(),
+                &mut deprecations,
+            );
+        }
+    }
+    Ok(deprecations)
+}
+
+fn rename_in_value(
+    value: &mut Value,
+    table: &[&str],
+    rename: &Rename,
+    path: &mut Vec<String>,
+    deprecations: &mut Vec<Deprecation>,
+) {
+    let Some(map) = value.as_object_mut() else {
+        return;
+    };
+    match table.split_first() {
+        Some((&"*", rest)) => {
+            for (key, inner) in map.iter_mut() {
+                path.push(key.clone());
+                rename_in_value(inner, rest, rename, path, deprecations);
+                path.pop();
+            }
+        }
+        Some((key, rest)) => {
+            if let Some(inner) = map.get_mut(*key) {
+                path.push(key.to_string());
+                rename_in_value(inner, rest, rename, path, deprecations);
+                path.pop();
+            }
+        }
+        None => {
+            if !map.contains_key(rename.from) || map.contains_key(rename.to) {
+                return;
+            }
+            // Rebuild the map to keep the key where it was
+            *map = std::mem::take(map)
+                .into_iter()
+                .map(|(key, inner)| match key == rename.from {
+                    true => (rename.to.to_string(), inner),
+                    false => (key, inner),
+                })
+                .collect();
+            deprecations.push(deprecation(path, rename));
+        }
+    }
+}
+
+fn deprecation(path: &[String], rename: &Rename) -> Deprecation {
+    let dotted = |key: &str| {
+        path.iter()
+            .map(String::as_str)
+            .chain([key])
+            .collect::<Vec<_>>()
+            .join(".")
+    };
+    Deprecation {
+        key: dotted(rename.from),
+        replacement: dotted(rename.to),
+    }
+}
+
+/// Result of [`migrate_source`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Version the configuration declared before migrating
+    pub from_version: u32,
+    /// Deprecated keys that were renamed
+    pub deprecations: Vec<Deprecation>,
+}
+
+impl MigrationReport {
+    /// Whether the file was already at the current version
+    pub fn is_up_to_date(&self) -> bool {
+        self.from_version == CURRENT_CONFIG_VERSION && self.deprecations.is_empty()
+    }
+}
+
+/// Rewrite a configuration at the current schema version
+///
+/// TOML is edited in place, keeping comments and formatting; JSON and YAML
+/// are re-serialized. Nothing is written when the file is up to date.
+pub fn migrate_source(source: &ConfigSource) -> Result<MigrationReport> {
+    let content = fs::read_to_string(source.path())?;
+    let table_path = source.table_path();
+    let (migrated, report) = match source.format() {
+        ConfigFormat::Toml => migrate_toml(&content, table_path)?,
+        ConfigFormat::Json => {
+            let mut document: Value = serde_json::from_str(&content)
+                .map_err(|e| GitPublishError::config(e.to_string()))?;
+            let report = migrate_embedded_value(&mut document, table_path)?;
+            let mut migrated = serde_json::to_string_pretty(&document)
+                .map_err(|e| GitPublishError::config(e.to_string()))?;
+            migrated.push('\n');
+            (migrated, report)
+        }
+        ConfigFormat::Yaml => {
+            let mut document: Value = serde_norway::from_str(&content)
+                .map_err(|e| GitPublishError::config(e.to_string()))?;
+            let report = migrate_embedded_value(&mut document, table_path)?;
+            let migrated = serde_norway::to_string(&document)
+                .map_err(|e| GitPublishError::config(e.to_string()))?;
+            (migrated, report)
+        }
+    };
+    if !report.is_up_to_date() {
+        fs::write(source.path(), migrated)?;
+    }
+    Ok(report)
+}
+
+/// Migrate the configuration at `table_path` in a JSON/YAML document
+fn migrate_embedded_value(document: &mut Value, table_path: &[&str]) -> Result<MigrationReport> {
+    let mut config = document;
+    for key in table_path {
+        config = config.get_mut(*key).ok_or_else(|| {
+            GitPublishError::config(format!("{} not found", table_path.join(".")))
+        })?;
+    }
+    let from_version = declared_version(config.get("config_version"))?;
+    let deprecations = migrate_value(config)?;
+    let Some(map) = config.as_object_mut() else {
+        return Err(GitPublishError::config("configuration must be a table"));
+    };
+    if from_version != CURRENT_CONFIG_VERSION || !deprecations.is_empty() {
+        // config_version goes first so it is seen before anything else
+        let rest = std::mem::take(map);
+        map.insert(
+            "config_version".to_string(),
+            Value::from(CURRENT_CONFIG_VERSION),
+        );
+        map.extend(rest.into_iter().filter(|(key, _)| key != "config_version"));
+    }
+    Ok(MigrationReport {
+        from_version,
+        deprecations,
+    })
+}
+
+/// Migrate a TOML document in place, keeping comments and formatting
+fn migrate_toml(content: &str, table_path: &[&str]) -> Result<(String, MigrationReport)> {
+    let mut document: toml_edit::DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| GitPublishError::config(e.to_string()))?;
+
+    let mut config: &mut dyn toml_edit::TableLike = document.as_table_mut();
+    for key in table_path {
+        config = config
+            .get_mut(key)
+            .and_then(toml_edit::Item::as_table_like_mut)
+            .ok_or_else(|| {
+                GitPublishError::config(format!("{} not found", table_path.join(".")))
+            })?;
+    }
+
+    let version_value = config
+        .get("config_version")
+        .and_then(toml_edit::Item::as_integer)
+        .map(Value::from);
+    if config.contains_key("config_version") && version_value.is_none() {
+        return Err(GitPublishError::config(
+            "config_version must be a non-negative integer",
+        ));
+    }
+    let from_version = declared_version(version_value.as_ref())?;
+
+    let mut deprecations = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version > from_version) {
+        for rename in migration.renames {
+            rename_in_toml(
+                config,
+                rename.table,
+                rename,
+                &mut Vec::new(),
+                &mut deprecations,
+            );
+        }
+    }
+    config.insert(
+        "config_version",
+        toml_edit::value(i64::from(CURRENT_CONFIG_VERSION)),
+    );
+
+    Ok((
+        document.to_string(),
+        MigrationReport {
+            from_version,
+            deprecations,
+        },
+    ))
+}
+
+fn rename_in_toml(
+    table: &mut dyn toml_edit::TableLike,
+    path_in_table: &[&str],
+    rename: &Rename,
+    path: &mut Vec<String>,
+    deprecations: &mut Vec<Deprecation>,
+) {
+    match path_in_table.split_first() {
+        Some((&"*", rest)) => {
+            for (key, item) in table.iter_mut() {
+                if let Some(inner) = item.as_table_like_mut() {
+                    path.push(key.get().to_string());
+                    rename_in_toml(inner, rest, rename, path, deprecations);
+                    path.pop();
+                }
+            }
+        }
+        Some((key, rest)) => {
+            if let Some(inner) = table
+                .get_mut(key)
+                .and_then(toml_edit::Item::as_table_like_mut)
+            {
+                path.push(key.to_string());
+                rename_in_toml(inner, rest, rename, path, deprecations);
+                path.pop();
+            }
+        }
+        None => {
+            if table.contains_key(rename.to) {
+                return;
+            }
+            // Carry the comments above the key over to its new name
+            let Some(decor) = table.key(rename.from).map(|key| key.leaf_decor().clone()) else {
+                return;
+            };
+            if let Some(item) = table.remove(rename.from) {
+                let key = toml_edit::Key::new(rename.to).with_leaf_decor(decor);
+                table.entry_format(&key).or_insert(item);
+                deprecations.push(deprecation(path, rename));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::ManifestKind;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_migrate_toml_keeps_comments() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("gitpublish.toml");
+        fs::write(
+            &path,
+            "# Release settings\n[branches]\nmain = \"v{version}\"\n\n[hooks]\n# Tell the team\npost_push = \"./notify.sh\"\n",
+        )
+        .unwrap();
+        let source = ConfigSource::File(path.clone());
+
+        let report = migrate_source(&source).unwrap();
+        assert_eq!(report.from_version, 0);
+        assert_eq!(
+            report.deprecations,
+            [Deprecation {
+                key: "hooks.post_push".to_string(),
+                replacement: "hooks.post-push".to_string(),
+            }]
+        );
+        let migrated = fs::read_to_string(&path).unwrap();
+        assert!(migrated.starts_with("config_version = 1\n# Release settings\n[branches]"));
+        assert!(migrated.contains("# Tell the team\npost-push = \"./notify.sh\""));
+
+        let again = migrate_source(&source).unwrap();
+        assert!(again.is_up_to_date());
+        assert_eq!(fs::read_to_string(&path).unwrap(), migrated);
+    }
+
+    #[test]
+    fn test_migrate_embedded_configs() {
+        let temp_dir = TempDir::new().unwrap();
+        let cargo_path = temp_dir.path().join("Cargo.toml");
+        fs::write(
+            &cargo_path,
+            "[package]\nname = \"demo\"\n\n[package.metadata.gitpublish.hooks]\npre_tag_create = \"./check.sh\"\n",
+        )
+        .unwrap();
+        let report = migrate_source(&ConfigSource::Manifest {
+            path: cargo_path.clone(),
+            kind: ManifestKind::Cargo,
+            table_path: &["package", "metadata", "gitpublish"],
+        })
+        .unwrap();
+        assert_eq!(report.deprecations[0].key, "hooks.pre_tag_create");
+        let migrated: toml::Table =
+            toml::from_str(&fs::read_to_string(&cargo_path).unwrap()).unwrap();
+        let config = &migrated["package"]["metadata"]["gitpublish"];
+        assert_eq!(config["config_version"].as_integer(), Some(1));
+        assert_eq!(
+            config["hooks"]["pre-tag-create"].as_str(),
+            Some("./check.sh")
+        );
+        assert_eq!(migrated["package"]["name"].as_str(), Some("demo"));
+
+        let npm_path = temp_dir.path().join("package.json");
+        fs::write(
+            &npm_path,
+            r#"{"name": "demo", "version": "1.0.0", "gitpublish": {"hooks": {"post_push": "./notify.sh", "env": {}}}}"#,
+        )
+        .unwrap();
+        migrate_source(&ConfigSource::Manifest {
+            path: npm_path.clone(),
+            kind: ManifestKind::Npm,
+            table_path: &["gitpublish"],
+        })
+        .unwrap();
+        let migrated = fs::read_to_string(&npm_path).unwrap();
+        let name = migrated.find("\"name\"").unwrap();
+        let version = migrated.find("\"version\"").unwrap();
+        let config_version = migrated.find("\"config_version\": 1").unwrap();
+        let post_push = migrated.find("\"post-push\"").unwrap();
+        let env = migrated.find("\"env\"").unwrap();
+        assert!(name < version && version < config_version);
+        assert!(config_version < post_push && post_push < env);
+    }
+}
@@ -0,0 +1,344 @@
+//! Static HTML release dashboard for `git-publish report --html`.
+//!
+//! Puts the release report, the release statistics and the changelogs of the
+//! most recent releases on a single self-contained page (`index.html`, with
+//! inline styles and no scripts), so CI can publish it as an artifact or on a
+//! static site without running a server.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::config::Config;
+use crate::domain::changelog::escape_html;
+use crate::domain::Changelog;
+use crate::git_ops::GitRepo;
+use crate::i18n::{self, text, tr, Msg};
+use crate::report::ReleaseReport;
+use crate::stats::{self, ReleaseStats};
+
+/// Name of the page written to the output directory
+pub const INDEX_FILE: &str = "index.html";
+
+/// Changelogs shown on the page, newest releases first
+const RECENT_CHANGELOGS: usize = 10;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:960px;margin:2rem auto;padding:0 1rem;color:#1f2328}\
+table{border-collapse:collapse;margin-bottom:1rem}\
+th,td{border:1px solid #d0d7de;padding:.3rem .6rem;text-align:left}\
+th{background:#f6f8fa}\
+.meta{color:#59636e}\
+section.release{border-top:1px solid #d0d7de;margin-top:1.5rem}";
+
+/// The release notes of one release
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseNotes {
+    pub branch: String,
+    pub tag: String,
+    /// Release date (`YYYY-MM-DD`, UTC)
+    pub date: String,
+    pub changelog: Changelog,
+}
+
+/// Everything shown on the dashboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dashboard {
+    pub report: ReleaseReport,
+    pub stats: ReleaseStats,
+    /// Changelogs of the latest releases in the report, newest first
+    pub changelogs: Vec<ReleaseNotes>,
+}
+
+impl Dashboard {
+    /// Render the dashboard as a complete HTML page
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "<!DOCTYPE html>");
+        let _ = writeln!(
+            out,
+            "<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">",
+            i18n::locale().tag()
+        );
+        let _ = writeln!(
+            out,
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">"
+        );
+        let _ = writeln!(out, "<title>{}</title>", text(Msg::DashboardTitle));
+        let _ = writeln!(out, "<style>{}</style>\n</head>\n<body>", STYLE);
+        let _ = writeln!(out, "<h1>{}</h1>", text(Msg::DashboardTitle));
+        let meta = tr(
+            Msg::DashboardMeta,
+            &[
+                ("since", &self.report.since),
+                ("until", &self.report.until),
+                ("releases", &self.report.release_count()),
+                ("commits", &self.report.commit_count()),
+                ("contributors", &self.report.contributors().len()),
+            ],
+        );
+        let _ = writeln!(out, "<p class=\"meta\">{}</p>", meta);
+
+        self.write_stats(&mut out);
+        self.write_releases(&mut out);
+        self.write_changelogs(&mut out);
+
+        let _ = writeln!(out, "</body>\n</html>");
+        out
+    }
+
+    fn write_stats(&self, out: &mut String) {
+        let _ = writeln!(out, "<h2>{}</h2>", text(Msg::DashboardStatistics));
+        if self.stats.branches.is_empty() {
+            let _ = writeln!(out, "<p>{}</p>", text(Msg::DashboardNoBranches));
+            return;
+        }
+        write_table_header(
+            out,
+            &[
+                Msg::ColumnBranch,
+                Msg::ColumnReleases,
+                Msg::ColumnFirst,
+                Msg::ColumnLatest,
+                Msg::ColumnDaysBetween,
+                Msg::ColumnCommitsPerRelease,
+                Msg::ColumnMajor,
+                Msg::ColumnMinor,
+                Msg::ColumnPatch,
+                Msg::ColumnPrerelease,
+            ],
+        );
+        let average =
+            |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{:.1}", v));
+        for branch in &self.stats.branches {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+                 <td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&branch.branch),
+                branch.release_count,
+                branch.first_release.as_deref().unwrap_or("-"),
+                branch.latest_release.as_deref().unwrap_or("-"),
+                average(branch.average_days_between_releases),
+                average(branch.average_commits_per_release),
+                branch.bumps.major,
+                branch.bumps.minor,
+                branch.bumps.patch,
+                branch.bumps.prerelease
+            );
+        }
+        let _ = writeln!(out, "</table>");
+    }
+
+    fn write_releases(&self, out: &mut String) {
+        let _ = writeln!(out, "<h2>{}</h2>", text(Msg::DashboardReleases));
+        for branch in &self.report.branches {
+            let _ = writeln!(out, "<h3>{}</h3>", escape_html(&branch.branch));
+            if branch.releases.is_empty() {
+                let _ = writeln!(out, "<p>{}</p>", text(Msg::DashboardNoReleases));
+                continue;
+            }
+            write_table_header(
+                out,
+                &[
+                    Msg::ColumnTag,
+                    Msg::ColumnDate,
+                    Msg::ColumnCommits,
+                    Msg::ColumnContributors,
+                ],
+            );
+            for release in &branch.releases {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&release.tag),
+                    release.date,
+                    release.commit_count,
+                    escape_html(&release.contributors.join(", "))
+                );
+            }
+            let _ = writeln!(out, "</table>");
+        }
+    }
+
+    fn write_changelogs(&self, out: &mut String) {
+        let _ = writeln!(out, "<h2>{}</h2>", text(Msg::DashboardChangelogs));
+        if self.changelogs.is_empty() {
+            let _ = writeln!(out, "<p>{}</p>", text(Msg::DashboardNoReleases));
+            return;
+        }
+        for notes in &self.changelogs {
+            let _ = writeln!(
+                out,
+                "<section class=\"release\">\n<h3>{} <span class=\"meta\">{}, {}</span></h3>",
+                escape_html(&notes.tag),
+                escape_html(&notes.branch),
+                notes.date
+            );
+            if notes.changelog.is_empty() {
+                let _ = writeln!(out, "<p>{}</p>", text(Msg::DashboardNoChanges));
+            } else {
+                out.push_str(&notes.changelog.to_html());
+            }
+            let _ = writeln!(out, "</section>");
+        }
+    }
+
+    /// Write the page to `dir`, creating the directory if needed
+    ///
+    /// # Returns
+    /// * `Ok(path)` - Path of the written page
+    pub fn write_to(&self, dir: &Path) -> Result<PathBuf> {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(INDEX_FILE);
+        fs::write(&path, self.to_html())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Write a table's opening tag and its header row, labelled in the current locale
+fn write_table_header(out: &mut String, columns: &[Msg]) {
+    let _ = write!(out, "<table>\n<tr>");
+    for &column in columns {
+        let _ = write!(out, "<th>{}</th>", text(column));
+    }
+    let _ = writeln!(out, "</tr>");
+}
+
+/// Collect the dashboard of `report`: the statistics of the same branches and
+/// the changelogs of its latest releases
+///
+/// # Arguments
+/// * `git_repo` - Repository to read tags and history from
+/// * `config` - Configuration providing branches, tag patterns and the changelog layout
+/// * `branch` - Only include this branch instead of every configured branch
+/// * `report` - Releases of the reporting period
+/// * `repo_url` - Web URL of the repository, for pull request links
+pub fn build_dashboard(
+    git_repo: &GitRepo,
+    config: &Config,
+    branch: Option<&str>,
+    report: ReleaseReport,
+    repo_url: Option<&str>,
+) -> Result<Dashboard> {
+    let stats = stats::build_stats(git_repo, config, branch)?;
+
+    let mut changelogs: Vec<(i64, ReleaseNotes)> = Vec::new();
+    for branch_report in &report.branches {
+        let pattern = config
+            .branches
+            .get(&branch_report.branch)
+            .map(|s| s.as_str());
+        // The whole history, as the previous release may predate the period
+        let releases = git_repo.get_tag_releases_on_branch(&branch_report.branch, pattern)?;
+        for (index, release) in releases.iter().enumerate() {
+            let Some(summary) = branch_report
+                .releases
+                .iter()
+                .find(|summary| summary.tag == release.tag)
+            else {
+                continue;
+            };
+            let previous_tag = index
+                .checked_sub(1)
+                .map(|previous| releases[previous].tag.as_str());
+            let messages = git_repo.get_release_commit_messages(&release.tag, previous_tag)?;
+            let mut changelog = Changelog::from_commits_with_indicators(
+                &messages,
+                &config.conventional_commits.breaking_change_indicators,
+            )
+            .with_layout(config.changelog.layout);
+            if let Some(template) =
+                repo_url.and_then(|url| config.changelog.pull_request_link_template(url))
+            {
+                changelog = changelog.with_pull_request_links(template);
+            }
+            changelogs.push((
+                release.time,
+                ReleaseNotes {
+                    branch: branch_report.branch.clone(),
+                    tag: release.tag.clone(),
+                    date: summary.date.clone(),
+                    changelog,
+                },
+            ));
+        }
+    }
+    changelogs.sort_by_key(|(time, _)| std::cmp::Reverse(*time));
+    changelogs.truncate(RECENT_CHANGELOGS);
+
+    Ok(Dashboard {
+        report,
+        stats,
+        changelogs: changelogs.into_iter().map(|(_, notes)| notes).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{BranchReport, ReleaseSummary};
+    use crate::stats::{BranchStats, BumpCounts};
+
+    fn dashboard() -> Dashboard {
+        Dashboard {
+            report: ReleaseReport {
+                since: "2024-03-01".to_string(),
+                until: "2024-03-31".to_string(),
+                branches: vec![BranchReport {
+                    branch: "main".to_string(),
+                    releases: vec![ReleaseSummary {
+                        tag: "v1.1.0".to_string(),
+                        date: "2024-03-10".to_string(),
+                        commit_count: 2,
+                        contributors: vec!["Alice <ops>".to_string()],
+                    }],
+                }],
+            },
+            stats: ReleaseStats {
+                branches: vec![BranchStats {
+                    branch: "main".to_string(),
+                    release_count: 2,
+                    first_release: Some("2024-01-05".to_string()),
+                    latest_release: Some("2024-03-10".to_string()),
+                    average_days_between_releases: Some(65.0),
+                    average_commits_per_release: Some(2.0),
+                    bumps: BumpCounts {
+                        minor: 1,
+                        ..BumpCounts::default()
+                    },
+                }],
+            },
+            changelogs: vec![ReleaseNotes {
+                branch: "main".to_string(),
+                tag: "v1.1.0".to_string(),
+                date: "2024-03-10".to_string(),
+                changelog: Changelog::from_commits(&["feat: add export".to_string()]),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_dashboard_html() {
+        let html = dashboard().to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("Releases from 2024-03-01 to 2024-03-31: 1 releases"));
+        assert!(html.contains(
+            "<tr><td>main</td><td>2</td><td>2024-01-05</td><td>2024-03-10</td><td>65.0</td>"
+        ));
+        assert!(html.contains("<td>Alice &lt;ops&gt;</td>"));
+        assert!(html.contains("<h4>Features</h4>\n<ul>\n<li>add export</li>"));
+        assert!(html.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_dashboard_write_to() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("site/releases");
+        let path = dashboard().write_to(&out).unwrap();
+        assert_eq!(path, out.join(INDEX_FILE));
+        assert_eq!(fs::read_to_string(path).unwrap(), dashboard().to_html());
+    }
+}
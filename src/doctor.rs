@@ -0,0 +1,345 @@
+//! Environment diagnostics for `git-publish doctor`.
+//!
+//! Runs the same setup steps as a publish (config, repository, remotes, branches,
+//! tags) without changing anything, and collects a pass/warn/fail result for each
+//! so problems can be triaged in one go instead of failing one at a time.
+
+use std::fmt;
+use std::path::Path;
+
+use crate::config::{self, Config, HooksConfig, HostKeyChecking};
+use crate::domain::Version;
+use crate::error::GitPublishError;
+use crate::git_ops::{GitRepo, HostKeyVerifier};
+use crate::hooks::{discovery, HookExecutor, HookType};
+use crate::i18n::{text, tr, Msg};
+use crate::known_hosts::KnownHosts;
+
+/// Outcome of a single diagnostic check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// Check succeeded
+    Pass,
+    /// Not broken, but likely to cause surprises
+    Warn,
+    /// Publishing will fail until this is fixed
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckStatus::Pass => write!(f, "{}", text(Msg::CheckPass)),
+            CheckStatus::Warn => write!(f, "{}", text(Msg::CheckWarn)),
+            CheckStatus::Fail => write!(f, "{}", text(Msg::CheckFail)),
+        }
+    }
+}
+
+/// Result of a single diagnostic check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    /// Short name of what was checked (e.g. "remote 'origin'")
+    pub name: String,
+    /// Outcome of the check
+    pub status: CheckStatus,
+    /// Human-readable explanation
+    pub detail: String,
+}
+
+/// Collected results of all diagnostic checks
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DoctorReport {
+    pub checks: Vec<CheckResult>,
+}
+
+impl DoctorReport {
+    fn record(&mut self, name: impl Into<String>, status: CheckStatus, detail: impl Into<String>) {
+        self.checks.push(CheckResult {
+            name: name.into(),
+            status,
+            detail: detail.into(),
+        });
+    }
+
+    /// Number of checks with the given status
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Whether any check failed
+    pub fn has_failures(&self) -> bool {
+        self.count(CheckStatus::Fail) > 0
+    }
+}
+
+/// Run all diagnostic checks
+///
+/// Never prompts: unknown SSH hosts are reported as failures rather than offered
+/// for confirmation.
+///
+/// # Arguments
+/// * `config_path` - Custom configuration file path, if any
+/// * `branch` - Only check this branch instead of every configured branch
+pub fn run_doctor(config_path: Option<&str>, branch: Option<&str>) -> DoctorReport {
+    let mut report = DoctorReport::default();
+
+    let config = match config::load_config_with_warnings(config_path) {
+        Ok((config, warnings)) => {
+            report.record(
+                text(Msg::DoctorConfig),
+                CheckStatus::Pass,
+                text(Msg::DoctorConfigValid),
+            );
+            for warning in warnings {
+                report.record(
+                    text(Msg::DoctorConfig),
+                    CheckStatus::Warn,
+                    warning.message(),
+                );
+            }
+            config
+        }
+        Err(e) => {
+            report.record(text(Msg::DoctorConfig), CheckStatus::Fail, e.to_string());
+            Config::default()
+        }
+    };
+
+    let mut git_repo = match GitRepo::new() {
+        Ok(repo) => {
+            report.record(
+                text(Msg::DoctorRepository),
+                CheckStatus::Pass,
+                text(Msg::DoctorInsideRepository),
+            );
+            repo
+        }
+        Err(e) => {
+            report.record(
+                text(Msg::DoctorRepository),
+                CheckStatus::Fail,
+                e.to_string(),
+            );
+            return report;
+        }
+    };
+
+    if let Some(known_hosts_path) = KnownHosts::default_path() {
+        let mode = match config.behavior.host_key_checking {
+            HostKeyChecking::Ask => HostKeyChecking::Strict,
+            mode => mode,
+        };
+        git_repo.set_host_key_verifier(HostKeyVerifier {
+            known_hosts_path,
+            mode,
+            confirm_unknown: |_, _| false,
+        });
+    }
+    for (remote_name, auth) in &config.auth.remotes {
+        if let Some(key_path) = auth.ssh_key_path() {
+            git_repo.set_ssh_identity(remote_name, key_path);
+        }
+    }
+
+    check_remotes(&git_repo, &config, &mut report);
+    check_branches(&git_repo, &config, branch, &mut report);
+
+    check_hooks(&config, git_repo.workdir(), &mut report);
+
+    report
+}
+
+fn check_hooks(config: &Config, repo_root: Option<&Path>, report: &mut DoctorReport) {
+    let scripts_dir = repo_root.map(|root| config.hooks.scripts_dir(root));
+    check_hook_scripts(&config.hooks, None, scripts_dir.as_deref(), report);
+
+    let mut overrides: Vec<_> = config.branches.branch_hooks().collect();
+    overrides.sort_by(|a, b| a.0.cmp(b.0));
+    for (branch, hooks) in overrides {
+        let scripts_dir = repo_root.map(|root| config.hooks_for(branch).scripts_dir(root));
+        check_hook_scripts(hooks, Some(branch), scripts_dir.as_deref(), report);
+    }
+
+    let Some(repo_root) = repo_root else {
+        return;
+    };
+    let hooks_dir = discovery::hooks_dir(&config.hooks, repo_root);
+    for hook_type in HookType::ALL {
+        let path = discovery::hook_path(&hooks_dir, hook_type);
+        if !path.exists() {
+            continue;
+        }
+        let name = tr(Msg::DoctorHook, &[("hook", &path.display())]);
+        if discovery::discover_hook(&hooks_dir, hook_type).is_some() {
+            report.record(name, CheckStatus::Pass, text(Msg::DoctorHookExecutable));
+        } else {
+            report.record(name, CheckStatus::Warn, text(Msg::DoctorHookNotExecutable));
+        }
+    }
+}
+
+fn check_hook_scripts(
+    hooks: &HooksConfig,
+    branch: Option<&str>,
+    scripts_dir: Option<&Path>,
+    report: &mut DoctorReport,
+) {
+    for hook_type in HookType::ALL {
+        for command in hooks.scripts(hook_type) {
+            let resolved = scripts_dir.map(|dir| command.relative_to(dir));
+            let name = match branch {
+                Some(branch) => tr(
+                    Msg::DoctorBranchHook,
+                    &[("hook", &hook_type.name()), ("branch", &branch)],
+                ),
+                None => tr(Msg::DoctorHook, &[("hook", &hook_type.name())]),
+            };
+            match HookExecutor::interpreter(resolved.as_ref().unwrap_or(command)) {
+                Ok(None) => report.record(
+                    name,
+                    CheckStatus::Pass,
+                    tr(Msg::DoctorScriptExecutable, &[("script", &command.script)]),
+                ),
+                Ok(Some(interpreter)) => report.record(
+                    name,
+                    CheckStatus::Pass,
+                    tr(
+                        Msg::DoctorScriptInterpreter,
+                        &[
+                            ("script", &command.script),
+                            ("interpreter", &interpreter.join(" ")),
+                        ],
+                    ),
+                ),
+                Err(e) => report.record(name, CheckStatus::Fail, e.to_string()),
+            }
+        }
+    }
+}
+
+fn check_remotes(git_repo: &GitRepo, config: &Config, report: &mut DoctorReport) {
+    let remotes = match git_repo.list_remotes() {
+        Ok(remotes) if remotes.is_empty() => {
+            report.record(
+                text(Msg::DoctorRemotes),
+                CheckStatus::Fail,
+                text(Msg::DoctorNoRemotes),
+            );
+            return;
+        }
+        Ok(remotes) => remotes,
+        Err(e) => {
+            report.record(text(Msg::DoctorRemotes), CheckStatus::Fail, e.to_string());
+            return;
+        }
+    };
+
+    for remote in remotes {
+        let name = tr(Msg::DoctorRemote, &[("remote", &remote)]);
+        match git_repo.check_remote_connection(&remote, config.remote_overrides.get(&remote)) {
+            Ok(()) => report.record(name, CheckStatus::Pass, text(Msg::DoctorRemoteReachable)),
+            Err(e) => {
+                let kind = e
+                    .downcast_ref::<GitPublishError>()
+                    .and_then(|err| err.remote_failure_kind());
+                let detail = match kind {
+                    Some(kind) => format!("{}: {}", kind, e),
+                    None => e.to_string(),
+                };
+                report.record(name, CheckStatus::Fail, detail);
+            }
+        }
+    }
+}
+
+fn check_branches(
+    git_repo: &GitRepo,
+    config: &Config,
+    branch: Option<&str>,
+    report: &mut DoctorReport,
+) {
+    let branches: Vec<String> = match branch {
+        Some(branch) => vec![branch.to_string()],
+        None => {
+            let mut branches: Vec<String> = config.branches.keys().cloned().collect();
+            branches.sort();
+            branches
+        }
+    };
+    if branches.is_empty() {
+        report.record(
+            text(Msg::DoctorBranches),
+            CheckStatus::Fail,
+            text(Msg::DoctorNoBranches),
+        );
+        return;
+    }
+
+    for branch in branches {
+        let name = tr(Msg::DoctorBranch, &[("branch", &branch)]);
+        let Some(pattern) = config.branches.get(&branch) else {
+            report.record(
+                name,
+                CheckStatus::Fail,
+                text(Msg::DoctorBranchNotConfigured),
+            );
+            continue;
+        };
+        if git_repo.get_branch_head_oid(&branch).is_err() {
+            report.record(name, CheckStatus::Warn, text(Msg::DoctorBranchMissing));
+            continue;
+        }
+        report.record(
+            name,
+            CheckStatus::Pass,
+            tr(Msg::DoctorBranchExists, &[("pattern", pattern)]),
+        );
+
+        let tag_name = tr(Msg::DoctorTags, &[("branch", &branch)]);
+        match git_repo.get_latest_tag_on_branch(&branch, Some(pattern)) {
+            Ok(None) => report.record(tag_name, CheckStatus::Pass, text(Msg::DoctorNoTags)),
+            Ok(Some(tag)) => match Version::parse(&tag) {
+                Ok(version) => report.record(
+                    tag_name,
+                    CheckStatus::Pass,
+                    tr(
+                        Msg::DoctorLatestTag,
+                        &[("tag", &tag), ("version", &version)],
+                    ),
+                ),
+                Err(e) => report.record(
+                    tag_name,
+                    CheckStatus::Warn,
+                    tr(Msg::DoctorUnparsableTag, &[("tag", &tag), ("reason", &e)]),
+                ),
+            },
+            Err(e) => report.record(tag_name, CheckStatus::Fail, e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_counts() {
+        let mut report = DoctorReport::default();
+        report.record("config", CheckStatus::Pass, "ok");
+        report.record("remote 'origin'", CheckStatus::Warn, "slow");
+        assert!(!report.has_failures());
+
+        report.record("branch 'main'", CheckStatus::Fail, "missing");
+        assert!(report.has_failures());
+        assert_eq!(report.count(CheckStatus::Pass), 1);
+        assert_eq!(report.count(CheckStatus::Fail), 1);
+    }
+
+    #[test]
+    fn test_check_status_display() {
+        assert_eq!(CheckStatus::Pass.to_string(), "PASS");
+        assert_eq!(CheckStatus::Warn.to_string(), "WARN");
+        assert_eq!(CheckStatus::Fail.to_string(), "FAIL");
+    }
+}
@@ -0,0 +1,497 @@
+//! Changelog of a release, built from the commit messages since the previous tag
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::config::{ChangelogLayout, CherryPicks, DEFAULT_BREAKING_CHANGE_INDICATORS};
+use crate::domain::commit::DEPRECATION_TYPES;
+use crate::domain::ParsedCommit;
+
+/// A single changelog line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogEntry {
+    pub scope: Option<String>,
+    pub description: String,
+    /// Pull request the commit came from, e.g. `123` for a `(#123)` suffix
+    pub pull_request: Option<u64>,
+    /// Tag that already released this change from another branch
+    pub released_in: Option<String>,
+}
+
+/// A titled group of changelog entries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogSection {
+    pub title: &'static str,
+    pub entries: Vec<ChangelogEntry>,
+}
+
+/// Commits grouped into breaking changes, deprecations, features, fixes and
+/// everything else
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Changelog {
+    /// Non-empty sections in display order
+    pub sections: Vec<ChangelogSection>,
+    /// Pull request link template with a `{number}` placeholder
+    pull_request_url: Option<String>,
+    layout: ChangelogLayout,
+}
+
+const BREAKING: &str = "Breaking Changes";
+const DEPRECATIONS: &str = "Deprecations";
+const FEATURES: &str = "Features";
+const FIXES: &str = "Bug Fixes";
+const OTHER: &str = "Other Changes";
+
+impl Changelog {
+    /// Group commit messages into sections; entries keep the order of the
+    /// messages
+    ///
+    /// Breaking changes are recognized by the default indicators, see
+    /// [`ParsedCommit::parse`].
+    pub fn from_commits(commit_messages: &[String]) -> Self {
+        Self::from_commits_with_indicators(commit_messages, &DEFAULT_BREAKING_CHANGE_INDICATORS)
+    }
+
+    /// Like [`Changelog::from_commits`], with the configured
+    /// `breaking_change_indicators`
+    pub fn from_commits_with_indicators<S: AsRef<str>>(
+        commit_messages: &[String],
+        breaking_change_indicators: &[S],
+    ) -> Self {
+        Self::from_commits_with_cherry_picks(
+            commit_messages,
+            breaking_change_indicators,
+            &HashMap::new(),
+            CherryPicks::Keep,
+        )
+    }
+
+    /// Like [`Changelog::from_commits_with_indicators`], treating the commits
+    /// in `cherry_picks` (message → tag that already released the change)
+    /// as `handling` says
+    pub fn from_commits_with_cherry_picks<S: AsRef<str>>(
+        commit_messages: &[String],
+        breaking_change_indicators: &[S],
+        cherry_picks: &HashMap<String, String>,
+        handling: CherryPicks,
+    ) -> Self {
+        let mut sections: Vec<ChangelogSection> = [BREAKING, DEPRECATIONS, FEATURES, FIXES, OTHER]
+            .into_iter()
+            .map(|title| ChangelogSection {
+                title,
+                entries: Vec::new(),
+            })
+            .collect();
+
+        for message in commit_messages {
+            let released_in = match handling {
+                CherryPicks::Keep => None,
+                CherryPicks::Annotate => cherry_picks.get(message),
+                CherryPicks::Exclude if cherry_picks.contains_key(message) => continue,
+                CherryPicks::Exclude => None,
+            };
+            let (message, pull_request) = pull_request_reference(message);
+            let commit = ParsedCommit::parse_with_indicators(&message, breaking_change_indicators);
+            let deprecate_type = DEPRECATION_TYPES.contains(&commit.r#type.as_str());
+            let title = if commit.is_breaking_change {
+                Some(BREAKING)
+            } else {
+                match commit.r#type.as_str() {
+                    // Listed under Deprecations below
+                    _ if deprecate_type => None,
+                    "feat" | "feature" => Some(FEATURES),
+                    "fix" => Some(FIXES),
+                    _ => Some(OTHER),
+                }
+            };
+
+            let mut add = |title: &str, description: &str| {
+                let entry = ChangelogEntry {
+                    scope: commit.scope.clone(),
+                    description: description.to_string(),
+                    pull_request,
+                    released_in: released_in.cloned(),
+                };
+                if let Some(section) = sections.iter_mut().find(|s| s.title == title) {
+                    section.entries.push(entry);
+                }
+            };
+            if let Some(title) = title {
+                add(title, commit.subject_description());
+            }
+            if let Some(deprecation) = &commit.deprecation {
+                add(DEPRECATIONS, deprecation);
+            }
+        }
+
+        sections.retain(|section| !section.entries.is_empty());
+        Changelog {
+            sections,
+            pull_request_url: None,
+            layout: ChangelogLayout::default(),
+        }
+    }
+
+    /// Arrange the entries of each section according to `layout`
+    pub fn with_layout(mut self, layout: ChangelogLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Link pull request references using `template`, in which `{number}` is
+    /// replaced with the pull request number
+    pub fn with_pull_request_links(mut self, template: impl Into<String>) -> Self {
+        self.pull_request_url = Some(template.into());
+        self
+    }
+
+    /// Whether there are no entries at all
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Render as Markdown with a `###` heading per section, and a `####`
+    /// heading per scope with [`ChangelogLayout::Scope`]
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for section in &self.sections {
+            let _ = writeln!(out, "### {}\n", section.title);
+            match self.layout {
+                ChangelogLayout::Flat => {
+                    for entry in &section.entries {
+                        self.write_entry(&mut out, entry, true);
+                    }
+                    out.push('\n');
+                }
+                ChangelogLayout::Scope => self.write_scoped(&mut out, &section.entries),
+            }
+        }
+        out
+    }
+
+    /// Render as an HTML fragment with an `<h4>` heading and a list per
+    /// section; entries are grouped by scope as in [`Changelog::to_markdown`]
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        for section in &self.sections {
+            let _ = writeln!(out, "<h4>{}</h4>", section.title);
+            let mut groups: Vec<(Option<&str>, Vec<&ChangelogEntry>)> = Vec::new();
+            match self.layout {
+                ChangelogLayout::Flat => groups.push((None, section.entries.iter().collect())),
+                ChangelogLayout::Scope => {
+                    let mut scopes: Vec<Option<&str>> =
+                        section.entries.iter().map(|e| e.scope.as_deref()).collect();
+                    // Unscoped entries first
+                    scopes.sort_unstable();
+                    scopes.dedup();
+                    for scope in scopes {
+                        let entries = section
+                            .entries
+                            .iter()
+                            .filter(|e| e.scope.as_deref() == scope)
+                            .collect();
+                        groups.push((scope, entries));
+                    }
+                }
+            }
+            let show_scope = self.layout == ChangelogLayout::Flat;
+            for (scope, entries) in groups {
+                if let Some(scope) = scope {
+                    let _ = writeln!(out, "<h5>{}</h5>", escape_html(scope));
+                }
+                out.push_str("<ul>\n");
+                for entry in entries {
+                    self.write_html_entry(&mut out, entry, show_scope);
+                }
+                out.push_str("</ul>\n");
+            }
+        }
+        out
+    }
+
+    /// One `<li>`, optionally prefixed with the entry's scope
+    fn write_html_entry(&self, out: &mut String, entry: &ChangelogEntry, show_scope: bool) {
+        out.push_str("<li>");
+        if let Some(scope) = entry.scope.as_ref().filter(|_| show_scope) {
+            let _ = write!(out, "<strong>{}:</strong> ", escape_html(scope));
+        }
+        out.push_str(&escape_html(&entry.description));
+        match (entry.pull_request, &self.pull_request_url) {
+            (Some(number), Some(template)) => {
+                let url = template.replace("{number}", &number.to_string());
+                let _ = write!(out, " (<a href=\"{}\">#{}</a>)", escape_html(&url), number);
+            }
+            (Some(number), None) => {
+                let _ = write!(out, " (#{})", number);
+            }
+            (None, _) => {}
+        }
+        if let Some(tag) = &entry.released_in {
+            let _ = write!(out, " (also released in {})", escape_html(tag));
+        }
+        out.push_str("</li>\n");
+    }
+
+    /// Unscoped entries, then one sub-heading per scope in alphabetical order
+    fn write_scoped(&self, out: &mut String, entries: &[ChangelogEntry]) {
+        let unscoped: Vec<&ChangelogEntry> = entries.iter().filter(|e| e.scope.is_none()).collect();
+        if !unscoped.is_empty() {
+            for entry in unscoped {
+                self.write_entry(out, entry, false);
+            }
+            out.push('\n');
+        }
+
+        let mut scopes: Vec<&str> = entries.iter().filter_map(|e| e.scope.as_deref()).collect();
+        scopes.sort_unstable();
+        scopes.dedup();
+        for scope in scopes {
+            let _ = writeln!(out, "#### {}\n", scope);
+            for entry in entries.iter().filter(|e| e.scope.as_deref() == Some(scope)) {
+                self.write_entry(out, entry, false);
+            }
+            out.push('\n');
+        }
+    }
+
+    /// One list item, optionally prefixed with the entry's scope
+    fn write_entry(&self, out: &mut String, entry: &ChangelogEntry, show_scope: bool) {
+        out.push_str("- ");
+        if let Some(scope) = entry.scope.as_ref().filter(|_| show_scope) {
+            let _ = write!(out, "**{}:** ", scope);
+        }
+        out.push_str(&entry.description);
+        match (entry.pull_request, &self.pull_request_url) {
+            (Some(number), Some(template)) => {
+                let url = template.replace("{number}", &number.to_string());
+                let _ = write!(out, " ([#{}]({}))", number, url);
+            }
+            (Some(number), None) => {
+                let _ = write!(out, " (#{})", number);
+            }
+            (None, _) => {}
+        }
+        if let Some(tag) = &entry.released_in {
+            let _ = write!(out, " (also released in {})", tag);
+        }
+        out.push('\n');
+    }
+}
+
+/// Escapes text for use in HTML content and attribute values
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Splits the pull request number off a commit message.
+///
+/// Squash merges end the subject with `(#123)`; merge commits start with
+/// `Merge pull request #123 from owner/branch` and carry the pull request
+/// title in the body, which is returned as the message instead.
+fn pull_request_reference(message: &str) -> (String, Option<u64>) {
+    let (subject, body) = message.split_once('\n').unwrap_or((message, ""));
+
+    static MERGE: OnceLock<Regex> = OnceLock::new();
+    static SQUASH: OnceLock<Regex> = OnceLock::new();
+
+    let merge = MERGE
+        .get_or_init(|| Regex::new(r"^Merge pull request #(\d+) from \S+").expect("valid regex"));
+    if let Some(captures) = merge.captures(subject) {
+        let number = captures[1].parse().ok();
+        let title = body.trim_start();
+        let message = if title.is_empty() { subject } else { title };
+        return (message.to_string(), number);
+    }
+
+    let squash = SQUASH.get_or_init(|| Regex::new(r"\s*\(#(\d+)\)$").expect("valid regex"));
+    if let Some(captures) = squash.captures(subject) {
+        let number = captures[1].parse().ok();
+        let start = captures.get(0).map_or(subject.len(), |m| m.start());
+        let message = format!("{}\n{}", &subject[..start], body);
+        return (message.trim_end().to_string(), number);
+    }
+
+    (message.to_string(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(messages: &[&str]) -> Vec<String> {
+        messages.iter().map(|m| m.to_string()).collect()
+    }
+
+    #[test]
+    fn test_changelog_groups_commits() {
+        let changelog = Changelog::from_commits(&messages(&[
+            "fix(auth): handle expired tokens",
+            "docs: update README",
+            "feat!: drop the v1 API",
+            "feat(ui): add dark mode\n\nLong description",
+            "Update dependencies",
+        ]));
+
+        let titles: Vec<&str> = changelog.sections.iter().map(|s| s.title).collect();
+        assert_eq!(
+            titles,
+            vec!["Breaking Changes", "Features", "Bug Fixes", "Other Changes"]
+        );
+        assert_eq!(
+            changelog.to_markdown(),
+            "### Breaking Changes\n\n- drop the v1 API\n\n\
+             ### Features\n\n- **ui:** add dark mode\n\n\
+             ### Bug Fixes\n\n- **auth:** handle expired tokens\n\n\
+             ### Other Changes\n\n- update README\n- Update dependencies\n\n"
+        );
+    }
+
+    #[test]
+    fn test_changelog_empty() {
+        let changelog = Changelog::from_commits(&[]);
+        assert!(changelog.is_empty());
+        assert_eq!(changelog.to_markdown(), "");
+    }
+
+    #[test]
+    fn test_changelog_links_pull_requests() {
+        let commits = messages(&[
+            "feat(api): add search (#42)",
+            "Merge pull request #7 from team/fix-login\n\nfix: handle expired sessions",
+            "chore: bump deps",
+        ]);
+
+        let changelog = Changelog::from_commits(&commits);
+        assert_eq!(changelog.sections[0].entries[0].pull_request, Some(42));
+        assert_eq!(changelog.sections[0].entries[0].description, "add search");
+        assert_eq!(
+            changelog.to_markdown(),
+            "### Features\n\n- **api:** add search (#42)\n\n\
+             ### Bug Fixes\n\n- handle expired sessions (#7)\n\n\
+             ### Other Changes\n\n- bump deps\n\n"
+        );
+
+        let linked = Changelog::from_commits(&commits)
+            .with_pull_request_links("https://github.com/team/app/pull/{number}");
+        assert!(linked
+            .to_markdown()
+            .contains("- **api:** add search ([#42](https://github.com/team/app/pull/42))\n"));
+    }
+
+    #[test]
+    fn test_changelog_groups_scopes() {
+        let changelog = Changelog::from_commits(&messages(&[
+            "feat(ui): add dark mode",
+            "feat: add export",
+            "feat(api): add search",
+            "feat(ui): add shortcuts",
+            "fix(api): handle timeouts",
+        ]))
+        .with_layout(ChangelogLayout::Scope);
+
+        assert_eq!(
+            changelog.to_markdown(),
+            "### Features\n\n\
+             - add export\n\n\
+             #### api\n\n- add search\n\n\
+             #### ui\n\n- add dark mode\n- add shortcuts\n\n\
+             ### Bug Fixes\n\n\
+             #### api\n\n- handle timeouts\n\n"
+        );
+    }
+
+    #[test]
+    fn test_changelog_lists_deprecations() {
+        let changelog = Changelog::from_commits(&messages(&[
+            "deprecate(api): the v1 search endpoint",
+            "feat: add v2 search\n\nDeprecated: the `query` parameter",
+            "fix: typo\n\nDeprecated:",
+        ]));
+
+        assert_eq!(
+            changelog.to_markdown(),
+            "### Deprecations\n\n\
+             - **api:** the v1 search endpoint\n\
+             - the `query` parameter\n\
+             - typo\n\n\
+             ### Features\n\n- add v2 search\n\n\
+             ### Bug Fixes\n\n- typo\n\n"
+        );
+    }
+
+    #[test]
+    fn test_changelog_uses_configured_indicators() {
+        let commits = messages(&["fix: rename flag\n\nBC: --out is now --output"]);
+        let changelog = Changelog::from_commits_with_indicators(&commits, &["BC:".to_string()]);
+        assert_eq!(changelog.sections[0].title, "Breaking Changes");
+        assert_eq!(
+            Changelog::from_commits(&commits).sections[0].title,
+            "Bug Fixes"
+        );
+    }
+
+    #[test]
+    fn test_changelog_handles_cherry_picks() {
+        let commits = messages(&["fix: close files (#7)", "feat: add export"]);
+        let cherry_picks = HashMap::from([(commits[0].clone(), "v1.1.5".to_string())]);
+        let changelog = |handling| {
+            Changelog::from_commits_with_cherry_picks(
+                &commits,
+                &DEFAULT_BREAKING_CHANGE_INDICATORS,
+                &cherry_picks,
+                handling,
+            )
+            .to_markdown()
+        };
+
+        assert_eq!(
+            changelog(CherryPicks::Annotate),
+            "### Features\n\n- add export\n\n\
+             ### Bug Fixes\n\n- close files (#7) (also released in v1.1.5)\n\n"
+        );
+        assert_eq!(
+            changelog(CherryPicks::Exclude),
+            "### Features\n\n- add export\n\n"
+        );
+        assert_eq!(
+            changelog(CherryPicks::Keep),
+            Changelog::from_commits(&commits).to_markdown()
+        );
+    }
+
+    #[test]
+    fn test_changelog_to_html() {
+        let commits = messages(&["feat(ui): show <b>tags</b> (#42)", "fix: close files"]);
+        let html = Changelog::from_commits(&commits)
+            .with_pull_request_links("https://github.com/team/app/pull/{number}")
+            .to_html();
+        assert_eq!(
+            html,
+            "<h4>Features</h4>\n<ul>\n<li><strong>ui:</strong> show &lt;b&gt;tags&lt;/b&gt; \
+             (<a href=\"https://github.com/team/app/pull/42\">#42</a>)</li>\n</ul>\n\
+             <h4>Bug Fixes</h4>\n<ul>\n<li>close files</li>\n</ul>\n"
+        );
+
+        let scoped = Changelog::from_commits(&messages(&["feat(ui): a", "feat: b"]))
+            .with_layout(ChangelogLayout::Scope)
+            .to_html();
+        assert_eq!(
+            scoped,
+            "<h4>Features</h4>\n<ul>\n<li>b</li>\n</ul>\n<h5>ui</h5>\n<ul>\n<li>a</li>\n</ul>\n"
+        );
+    }
+}
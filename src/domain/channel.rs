@@ -0,0 +1,131 @@
+//! Release channels: commits in some scopes only feed pre-releases.
+//!
+//! `[channels]` maps a commit scope to a pre-release identifier, e.g.
+//! `experimental = "beta"`. Such commits don't call for a stable release; when
+//! they are all there is since the latest tag, a pre-release is proposed.
+
+use std::collections::HashMap;
+
+use crate::config::ConventionalCommitsConfig;
+use crate::domain::commit::BumpAnalysis;
+use crate::domain::{ParsedCommit, PreReleaseType, VersionBump};
+
+/// The version bump of a range of commits, with commits in channel scopes
+/// kept apart from the stable release
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelAnalysis {
+    stable: BumpAnalysis,
+    stable_commits: usize,
+    channel: BumpAnalysis,
+    /// The least stable identifier of the channel commits, if any
+    identifier: Option<PreReleaseType>,
+}
+
+impl ChannelAnalysis {
+    /// Classify one more commit.
+    ///
+    /// # Returns
+    /// `true` once a breaking change outside the channels was found: the
+    /// release is then a stable major one whatever follows
+    pub fn add(
+        &mut self,
+        message: &str,
+        config: &ConventionalCommitsConfig,
+        channels: &HashMap<String, String>,
+    ) -> bool {
+        if self.is_settled() {
+            return true;
+        }
+        let identifier = (!channels.is_empty())
+            .then(|| {
+                ParsedCommit::parse_with_indicators(message, &config.breaking_change_indicators)
+                    .scope
+            })
+            .flatten()
+            .and_then(|scope| channels.get(&scope))
+            .and_then(|identifier| identifier.parse::<PreReleaseType>().ok());
+
+        match identifier {
+            Some(identifier) => {
+                self.channel.add(message, config);
+                if self
+                    .identifier
+                    .as_ref()
+                    .is_none_or(|least| identifier < *least)
+                {
+                    self.identifier = Some(identifier);
+                }
+            }
+            None => {
+                self.stable_commits += 1;
+                self.stable.add(message, config);
+            }
+        }
+        self.is_settled()
+    }
+
+    /// Whether a breaking change outside the channels was found
+    pub fn is_settled(&self) -> bool {
+        self.stable.is_settled()
+    }
+
+    /// Identifier of the pre-release to propose: set when every commit added
+    /// is in a channel scope
+    pub fn prerelease(&self) -> Option<&PreReleaseType> {
+        self.identifier
+            .as_ref()
+            .filter(|_| self.stable_commits == 0)
+    }
+
+    /// The bump of the stable commits, or of the channel commits for a
+    /// pre-release
+    pub fn bump(&self) -> VersionBump {
+        if self.prerelease().is_some() {
+            self.channel.bump()
+        } else {
+            self.stable.bump()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channels() -> HashMap<String, String> {
+        HashMap::from([
+            ("experimental".to_string(), "beta".to_string()),
+            ("lab".to_string(), "alpha".to_string()),
+        ])
+    }
+
+    fn analyze(messages: &[&str]) -> ChannelAnalysis {
+        let config = ConventionalCommitsConfig::default();
+        let mut analysis = ChannelAnalysis::default();
+        for message in messages {
+            analysis.add(message, &config, &channels());
+        }
+        analysis
+    }
+
+    #[test]
+    fn test_channel_commits_only_feed_prereleases() {
+        let analysis = analyze(&["feat(experimental): new planner", "fix(experimental): typo"]);
+        assert_eq!(analysis.prerelease(), Some(&PreReleaseType::Beta));
+        assert_eq!(analysis.bump(), VersionBump::Minor);
+
+        let analysis = analyze(&["feat(experimental): new planner", "fix(lab): probe"]);
+        assert_eq!(analysis.prerelease(), Some(&PreReleaseType::Alpha));
+    }
+
+    #[test]
+    fn test_stable_commits_ignore_channel_bumps() {
+        let analysis = analyze(&["feat(experimental)!: new planner", "fix(io): close files"]);
+        assert_eq!(analysis.prerelease(), None);
+        assert_eq!(analysis.bump(), VersionBump::Patch);
+
+        let analysis = analyze(&[]);
+        assert_eq!(analysis.prerelease(), None);
+        assert_eq!(analysis.bump(), VersionBump::Patch);
+    }
+}
@@ -1,5 +1,12 @@
+use std::fmt;
+use std::sync::OnceLock;
+
 use regex::Regex;
 
+use crate::config::{ConventionalCommitsConfig, ScopeRule, DEFAULT_BREAKING_CHANGE_INDICATORS};
+use crate::domain::VersionBump;
+use crate::i18n::{text, tr, Msg};
+
 /// Parsed representation of a conventional commit message
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParsedCommit {
@@ -7,8 +14,16 @@ pub struct ParsedCommit {
     pub scope: Option<String>,
     pub description: String,
     pub is_breaking_change: bool,
+    /// Classification forced by a `Semver: major|minor|patch` trailer, if present
+    pub semver_override: Option<VersionBump>,
+    /// What the commit deprecates: the description of a `deprecate:` commit or
+    /// the value of a `Deprecated:` trailer
+    pub deprecation: Option<String>,
 }
 
+/// Commit types that mark a deprecation; accepted even when not listed in `types`
+pub const DEPRECATION_TYPES: &[&str] = &["deprecate", "deprecated"];
+
 impl ParsedCommit {
     /// Parse a commit message according to conventional commits spec
     /// Supports formats:
@@ -17,11 +32,73 @@ impl ParsedCommit {
     /// - type!: description
     /// - type: description
     /// - non-conventional text
+    ///
+    /// A `Semver: major|minor|patch` trailer in the last paragraph overrides how
+    /// the commit is classified, e.g. for a `feat:` that is actually internal.
+    /// A `deprecate:` type or a `Deprecated:` trailer marks a deprecation.
+    ///
+    /// Breaking changes are recognized by the default indicators
+    /// (`BREAKING CHANGE:`, `BREAKING-CHANGE:`); use
+    /// [`ParsedCommit::parse_with_indicators`] for configured ones.
     pub fn parse(message: &str) -> Self {
+        Self::parse_with_indicators(message, &DEFAULT_BREAKING_CHANGE_INDICATORS)
+    }
+
+    /// Like [`ParsedCommit::parse`], with the `breaking_change_indicators` of
+    /// the configuration: a message containing any of them is a breaking change.
+    pub fn parse_with_indicators<S: AsRef<str>>(
+        message: &str,
+        breaking_change_indicators: &[S],
+    ) -> Self {
+        let has_indicator = breaking_change_indicators
+            .iter()
+            .any(|indicator| message.contains(indicator.as_ref()));
+        let mut commit = Self::parse_header(message, has_indicator);
+        commit.semver_override = parse_semver_trailer(message);
+        commit.deprecation = if DEPRECATION_TYPES.contains(&commit.r#type.as_str()) {
+            Some(commit.subject_description().to_string())
+        } else {
+            parse_deprecation_trailer(message)
+                .map(|note| note.unwrap_or_else(|| commit.subject_description().to_string()))
+        };
+        commit
+    }
+
+    /// The description up to the end of the subject line
+    pub fn subject_description(&self) -> &str {
+        self.description.lines().next().unwrap_or_default().trim()
+    }
+
+    /// The bump this commit calls for on its own, honoring a `Semver:` trailer
+    ///
+    /// Returns `None` for commits that don't affect the version by type alone
+    /// (e.g. `docs:`); keyword-based detection is left to [`analyze_version_bump`].
+    pub fn version_bump(&self) -> Option<VersionBump> {
+        if self.semver_override.is_some() {
+            return self.semver_override;
+        }
+        if self.is_breaking_change {
+            return Some(VersionBump::Major);
+        }
+        match self.r#type.as_str() {
+            "feat" | "feature" => Some(VersionBump::Minor),
+            "fix" | "perf" | "refactor" => Some(VersionBump::Patch),
+            _ => None,
+        }
+    }
+
+    /// `has_indicator` tells whether the message contains a breaking change
+    /// indicator; it only counts for conventional commits
+    fn parse_header(message: &str, has_indicator: bool) -> Self {
+        // Compiled once, as every commit since the tag is parsed
+        static SCOPED: OnceLock<Regex> = OnceLock::new();
+        static BREAKING: OnceLock<Regex> = OnceLock::new();
+        static PLAIN: OnceLock<Regex> = OnceLock::new();
+
         // Try format: type(scope)!: description
-        if let Some(captures) = Regex::new(r"^([a-z]+)\(([^)]+)\)(!?):\s*(.*)")
-            .ok()
-            .and_then(|re| re.captures(message))
+        if let Some(captures) = SCOPED
+            .get_or_init(|| Regex::new(r"^([a-z]+)\(([^)]+)\)(!?):\s*(.*)").expect("valid regex"))
+            .captures(message)
         {
             let r#type = captures
                 .get(1)
@@ -34,20 +111,20 @@ impl ParsedCommit {
                 .map(|m| m.as_str().to_string())
                 .unwrap_or_default();
 
-            let is_breaking = has_exclamation || message.contains("BREAKING CHANGE:");
-
             return ParsedCommit {
                 r#type,
                 scope,
                 description,
-                is_breaking_change: is_breaking,
+                is_breaking_change: has_exclamation || has_indicator,
+                semver_override: None,
+                deprecation: None,
             };
         }
 
         // Try format: type!: description
-        if let Some(captures) = Regex::new(r"^([a-z]+)!:\s*(.*)")
-            .ok()
-            .and_then(|re| re.captures(message))
+        if let Some(captures) = BREAKING
+            .get_or_init(|| Regex::new(r"^([a-z]+)!:\s*(.*)").expect("valid regex"))
+            .captures(message)
         {
             let r#type = captures
                 .get(1)
@@ -63,13 +140,15 @@ impl ParsedCommit {
                 scope: None,
                 description,
                 is_breaking_change: true,
+                semver_override: None,
+                deprecation: None,
             };
         }
 
         // Try format: type: description
-        if let Some(captures) = Regex::new(r"^([a-z]+):\s*(.*)")
-            .ok()
-            .and_then(|re| re.captures(message))
+        if let Some(captures) = PLAIN
+            .get_or_init(|| Regex::new(r"^([a-z]+):\s*(.*)").expect("valid regex"))
+            .captures(message)
         {
             let r#type = captures
                 .get(1)
@@ -80,13 +159,13 @@ impl ParsedCommit {
                 .map(|m| m.as_str().to_string())
                 .unwrap_or_default();
 
-            let is_breaking = message.contains("BREAKING CHANGE:");
-
             return ParsedCommit {
                 r#type,
                 scope: None,
                 description,
-                is_breaking_change: is_breaking,
+                is_breaking_change: has_indicator,
+                semver_override: None,
+                deprecation: None,
             };
         }
 
@@ -96,8 +175,209 @@ impl ParsedCommit {
             scope: None,
             description: message.to_string(),
             is_breaking_change: false,
+            semver_override: None,
+            deprecation: None,
+        }
+    }
+}
+
+/// Number of breaking, feature and fix commits in a set of commits
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommitTypeCounts {
+    /// Commits that call for a major bump (`!`, `BREAKING CHANGE:` or `Semver: major`)
+    pub breaking: usize,
+    /// `feat:` commits
+    pub feat: usize,
+    /// `fix:` commits
+    pub fix: usize,
+}
+
+/// Counts breaking, feature and fix commits using the conventional commit parser
+///
+/// # Arguments
+/// * `commit_messages` - List of commit messages to count
+/// * `breaking_change_indicators` - Footers that mark a commit as breaking
+pub fn count_commit_types(
+    commit_messages: &[String],
+    breaking_change_indicators: &[String],
+) -> CommitTypeCounts {
+    let mut counts = CommitTypeCounts::default();
+    for message in commit_messages {
+        let parsed = ParsedCommit::parse_with_indicators(message, breaking_change_indicators);
+        if parsed.version_bump() == Some(VersionBump::Major) {
+            counts.breaking += 1;
+        }
+        match parsed.r#type.as_str() {
+            "feat" => counts.feat += 1,
+            "fix" => counts.fix += 1,
+            _ => {}
+        }
+    }
+    counts
+}
+
+/// A way in which a commit departs from the configured commit conventions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConformanceIssue {
+    /// Subject line is longer than `max_subject_length`
+    SubjectTooLong { length: usize, max: usize },
+    /// Subject doesn't start with `type:` / `type(scope):`
+    MissingType,
+    /// Subject has a type that isn't listed in `types`
+    UnknownType(String),
+}
+
+impl fmt::Display for ConformanceIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ConformanceIssue::SubjectTooLong { length, max } => {
+                tr(Msg::SubjectTooLong, &[("length", length), ("max", max)])
+            }
+            ConformanceIssue::MissingType => text(Msg::MissingType).to_string(),
+            ConformanceIssue::UnknownType(r#type) => tr(Msg::UnknownType, &[("type", r#type)]),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+/// A commit flagged during analysis, with everything wrong with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonConformingCommit {
+    pub subject: String,
+    pub issues: Vec<ConformanceIssue>,
+}
+
+/// Checks commits against the optional subject length and type rules.
+///
+/// Only rules enabled in the config are applied (`max_subject_length`,
+/// `flag_missing_type`); merge commits are skipped. The result is meant as a
+/// warning and never affects the version bump.
+///
+/// # Arguments
+/// * `commit_messages` - List of commit messages to check
+/// * `config` - Conventional commit settings with the rules to apply
+pub fn find_non_conforming_commits(
+    commit_messages: &[String],
+    config: &ConventionalCommitsConfig,
+) -> Vec<NonConformingCommit> {
+    let header = Regex::new(r"^([a-zA-Z]+)(\([^)]*\))?!?:\s").ok();
+
+    commit_messages
+        .iter()
+        .filter_map(|message| {
+            let subject = message.lines().next().unwrap_or_default();
+            if subject.starts_with("Merge ") {
+                return None;
+            }
+
+            let mut issues = Vec::new();
+            if let Some(max) = config.max_subject_length {
+                let length = subject.chars().count();
+                if length > max {
+                    issues.push(ConformanceIssue::SubjectTooLong { length, max });
+                }
+            }
+            if config.flag_missing_type {
+                match header.as_ref().and_then(|re| re.captures(subject)) {
+                    Some(captures) => {
+                        let r#type = &captures[1];
+                        if !config.types.iter().any(|t| t == r#type)
+                            && !DEPRECATION_TYPES.contains(&r#type)
+                        {
+                            issues.push(ConformanceIssue::UnknownType(r#type.to_string()));
+                        }
+                    }
+                    None => issues.push(ConformanceIssue::MissingType),
+                }
+            }
+
+            (!issues.is_empty()).then(|| NonConformingCommit {
+                subject: subject.to_string(),
+                issues,
+            })
+        })
+        .collect()
+}
+
+/// Reads the `Semver:` trailer from the last paragraph of a commit message.
+///
+/// The key is case-insensitive; values other than major, minor or patch are ignored.
+fn parse_semver_trailer(message: &str) -> Option<VersionBump> {
+    let trimmed = message.trim_end();
+    let (_, trailers) = trimmed.rsplit_once("\n\n")?;
+    trailers.lines().rev().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if !key.trim().eq_ignore_ascii_case("semver") {
+            return None;
+        }
+        match value.trim().to_ascii_lowercase().as_str() {
+            "major" => Some(VersionBump::Major),
+            "minor" => Some(VersionBump::Minor),
+            "patch" => Some(VersionBump::Patch),
+            _ => None,
+        }
+    })
+}
+
+/// Reads a `Deprecated:` (or `Deprecate:`) trailer from the last paragraph.
+///
+/// Returns `Some(None)` for a trailer without a value.
+fn parse_deprecation_trailer(message: &str) -> Option<Option<String>> {
+    let trimmed = message.trim_end();
+    let (_, trailers) = trimmed.rsplit_once("\n\n")?;
+    trailers.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let key = key.trim();
+        if !DEPRECATION_TYPES
+            .iter()
+            .any(|k| key.eq_ignore_ascii_case(k))
+        {
+            return None;
         }
+        let value = value.trim();
+        Some((!value.is_empty()).then(|| value.to_string()))
+    })
+}
+
+/// The first of `keywords` found in a commit message, the way `major_keywords`
+/// and `minor_keywords` are matched.
+///
+/// Keywords match case-insensitively as whole words in the subject line or the
+/// footer (last paragraph), so "featured" doesn't match `feat`. With `legacy`
+/// (`legacy_keyword_matching`) they match anywhere in the message, also inside
+/// other words.
+pub fn find_keyword<'k>(message: &str, keywords: &'k [String], legacy: bool) -> Option<&'k str> {
+    let lowercase = message.to_lowercase();
+    if legacy {
+        return keywords
+            .iter()
+            .find(|keyword| lowercase.contains(keyword.as_str()))
+            .map(String::as_str);
     }
+
+    let subject = lowercase.lines().next().unwrap_or_default();
+    let footer = lowercase
+        .trim_end()
+        .rsplit_once("\n\n")
+        .map_or("", |(_, footer)| footer);
+    keywords
+        .iter()
+        .find(|keyword| contains_word(subject, keyword) || contains_word(footer, keyword))
+        .map(String::as_str)
+}
+
+/// Whether `word` occurs in `text` without a letter, digit or `_` right
+/// before or after it
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    !word.is_empty()
+        && text.match_indices(word).any(|(start, _)| {
+            !text[..start].chars().next_back().is_some_and(is_word_char)
+                && !text[start + word.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(is_word_char)
+        })
 }
 
 /// Determines the semantic version bump type based on conventional commits.
@@ -118,59 +398,274 @@ pub fn analyze_version_bump(
     commit_messages: &[String],
     config: &crate::config::ConventionalCommitsConfig,
 ) -> crate::domain::VersionBump {
-    let mut has_breaking_changes = false;
-    let mut has_features = false;
-    let mut has_fixes = false;
-
+    let mut analysis = BumpAnalysis::default();
     for message in commit_messages {
-        let parsed_commit = ParsedCommit::parse(message);
+        if analysis.add(message, config) {
+            break;
+        }
+    }
+    analysis.bump()
+}
+
+/// The version bump of a range of commits, accumulated one commit at a time
+/// so commits can be analyzed as the repository yields them.
+///
+/// Gives the same result as [`analyze_version_bump`] over the same commits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BumpAnalysis {
+    has_breaking_changes: bool,
+    has_features: bool,
+    has_fixes: bool,
+}
+
+impl BumpAnalysis {
+    /// Classify one more commit.
+    ///
+    /// # Returns
+    /// `true` once a breaking change was found: the bump is then major and
+    /// further commits can't change it
+    pub fn add(
+        &mut self,
+        message: &str,
+        config: &crate::config::ConventionalCommitsConfig,
+    ) -> bool {
+        if self.is_settled() {
+            return true;
+        }
+        let parsed_commit =
+            ParsedCommit::parse_with_indicators(message, &config.breaking_change_indicators);
+
+        // A Semver: trailer replaces all other classification for this commit
+        if let Some(bump) = parsed_commit.semver_override {
+            match bump {
+                crate::domain::VersionBump::Major => self.has_breaking_changes = true,
+                crate::domain::VersionBump::Minor => self.has_features = true,
+                crate::domain::VersionBump::Patch => self.has_fixes = true,
+            }
+            return self.is_settled();
+        }
 
         // Check for breaking changes
         if parsed_commit.is_breaking_change {
-            has_breaking_changes = true;
+            self.has_breaking_changes = true;
         }
 
-        // Check for major version indicators
-        for keyword in &config.major_keywords {
-            if message.to_lowercase().contains(keyword) {
-                has_features = true;
-            }
+        // Commits in ignored scopes only count when they are breaking
+        match config.scopes.rule_for(parsed_commit.scope.as_deref()) {
+            Some(ScopeRule::Ignore(_)) => return self.is_settled(),
+            Some(ScopeRule::Bump(bump, _)) => match bump {
+                crate::domain::VersionBump::Major => self.has_breaking_changes = true,
+                crate::domain::VersionBump::Minor => self.has_features = true,
+                crate::domain::VersionBump::Patch => self.has_fixes = true,
+            },
+            None => {}
         }
 
-        // Check for minor version indicators
-        for keyword in &config.minor_keywords {
-            if message.to_lowercase().contains(keyword) {
-                has_features = true;
+        // Deprecations call for at least the configured bump; keywords such as
+        // "deprecate" don't apply to them
+        if parsed_commit.deprecation.is_some() {
+            match config.deprecation_bump {
+                crate::domain::VersionBump::Major => self.has_breaking_changes = true,
+                crate::domain::VersionBump::Minor => self.has_features = true,
+                crate::domain::VersionBump::Patch => self.has_fixes = true,
+            }
+        } else {
+            let legacy = config.legacy_keyword_matching;
+
+            // Check for major version indicators
+            if find_keyword(message, &config.major_keywords, legacy).is_some() {
+                self.has_features = true;
+            }
+
+            // Check for minor version indicators
+            if find_keyword(message, &config.minor_keywords, legacy).is_some() {
+                self.has_features = true;
             }
         }
 
         // Check for commit types that might indicate features or fixes
         match parsed_commit.r#type.as_str() {
-            "feat" | "feature" => has_features = true,
-            "fix" | "perf" | "refactor" => has_fixes = true,
+            "feat" | "feature" => self.has_features = true,
+            "fix" | "perf" | "refactor" => self.has_fixes = true,
             _ => {}
         }
 
-        // If we found a breaking change, we can return early
-        if has_breaking_changes {
-            return crate::domain::VersionBump::Major;
+        self.is_settled()
+    }
+
+    /// Whether a breaking change was found, so the bump can only be major
+    pub fn is_settled(&self) -> bool {
+        self.has_breaking_changes
+    }
+
+    /// The bump for the commits added so far; patch when none of them is a
+    /// conventional commit
+    pub fn bump(&self) -> crate::domain::VersionBump {
+        if self.has_breaking_changes {
+            crate::domain::VersionBump::Major
+        } else if self.has_features {
+            crate::domain::VersionBump::Minor
+        } else if self.has_fixes {
+            crate::domain::VersionBump::Patch
+        } else {
+            // If no conventional commits detected, default to patch
+            crate::domain::VersionBump::Patch
         }
     }
+}
+
+/// The rule that decided the bump of a single commit, see [`explain_commit`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BumpRule {
+    /// A `Semver:` trailer, which replaces every other rule
+    SemverTrailer,
+    /// A `!` after the type or a `BREAKING CHANGE:` footer
+    BreakingChange,
+    /// A `deprecate:` type or `Deprecated:` trailer, bumped by `deprecation_bump`
+    Deprecation,
+    /// A scope listed under `scopes.major`, `scopes.minor` or `scopes.patch`
+    Scope(String),
+    /// The commit type, such as `feat` or `fix`
+    CommitType(String),
+    /// A keyword of `major_keywords` found in the message
+    MajorKeyword(String),
+    /// A keyword of `minor_keywords` found in the message
+    MinorKeyword(String),
+}
+
+impl fmt::Display for BumpRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            BumpRule::SemverTrailer => text(Msg::RuleSemverTrailer).to_string(),
+            BumpRule::BreakingChange => text(Msg::RuleBreakingChange).to_string(),
+            BumpRule::Deprecation => text(Msg::RuleDeprecation).to_string(),
+            BumpRule::Scope(scope) => tr(Msg::RuleScope, &[("scope", scope)]),
+            BumpRule::MajorKeyword(keyword) => tr(Msg::RuleMajorKeyword, &[("keyword", keyword)]),
+            BumpRule::MinorKeyword(keyword) => tr(Msg::RuleMinorKeyword, &[("keyword", keyword)]),
+            BumpRule::CommitType(r#type) => tr(Msg::RuleCommitType, &[("type", r#type)]),
+        };
+        write!(f, "{}", message)
+    }
+}
+
+/// How one commit is classified on its own, for `git-publish explain`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitExplanation {
+    pub parsed: ParsedCommit,
+    /// Whether the subject has a `type:` or `type(scope):` header
+    pub conventional: bool,
+    /// The bump the commit calls for and the rule that decided it; `None`
+    /// when the commit doesn't affect the version
+    pub bump: Option<(VersionBump, BumpRule)>,
+    /// The scope under `scopes.ignore` that keeps the commit from bumping
+    /// the version
+    pub ignored_scope: Option<String>,
+}
+
+/// Classify a single commit message the way [`BumpAnalysis::add`] does,
+/// keeping track of which rule decided the bump.
+///
+/// When several rules apply, the one calling for the largest bump is reported;
+/// among equal bumps the first in [`BumpRule`] order.
+pub fn explain_commit(message: &str, config: &ConventionalCommitsConfig) -> CommitExplanation {
+    let parsed = ParsedCommit::parse_with_indicators(message, &config.breaking_change_indicators);
+    // Only the non-conventional fallback keeps the whole message as description
+    let conventional = parsed.description != message;
 
-    if has_features {
-        crate::domain::VersionBump::Minor
-    } else if has_fixes {
-        crate::domain::VersionBump::Patch
+    let mut rules = Vec::new();
+    let mut ignored_scope = None;
+    if let Some(bump) = parsed.semver_override {
+        rules.push((bump, BumpRule::SemverTrailer));
     } else {
-        // If no conventional commits detected, default to patch
-        crate::domain::VersionBump::Patch
+        if parsed.is_breaking_change {
+            rules.push((VersionBump::Major, BumpRule::BreakingChange));
+        }
+        match config.scopes.rule_for(parsed.scope.as_deref()) {
+            Some(ScopeRule::Ignore(scope)) => ignored_scope = Some(scope),
+            Some(ScopeRule::Bump(bump, scope)) => rules.push((bump, BumpRule::Scope(scope))),
+            None => {}
+        }
     }
+    if parsed.semver_override.is_none() && ignored_scope.is_none() {
+        if parsed.deprecation.is_some() {
+            rules.push((config.deprecation_bump, BumpRule::Deprecation));
+        }
+        let type_bump = match parsed.r#type.as_str() {
+            "feat" | "feature" => Some(VersionBump::Minor),
+            "fix" | "perf" | "refactor" => Some(VersionBump::Patch),
+            _ => None,
+        };
+        if let Some(bump) = type_bump {
+            rules.push((bump, BumpRule::CommitType(parsed.r#type.clone())));
+        }
+        if parsed.deprecation.is_none() {
+            let legacy = config.legacy_keyword_matching;
+            // Keywords mark a feature, whichever list they come from
+            if let Some(keyword) = find_keyword(message, &config.major_keywords, legacy) {
+                rules.push((
+                    VersionBump::Minor,
+                    BumpRule::MajorKeyword(keyword.to_string()),
+                ));
+            }
+            if let Some(keyword) = find_keyword(message, &config.minor_keywords, legacy) {
+                rules.push((
+                    VersionBump::Minor,
+                    BumpRule::MinorKeyword(keyword.to_string()),
+                ));
+            }
+        }
+    }
+
+    let rank = |bump: VersionBump| match bump {
+        VersionBump::Major => 3,
+        VersionBump::Minor => 2,
+        VersionBump::Patch => 1,
+    };
+    let bump = rules.into_iter().fold(
+        None,
+        |best: Option<(VersionBump, BumpRule)>, rule| match best {
+            Some(best) if rank(best.0) >= rank(rule.0) => Some(best),
+            _ => Some(rule),
+        },
+    );
+
+    CommitExplanation {
+        parsed,
+        conventional,
+        bump,
+        ignored_scope,
+    }
+}
+
+/// Returns the subject lines of commits that are marked as breaking changes.
+///
+/// Used to show the user exactly which commits caused a major version bump.
+///
+/// # Arguments
+/// * `commit_messages` - List of commit messages to inspect
+/// * `breaking_change_indicators` - Footers that mark a commit as breaking
+pub fn breaking_commits(
+    commit_messages: &[String],
+    breaking_change_indicators: &[String],
+) -> Vec<String> {
+    commit_messages
+        .iter()
+        .filter(|message| {
+            ParsedCommit::parse_with_indicators(message, breaking_change_indicators).version_bump()
+                == Some(VersionBump::Major)
+        })
+        .map(|message| message.lines().next().unwrap_or_default().to_string())
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn indicators() -> Vec<String> {
+        ConventionalCommitsConfig::default().breaking_change_indicators
+    }
+
     #[test]
     fn test_parse_with_scope() {
         let commit = ParsedCommit::parse("feat(auth): add login");
@@ -328,4 +823,338 @@ BREAKING CHANGE: changed response format"#;
             assert_eq!(parsed.r#type, expected_type, "Failed for: {}", msg);
         }
     }
+
+    #[test]
+    fn test_breaking_commits_lists_subjects() {
+        let messages = vec![
+            "feat: add login".to_string(),
+            "feat(api)!: drop v1 endpoints".to_string(),
+            "fix: handle nulls\n\nBREAKING CHANGE: null is now an error".to_string(),
+        ];
+        assert_eq!(
+            breaking_commits(&messages, &indicators()),
+            vec!["feat(api)!: drop v1 endpoints", "fix: handle nulls"]
+        );
+        assert!(breaking_commits(&["fix: typo".to_string()], &indicators()).is_empty());
+    }
+
+    #[test]
+    fn test_parse_semver_trailer() {
+        let commit = ParsedCommit::parse("feat: internal helper\n\nSemver: patch");
+        assert_eq!(commit.r#type, "feat");
+        assert_eq!(commit.semver_override, Some(VersionBump::Patch));
+        assert_eq!(commit.version_bump(), Some(VersionBump::Patch));
+
+        let commit =
+            ParsedCommit::parse("fix: tweak\n\nLonger body.\n\nReviewed-by: Bob\nsemver: Major");
+        assert_eq!(commit.semver_override, Some(VersionBump::Major));
+    }
+
+    #[test]
+    fn test_semver_trailer_only_in_last_paragraph() {
+        assert_eq!(
+            ParsedCommit::parse("feat: x\n\nSemver: patch\n\nSigned-off-by: A").semver_override,
+            None
+        );
+        assert_eq!(ParsedCommit::parse("Semver: patch").semver_override, None);
+        assert_eq!(
+            ParsedCommit::parse("feat: x\n\nSemver: whatever").semver_override,
+            None
+        );
+    }
+
+    #[test]
+    fn test_semver_trailer_overrides_analysis() {
+        let config = crate::config::ConventionalCommitsConfig::default();
+        let internal_feat = vec!["feat: internal helper\n\nSemver: patch".to_string()];
+        assert_eq!(
+            analyze_version_bump(&internal_feat, &config),
+            VersionBump::Patch
+        );
+
+        let not_breaking = vec!["feat!: rename flag\n\nSemver: minor".to_string()];
+        assert_eq!(
+            analyze_version_bump(&not_breaking, &config),
+            VersionBump::Minor
+        );
+        assert!(breaking_commits(&not_breaking, &indicators()).is_empty());
+    }
+
+    #[test]
+    fn test_parse_deprecation() {
+        let commit = ParsedCommit::parse("deprecate(api): the v1 endpoint\n\nUse v2.");
+        assert_eq!(commit.deprecation.as_deref(), Some("the v1 endpoint"));
+
+        let commit = ParsedCommit::parse("feat: add v2\n\nDeprecated: the v1 endpoint");
+        assert_eq!(commit.r#type, "feat");
+        assert_eq!(commit.deprecation.as_deref(), Some("the v1 endpoint"));
+
+        let commit = ParsedCommit::parse("fix: old flag\n\ndeprecated:");
+        assert_eq!(commit.deprecation.as_deref(), Some("old flag"));
+
+        assert_eq!(ParsedCommit::parse("fix: typo").deprecation, None);
+    }
+
+    #[test]
+    fn test_deprecation_bump() {
+        let config = ConventionalCommitsConfig::default();
+        let deprecation = vec![
+            "docs: typo".to_string(),
+            "chore: old flag\n\nDeprecated: --legacy".to_string(),
+        ];
+        assert_eq!(
+            analyze_version_bump(&deprecation, &config),
+            VersionBump::Minor
+        );
+
+        let config = ConventionalCommitsConfig {
+            deprecation_bump: VersionBump::Patch,
+            ..ConventionalCommitsConfig::default()
+        };
+        assert_eq!(
+            analyze_version_bump(&["deprecate: the v1 API".to_string()], &config),
+            VersionBump::Patch
+        );
+        // A feature stays a minor bump
+        assert_eq!(
+            analyze_version_bump(&["feat: v2\n\nDeprecated: v1".to_string()], &config),
+            VersionBump::Minor
+        );
+    }
+
+    #[test]
+    fn test_bump_analysis_settles_on_breaking_change() {
+        let config = ConventionalCommitsConfig::default();
+        let mut analysis = BumpAnalysis::default();
+        assert_eq!(analysis.bump(), VersionBump::Patch);
+
+        assert!(!analysis.add("fix: close files", &config));
+        assert!(!analysis.add("feat: add export", &config));
+        assert_eq!(analysis.bump(), VersionBump::Minor);
+
+        assert!(analysis.add("feat!: drop the v1 API", &config));
+        assert!(analysis.is_settled());
+        assert!(analysis.add("docs: typo", &config));
+        assert_eq!(analysis.bump(), VersionBump::Major);
+    }
+
+    #[test]
+    fn test_deprecation_type_is_conforming() {
+        let config = ConventionalCommitsConfig {
+            flag_missing_type: true,
+            ..ConventionalCommitsConfig::default()
+        };
+        let messages = vec!["deprecate(api): the v1 endpoint".to_string()];
+        assert!(find_non_conforming_commits(&messages, &config).is_empty());
+    }
+
+    #[test]
+    fn test_non_conforming_commits_disabled_by_default() {
+        let config = ConventionalCommitsConfig::default();
+        let messages = vec!["whatever I did today, it was a lot of stuff really".to_string()];
+        assert!(find_non_conforming_commits(&messages, &config).is_empty());
+    }
+
+    #[test]
+    fn test_non_conforming_commits_flags_issues() {
+        let config = ConventionalCommitsConfig {
+            max_subject_length: Some(20),
+            flag_missing_type: true,
+            ..ConventionalCommitsConfig::default()
+        };
+        let messages = vec![
+            "feat: short".to_string(),
+            "fix(parser): handle very long input lines".to_string(),
+            "updated stuff".to_string(),
+            "wip: halfway".to_string(),
+            "Merge branch 'develop' into main with a long subject".to_string(),
+        ];
+
+        let flagged = find_non_conforming_commits(&messages, &config);
+        assert_eq!(flagged.len(), 3);
+        assert_eq!(
+            flagged[0].issues,
+            vec![ConformanceIssue::SubjectTooLong {
+                length: 41,
+                max: 20
+            }]
+        );
+        assert_eq!(flagged[1].subject, "updated stuff");
+        assert_eq!(flagged[1].issues, vec![ConformanceIssue::MissingType]);
+        assert_eq!(
+            flagged[2].issues,
+            vec![ConformanceIssue::UnknownType("wip".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_count_commit_types() {
+        let messages = vec![
+            "feat: add api".to_string(),
+            "feat(ui)!: redesign settings".to_string(),
+            "fix: crash on start".to_string(),
+            "fix: typo\n\nSemver: major".to_string(),
+            "docs: update readme".to_string(),
+        ];
+        assert_eq!(
+            count_commit_types(&messages, &indicators()),
+            CommitTypeCounts {
+                breaking: 2,
+                feat: 2,
+                fix: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_explain_commit() {
+        let config = ConventionalCommitsConfig::default();
+        let explain = |message: &str| explain_commit(message, &config);
+
+        let explanation = explain("feat(ui)!: redesign settings");
+        assert!(explanation.conventional);
+        assert_eq!(explanation.parsed.scope.as_deref(), Some("ui"));
+        assert_eq!(
+            explanation.bump,
+            Some((VersionBump::Major, BumpRule::BreakingChange))
+        );
+
+        assert_eq!(
+            explain("feat: add export").bump,
+            Some((VersionBump::Minor, BumpRule::CommitType("feat".to_string())))
+        );
+        assert_eq!(
+            explain("fix: typo\n\nSemver: minor").bump,
+            Some((VersionBump::Minor, BumpRule::SemverTrailer))
+        );
+        assert_eq!(
+            explain("fix: avoid breaking the parser").bump,
+            Some((
+                VersionBump::Minor,
+                BumpRule::MajorKeyword("breaking".to_string())
+            ))
+        );
+        assert_eq!(explain("docs: update readme").bump, None);
+
+        let explanation = explain("updated stuff");
+        assert!(!explanation.conventional);
+        assert_eq!(explanation.bump, None);
+    }
+
+    #[test]
+    fn test_explain_commit_matches_bump_analysis() {
+        let config = ConventionalCommitsConfig::default();
+        for message in [
+            "feat(ui)!: redesign settings",
+            "fix: crash on start",
+            "chore: new enhancement to builds",
+            "deprecate: old endpoint",
+            "refactor: x\n\nSemver: major",
+            "docs: readme",
+        ] {
+            let mut analysis = BumpAnalysis::default();
+            analysis.add(message, &config);
+            let explained = explain_commit(message, &config)
+                .bump
+                .map_or(VersionBump::Patch, |(bump, _)| bump);
+            assert_eq!(explained, analysis.bump(), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_explain_commit_scopes() {
+        let mut config = ConventionalCommitsConfig::default();
+        config.scopes.ignore = vec!["ci".to_string()];
+        config.scopes.minor = vec!["api".to_string()];
+
+        let explanation = explain_commit("feat(ci): cache the build", &config);
+        assert_eq!(explanation.bump, None);
+        assert_eq!(explanation.ignored_scope.as_deref(), Some("ci"));
+        assert_eq!(
+            explain_commit("fix(api): accept ids", &config).bump,
+            Some((VersionBump::Minor, BumpRule::Scope("api".to_string())))
+        );
+
+        for message in [
+            "feat(ci): cache the build",
+            "feat(ci)!: drop the old runner",
+            "fix(api): accept ids",
+        ] {
+            let mut analysis = BumpAnalysis::default();
+            analysis.add(message, &config);
+            let explained = explain_commit(message, &config)
+                .bump
+                .map_or(VersionBump::Patch, |(bump, _)| bump);
+            assert_eq!(explained, analysis.bump(), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_parse_breaking_change_indicators() {
+        assert!(ParsedCommit::parse("fix: x\n\nBREAKING-CHANGE: y").is_breaking_change);
+        // Only conventional commits are breaking through a footer
+        assert!(!ParsedCommit::parse("Update x\n\nBREAKING CHANGE: y").is_breaking_change);
+
+        let custom = ["BC:".to_string()];
+        let commit = ParsedCommit::parse_with_indicators("fix(api): x\n\nBC: y", &custom);
+        assert!(commit.is_breaking_change);
+        assert!(
+            !ParsedCommit::parse_with_indicators("fix: x\n\nBREAKING CHANGE: y", &custom)
+                .is_breaking_change
+        );
+
+        let config = ConventionalCommitsConfig {
+            breaking_change_indicators: custom.to_vec(),
+            ..ConventionalCommitsConfig::default()
+        };
+        assert_eq!(
+            analyze_version_bump(&["fix: x\n\nBC: y".to_string()], &config),
+            VersionBump::Major
+        );
+        assert_eq!(
+            breaking_commits(&["fix: x\n\nBC: y".to_string()], &custom),
+            vec!["fix: x"]
+        );
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words_in_subject_and_footer() {
+        let keywords: Vec<String> = vec!["feat".to_string(), "breaking".to_string()];
+        assert_eq!(
+            find_keyword("docs: list featured plugins", &keywords, false),
+            None
+        );
+        assert_eq!(
+            find_keyword("chore: debreaking the build", &keywords, false),
+            None
+        );
+        assert_eq!(
+            find_keyword("chore: Breaking, but internal", &keywords, false),
+            Some("breaking")
+        );
+        // Only the subject and the last paragraph are scanned
+        let message = "chore: tidy\n\nNothing breaking here.\n\nRefs: #12";
+        assert_eq!(find_keyword(message, &keywords, false), None);
+        assert_eq!(
+            find_keyword("chore: tidy\n\nNote: breaking", &keywords, false),
+            Some("breaking")
+        );
+
+        assert_eq!(
+            find_keyword("docs: list featured plugins", &keywords, true),
+            Some("feat")
+        );
+        assert_eq!(find_keyword(message, &keywords, true), Some("breaking"));
+
+        let legacy = ConventionalCommitsConfig {
+            legacy_keyword_matching: true,
+            ..ConventionalCommitsConfig::default()
+        };
+        let messages = vec!["docs: list featured plugins".to_string()];
+        assert_eq!(
+            analyze_version_bump(&messages, &ConventionalCommitsConfig::default()),
+            VersionBump::Patch
+        );
+        assert_eq!(analyze_version_bump(&messages, &legacy), VersionBump::Minor);
+    }
 }
@@ -1,11 +1,14 @@
 //! Domain logic - pure business rules independent of git operations
 
+pub mod changelog;
+pub mod channel;
 pub mod commit;
 pub mod prerelease;
 pub mod tag;
 pub mod version;
 
+pub use changelog::Changelog;
 pub use commit::ParsedCommit;
 pub use prerelease::{PreRelease, PreReleaseType};
-pub use tag::{Tag, TagPattern};
-pub use version::{Version, VersionBump};
+pub use tag::{ReleaseNote, Tag, TagMetadata, TagPattern};
+pub use version::{BumpOverride, Version, VersionBump};
@@ -77,7 +77,10 @@ impl fmt::Display for PreReleaseType {
 /// - "alpha" -> PreRelease { identifier: Alpha, iteration: None }
 /// - "beta.1" -> PreRelease { identifier: Beta, iteration: Some(1) }
 /// - "rc.3" -> PreRelease { identifier: ReleaseCandidate, iteration: Some(3) }
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Ordered by identifier (alpha < beta < rc < custom), then iteration, with
+/// no iteration first.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct PreRelease {
     /// The pre-release identifier (alpha, beta, rc, or custom)
     pub identifier: PreReleaseType,
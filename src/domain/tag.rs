@@ -1,3 +1,6 @@
+use std::fmt::{self, Write as _};
+
+use crate::domain::{Version, VersionBump};
 use crate::error::{GitPublishError, Result};
 
 /// Represents a git tag
@@ -20,25 +23,124 @@ impl Tag {
 }
 
 /// Tag naming pattern (e.g., "v{version}", "release-{version}")
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TagPattern {
     pub pattern: String,
 }
 
 impl TagPattern {
-    /// Create a new tag pattern
+    /// Create a new tag pattern without validating it
     pub fn new(pattern: impl Into<String>) -> Self {
         TagPattern {
             pattern: pattern.into(),
         }
     }
 
+    /// Parse and validate a tag pattern
+    ///
+    /// The pattern must contain exactly one `{version}` placeholder and no
+    /// other `{...}` placeholders.
+    pub fn parse(pattern: &str) -> Result<Self> {
+        let mut version_placeholders = 0;
+        let mut rest = pattern;
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                return Err(GitPublishError::tag(format!(
+                    "pattern '{}' has an unclosed '{{'",
+                    pattern
+                )));
+            };
+            match &rest[start + 1..start + len] {
+                "version" => version_placeholders += 1,
+                name => {
+                    return Err(GitPublishError::tag(format!(
+                        "pattern '{}' has unknown placeholder {{{}}}",
+                        pattern, name
+                    )));
+                }
+            }
+            rest = &rest[start + len + 1..];
+        }
+        if version_placeholders != 1 {
+            return Err(GitPublishError::tag(format!(
+                "pattern '{}' must contain exactly one {{version}} placeholder",
+                pattern
+            )));
+        }
+        Ok(TagPattern::new(pattern))
+    }
+
+    /// The pattern an existing release tag follows: everything before the
+    /// version is taken as the prefix
+    /// Example: tag="release-1.2.3" -> Some("release-{version}")
+    ///
+    /// Returns `None` if the tag has no version after its prefix.
+    pub fn detect(tag: &str) -> Option<Self> {
+        let start = tag.find(|c: char| c.is_ascii_digit())?;
+        let (prefix, version) = tag.split_at(start);
+        if prefix.contains(['{', '}']) || Version::parse(version).is_err() {
+            return None;
+        }
+        Some(TagPattern::new(format!("{}{{version}}", prefix)))
+    }
+
+    /// The pattern as written (e.g. "v{version}")
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
     /// Format a version according to pattern
     /// Example: pattern="v{version}", version="1.2.3" -> "v1.2.3"
     pub fn format(&self, version: &str) -> String {
         self.pattern.replace("{version}", version)
     }
 
+    /// Extract the version from a tag formatted with this pattern
+    /// Example: pattern="release-{version}", tag="release-1.2.3" -> Some("1.2.3")
+    ///
+    /// Returns `None` if the tag doesn't have the pattern's prefix and suffix.
+    pub fn extract_version(&self, tag: &str) -> Option<String> {
+        let (prefix, suffix) = self.pattern.split_once("{version}")?;
+        let version = tag.strip_prefix(prefix)?.strip_suffix(suffix)?;
+        (!version.is_empty()).then(|| version.to_string())
+    }
+
+    /// The `count` highest-versioned of `tags` that follow this pattern,
+    /// lowest first; tags whose version doesn't parse are left out
+    /// Example: pattern="v{version}", tags=["v1.10.0", "v1.2.3", "g2.0.0"], count=5
+    /// -> ["v1.2.3", "v1.10.0"]
+    pub fn recent_tags(&self, tags: &[String], count: usize) -> Vec<String> {
+        let mut versioned: Vec<(Version, &String)> = tags
+            .iter()
+            .filter_map(|tag| {
+                let version = Version::parse(&self.extract_version(tag)?).ok()?;
+                Some((version, tag))
+            })
+            .collect();
+        versioned.sort();
+        let skip = versioned.len().saturating_sub(count);
+        versioned
+            .into_iter()
+            .skip(skip)
+            .map(|(_, tag)| tag.clone())
+            .collect()
+    }
+
+    /// First tag after `tag` that `exists` says is free, bumping the patch version
+    /// Example: pattern="v{version}", tag="v1.2.3", v1.2.4 taken -> Some("v1.2.5")
+    ///
+    /// Returns `None` if the tag's version can't be parsed.
+    pub fn next_available(&self, tag: &str, exists: impl Fn(&str) -> bool) -> Option<String> {
+        let mut version = Version::parse(&self.extract_version(tag)?).ok()?;
+        loop {
+            version = version.bump(&VersionBump::Patch);
+            let candidate = self.format(&version.to_string());
+            if !exists(&candidate) {
+                return Some(candidate);
+            }
+        }
+    }
+
     /// Validate if a tag matches this pattern
     pub fn matches(&self, tag: &str) -> Result<bool> {
         // Extract the placeholder pattern part
@@ -60,10 +162,200 @@ impl TagPattern {
     }
 }
 
+/// Release metadata read from the message of an annotated tag
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TagMetadata {
+    /// Version recorded in a `Version:` trailer, or else the first version
+    /// found in the subject line (e.g. "Release v1.2.0")
+    pub version: Option<Version>,
+    /// Descriptions following a breaking change indicator such as
+    /// `BREAKING CHANGE:` or `BREAKING-CHANGE:`
+    pub breaking_changes: Vec<String>,
+    /// The message without the `Version:` trailer
+    pub notes: String,
+}
+
+impl TagMetadata {
+    /// Parse an annotated tag message
+    ///
+    /// # Arguments
+    /// * `message` - The tag message
+    /// * `breaking_change_indicators` - Prefixes marking a breaking change line
+    pub fn parse(message: &str, breaking_change_indicators: &[String]) -> Self {
+        let mut version = None;
+        let mut breaking_changes = Vec::new();
+        let mut notes = Vec::new();
+
+        for line in message.lines() {
+            if let Some((key, value)) = line.split_once(':') {
+                if key.trim().eq_ignore_ascii_case("version") {
+                    if let Ok(parsed) = Version::parse(value.trim()) {
+                        version.get_or_insert(parsed);
+                        continue;
+                    }
+                }
+            }
+            if let Some(change) = breaking_change_indicators
+                .iter()
+                .find_map(|indicator| line.trim_start().strip_prefix(indicator.as_str()))
+            {
+                breaking_changes.push(change.trim().to_string());
+            }
+            notes.push(line);
+        }
+
+        if version.is_none() {
+            version = message
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .split_whitespace()
+                .find_map(|word| Version::parse(word.trim_end_matches([',', ':', ')'])).ok());
+        }
+
+        TagMetadata {
+            version,
+            breaking_changes,
+            notes: notes.join("\n").trim().to_string(),
+        }
+    }
+}
+
+/// What an annotated release tag records, see [`ReleaseNote::message`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseNote<'a> {
+    pub tag: &'a str,
+    /// The released version, when the tag has one
+    pub version: Option<&'a Version>,
+    pub previous_tag: Option<&'a str>,
+    pub bump: VersionBump,
+    pub commit_count: usize,
+    /// Descriptions of the breaking changes
+    pub breaking_changes: &'a [String],
+}
+
+impl ReleaseNote<'_> {
+    /// Message of the annotated tag: a subject, a summary of the bump, the
+    /// breaking changes and a `Version:` trailer, so that
+    /// [`TagMetadata::parse`] reads the release back
+    pub fn message(&self) -> String {
+        let bump = format!("{:?}", self.bump).to_lowercase();
+        let commits = match self.commit_count {
+            1 => "1 commit".to_string(),
+            count => format!("{} commits", count),
+        };
+        let mut message = format!("Release {}\n\n{} bump, {}", self.tag, bump, commits);
+        if let Some(previous) = self.previous_tag {
+            let _ = write!(message, " since {}", previous);
+        }
+        message.push('\n');
+        if !self.breaking_changes.is_empty() {
+            message.push('\n');
+            for change in self.breaking_changes {
+                let _ = writeln!(message, "BREAKING CHANGE: {}", change);
+            }
+        }
+        if let Some(version) = self.version {
+            let _ = write!(message, "\nVersion: {}\n", version);
+        }
+        message
+    }
+}
+
+impl fmt::Display for TagPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_recent_tags_sorted_by_version() {
+        let pattern = TagPattern::new("v{version}");
+        let tags: Vec<String> = [
+            "v1.10.0",
+            "v1.2.3",
+            "g2.0.0",
+            "v1.9.0",
+            "v1.10.0-rc.1",
+            "v0.1.0",
+            "v1.0.0",
+            "vnext",
+        ]
+        .iter()
+        .map(|tag| tag.to_string())
+        .collect();
+
+        assert_eq!(
+            pattern.recent_tags(&tags, 5),
+            vec!["v1.0.0", "v1.2.3", "v1.9.0", "v1.10.0-rc.1", "v1.10.0"]
+        );
+        assert!(pattern.recent_tags(&[], 5).is_empty());
+    }
+
+    #[test]
+    fn test_tag_metadata_parse() {
+        let indicators = vec![
+            "BREAKING CHANGE:".to_string(),
+            "BREAKING-CHANGE:".to_string(),
+        ];
+        let metadata = TagMetadata::parse(
+            "Release 1.2.0\n\n- add search\nBREAKING-CHANGE: drop v1 API\n\nVersion: 1.2.1\n",
+            &indicators,
+        );
+        assert_eq!(metadata.version, Some(Version::new(1, 2, 1)));
+        assert_eq!(metadata.breaking_changes, vec!["drop v1 API".to_string()]);
+        assert_eq!(
+            metadata.notes,
+            "Release 1.2.0\n\n- add search\nBREAKING-CHANGE: drop v1 API"
+        );
+
+        let metadata = TagMetadata::parse("Release v2.0.0: the big one", &indicators);
+        assert_eq!(metadata.version, Some(Version::new(2, 0, 0)));
+        assert!(metadata.breaking_changes.is_empty());
+
+        assert_eq!(
+            TagMetadata::parse("Nightly build", &indicators).version,
+            None
+        );
+    }
+
+    #[test]
+    fn test_release_note_message() {
+        let version = Version::new(2, 0, 0);
+        let breaking_changes = vec!["drop the v1 API".to_string()];
+        let note = ReleaseNote {
+            tag: "v2.0.0",
+            version: Some(&version),
+            previous_tag: Some("v1.4.2"),
+            bump: VersionBump::Major,
+            commit_count: 3,
+            breaking_changes: &breaking_changes,
+        };
+        assert_eq!(
+            note.message(),
+            "Release v2.0.0\n\nmajor bump, 3 commits since v1.4.2\n\n\
+             BREAKING CHANGE: drop the v1 API\n\nVersion: 2.0.0\n"
+        );
+
+        let metadata = TagMetadata::parse(&note.message(), &["BREAKING CHANGE:".to_string()]);
+        assert_eq!(metadata.version, Some(version.clone()));
+        assert_eq!(metadata.breaking_changes, breaking_changes);
+
+        let note = ReleaseNote {
+            version: None,
+            previous_tag: None,
+            bump: VersionBump::Patch,
+            commit_count: 1,
+            breaking_changes: &[],
+            ..note
+        };
+        assert_eq!(note.message(), "Release v2.0.0\n\npatch bump, 1 commit\n");
+    }
+
     #[test]
     fn test_tag_new() {
         let tag = Tag::new("v1.2.3");
@@ -88,6 +380,27 @@ mod tests {
         assert_eq!(pattern.format("1.2.3"), "release-1.2.3");
     }
 
+    #[test]
+    fn test_pattern_parse() {
+        assert_eq!(
+            TagPattern::parse("release-{version}").unwrap(),
+            TagPattern::new("release-{version}")
+        );
+        assert!(TagPattern::parse("{version}").is_ok());
+
+        let invalid = [
+            ("release", "exactly one {version}"),
+            ("v{version}-{version}", "exactly one {version}"),
+            ("v{major}.{version}", "unknown placeholder {major}"),
+            ("v{Version}", "unknown placeholder {Version}"),
+            ("v{version", "unclosed"),
+        ];
+        for (pattern, expected) in invalid {
+            let err = TagPattern::parse(pattern).unwrap_err().to_string();
+            assert!(err.contains(expected), "{}: {}", pattern, err);
+        }
+    }
+
     #[test]
     fn test_pattern_matches() {
         let pattern = TagPattern::new("v{version}");
@@ -206,4 +519,45 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_pattern_extract_version() {
+        assert_eq!(
+            TagPattern::new("v{version}").extract_version("v1.2.3"),
+            Some("1.2.3".to_string())
+        );
+        assert_eq!(
+            TagPattern::new("release-{version}-final").extract_version("release-2.0.0-rc.1-final"),
+            Some("2.0.0-rc.1".to_string())
+        );
+        assert_eq!(
+            TagPattern::new("v{version}").extract_version("d1.2.3"),
+            None
+        );
+        assert_eq!(TagPattern::new("v{version}").extract_version("v"), None);
+    }
+
+    #[test]
+    fn test_pattern_detect() {
+        let detect = |tag| TagPattern::detect(tag).map(|pattern| pattern.pattern);
+        assert_eq!(detect("v1.2.3").as_deref(), Some("v{version}"));
+        assert_eq!(
+            detect("release-2.0.0-rc.1").as_deref(),
+            Some("release-{version}")
+        );
+        assert_eq!(detect("1.0.0").as_deref(), Some("{version}"));
+        assert_eq!(detect("v2"), None);
+        assert_eq!(detect("nightly"), None);
+    }
+
+    #[test]
+    fn test_next_available_skips_taken_tags() {
+        let pattern = TagPattern::new("release-{version}");
+        let taken = ["release-1.2.3", "release-1.2.4"];
+        assert_eq!(
+            pattern.next_available("release-1.2.3", |tag| taken.contains(&tag)),
+            Some("release-1.2.5".to_string())
+        );
+        assert_eq!(pattern.next_available("other-1.2.3", |_| false), None);
+    }
 }
@@ -1,5 +1,7 @@
-use crate::domain::PreRelease;
+use crate::domain::{PreRelease, PreReleaseType};
 use crate::error::{GitPublishError, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Semantic version representation
@@ -119,6 +121,31 @@ impl Version {
         }
     }
 
+    /// The next pre-release of `identifier`: the following iteration when
+    /// this already is such a pre-release (`1.3.0-beta.1` → `1.3.0-beta.2`),
+    /// the same release with `identifier.1` for another pre-release, and
+    /// otherwise `bump` applied (`1.2.0` → `1.3.0-beta.1` for a minor bump).
+    pub fn next_prerelease(&self, bump_type: &VersionBump, identifier: &PreReleaseType) -> Self {
+        let prerelease = match &self.prerelease {
+            Some(current) if current.identifier == *identifier => current.increment_iteration(),
+            _ => PreRelease::new(identifier.clone(), Some(1)),
+        };
+        let release = if self.prerelease.is_some() {
+            Version::new(self.major, self.minor, self.patch)
+        } else {
+            self.bump(bump_type)
+        };
+        Version {
+            prerelease: Some(prerelease),
+            ..release
+        }
+    }
+
+    /// The stable release a pre-release leads to (`1.3.0-beta.2` → `1.3.0`)
+    pub fn release(&self) -> Self {
+        Version::new(self.major, self.minor, self.patch)
+    }
+
     /// Return ordered candidate versions for the requested bump.
     pub fn bump_options(&self, bump_type: &VersionBump) -> Vec<Self> {
         match bump_type {
@@ -136,6 +163,27 @@ impl Version {
     }
 }
 
+/// Semantic version precedence: major, minor and patch compared numerically
+/// (so `1.10.0` > `1.2.3`), and a pre-release below its release.
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.prerelease, &other.prerelease) {
+                (None, None) => std::cmp::Ordering::Equal,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(b),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl fmt::Display for Version {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
@@ -147,19 +195,63 @@ impl fmt::Display for Version {
 }
 
 /// Version bump type decision
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum VersionBump {
     Major,
     Minor,
     Patch,
 }
 
+/// A bump requested by hand with `--bump`, overriding the one the commits
+/// call for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BumpOverride {
+    /// A stable release with this bump
+    Release(VersionBump),
+    /// The next pre-release: another iteration of the current pre-release,
+    /// or the pre-release of the next patch
+    PreRelease,
+}
+
+impl BumpOverride {
+    /// The bump applied to the version numbers
+    pub fn bump(&self) -> VersionBump {
+        match self {
+            BumpOverride::Release(bump) => *bump,
+            BumpOverride::PreRelease => VersionBump::Patch,
+        }
+    }
+}
+
+impl fmt::Display for BumpOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BumpOverride::Release(bump) => write!(f, "{}", format!("{:?}", bump).to_lowercase()),
+            BumpOverride::PreRelease => write!(f, "prerelease"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::PreReleaseType;
 
     // Basic version tests
+    #[test]
+    fn test_version_ordering() {
+        let parse = |tag| Version::parse(tag).unwrap();
+        assert!(parse("v1.10.0") > parse("v1.2.3"));
+        assert!(parse("v2.0.0") > parse("v1.99.99"));
+        assert!(parse("v1.3.0") > parse("v1.3.0-rc.2"));
+        assert!(parse("v1.3.0-rc.1") > parse("v1.3.0-beta.4"));
+        assert!(parse("v1.3.0-beta.2") > parse("v1.3.0-beta.1"));
+        assert_eq!(
+            parse("v1.3.0").cmp(&parse("1.3.0")),
+            std::cmp::Ordering::Equal
+        );
+    }
+
     #[test]
     fn test_version_parse() {
         let v = Version::parse("v1.2.3").unwrap();
@@ -263,6 +355,37 @@ mod tests {
         assert_eq!(bumped.prerelease, None);
     }
 
+    #[test]
+    fn test_next_prerelease() {
+        let beta = PreReleaseType::Beta;
+        let next = |tag: &str, bump| {
+            Version::parse(tag)
+                .unwrap()
+                .next_prerelease(&bump, &beta)
+                .to_string()
+        };
+        assert_eq!(next("v1.2.0", VersionBump::Minor), "1.3.0-beta.1");
+        assert_eq!(next("v1.3.0-beta.1", VersionBump::Minor), "1.3.0-beta.2");
+        assert_eq!(next("v1.3.0-alpha.4", VersionBump::Patch), "1.3.0-beta.1");
+    }
+
+    #[test]
+    fn test_bump_override_display() {
+        assert_eq!(
+            BumpOverride::Release(VersionBump::Major).to_string(),
+            "major"
+        );
+        assert_eq!(BumpOverride::PreRelease.to_string(), "prerelease");
+        assert_eq!(BumpOverride::PreRelease.bump(), VersionBump::Patch);
+    }
+
+    #[test]
+    fn test_version_release() {
+        let rc = Version::parse("v1.3.0-rc.2").unwrap();
+        assert_eq!(rc.release(), Version::new(1, 3, 0));
+        assert_eq!(Version::new(1, 3, 0).release(), Version::new(1, 3, 0));
+    }
+
     #[test]
     fn test_version_bump_removes_prerelease() {
         let v = Version::parse("v1.0.0-beta.1").unwrap();
@@ -1,5 +1,33 @@
+use std::fmt;
 use thiserror::Error;
 
+/// Category of a failed remote operation (fetch/push).
+///
+/// Used to pick a tailored boundary warning and remediation hint instead of
+/// string-matching error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteFailureKind {
+    /// Credentials were rejected or none were usable
+    Authentication,
+    /// The remote host could not be resolved or reached
+    NetworkUnreachable,
+    /// The remote's SSH host key or certificate could not be verified
+    HostKeyVerification,
+    /// Any other remote failure
+    Other,
+}
+
+impl fmt::Display for RemoteFailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteFailureKind::Authentication => write!(f, "authentication failed"),
+            RemoteFailureKind::NetworkUnreachable => write!(f, "network unreachable"),
+            RemoteFailureKind::HostKeyVerification => write!(f, "host key verification failed"),
+            RemoteFailureKind::Other => write!(f, "remote error"),
+        }
+    }
+}
+
 /// Unified error type for git-publish operations
 #[derive(Error, Debug)]
 pub enum GitPublishError {
@@ -18,6 +46,19 @@ pub enum GitPublishError {
     #[error("Remote operation failed: {0}")]
     Remote(String),
 
+    #[error("Remote operation failed for '{remote}' ({kind}): {message}")]
+    RemoteAccess {
+        remote: String,
+        kind: RemoteFailureKind,
+        message: String,
+    },
+
+    #[error("Hook error: {0}")]
+    Hook(String),
+
+    #[error("Manifest error: {0}")]
+    Manifest(String),
+
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -45,6 +86,37 @@ impl GitPublishError {
     pub fn remote(msg: impl Into<String>) -> Self {
         GitPublishError::Remote(msg.into())
     }
+
+    /// Create a hook error with context
+    pub fn hook(msg: impl Into<String>) -> Self {
+        GitPublishError::Hook(msg.into())
+    }
+
+    /// Create a manifest error with context
+    pub fn manifest(msg: impl Into<String>) -> Self {
+        GitPublishError::Manifest(msg.into())
+    }
+
+    /// Create a classified remote access error
+    pub fn remote_access(
+        remote: impl Into<String>,
+        kind: RemoteFailureKind,
+        message: impl Into<String>,
+    ) -> Self {
+        GitPublishError::RemoteAccess {
+            remote: remote.into(),
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the remote failure category, if this is a classified remote error
+    pub fn remote_failure_kind(&self) -> Option<RemoteFailureKind> {
+        match self {
+            GitPublishError::RemoteAccess { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -165,6 +237,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_remote_access_error_kind() {
+        let err = GitPublishError::remote_access(
+            "origin",
+            RemoteFailureKind::Authentication,
+            "no credentials",
+        );
+        assert_eq!(
+            err.remote_failure_kind(),
+            Some(RemoteFailureKind::Authentication)
+        );
+        assert!(err.to_string().starts_with("Remote operation failed"));
+        assert!(err.to_string().contains("authentication failed"));
+        assert_eq!(GitPublishError::remote("x").remote_failure_kind(), None);
+    }
+
     #[test]
     fn test_error_into_string() {
         let err = GitPublishError::tag("test tag issue");
@@ -176,7 +264,7 @@ mod tests {
     #[test]
     fn test_multiple_error_creations_same_type() {
         for i in 0..10 {
-            let err = GitPublishError::version(&format!("error {}", i));
+            let err = GitPublishError::version(format!("error {}", i));
             let msg = err.to_string();
             assert!(msg.contains(&format!("error {}", i)));
         }
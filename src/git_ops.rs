@@ -1,12 +1,494 @@
 use anyhow::Result;
 use git2::{BranchType, Commit, Oid, Repository};
 
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::config::{HostKeyChecking, RemoteOverride};
+use crate::domain::TagPattern;
+use crate::error::{GitPublishError, RemoteFailureKind};
+use crate::known_hosts::{self, HostKeyStatus, KnownHosts};
+use crate::path_ignore::{PathIgnore, IGNORE_FILE};
+use crate::ssh_config::SshConfig;
+
+/// A URL rewrite rule read from `url.<base>.insteadOf` / `url.<base>.pushInsteadOf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlRewrite {
+    /// Replacement base URL (the `<base>` in `url.<base>.insteadOf`)
+    pub base: String,
+    /// URL prefix that gets replaced by `base`
+    pub prefix: String,
+    /// Whether this rule comes from `pushInsteadOf` (applies to pushes only)
+    pub push_only: bool,
+}
+
+/// Applies git's `insteadOf`/`pushInsteadOf` rewrite rules to a URL.
+///
+/// Mirrors git's semantics: the longest matching prefix wins. For pushes,
+/// `pushInsteadOf` rules are consulted first and `insteadOf` rules are used
+/// only if none of them match. Fetches only consider `insteadOf` rules.
+///
+/// # Arguments
+/// * `url` - The URL to rewrite
+/// * `rules` - Rewrite rules read from git config
+/// * `for_push` - Whether the URL is used for pushing
+///
+/// # Returns
+/// The rewritten URL, or the original URL if no rule matches
+pub fn rewrite_url(url: &str, rules: &[UrlRewrite], for_push: bool) -> String {
+    let longest_match = |push_only: bool| {
+        rules
+            .iter()
+            .filter(|rule| rule.push_only == push_only && url.starts_with(&rule.prefix))
+            .max_by_key(|rule| rule.prefix.len())
+    };
+
+    let rule = if for_push {
+        longest_match(true).or_else(|| longest_match(false))
+    } else {
+        longest_match(false)
+    };
+
+    match rule {
+        Some(rule) => format!("{}{}", rule.base, &url[rule.prefix.len()..]),
+        None => url.to_string(),
+    }
+}
+
+/// Message and signing of an annotated tag
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagAnnotation {
+    pub message: String,
+    /// Sign the tag with GPG as configured for git
+    pub signed: bool,
+}
+
+/// What a tag reference points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagTarget {
+    /// A commit: a lightweight tag
+    Commit,
+    /// A tag object: an annotated tag, possibly layered over another tag
+    Tag,
+    /// A tree or blob
+    Other,
+}
+
+/// Whether a commit author lacks a usable identity
+fn is_anonymous(author: &git2::Signature) -> bool {
+    let name = String::from_utf8_lossy(author.name_bytes());
+    let name = name.trim();
+    name.is_empty()
+        || name.eq_ignore_ascii_case("unknown")
+        || author.email_bytes().iter().all(u8::is_ascii_whitespace)
+}
+
+/// Detached, armored signature of `content` made by `program` (a gpg
+/// compatible command) with `key`, as `git tag -s` asks for it
+fn gpg_sign(program: &str, key: &str, content: &str) -> Result<String> {
+    use std::io::Write as _;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(program)
+        .args(["--status-fd=2", "-bsau", key])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Could not run '{}' to sign the tag: {}", program, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(content.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    let signature = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() || !signature.contains("-----BEGIN PGP SIGNATURE-----") {
+        return Err(anyhow::anyhow!(
+            "'{}' failed to sign the tag: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(signature.into_owned())
+}
+
+/// Classifies a libgit2 error from a fetch or push into a remote failure category.
+///
+/// Uses the error code and class reported by libgit2 rather than the message text,
+/// falling back to well-known message fragments only for transport errors whose
+/// class is too generic to tell apart.
+pub fn classify_remote_error(err: &git2::Error) -> RemoteFailureKind {
+    let message = err.message().to_lowercase();
+
+    match err.code() {
+        git2::ErrorCode::Auth => return RemoteFailureKind::Authentication,
+        git2::ErrorCode::Certificate => return RemoteFailureKind::HostKeyVerification,
+        _ => {}
+    }
+
+    match err.class() {
+        git2::ErrorClass::Ssh => {
+            if message.contains("host key") || message.contains("hostkey") {
+                RemoteFailureKind::HostKeyVerification
+            } else if message.contains("authenticat") || message.contains("credentials") {
+                RemoteFailureKind::Authentication
+            } else {
+                RemoteFailureKind::NetworkUnreachable
+            }
+        }
+        git2::ErrorClass::Net | git2::ErrorClass::Os => RemoteFailureKind::NetworkUnreachable,
+        git2::ErrorClass::Http => {
+            if message.contains("401") || message.contains("403") {
+                RemoteFailureKind::Authentication
+            } else if message.contains("resolve") || message.contains("connect") {
+                RemoteFailureKind::NetworkUnreachable
+            } else {
+                RemoteFailureKind::Other
+            }
+        }
+        _ => RemoteFailureKind::Other,
+    }
+}
+
+/// Checks whether a tag looks like a release for the given tag pattern.
+///
+/// With a pattern such as "g{version}" the tag must start with the pattern's prefix
+/// followed by a digit; without one, any semver-like tag (optional `v`/`V`) matches.
+fn tag_matches_pattern(tag: &str, tag_pattern: Option<&str>) -> bool {
+    // Extract prefix from tag pattern (e.g., "g" from "g{version}", "v" from "v{version}")
+    let tag_prefix = tag_pattern
+        .and_then(|pattern| pattern.find("{version}").map(|pos| &pattern[..pos]))
+        .filter(|prefix| !prefix.is_empty());
+
+    if let Some(prefix) = tag_prefix {
+        // Tag must start with the expected prefix
+        if !tag.starts_with(prefix) {
+            return false;
+        }
+        // After the prefix, must start with a digit
+        let rest = &tag[prefix.len()..];
+        rest.chars().next().is_some_and(|c| c.is_ascii_digit())
+    } else {
+        // No pattern specified, accept any semver-like tag
+        // (starts with optional v/V followed by digit)
+        let trimmed = tag.trim_start_matches('v').trim_start_matches('V');
+        trimmed.chars().next().is_some_and(|c| c.is_ascii_digit())
+    }
+}
+
+/// A release tag on a branch together with the commits it introduced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagRelease {
+    /// Tag name
+    pub tag: String,
+    /// Tag time in seconds since the Unix epoch (tagger time, or commit time for lightweight tags)
+    pub time: i64,
+    /// Number of commits since the previous release tag on the branch
+    pub commit_count: usize,
+    /// Distinct commit authors since the previous release tag, in first-seen order
+    pub authors: Vec<String>,
+}
+
+/// A local tag with the commit it points at and when it was made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagDetails {
+    /// Tag name
+    pub name: String,
+    /// Full hash of the commit the tag peels to
+    pub target: String,
+    /// Tag time in seconds since the Unix epoch (tagger time, or commit time for lightweight tags)
+    pub time: i64,
+    /// Whether the tag is a tag object rather than a plain reference
+    pub annotated: bool,
+}
+
+/// Extracts the port from an `ssh://` URL, if one is given explicitly.
+fn ssh_port(url: &str) -> Option<u16> {
+    let rest = url.strip_prefix("ssh://")?;
+    let authority = rest.split('/').next()?;
+    let host_port = authority.rsplit('@').next()?;
+    let (_, port) = host_port.rsplit_once(':')?;
+    port.parse().ok()
+}
+
+/// Extracts the host from an SSH URL (`ssh://user@host:port/path` or `user@host:path`).
+fn ssh_host(url: &str) -> Option<&str> {
+    if let Some(rest) = url.strip_prefix("ssh://") {
+        let authority = rest.split('/').next()?;
+        let host_port = authority.rsplit('@').next()?;
+        let host = match host_port.strip_prefix('[') {
+            Some(bracketed) => bracketed.split(']').next()?,
+            None => host_port.split(':').next()?,
+        };
+        return Some(host);
+    }
+    if url.contains("://") {
+        return None;
+    }
+    let (authority, _) = url.split_once(':')?;
+    if authority.contains('/') {
+        return None;
+    }
+    authority.rsplit('@').next()
+}
+
+/// Derives the web URL of a hosted repository from its remote URL.
+///
+/// `git@host:owner/repo.git`, `ssh://git@host:22/owner/repo.git` and
+/// `https://user@host/owner/repo.git` all become `https://host/owner/repo`;
+/// `http://` URLs keep their scheme. Local paths and `file://` URLs have no
+/// web URL.
+pub fn web_url(remote_url: &str) -> Option<String> {
+    let (scheme, host, path) = if let Some((scheme, rest)) = remote_url.split_once("://") {
+        let scheme = match scheme {
+            "http" => "http",
+            "https" | "ssh" | "git" | "git+ssh" | "ssh+git" => "https",
+            _ => return None,
+        };
+        let (authority, path) = rest.split_once('/')?;
+        let host_port = authority.rsplit('@').next()?;
+        // A port only carries over to the web URL for http(s)
+        let host = match host_port.rsplit_once(':') {
+            Some((host, _)) if !remote_url.starts_with("http") => host,
+            _ => host_port,
+        };
+        (scheme, host, path)
+    } else {
+        let host = ssh_host(remote_url)?;
+        // `C:/repos/app` is a Windows path, not host `C`
+        if host.len() == 1 {
+            return None;
+        }
+        let (_, path) = remote_url.split_once(':')?;
+        ("https", host, path)
+    };
+
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if host.is_empty() || path.is_empty() {
+        return None;
+    }
+    Some(format!("{}://{}/{}", scheme, host, path))
+}
+
+/// Lists the SSH private keys to offer for a connection, in order of preference.
+///
+/// The identity configured for the remote comes first, followed by `IdentityFile`
+/// entries from the SSH config for `host`, then the default `~/.ssh/id_*` keys.
+/// Keys that don't exist on disk are skipped and duplicates are removed.
+///
+/// # Arguments
+/// * `configured` - Identity file configured for the remote, if any
+/// * `ssh_config` - Parsed `~/.ssh/config`
+/// * `host` - Host being connected to, if known
+pub fn ssh_identity_candidates(
+    configured: Option<&Path>,
+    ssh_config: &SshConfig,
+    host: Option<&str>,
+) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = configured.map(Path::to_path_buf).into_iter().collect();
+    if let Some(host) = host {
+        candidates.extend(ssh_config.identity_files(host));
+    }
+    if let Some(home) = dirs::home_dir() {
+        for name in ["id_ed25519", "id_rsa", "id_ecdsa"] {
+            candidates.push(home.join(".ssh").join(name));
+        }
+    }
+
+    let mut unique = Vec::new();
+    for path in candidates {
+        if path.exists() && !unique.contains(&path) {
+            unique.push(path);
+        }
+    }
+    unique
+}
+
+/// Environment variable holding an access token, only sent to the host of
+/// the remote being fetched or pushed
+pub const TOKEN_VAR: &str = "GIT_PUBLISH_TOKEN";
+
+/// Environment variable holding a GitHub access token, only sent to GitHub
+/// hosts so it can't leak to other servers
+pub const GITHUB_TOKEN_VAR: &str = "GITHUB_TOKEN";
+
+/// Host that is always sent [`GITHUB_TOKEN_VAR`]
+const GITHUB_HOST: &str = "github.com";
+
+/// Username sent with a token when the remote URL names none; GitHub and
+/// GitLab accept any non-empty name with a token
+const TOKEN_USERNAME: &str = "x-access-token";
+
+/// Asks for an HTTPS username and password; receives the remote URL and the
+/// username it names, if any
+pub type CredentialPrompt = fn(&str, Option<&str>) -> Option<(String, String)>;
+
+/// The token to send to `url`, looked up with `var`: [`TOKEN_VAR`] when the
+/// host is one of `remote_hosts` (those of the remote's configured URLs),
+/// otherwise [`GITHUB_TOKEN_VAR`] when the host is github.com or one of
+/// `github_hosts`. Tokens are only sent over `https://`, never in the clear.
+fn env_token(
+    url: &str,
+    remote_hosts: &[String],
+    github_hosts: &[String],
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<String> {
+    let host = https_host(url)?;
+    let listed = |hosts: &[String]| hosts.iter().any(|known| host.eq_ignore_ascii_case(known));
+    let non_empty = |name| var(name).filter(|token: &String| !token.is_empty());
+    listed(remote_hosts)
+        .then(|| non_empty(TOKEN_VAR))
+        .flatten()
+        .or_else(|| {
+            let github = host.eq_ignore_ascii_case(GITHUB_HOST) || listed(github_hosts);
+            github.then(|| non_empty(GITHUB_TOKEN_VAR)).flatten()
+        })
+}
+
+/// Extracts the host from an HTTPS URL (`https://user@host:port/path`).
+fn https_host(url: &str) -> Option<&str> {
+    let rest = url.strip_prefix("https://")?;
+    let authority = rest.split('/').next()?;
+    let host_port = authority.rsplit('@').next()?;
+    host_port.split(':').next().filter(|host| !host.is_empty())
+}
+
+/// Verifies SSH host keys presented during fetch and push against `known_hosts`.
+pub struct HostKeyVerifier {
+    /// Path of the `known_hosts` file to check and append to
+    pub known_hosts_path: PathBuf,
+    /// Verification mode
+    pub mode: HostKeyChecking,
+    /// Asks whether to trust an unknown host; receives the host and key fingerprint
+    pub confirm_unknown: fn(&str, &str) -> bool,
+}
+
+impl HostKeyVerifier {
+    /// Decide whether the certificate presented by `host` is acceptable.
+    ///
+    /// Non-SSH certificates and `off` mode defer to libgit2. Known keys are accepted,
+    /// mismatching keys are always rejected, and unknown keys are rejected in `strict`
+    /// mode or accepted (and saved) after confirmation in `ask` mode.
+    fn check(
+        &self,
+        cert: &git2::cert::Cert<'_>,
+        host: &str,
+        port: Option<u16>,
+    ) -> std::result::Result<git2::CertificateCheckStatus, git2::Error> {
+        let passthrough = Ok(git2::CertificateCheckStatus::CertificatePassthrough);
+        if self.mode == HostKeyChecking::Off {
+            return passthrough;
+        }
+        let Some(hostkey) = cert.as_hostkey() else {
+            return passthrough;
+        };
+        let (Some(key), Some(key_type)) = (hostkey.hostkey(), hostkey.hostkey_type()) else {
+            return passthrough;
+        };
+        let fingerprint = hostkey
+            .hash_sha256()
+            .map(|hash| known_hosts::format_fingerprint(hash))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let reject = |message: String| {
+            Err(git2::Error::new(
+                git2::ErrorCode::Certificate,
+                git2::ErrorClass::Ssh,
+                message,
+            ))
+        };
+
+        let known = KnownHosts::load(&self.known_hosts_path)
+            .map_err(|e| git2::Error::from_str(&e.to_string()))?;
+        match known.check(host, port, key_type.name(), key) {
+            HostKeyStatus::Trusted => Ok(git2::CertificateCheckStatus::CertificateOk),
+            HostKeyStatus::Mismatch => reject(format!(
+                "Host key for '{}' ({}) does not match {}; refusing to connect",
+                host,
+                fingerprint,
+                self.known_hosts_path.display()
+            )),
+            HostKeyStatus::Unknown if self.mode == HostKeyChecking::Strict => reject(format!(
+                "Host key for '{}' ({}) is not in {} (strict host key checking)",
+                host,
+                fingerprint,
+                self.known_hosts_path.display()
+            )),
+            HostKeyStatus::Unknown => {
+                if !(self.confirm_unknown)(host, &fingerprint) {
+                    return reject(format!("Host key for '{}' was not accepted", host));
+                }
+                KnownHosts::append(&self.known_hosts_path, host, port, key_type.name(), key)
+                    .map_err(|e| git2::Error::from_str(&e.to_string()))?;
+                Ok(git2::CertificateCheckStatus::CertificateOk)
+            }
+        }
+    }
+}
+
+/// Gives up on fetches and pushes whose remote stops responding.
+///
+/// libgit2 can't cancel a blocked network read, so a watchdog thread calls
+/// `on_stall` once the remote has made no progress for `duration`; it is
+/// expected to report the problem and end the process.
+pub struct NetworkTimeout {
+    /// How long a remote may go without progress
+    pub duration: Duration,
+    /// Called with the remote name and `duration` when the remote stalls
+    pub on_stall: fn(&str, Duration),
+}
+
+/// Signals from a network operation to its watchdog
+enum Heartbeat {
+    /// Data was transferred
+    Progress,
+    /// Waiting on the user (e.g. a host key prompt), not the remote
+    Pause,
+    /// Back to waiting on the remote
+    Resume,
+}
+
+/// Waits for heartbeats until the operation ends (all senders dropped), calling
+/// `on_stall` if none arrives within `duration` while not paused.
+fn watch(heartbeats: mpsc::Receiver<Heartbeat>, duration: Duration, on_stall: impl FnOnce()) {
+    let mut paused = false;
+    loop {
+        let heartbeat = if paused {
+            heartbeats
+                .recv()
+                .map_err(|_| RecvTimeoutError::Disconnected)
+        } else {
+            heartbeats.recv_timeout(duration)
+        };
+        match heartbeat {
+            Ok(Heartbeat::Progress) => {}
+            Ok(Heartbeat::Pause) => paused = true,
+            Ok(Heartbeat::Resume) => paused = false,
+            Err(RecvTimeoutError::Timeout) => return on_stall(),
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
 /// Wrapper around git2 Repository for tag and commit operations.
 ///
 /// Provides high-level abstractions for common git operations used by git-publish,
 /// including fetching, tagging, pushing, and commit history traversal.
 pub struct GitRepo {
     repo: Repository,
+    host_key_verifier: Option<HostKeyVerifier>,
+    ssh_identities: HashMap<String, PathBuf>,
+    network_timeout: Option<NetworkTimeout>,
+    /// Paths of the repository's `.gitpublishignore`, if it has one
+    ignored_paths: Option<PathIgnore>,
+    /// Only commits changing these paths count, when releasing a package
+    path_scope: Option<PathIgnore>,
+    credential_prompt: Option<CredentialPrompt>,
+    /// Hosts besides github.com that are sent `GITHUB_TOKEN`
+    github_hosts: Vec<String>,
 }
 
 impl GitRepo {
@@ -23,7 +505,231 @@ impl GitRepo {
             Ok(repo) => repo,
             Err(e) => return Err(anyhow::anyhow!("Not in a git repository: {}", e)),
         };
-        Ok(GitRepo { repo })
+        let ignored_paths = match repo.workdir() {
+            Some(root) => PathIgnore::load(root)
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", IGNORE_FILE, e))?,
+            None => None,
+        };
+        Ok(GitRepo {
+            repo,
+            host_key_verifier: None,
+            ssh_identities: HashMap::new(),
+            network_timeout: None,
+            ignored_paths,
+            path_scope: None,
+            credential_prompt: None,
+            github_hosts: Vec::new(),
+        })
+    }
+
+    /// Enables SSH host key verification for subsequent fetch and push operations.
+    pub fn set_host_key_verifier(&mut self, verifier: HostKeyVerifier) {
+        self.host_key_verifier = Some(verifier);
+    }
+
+    /// Ignores changes to `patterns` (`.gitpublishignore` syntax) from now on,
+    /// on top of the repository's `.gitpublishignore`, whose patterns come last.
+    pub fn add_ignored_paths(&mut self, patterns: &[String]) {
+        if patterns.is_empty() {
+            return;
+        }
+        let mut ignored = PathIgnore::parse(&patterns.join("\n"));
+        if let Some(file) = self.ignored_paths.take() {
+            ignored.extend(file);
+        }
+        self.ignored_paths = Some(ignored);
+    }
+
+    /// Only counts commits changing files matched by `paths` from now on,
+    /// for releasing one package of a monorepo.
+    ///
+    /// Merge commits and empty commits still count, as with `.gitpublishignore`.
+    pub fn set_path_scope(&mut self, paths: PathIgnore) {
+        self.path_scope = Some(paths);
+    }
+
+    /// Enables the stall watchdog for subsequent fetch and push operations.
+    pub fn set_network_timeout(&mut self, timeout: NetworkTimeout) {
+        self.network_timeout = Some(timeout);
+    }
+
+    /// Sets the SSH private key to offer first when talking to `remote_name`.
+    pub fn set_ssh_identity(&mut self, remote_name: &str, key_path: PathBuf) {
+        self.ssh_identities
+            .insert(remote_name.to_string(), key_path);
+    }
+
+    /// Also sends `GITHUB_TOKEN` to these hosts, such as a GitHub Enterprise
+    /// Server; github.com always gets it.
+    pub fn set_github_hosts(&mut self, hosts: Vec<String>) {
+        self.github_hosts = hosts;
+    }
+
+    /// Sets the prompt for an HTTPS username and password, asked once no
+    /// token or credential helper was accepted.
+    pub fn set_credential_prompt(&mut self, prompt: CredentialPrompt) {
+        self.credential_prompt = Some(prompt);
+    }
+
+    /// Registers credential lookup for `remote_name` on a set of remote callbacks.
+    ///
+    /// SSH keys from [`ssh_identity_candidates`] are offered one at a time, so a key
+    /// the server rejects moves on to the next, followed by the SSH agent and finally
+    /// libgit2's default credentials.
+    ///
+    /// HTTPS remotes get, one at a time, a token from [`TOKEN_VAR`] (or
+    /// [`GITHUB_TOKEN_VAR`] for GitHub hosts; see [`env_token`]), the git
+    /// credential helpers and the [credential prompt](GitRepo::set_credential_prompt),
+    /// which pauses the watchdog (if any) while it waits.
+    fn add_credentials<'a>(
+        &'a self,
+        callbacks: &mut git2::RemoteCallbacks<'a>,
+        remote_name: &str,
+        heartbeat: Option<mpsc::Sender<Heartbeat>>,
+    ) {
+        let configured = self.ssh_identities.get(remote_name);
+        // A redirect can send the request to another host, which must not
+        // get the token meant for the remote's own
+        let remote_hosts: Vec<String> = match self.repo.find_remote(remote_name) {
+            Ok(remote) => [remote.url(), remote.pushurl()]
+                .into_iter()
+                .flatten()
+                .filter_map(https_host)
+                .map(str::to_string)
+                .collect(),
+            Err(_) => https_host(remote_name)
+                .map(str::to_string)
+                .into_iter()
+                .collect(),
+        };
+        let mut candidates: Option<std::vec::IntoIter<PathBuf>> = None;
+        let mut agent_tried = false;
+        // libgit2 asks again after a rejection, so each source is tried once
+        let (mut token_tried, mut helper_tried, mut prompted) = (false, false, false);
+
+        callbacks.credentials(move |url, username_from_url, allowed_types| {
+            if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+                if !token_tried {
+                    token_tried = true;
+                    let token = env_token(url, &remote_hosts, &self.github_hosts, |name| {
+                        std::env::var(name).ok()
+                    });
+                    if let Some(token) = token {
+                        let username = username_from_url.unwrap_or(TOKEN_USERNAME);
+                        return git2::Cred::userpass_plaintext(username, &token);
+                    }
+                }
+                if !helper_tried {
+                    helper_tried = true;
+                    let helper = self.repo.config().and_then(|config| {
+                        git2::Cred::credential_helper(&config, url, username_from_url)
+                    });
+                    if let Ok(cred) = helper {
+                        return Ok(cred);
+                    }
+                }
+                if let Some(prompt) = self.credential_prompt.filter(|_| !prompted) {
+                    prompted = true;
+                    let beat = |signal| heartbeat.as_ref().map(|tx| tx.send(signal));
+                    beat(Heartbeat::Pause);
+                    let answer = prompt(url, username_from_url);
+                    beat(Heartbeat::Resume);
+                    if let Some((username, password)) = answer {
+                        return git2::Cred::userpass_plaintext(&username, &password);
+                    }
+                }
+                if !allowed_types.contains(git2::CredentialType::DEFAULT) {
+                    return Err(git2::Error::new(
+                        git2::ErrorCode::Auth,
+                        git2::ErrorClass::Http,
+                        "no HTTPS credentials were accepted",
+                    ));
+                }
+            }
+
+            let username = username_from_url.unwrap_or("git");
+            if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+                let keys = candidates.get_or_insert_with(|| {
+                    let ssh_config = SshConfig::default_path()
+                        .and_then(|path| SshConfig::load(&path).ok())
+                        .unwrap_or_default();
+                    ssh_identity_candidates(
+                        configured.map(PathBuf::as_path),
+                        &ssh_config,
+                        ssh_host(url),
+                    )
+                    .into_iter()
+                });
+
+                for key_path in keys.by_ref() {
+                    if let Ok(cred) = git2::Cred::ssh_key(username, None, &key_path, None) {
+                        return Ok(cred);
+                    }
+                }
+
+                // Try SSH agent as fallback
+                if !agent_tried {
+                    agent_tried = true;
+                    if let Ok(cred) = git2::Cred::ssh_key_from_agent(username) {
+                        return Ok(cred);
+                    }
+                }
+            }
+
+            // Fall back to default credentials
+            git2::Cred::default()
+        });
+    }
+
+    /// Registers the configured SSH host key check on a set of remote callbacks.
+    ///
+    /// The watchdog (if any) is paused while the check runs, since it may prompt.
+    fn add_host_key_check<'a>(
+        &'a self,
+        callbacks: &mut git2::RemoteCallbacks<'a>,
+        url: &str,
+        heartbeat: Option<mpsc::Sender<Heartbeat>>,
+    ) {
+        if let Some(verifier) = self.host_key_verifier.as_ref() {
+            let port = ssh_port(url);
+            callbacks.certificate_check(move |cert, host| {
+                let beat = |signal| heartbeat.as_ref().map(|tx| tx.send(signal));
+                beat(Heartbeat::Pause);
+                let result = verifier.check(cert, host, port);
+                beat(Heartbeat::Resume);
+                result
+            });
+        }
+    }
+
+    /// Starts the stall watchdog for an operation on `remote_name`, if enabled,
+    /// and reports transfer progress to it from a set of remote callbacks.
+    ///
+    /// The watchdog stops once the callbacks and the returned sender are dropped.
+    fn add_network_timeout(
+        &self,
+        callbacks: &mut git2::RemoteCallbacks<'_>,
+        remote_name: &str,
+    ) -> Option<mpsc::Sender<Heartbeat>> {
+        let timeout = self.network_timeout.as_ref()?;
+        let (tx, rx) = mpsc::channel();
+        let (duration, on_stall) = (timeout.duration, timeout.on_stall);
+        let remote_name = remote_name.to_string();
+        std::thread::spawn(move || watch(rx, duration, || on_stall(&remote_name, duration)));
+
+        let progress = tx.clone();
+        callbacks.transfer_progress(move |_| progress.send(Heartbeat::Progress).is_ok());
+        let progress = tx.clone();
+        callbacks.sideband_progress(move |_| progress.send(Heartbeat::Progress).is_ok());
+        let progress = tx.clone();
+        callbacks.pack_progress(move |_, _, _| {
+            let _ = progress.send(Heartbeat::Progress);
+        });
+        let progress = tx.clone();
+        callbacks.push_transfer_progress(move |_, _, _| {
+            let _ = progress.send(Heartbeat::Progress);
+        });
+        Some(tx)
     }
 
     /// Gets all configured remote names from the repository.
@@ -55,6 +761,71 @@ impl GitRepo {
         Ok(remotes)
     }
 
+    /// Returns the branch HEAD points at when it has no commit yet, as right
+    /// after `git init`.
+    ///
+    /// # Returns
+    /// * `Some(branch)` - HEAD is unborn
+    /// * `None` - HEAD points at a commit (or is detached)
+    pub fn unborn_branch(&self) -> Option<String> {
+        match self.repo.head() {
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+                let head = self.repo.find_reference("HEAD").ok()?;
+                let target = head.symbolic_target()?;
+                Some(target.trim_start_matches("refs/heads/").to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the names of all local branches and remote-tracking branches.
+    ///
+    /// Remote-tracking branches are listed without their remote prefix
+    /// (`origin/main` becomes `main`) and merged with the local ones.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<String>)` - Sorted, deduplicated branch names
+    /// * `Err` - If unable to list branches
+    pub fn list_branches(&self) -> Result<Vec<String>> {
+        let remotes = self.list_remotes()?;
+        let mut names = Vec::new();
+
+        for branch in self.repo.branches(None)? {
+            let (branch, branch_type) = branch?;
+            let Some(name) = branch.name()? else {
+                continue;
+            };
+            let name = match branch_type {
+                BranchType::Local => name,
+                BranchType::Remote => {
+                    let Some(short) = remotes
+                        .iter()
+                        .find_map(|remote| name.strip_prefix(&format!("{}/", remote)))
+                    else {
+                        continue;
+                    };
+                    if short == "HEAD" {
+                        continue;
+                    }
+                    short
+                }
+            };
+            names.push(name.to_string());
+        }
+
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    /// Check if a branch exists locally or as a remote-tracking branch of any remote.
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the branch (e.g., "main")
+    pub fn branch_exists(&self, branch_name: &str) -> Result<bool> {
+        Ok(self.list_branches()?.iter().any(|name| name == branch_name))
+    }
+
     /// Check if a remote with the given name exists in the repository.
     ///
     /// # Arguments
@@ -72,117 +843,338 @@ impl GitRepo {
         }
     }
 
-    /// Fetches latest data from a remote repository and updates the specified branch.
-    ///
-    /// Fetches from the remote and updates both remote-tracking branches and the specified
-    /// local branch if it can be fast-forwarded. This ensures the selected branch is in sync
-    /// with its remote counterpart before processing.
-    ///
-    /// Supports SSH authentication via SSH agent, SSH keys from ~/.ssh/, or other credential helpers.
+    /// Returns the fetch URL of a remote with `insteadOf` rules applied.
     ///
     /// # Arguments
     /// * `remote_name` - Name of the remote (e.g., "origin")
-    /// * `branch_name` - Name of the local branch to update (e.g., "master")
     ///
     /// # Returns
-    /// * `Ok(())` - Successfully fetched and updated
-    /// * `Err` - If remote not found or fetch fails
-    pub fn fetch_from_remote(&self, remote_name: &str, branch_name: &str) -> Result<()> {
-        let mut remote = self
+    /// * `Ok(Some(url))` - The remote's URL
+    /// * `Ok(None)` - The remote has no URL
+    /// * `Err` - If the remote does not exist
+    pub fn remote_url(&self, remote_name: &str) -> Result<Option<String>> {
+        let remote = self
             .repo
             .find_remote(remote_name)
             .map_err(|_| anyhow::anyhow!("Remote '{}' not found", remote_name))?;
+        let rules = self.url_rewrites().unwrap_or_default();
+        Ok(remote.url().map(|url| rewrite_url(url, &rules, false)))
+    }
 
-        let mut fetch_options = git2::FetchOptions::new();
-
-        // Set credentials callback for authentication
-        let mut callbacks = git2::RemoteCallbacks::new();
-        callbacks.credentials(|_url, username_from_url, allowed_types| {
-            // SSH key authentication
-            if allowed_types.contains(git2::CredentialType::SSH_KEY) {
-                // Try different key types in order of preference
-                let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-                let key_paths = vec![
-                    format!("{}/.ssh/id_ed25519", home),
-                    format!("{}/.ssh/id_rsa", home),
-                    format!("{}/.ssh/id_ecdsa", home),
-                ];
-
-                for key_path in key_paths {
-                    let path = std::path::Path::new(&key_path);
-                    if path.exists() {
-                        if let Ok(cred) = git2::Cred::ssh_key(
-                            username_from_url.unwrap_or("git"),
-                            None,
-                            path,
-                            None,
-                        ) {
-                            return Ok(cred);
-                        }
-                    }
-                }
-
-                // Try SSH agent as fallback
-                if let Ok(cred) = git2::Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"))
-                {
-                    return Ok(cred);
-                }
-            }
-
-            // Fall back to default credentials
-            git2::Cred::default()
-        });
+    /// Reads `url.<base>.insteadOf` and `url.<base>.pushInsteadOf` rules from git config.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<UrlRewrite>)` - All rewrite rules visible to this repository
+    /// * `Err` - If git config cannot be read
+    pub fn url_rewrites(&self) -> Result<Vec<UrlRewrite>> {
+        let config = self.repo.config()?.snapshot()?;
+        let mut rules = Vec::new();
 
-        fetch_options.remote_callbacks(callbacks);
+        let mut entries = config.entries(Some(r"^url\..*\.(push)?insteadof$"))?;
+        while let Some(entry) = entries.next() {
+            let entry = entry?;
+            let (Some(name), Some(prefix)) = (entry.name(), entry.value()) else {
+                continue;
+            };
 
-        // Use explicit refspecs to fetch all branches and tags from the remote.
-        // The refspecs mean:
-        // - "+refs/heads/*:refs/remotes/{remote_name}/*" - Fetch all remote branches
-        // - "+refs/tags/*:refs/tags/*" - Fetch all tags
-        let refspec_heads = format!("+refs/heads/*:refs/remotes/{}/*", remote_name);
-        let refspecs = &[refspec_heads.as_str(), "+refs/tags/*:refs/tags/*"];
-        remote
-            .fetch(refspecs, Some(&mut fetch_options), None)
-            .map_err(|e| anyhow::anyhow!("Failed to fetch from remote '{}': {}", remote_name, e))?;
+            let (rest, push_only) = if let Some(rest) = name.strip_suffix(".pushinsteadof") {
+                (rest, true)
+            } else if let Some(rest) = name.strip_suffix(".insteadof") {
+                (rest, false)
+            } else {
+                continue;
+            };
 
-        // After fetching, try to fast-forward the specified branch with its remote counterpart
-        self.update_branch_from_remote(branch_name, remote_name)?;
+            if let Some(base) = rest.strip_prefix("url.") {
+                rules.push(UrlRewrite {
+                    base: base.to_string(),
+                    prefix: prefix.to_string(),
+                    push_only,
+                });
+            }
+        }
 
-        Ok(())
+        Ok(rules)
     }
 
-    /// Updates a local branch to match its remote counterpart via fast-forward merge.
+    /// Looks up a remote, applying URL rewrite rules from git config.
     ///
-    /// If the remote has new commits that can be fast-forwarded into the local branch,
-    /// this method will perform the merge. This is similar to `git pull --ff-only`.
+    /// libgit2 does not apply `insteadOf`/`pushInsteadOf` in every code path, so the
+    /// rewritten URL is resolved here. When a rewrite applies (or an explicit URL is
+    /// given), an anonymous remote for the final URL is returned.
     ///
     /// # Arguments
-    /// * `branch_name` - Name of the local branch to update
     /// * `remote_name` - Name of the remote (e.g., "origin")
+    /// * `explicit_url` - URL to use instead of the remote's configured URL
+    /// * `for_push` - Whether the remote is used for pushing
     ///
     /// # Returns
-    /// * `Ok(())` - Successfully updated or no update needed
-    /// * `Err` - If the operation cannot be completed
-    fn update_branch_from_remote(&self, branch_name: &str, remote_name: &str) -> Result<()> {
-        // Get the remote-tracking branch OID
-        let remote_tracking_branch_name = format!("{}/{}", remote_name, branch_name);
-        let remote_ref = match self
-            .repo
-            .find_reference(&format!("refs/remotes/{}", remote_tracking_branch_name))
-        {
-            Ok(r) => r,
-            Err(_) => {
-                // Remote branch doesn't exist, nothing to update
-                return Ok(());
+    /// * `Ok(Remote)` - The remote to fetch from or push to
+    /// * `Err` - If the remote does not exist or the URL is invalid
+    fn resolve_remote(
+        &self,
+        remote_name: &str,
+        explicit_url: Option<&str>,
+        for_push: bool,
+    ) -> Result<git2::Remote<'_>> {
+        let rules = self.url_rewrites().unwrap_or_default();
+
+        let (remote, url, has_push_url) = match explicit_url {
+            Some(url) => (None, url.to_string(), true),
+            None => {
+                let remote = self
+                    .repo
+                    .find_remote(remote_name)
+                    .map_err(|_| anyhow::anyhow!("Remote '{}' not found", remote_name))?;
+                let push_url = if for_push { remote.pushurl() } else { None };
+                let url = push_url.or(remote.url()).unwrap_or_default().to_string();
+                let has_push_url = push_url.is_some();
+                (Some(remote), url, has_push_url)
             }
         };
 
-        let remote_oid = remote_ref.target().ok_or_else(|| {
-            anyhow::anyhow!(
-                "Remote {} reference is invalid",
-                remote_tracking_branch_name
-            )
-        })?;
+        // git ignores pushInsteadOf for URLs that were explicitly configured as push URLs
+        let rewritten = if has_push_url {
+            rewrite_url(&url, &rules, false)
+        } else {
+            rewrite_url(&url, &rules, for_push)
+        };
+
+        match remote {
+            Some(remote) if rewritten == url => Ok(remote),
+            _ => self
+                .repo
+                .remote_anonymous(&rewritten)
+                .map_err(|e| anyhow::anyhow!("Invalid remote URL '{}': {}", rewritten, e)),
+        }
+    }
+
+    /// Fetches latest data from a remote repository and updates the specified branch.
+    ///
+    /// Fetches from the remote and updates both remote-tracking branches and the specified
+    /// local branch if it can be fast-forwarded. This ensures the selected branch is in sync
+    /// with its remote counterpart before processing.
+    ///
+    /// Supports SSH authentication via SSH agent or SSH keys from ~/.ssh/, and HTTPS
+    /// tokens, credential helpers or prompts (see `add_credentials`).
+    ///
+    /// # Arguments
+    /// * `remote_name` - Name of the remote (e.g., "origin")
+    /// * `branch_name` - Name of the local branch to update (e.g., "master")
+    ///
+    /// # Returns
+    /// * `Ok(())` - Successfully fetched and updated
+    /// * `Err` - If remote not found or fetch fails
+    pub fn fetch_from_remote(&self, remote_name: &str, branch_name: &str) -> Result<()> {
+        self.fetch_from_remote_with_branches(remote_name, branch_name, &[])
+    }
+
+    /// Fetches from a remote like [`GitRepo::fetch_from_remote`], also
+    /// fast-forwarding other local branches from the same fetch.
+    ///
+    /// The fetch downloads every head of the remote anyway, so all configured
+    /// branches can be brought up to date at once instead of with a fetch
+    /// each. Unlike `branch_name`, the other branches are only updated when
+    /// they exist locally, and are left alone when they have diverged.
+    ///
+    /// # Arguments
+    /// * `remote_name` - Name of the remote (e.g., "origin")
+    /// * `branch_name` - Name of the local branch to update, created from the remote when missing
+    /// * `other_branches` - Other local branches to fast-forward
+    ///
+    /// # Returns
+    /// * `Ok(())` - Successfully fetched and updated
+    /// * `Err` - If remote not found or fetch fails
+    pub fn fetch_from_remote_with_branches(
+        &self,
+        remote_name: &str,
+        branch_name: &str,
+        other_branches: &[String],
+    ) -> Result<()> {
+        self.fetch_remote_refs(remote_name)?;
+
+        // After fetching, try to fast-forward the specified branch with its remote counterpart
+        self.update_branch_from_remote(branch_name, remote_name)?;
+        for other in other_branches {
+            if other != branch_name && self.repo.find_branch(other, BranchType::Local).is_ok() {
+                self.update_branch_from_remote(other, remote_name)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fetches the remote's branches and tags without updating any local branch.
+    ///
+    /// Only the remote-tracking branches (`refs/remotes/<remote>/*`) and the tags
+    /// change, as for a dry run.
+    ///
+    /// # Arguments
+    /// * `remote_name` - Name of the remote (e.g., "origin")
+    ///
+    /// # Returns
+    /// * `Ok(())` - Successfully fetched
+    /// * `Err` - If remote not found or fetch fails
+    pub fn fetch_remote_refs(&self, remote_name: &str) -> Result<()> {
+        let mut remote = self.resolve_remote(remote_name, None, false)?;
+
+        let mut fetch_options = git2::FetchOptions::new();
+
+        // Set credentials callback for authentication
+        let mut callbacks = git2::RemoteCallbacks::new();
+        let heartbeat = self.add_network_timeout(&mut callbacks, remote_name);
+        self.add_credentials(&mut callbacks, remote_name, heartbeat.clone());
+        let remote_url = remote.url().unwrap_or_default().to_string();
+        self.add_host_key_check(&mut callbacks, &remote_url, heartbeat);
+
+        fetch_options.remote_callbacks(callbacks);
+
+        // Use explicit refspecs to fetch all branches and tags from the remote.
+        // The refspecs mean:
+        // - "+refs/heads/*:refs/remotes/{remote_name}/*" - Fetch all remote branches
+        // - "+refs/tags/*:refs/tags/*" - Fetch all tags
+        let refspec_heads = format!("+refs/heads/*:refs/remotes/{}/*", remote_name);
+        let refspecs = &[refspec_heads.as_str(), "+refs/tags/*:refs/tags/*"];
+        remote
+            .fetch(refspecs, Some(&mut fetch_options), None)
+            .map_err(|e| {
+                GitPublishError::remote_access(
+                    remote_name,
+                    classify_remote_error(&e),
+                    format!("Failed to fetch: {}", e),
+                )
+            })?;
+        Ok(())
+    }
+
+    /// Opens and closes a push connection to a remote without transferring anything.
+    ///
+    /// Connecting reads the remote's ref advertisement, like `git ls-remote`.
+    ///
+    /// Exercises URL rewriting, credentials and host key verification the same way a
+    /// real push would, so problems surface before a tag is created.
+    ///
+    /// # Arguments
+    /// * `remote_name` - Name of the remote to connect to
+    /// * `remote_override` - Optional push URL override for this remote
+    ///
+    /// # Returns
+    /// * `Ok(())` - The remote accepted the connection
+    /// * `Err` - If the remote is missing, unreachable or rejects the credentials
+    pub fn check_remote_connection(
+        &self,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+    ) -> Result<()> {
+        let push_url = remote_override.and_then(|o| o.push_url.as_deref());
+        let mut remote = self.resolve_remote(remote_name, push_url, true)?;
+        let remote_url = remote
+            .pushurl()
+            .or(remote.url())
+            .unwrap_or_default()
+            .to_string();
+
+        let mut callbacks = git2::RemoteCallbacks::new();
+        let heartbeat = self.add_network_timeout(&mut callbacks, remote_name);
+        self.add_credentials(&mut callbacks, remote_name, heartbeat.clone());
+        self.add_host_key_check(&mut callbacks, &remote_url, heartbeat);
+
+        let connection = remote
+            .connect_auth(git2::Direction::Push, Some(callbacks), None)
+            .map_err(|e| {
+                GitPublishError::remote_access(
+                    remote_name,
+                    classify_remote_error(&e),
+                    format!("Failed to connect: {}", e),
+                )
+            })?;
+        drop(connection);
+        Ok(())
+    }
+
+    /// Lists the tags on the remote that tags are pushed to.
+    ///
+    /// # Arguments
+    /// * `remote_name` - Name of the remote (e.g., "origin")
+    /// * `remote_override` - Optional push URL override for the remote
+    ///
+    /// # Returns
+    /// * `Ok(tags)` - Tag names without `refs/tags/`, in the order the remote lists them
+    /// * `Err` - If the remote can't be reached
+    pub fn remote_tags(
+        &self,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+    ) -> Result<Vec<String>> {
+        let push_url = remote_override.and_then(|o| o.push_url.as_deref());
+        let mut remote = self.resolve_remote(remote_name, push_url, true)?;
+        let remote_url = remote
+            .pushurl()
+            .or(remote.url())
+            .unwrap_or_default()
+            .to_string();
+
+        let mut callbacks = git2::RemoteCallbacks::new();
+        let heartbeat = self.add_network_timeout(&mut callbacks, remote_name);
+        self.add_credentials(&mut callbacks, remote_name, heartbeat.clone());
+        self.add_host_key_check(&mut callbacks, &remote_url, heartbeat);
+
+        let connection = remote
+            .connect_auth(git2::Direction::Push, Some(callbacks), None)
+            .map_err(|e| {
+                GitPublishError::remote_access(
+                    remote_name,
+                    classify_remote_error(&e),
+                    format!("Failed to connect: {}", e),
+                )
+            })?;
+        let mut tags = Vec::new();
+        for head in connection.list()? {
+            // Annotated tags are also listed peeled, as `refs/tags/v1.0.0^{}`
+            if let Some(tag) = head.name().strip_prefix("refs/tags/") {
+                if !tag.ends_with("^{}") {
+                    tags.push(tag.to_string());
+                }
+            }
+        }
+        Ok(tags)
+    }
+
+    /// Updates a local branch to match its remote counterpart via fast-forward merge.
+    ///
+    /// If the remote has new commits that can be fast-forwarded into the local branch,
+    /// this method will perform the merge. This is similar to `git pull --ff-only`.
+    ///
+    /// The checked-out branch is only moved along with its index and working tree,
+    /// when neither has changes to tracked files; otherwise it is left alone.
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the local branch to update
+    /// * `remote_name` - Name of the remote (e.g., "origin")
+    ///
+    /// # Returns
+    /// * `Ok(())` - Successfully updated or no update needed
+    /// * `Err` - If the operation cannot be completed
+    fn update_branch_from_remote(&self, branch_name: &str, remote_name: &str) -> Result<()> {
+        // Get the remote-tracking branch OID
+        let remote_tracking_branch_name = format!("{}/{}", remote_name, branch_name);
+        let remote_ref = match self
+            .repo
+            .find_reference(&format!("refs/remotes/{}", remote_tracking_branch_name))
+        {
+            Ok(r) => r,
+            Err(_) => {
+                // Remote branch doesn't exist, nothing to update
+                return Ok(());
+            }
+        };
+
+        let remote_oid = remote_ref.target().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Remote {} reference is invalid",
+                remote_tracking_branch_name
+            )
+        })?;
 
         // Get the local branch OID
         let local_branch = match self.repo.find_branch(branch_name, BranchType::Local) {
@@ -218,8 +1210,27 @@ impl GitRepo {
             return Ok(());
         }
 
-        // Perform the fast-forward: update the local branch reference to point to remote's commit
+        // The checked-out branch takes the index and working tree along
         let branch_ref_name = format!("refs/heads/{}", branch_name);
+        let checked_out = self
+            .repo
+            .head()
+            .ok()
+            .is_some_and(|head| head.name() == Some(branch_ref_name.as_str()));
+        if checked_out {
+            let mut options = git2::StatusOptions::new();
+            options.include_untracked(false).include_ignored(false);
+            if !self.repo.statuses(Some(&mut options))?.is_empty() {
+                return Ok(());
+            }
+            let remote_commit = self.repo.find_commit(remote_oid)?;
+            self.repo.checkout_tree(
+                remote_commit.as_object(),
+                Some(git2::build::CheckoutBuilder::new().safe()),
+            )?;
+        }
+
+        // Perform the fast-forward: update the local branch reference to point to remote's commit
         match self.repo.find_reference(&branch_ref_name) {
             Ok(mut reference) => {
                 reference.set_target(
@@ -273,6 +1284,26 @@ impl GitRepo {
         Ok(commit.id())
     }
 
+    /// Finds the best common ancestor of a branch and another branch.
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the local branch
+    /// * `other` - The other branch, e.g. `main` or `origin/main` (any revision works)
+    ///
+    /// # Returns
+    /// * `Ok(hash)` - Hash of the merge-base commit
+    /// * `Err` - If either branch is not found or they share no history
+    pub fn merge_base(&self, branch_name: &str, other: &str) -> Result<String> {
+        let branch_oid = self.get_branch_head_oid(branch_name)?;
+        let other_oid = self
+            .repo
+            .revparse_single(other)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| anyhow::anyhow!("Branch '{}' not found", other))?
+            .id();
+        Ok(self.repo.merge_base(branch_oid, other_oid)?.to_string())
+    }
+
     /// Finds the latest tag on a specific branch, checking both local and remote-tracking branches.
     ///
     /// Walks the commit history from the branch head backwards to find the most recent tag.
@@ -295,201 +1326,864 @@ impl GitRepo {
         remote_name: Option<&str>,
         tag_pattern: Option<&str>,
     ) -> Result<Option<String>> {
-        // Extract prefix from tag pattern (e.g., "g" from "g{version}", "v" from "v{version}")
-        let tag_prefix: Option<String> = tag_pattern.and_then(|pattern| {
-            if let Some(pos) = pattern.find("{version}") {
-                let prefix = &pattern[..pos];
-                if !prefix.is_empty() {
-                    return Some(prefix.to_string());
+        // First, try to get tag from the remote-tracking branch if remote is provided
+        if let Some(remote) = remote_name {
+            let remote_tracking_branch = format!("{}/{}", remote, branch_name);
+            if let Ok(remote_oid) = self
+                .get_branch_head_oid_from_ref(&format!("refs/remotes/{}", remote_tracking_branch))
+            {
+                if let Ok(Some(tag)) = self.latest_tag_from(remote_oid, tag_pattern) {
+                    return Ok(Some(tag));
                 }
             }
-            None
-        });
+        }
+
+        // Fall back to local branch
+        let local_oid = self.get_branch_head_oid(branch_name)?;
+        self.latest_tag_from(local_oid, tag_pattern)
+    }
 
-        // Helper function to check if a tag matches the expected pattern
-        let matches_tag_pattern = |tag: &str| -> bool {
-            if let Some(ref prefix) = tag_prefix {
-                // Tag must start with the expected prefix
-                if !tag.starts_with(prefix.as_str()) {
-                    return false;
+    /// Finds the latest tag in the history of a commit, as
+    /// [`GitRepo::get_latest_tag_on_branch`] does for a branch head.
+    ///
+    /// # Arguments
+    /// * `revision` - The commit to start from (e.g. a SHA, `HEAD~2` or a tag)
+    /// * `tag_pattern` - Only consider tags matching this pattern
+    ///
+    /// # Returns
+    /// * `Ok(Some(tag))` - The latest tag found
+    /// * `Ok(None)` - If no tag is reachable from the commit
+    /// * `Err` - If the revision isn't a commit
+    pub fn get_latest_tag_at(
+        &self,
+        revision: &str,
+        tag_pattern: Option<&str>,
+    ) -> Result<Option<String>> {
+        let oid = self.find_commit_at(revision)?.id();
+        self.latest_tag_from(oid, tag_pattern)
+    }
+
+    /// Latest tag matching the pattern reachable from `oid`
+    fn latest_tag_from(&self, oid: Oid, tag_pattern: Option<&str>) -> Result<Option<String>> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(oid)?;
+
+        // Get all tags and their OIDs (handles both lightweight and annotated tags)
+        // Only include tags that match the expected pattern
+        let mut tag_oids = std::collections::HashMap::new();
+        let tags = self.repo.tag_names(None)?;
+
+        for tag_name in tags.iter().flatten() {
+            // Skip tags that don't match the pattern
+            if !tag_matches_pattern(tag_name, tag_pattern) {
+                continue;
+            }
+            if let Ok(tag_ref) = self.repo.find_reference(&format!("refs/tags/{}", tag_name)) {
+                // Peel to any object (commit, tag, etc.)
+                if let Ok(tag_obj) = tag_ref.peel(git2::ObjectType::Any) {
+                    let tag_oid = tag_obj.id();
+                    tag_oids.insert(tag_oid, tag_name.to_string());
                 }
-                // After the prefix, must start with a digit
-                let rest = &tag[prefix.len()..];
-                rest.chars().next().is_some_and(|c| c.is_ascii_digit())
-            } else {
-                // No pattern specified, accept any semver-like tag
-                // (starts with optional v/V followed by digit)
-                let trimmed = tag.trim_start_matches('v').trim_start_matches('V');
-                trimmed.chars().next().is_some_and(|c| c.is_ascii_digit())
             }
+        }
+
+        // Find the latest tag in the history
+        for oid in revwalk {
+            match oid {
+                Ok(oid) => {
+                    if let Some(tag_name) = tag_oids.get(&oid) {
+                        return Ok(Some(tag_name.clone()));
+                    }
+                }
+                Err(_) => continue,
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Finds the latest tag on a specific branch (local branch only).
+    ///
+    /// Walks the commit history from the branch head backwards to find the most recent tag.
+    /// Handles both lightweight and annotated tags.
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the branch to search
+    ///
+    /// # Returns
+    /// * `Ok(Some(tag))` - The latest tag name found
+    /// * `Ok(None)` - If no tags exist on this branch
+    /// * `Err` - If branch lookup fails
+    pub fn get_latest_tag_on_branch(
+        &self,
+        branch_name: &str,
+        tag_pattern: Option<&str>,
+    ) -> Result<Option<String>> {
+        self.get_latest_tag_on_branch_with_remote(branch_name, None, tag_pattern)
+    }
+
+    /// Finds the latest tag on a branch that has a version, whatever its
+    /// prefix, for detecting the tag pattern a branch already uses.
+    ///
+    /// Only the first-parent history is searched, so the tags of a branch
+    /// merged in (such as `develop` into `main`) aren't taken for the
+    /// branch's own.
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the local branch
+    ///
+    /// # Returns
+    /// * `Ok(Some(tag))` - The nearest tag with a version, walking back from the head
+    /// * `Ok(None)` - If no such tag is reachable from the branch
+    /// * `Err` - If branch lookup fails
+    pub fn get_latest_version_tag_on_branch(&self, branch_name: &str) -> Result<Option<String>> {
+        let mut tags_by_commit: HashMap<Oid, String> = HashMap::new();
+        for tag_name in self.repo.tag_names(None)?.iter().flatten() {
+            if TagPattern::detect(tag_name).is_none() {
+                continue;
+            }
+            if let Ok(commit) = self
+                .repo
+                .find_reference(&format!("refs/tags/{}", tag_name))
+                .and_then(|r| r.peel_to_commit())
+            {
+                tags_by_commit.insert(commit.id(), tag_name.to_string());
+            }
+        }
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(self.get_branch_head_oid(branch_name)?)?;
+        revwalk.simplify_first_parent()?;
+        for oid in revwalk {
+            if let Some(tag_name) = tags_by_commit.get(&oid?) {
+                return Ok(Some(tag_name.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Lists the release tags on a branch, oldest first.
+    ///
+    /// Each entry records when the tag was made and the commits and authors it covers,
+    /// i.e. commits reachable from the tag but not from the previous release tag.
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the local branch
+    /// * `tag_pattern` - Optional tag pattern (e.g. "v{version}") to select release tags
+    ///
+    /// # Returns
+    /// * `Ok(releases)` - Release tags in chronological order
+    /// * `Err` - If branch lookup or history traversal fails
+    pub fn get_tag_releases_on_branch(
+        &self,
+        branch_name: &str,
+        tag_pattern: Option<&str>,
+    ) -> Result<Vec<TagRelease>> {
+        let branch_oid = self.get_branch_head_oid(branch_name)?;
+
+        let mut tags_by_commit: HashMap<Oid, Vec<(String, i64)>> = HashMap::new();
+        for tag_name in self.repo.tag_names(None)?.iter().flatten() {
+            if !tag_matches_pattern(tag_name, tag_pattern) {
+                continue;
+            }
+            let Ok(tag_ref) = self.repo.find_reference(&format!("refs/tags/{}", tag_name)) else {
+                continue;
+            };
+            let Ok(commit) = tag_ref.peel_to_commit() else {
+                continue;
+            };
+            let time = tag_ref
+                .peel_to_tag()
+                .ok()
+                .and_then(|tag| tag.tagger().map(|tagger| tagger.when().seconds()))
+                .unwrap_or_else(|| commit.time().seconds());
+            tags_by_commit
+                .entry(commit.id())
+                .or_default()
+                .push((tag_name.to_string(), time));
+        }
+
+        // Walk oldest first so each tag's range starts at the previous tag
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        revwalk.push(branch_oid)?;
+
+        let mut releases = Vec::new();
+        let mut previous: Option<Oid> = None;
+        for oid in revwalk {
+            let oid = oid?;
+            let Some(tags) = tags_by_commit.get(&oid) else {
+                continue;
+            };
+
+            let mut range = self.repo.revwalk()?;
+            range.push(oid)?;
+            if let Some(previous) = previous {
+                range.hide(previous)?;
+            }
+            let mut commit_count = 0;
+            let mut authors: Vec<String> = Vec::new();
+            for commit_oid in range {
+                let commit = self.repo.find_commit(commit_oid?)?;
+                commit_count += 1;
+                let author = commit.author().name().unwrap_or("unknown").to_string();
+                if !authors.contains(&author) {
+                    authors.push(author);
+                }
+            }
+
+            // Several tags on one commit: only the first is credited with the commits
+            for (tag, time) in tags {
+                releases.push(TagRelease {
+                    tag: tag.clone(),
+                    time: *time,
+                    commit_count: std::mem::take(&mut commit_count),
+                    authors: std::mem::take(&mut authors),
+                });
+            }
+            previous = Some(oid);
+        }
+
+        Ok(releases)
+    }
+
+    /// Gets the messages of the commits a release tag introduced.
+    ///
+    /// These are the commits reachable from `tag_name` but not from
+    /// `previous_tag`, oldest first, leaving out commits that only change
+    /// ignored paths like the commit walk of a release does.
+    ///
+    /// # Arguments
+    /// * `tag_name` - The release tag
+    /// * `previous_tag` - The release before it; if None, its whole history counts
+    ///
+    /// # Returns
+    /// * `Ok(messages)` - Commit messages in chronological order
+    /// * `Err` - If either tag is not found
+    pub fn get_release_commit_messages(
+        &self,
+        tag_name: &str,
+        previous_tag: Option<&str>,
+    ) -> Result<Vec<String>> {
+        let tag_commit = |name: &str| {
+            self.repo
+                .find_reference(&format!("refs/tags/{}", name))
+                .and_then(|r| r.peel_to_commit())
+                .map(|commit| commit.id())
         };
 
-        // Helper function to find latest tag starting from a given OID
-        let find_tag_from_oid = |oid: git2::Oid| -> Result<Option<String>> {
-            let mut revwalk = self.repo.revwalk()?;
-            revwalk.push(oid)?;
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(tag_commit(tag_name)?)?;
+        if let Some(previous_tag) = previous_tag {
+            revwalk.hide(tag_commit(previous_tag)?)?;
+        }
 
-            // Get all tags and their OIDs (handles both lightweight and annotated tags)
-            // Only include tags that match the expected pattern
-            let mut tag_oids = std::collections::HashMap::new();
-            let tags = self.repo.tag_names(None)?;
+        let mut messages = Vec::new();
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            if self.skips_commit(&commit) {
+                continue;
+            }
+            messages.push(commit.message().unwrap_or_default().to_string());
+        }
+        messages.reverse();
+        Ok(messages)
+    }
 
-            for tag_name in tags.iter().flatten() {
-                // Skip tags that don't match the pattern
-                if !matches_tag_pattern(tag_name) {
+    /// Gets all commits on a branch since a specific tag.
+    ///
+    /// Collects the commits visited by [`GitRepo::walk_commits_since_tag`].
+    /// Returns commits in chronological order (oldest first).
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the branch
+    /// * `tag_name` - Optional tag to stop at; if None, returns all commits on branch
+    ///
+    /// # Returns
+    /// * `Ok(commits)` - Vector of commits since tag (chronological order)
+    /// * `Err` - If branch lookup fails
+    pub fn get_commits_since_tag(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+    ) -> Result<Vec<Commit<'_>>> {
+        let mut commits = Vec::new();
+        self.walk_commits_since_tag(branch_name, tag_name, |commit| {
+            commits.push(commit);
+            ControlFlow::Continue(())
+        })?;
+
+        // Reverse to get chronological order (oldest first)
+        commits.reverse();
+        Ok(commits)
+    }
+
+    /// Visits the commits on a branch since a specific tag, newest first,
+    /// without collecting them.
+    ///
+    /// Walks the commit history from the branch head backwards, leaving out the tag
+    /// commit and everything reachable from it. Commits merged in after the tag are
+    /// excluded through any parent, so a release branch merged back (or part of an
+    /// octopus merge) doesn't bring its already released history along.
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the branch
+    /// * `tag_name` - Optional tag to stop at; if None, visits all commits on branch
+    /// * `visit` - Called for each commit; returning `ControlFlow::Break` ends the walk
+    ///
+    /// # Returns
+    /// * `Ok(())` - The walk finished or was stopped by `visit`
+    /// * `Err` - If branch lookup fails
+    pub fn walk_commits_since_tag<'repo>(
+        &'repo self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        visit: impl FnMut(Commit<'repo>) -> ControlFlow<()>,
+    ) -> Result<()> {
+        self.walk_commits_since(branch_name, tag_name, None, visit)
+    }
+
+    /// Like [`GitRepo::walk_commits_since_tag`], also leaving out the commits
+    /// reachable from `base`, a commit hash such as a [merge base](GitRepo::merge_base).
+    ///
+    /// Commits that only change paths listed in `.gitpublishignore` are
+    /// skipped.
+    pub fn walk_commits_since<'repo>(
+        &'repo self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        base: Option<&str>,
+        visit: impl FnMut(Commit<'repo>) -> ControlFlow<()>,
+    ) -> Result<()> {
+        let branch_oid = self.get_branch_head_oid(branch_name)?;
+        self.walk_commits_from(branch_oid, tag_name, base, visit)
+    }
+
+    /// Like [`GitRepo::walk_commits_since_tag`], starting from a commit
+    /// (e.g. a SHA, `HEAD~2` or a tag) instead of a branch head.
+    pub fn walk_commits_since_tag_at<'repo>(
+        &'repo self,
+        revision: &str,
+        tag_name: Option<&str>,
+        visit: impl FnMut(Commit<'repo>) -> ControlFlow<()>,
+    ) -> Result<()> {
+        let oid = self.find_commit_at(revision)?.id();
+        self.walk_commits_from(oid, tag_name, None, visit)
+    }
+
+    fn walk_commits_from<'repo>(
+        &'repo self,
+        start: Oid,
+        tag_name: Option<&str>,
+        base: Option<&str>,
+        mut visit: impl FnMut(Commit<'repo>) -> ControlFlow<()>,
+    ) -> Result<()> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(start)?;
+
+        // A missing tag hides nothing, so every commit on the branch is visited
+        let tag_oid = tag_name.and_then(|tag_name| {
+            self.repo
+                .find_reference(&format!("refs/tags/{}", tag_name))
+                .ok()
+                .and_then(|r| r.peel_to_commit().ok())
+                .map(|commit| commit.id())
+        });
+        if let Some(tag_oid) = tag_oid {
+            revwalk.hide(tag_oid)?;
+        }
+        if let Some(base) = base {
+            revwalk.hide(Oid::from_str(base)?)?;
+        }
+
+        for oid in revwalk {
+            let oid = oid?;
+            if let Ok(commit) = self.repo.find_commit(oid) {
+                if self.skips_commit(&commit) {
                     continue;
                 }
-                if let Ok(tag_ref) = self.repo.find_reference(&format!("refs/tags/{}", tag_name)) {
-                    // Peel to any object (commit, tag, etc.)
-                    if let Ok(tag_obj) = tag_ref.peel(git2::ObjectType::Any) {
-                        let tag_oid = tag_obj.id();
-                        tag_oids.insert(tag_oid, tag_name.to_string());
-                    }
+                if visit(commit).is_break() {
+                    break;
                 }
             }
+        }
+        Ok(())
+    }
 
-            // Find the latest tag on this branch
-            for oid in revwalk {
-                match oid {
-                    Ok(oid) => {
-                        if let Some(tag_name) = tag_oids.get(&oid) {
-                            return Ok(Some(tag_name.clone()));
-                        }
-                    }
-                    Err(_) => continue,
-                }
+    /// Finds the commits on a branch since a tag whose change was already
+    /// released by a tag of another branch, such as fixes cherry-picked
+    /// between `main` and a maintenance line.
+    ///
+    /// Changes are compared by patch-id, which ignores line numbers and
+    /// whitespace, so a cherry-pick matches its original even when it applied
+    /// with an offset. Merge commits are never matched.
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the branch being released
+    /// * `tag_name` - The branch's latest tag; every commit is checked without one
+    /// * `released_tag` - Latest tag of the other branch
+    ///
+    /// # Returns
+    /// * `Ok(messages)` - Messages of the matching commits, newest first
+    /// * `Err` - If the branch or `released_tag` lookup fails
+    pub fn released_cherry_picks(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        released_tag: &str,
+    ) -> Result<Vec<String>> {
+        let branch_oid = self.get_branch_head_oid(branch_name)?;
+        let released_oid = self
+            .repo
+            .find_reference(&format!("refs/tags/{}", released_tag))
+            .map_err(|_| anyhow::anyhow!("Tag '{}' not found", released_tag))?
+            .peel_to_commit()?
+            .id();
+
+        // Changes released on the other side only
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(released_oid)?;
+        revwalk.hide(branch_oid)?;
+        let mut released = HashSet::new();
+        for oid in revwalk {
+            if let Some(patch_id) = self.patch_id(&self.repo.find_commit(oid?)?) {
+                released.insert(patch_id);
+            }
+        }
+        if released.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut messages = Vec::new();
+        self.walk_commits_since(branch_name, tag_name, None, |commit| {
+            if self
+                .patch_id(&commit)
+                .is_some_and(|patch_id| released.contains(&patch_id))
+            {
+                messages.extend(commit.message().map(str::to_string));
             }
+            ControlFlow::Continue(())
+        })?;
+        Ok(messages)
+    }
 
-            Ok(None)
+    /// Whether a commit doesn't count toward a release: every file it changes
+    /// is listed in `.gitpublishignore`, or none is in the path scope; never
+    /// for merge commits and commits that change nothing
+    fn skips_commit(&self, commit: &Commit) -> bool {
+        if self.ignored_paths.is_none() && self.path_scope.is_none() {
+            return false;
+        }
+        if commit.parent_count() > 1 {
+            return false;
+        }
+        let Ok(files) = self.files_changed_by(commit) else {
+            return false;
         };
+        let paths: Vec<&Path> = files.iter().map(PathBuf::as_path).collect();
+        if paths.is_empty() {
+            return false;
+        }
+        let ignored = self
+            .ignored_paths
+            .as_ref()
+            .is_some_and(|ignored| ignored.ignores_all(&paths));
+        let out_of_scope = self
+            .path_scope
+            .as_ref()
+            .is_some_and(|scope| !paths.iter().any(|path| scope.matches(path)));
+        ignored || out_of_scope
+    }
 
-        // First, try to get tag from the remote-tracking branch if remote is provided
-        if let Some(remote) = remote_name {
-            let remote_tracking_branch = format!("{}/{}", remote, branch_name);
-            if let Ok(remote_oid) = self
-                .get_branch_head_oid_from_ref(&format!("refs/remotes/{}", remote_tracking_branch))
+    /// Files a commit adds, changes, renames or deletes compared with its
+    /// first parent (the empty tree for a root commit); old and new names of
+    /// renamed files both count
+    fn files_changed_by(&self, commit: &Commit) -> Result<Vec<PathBuf>> {
+        // A root commit is compared with the empty tree
+        let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+        let diff =
+            self.repo
+                .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+        let mut files: Vec<PathBuf> = Vec::new();
+        for delta in diff.deltas() {
+            for path in [delta.old_file().path(), delta.new_file().path()]
+                .into_iter()
+                .flatten()
             {
-                if let Ok(Some(tag)) = find_tag_from_oid(remote_oid) {
-                    return Ok(Some(tag));
+                if !files.iter().any(|file| file == path) {
+                    files.push(path.to_path_buf());
                 }
             }
         }
+        Ok(files)
+    }
+
+    /// Returns the files changed by the commit a revision (e.g. a SHA,
+    /// `HEAD~2` or a tag) points at.
+    ///
+    /// # Returns
+    /// * `Ok(files)` - Paths relative to the repository root, in diff order
+    /// * `Err` - If the revision isn't a commit
+    pub fn changed_files(&self, revision: &str) -> Result<Vec<PathBuf>> {
+        self.files_changed_by(&self.find_commit_at(revision)?)
+    }
+
+    /// Returns the full hash of the commit a revision points at.
+    pub fn resolve_commit(&self, revision: &str) -> Result<String> {
+        Ok(self.find_commit_at(revision)?.id().to_string())
+    }
+
+    /// Finds the newest commit on a branch made before a point in time.
+    ///
+    /// Only the first-parent history is followed, so commits merged in from
+    /// other branches don't count.
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the local branch
+    /// * `time` - Seconds since the Unix epoch
+    ///
+    /// # Returns
+    /// * `Ok(Some(hash))` - The newest commit with an earlier commit time
+    /// * `Ok(None)` - If every commit on the branch was made at or after `time`
+    pub fn last_commit_before(&self, branch_name: &str, time: i64) -> Result<Option<String>> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(self.get_branch_head_oid(branch_name)?)?;
+        revwalk.simplify_first_parent()?;
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            if commit.time().seconds() < time {
+                return Ok(Some(commit.id().to_string()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Whether the commit a revision points at is in the history of the branch
+    pub fn is_commit_on_branch(&self, branch_name: &str, revision: &str) -> Result<bool> {
+        let branch_oid = self.get_branch_head_oid(branch_name)?;
+        let oid = self.find_commit_at(revision)?.id();
+        Ok(oid == branch_oid || self.repo.graph_descendant_of(branch_oid, oid)?)
+    }
+
+    fn find_commit_at(&self, revision: &str) -> Result<Commit<'_>> {
+        self.repo
+            .revparse_single(revision)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| anyhow::anyhow!("Commit '{}' not found", revision))
+    }
+
+    /// Whether changes to `path` never count toward a release
+    pub fn is_path_ignored(&self, path: &Path) -> bool {
+        self.ignored_paths
+            .as_ref()
+            .is_some_and(|ignored| ignored.is_ignored(path))
+    }
+
+    /// Patch-id of the change a commit makes to its parent; `None` for merge,
+    /// root and empty commits
+    fn patch_id(&self, commit: &Commit) -> Option<Oid> {
+        if commit.parent_count() != 1 {
+            return None;
+        }
+        let parent_tree = commit.parent(0).ok()?.tree().ok()?;
+        let diff = self
+            .repo
+            .diff_tree_to_tree(Some(&parent_tree), Some(&commit.tree().ok()?), None)
+            .ok()?;
+        // Every empty change has the same patch-id
+        if diff.deltas().len() == 0 {
+            return None;
+        }
+        diff.patchid(None).ok()
+    }
+
+    /// Finds the commits on a branch since a tag whose author has no name or
+    /// email (or is literally `unknown`), as some bots and squash tools leave.
+    ///
+    /// # Arguments
+    /// * `branch_name` - Name of the branch
+    /// * `tag_name` - Optional tag to stop at; if None, checks all commits on branch
+    /// * `base` - Optional commit whose history is left out, as in [`GitRepo::walk_commits_since`]
+    /// * `limit` - Number of most recent commits to check; 0 checks every commit
+    ///
+    /// # Returns
+    /// * `Ok(hashes)` - Short hashes of the anonymous commits, newest first
+    /// * `Err` - If branch lookup fails
+    pub fn anonymous_commits(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        base: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<String>> {
+        let mut checked = 0;
+        let mut hashes = Vec::new();
+        self.walk_commits_since(branch_name, tag_name, base, |commit| {
+            checked += 1;
+            if is_anonymous(&commit.author()) {
+                let hash = commit.id().to_string();
+                hashes.push(hash[..7].to_string());
+            }
+            if limit != 0 && checked >= limit {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })?;
+        Ok(hashes)
+    }
+
+    /// Whether `user.name` and `user.email` are configured, so tags and
+    /// commits can be attributed
+    pub fn has_identity(&self) -> bool {
+        self.repo.signature().is_ok()
+    }
+
+    /// Checks whether the commit a tag points to is in the history of a branch.
+    ///
+    /// A tag found on the remote-tracking branch is unreachable from the local
+    /// branch once either side's history has been rewritten (rebase, amend,
+    /// force-push); the rewritten copies of released commits then count as new.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The tag is the branch head or one of its ancestors
+    /// * `Ok(false)` - The tag is not in the branch history
+    /// * `Err` - If the branch or tag lookup fails
+    pub fn is_tag_reachable(&self, branch_name: &str, tag_name: &str) -> Result<bool> {
+        let branch_oid = self.get_branch_head_oid(branch_name)?;
+        let tag_oid = self
+            .repo
+            .find_reference(&format!("refs/tags/{}", tag_name))
+            .map_err(|_| anyhow::anyhow!("Tag '{}' not found", tag_name))?
+            .peel_to_commit()?
+            .id();
+        Ok(tag_oid == branch_oid || self.repo.graph_descendant_of(branch_oid, tag_oid)?)
+    }
+
+    /// Get the current HEAD git hash (full 40-character SHA-1)
+    #[allow(dead_code)]
+    pub fn get_current_head_hash(&self) -> Result<String> {
+        let head = self.repo.head()?;
+        let oid = head
+            .target()
+            .ok_or_else(|| anyhow::anyhow!("HEAD is detached or invalid"))?;
+        Ok(oid.to_string())
+    }
+
+    /// Path of the repository's git directory (e.g. `.git`)
+    pub fn git_dir(&self) -> &Path {
+        self.repo.path()
+    }
+
+    /// Root of the working tree, or `None` for a bare repository
+    pub fn workdir(&self) -> Option<&Path> {
+        self.repo.workdir()
+    }
+
+    /// Creates a lightweight tag on a specific branch's head commit.
+    ///
+    /// If a branch name is provided, the tag is created on that branch's head commit.
+    /// If no branch name is provided, falls back to tagging the current HEAD.
+    ///
+    /// # Arguments
+    /// * `tag_name` - Name of the tag to create
+    /// * `branch_name` - Optional name of the branch to tag; if not provided, uses current HEAD
+    ///
+    /// # Returns
+    /// * `Ok(())` - Tag created successfully
+    /// * `Err` - If tag creation fails
+    pub fn create_tag(&self, tag_name: &str, branch_name: Option<&str>) -> Result<()> {
+        self.write_tag(tag_name, branch_name, false, None)
+    }
 
-        // Fall back to local branch
-        let local_oid = self.get_branch_head_oid(branch_name)?;
-        find_tag_from_oid(local_oid)
+    /// Creates a lightweight tag like [`GitRepo::create_tag`], moving it if it
+    /// already exists.
+    pub fn replace_tag(&self, tag_name: &str, branch_name: Option<&str>) -> Result<()> {
+        self.write_tag(tag_name, branch_name, true, None)
     }
 
-    /// Finds the latest tag on a specific branch (local branch only).
+    /// Creates an annotated tag like [`GitRepo::create_tag`], signed when the
+    /// annotation says so.
     ///
-    /// Walks the commit history from the branch head backwards to find the most recent tag.
-    /// Handles both lightweight and annotated tags.
+    /// Signing works like `git tag -s`: the tag is signed by `gpg.program`
+    /// (default `gpg`) with `user.signingkey`, or else the committer identity.
     ///
     /// # Arguments
-    /// * `branch_name` - Name of the branch to search
+    /// * `tag_name` - Name of the tag to create
+    /// * `branch_name` - Optional name of the branch to tag; if not provided, uses current HEAD
+    /// * `annotation` - Tag message and whether to sign
+    /// * `force` - Move the tag if it already exists
     ///
     /// # Returns
-    /// * `Ok(Some(tag))` - The latest tag name found
-    /// * `Ok(None)` - If no tags exist on this branch
-    /// * `Err` - If branch lookup fails
-    pub fn get_latest_tag_on_branch(
+    /// * `Ok(())` - Tag created successfully
+    /// * `Err` - If tag creation or signing fails
+    pub fn create_annotated_tag(
         &self,
-        branch_name: &str,
-        tag_pattern: Option<&str>,
-    ) -> Result<Option<String>> {
-        self.get_latest_tag_on_branch_with_remote(branch_name, None, tag_pattern)
+        tag_name: &str,
+        branch_name: Option<&str>,
+        annotation: &TagAnnotation,
+        force: bool,
+    ) -> Result<()> {
+        self.write_tag(tag_name, branch_name, force, Some(annotation))
     }
 
-    /// Gets all commits on a branch since a specific tag.
+    /// Creates an annotated tag pointing at an existing tag rather than a
+    /// commit, to layer metadata over a release (e.g. `v1.2.3-signed` over
+    /// `v1.2.3`), like `git tag -a <tag> <target_tag>^{tag}`.
     ///
-    /// Walks the commit history from the branch head backwards, collecting all commits
-    /// until the tag commit is reached. Returns commits in chronological order (oldest first).
+    /// The new tag points at `target_tag`'s tag object, or at its commit
+    /// when `target_tag` is lightweight and has none.
     ///
     /// # Arguments
-    /// * `branch_name` - Name of the branch
-    /// * `tag_name` - Optional tag to stop at; if None, returns all commits on branch
+    /// * `tag_name` - Name of the tag to create
+    /// * `target_tag` - Existing tag to point at
+    /// * `annotation` - Tag message and whether to sign
+    /// * `force` - Move the tag if it already exists
     ///
     /// # Returns
-    /// * `Ok(commits)` - Vector of commits since tag (chronological order)
-    /// * `Err` - If branch lookup fails
-    pub fn get_commits_since_tag(
+    /// * `Ok(())` - Tag created successfully
+    /// * `Err` - If `target_tag` doesn't exist, or tag creation or signing fails
+    pub fn create_annotated_tag_on_tag(
         &self,
-        branch_name: &str,
-        tag_name: Option<&str>,
-    ) -> Result<Vec<Commit<'_>>> {
-        let branch_oid = self.get_branch_head_oid(branch_name)?;
-
-        // Walk commits from branch head backwards until the tag commit
-        let mut revwalk = self.repo.revwalk()?;
-        revwalk.push(branch_oid)?;
+        tag_name: &str,
+        target_tag: &str,
+        annotation: &TagAnnotation,
+        force: bool,
+    ) -> Result<()> {
+        // The reference's own target, not peeled: the tag object of an annotated tag
+        let target_object = self
+            .repo
+            .find_object(self.tag_target_oid(target_tag)?, None)?;
+        self.write_tag_object(tag_name, &target_object, force, Some(annotation))
+    }
 
-        if let Some(tag_name) = tag_name {
-            // Find the tag OID to stop at
-            let tag_oid = self
-                .repo
-                .find_reference(&format!("refs/tags/{}", tag_name))
-                .ok()
-                .and_then(|r| r.peel(git2::ObjectType::Any).ok())
-                .map(|obj| obj.id());
+    /// Returns what a tag points at.
+    ///
+    /// # Returns
+    /// * `Ok(target)` - A commit for lightweight tags, a tag object for annotated ones
+    /// * `Err` - If the tag does not exist
+    pub fn tag_target(&self, tag_name: &str) -> Result<TagTarget> {
+        let oid = self.tag_target_oid(tag_name)?;
+        Ok(match self.repo.find_object(oid, None)?.kind() {
+            Some(git2::ObjectType::Commit) => TagTarget::Commit,
+            Some(git2::ObjectType::Tag) => TagTarget::Tag,
+            _ => TagTarget::Other,
+        })
+    }
 
-            let mut commits = Vec::new();
+    /// The object a tag reference points at directly
+    fn tag_target_oid(&self, tag_name: &str) -> Result<Oid> {
+        self.repo
+            .find_reference(&format!("refs/tags/{}", tag_name))
+            .and_then(|reference| reference.resolve())
+            .ok()
+            .and_then(|reference| reference.target())
+            .ok_or_else(|| anyhow::anyhow!("Tag '{}' not found", tag_name))
+    }
 
-            for oid in revwalk {
-                let oid = oid?;
+    /// Returns the message of an annotated tag.
+    ///
+    /// # Returns
+    /// * `Ok(Some(message))` - The tag is annotated
+    /// * `Ok(None)` - The tag is lightweight
+    /// * `Err` - If the tag does not exist
+    pub fn tag_message(&self, tag_name: &str) -> Result<Option<String>> {
+        let reference = self
+            .repo
+            .find_reference(&format!("refs/tags/{}", tag_name))
+            .map_err(|_| anyhow::anyhow!("Tag '{}' not found", tag_name))?;
+        // A lightweight tag points at a commit, which doesn't peel to a tag object
+        Ok(reference
+            .peel_to_tag()
+            .ok()
+            .and_then(|tag| tag.message().map(str::to_string)))
+    }
 
-                // Stop if we reached the tag commit
-                if let Some(target_oid) = tag_oid {
-                    if oid == target_oid {
-                        break;
-                    }
-                }
+    /// Returns the full message of the commit a revision (e.g. a SHA, `HEAD~2`
+    /// or a tag) points at.
+    ///
+    /// `None` when nothing goes by that name or it isn't a commit.
+    pub fn commit_message(&self, revision: &str) -> Option<String> {
+        let commit = self
+            .repo
+            .revparse_single(revision)
+            .ok()?
+            .peel_to_commit()
+            .ok()?;
+        Some(commit.message().unwrap_or_default().to_string())
+    }
 
-                if let Ok(commit) = self.repo.find_commit(oid) {
-                    commits.push(commit);
-                }
-            }
+    /// Lists the local tags matching a tag pattern, in no particular order.
+    ///
+    /// # Arguments
+    /// * `tag_pattern` - Optional tag pattern (e.g. "v{version}"); without one,
+    ///   every semver-like tag is listed
+    pub fn list_tags(&self, tag_pattern: Option<&str>) -> Result<Vec<String>> {
+        Ok(self
+            .repo
+            .tag_names(None)?
+            .iter()
+            .flatten()
+            .filter(|tag| tag_matches_pattern(tag, tag_pattern))
+            .map(str::to_string)
+            .collect())
+    }
 
-            // Reverse to get chronological order (oldest first)
-            commits.reverse();
-            Ok(commits)
-        } else {
-            // If no tag, return all commits reachable from branch
-            let mut commits = Vec::new();
-            for oid in revwalk {
-                let oid = oid?;
-                if let Ok(commit) = self.repo.find_commit(oid) {
-                    commits.push(commit);
-                }
+    /// Lists the local tags matching a tag pattern with their target commit,
+    /// time and kind, in no particular order.
+    ///
+    /// Tags that don't point at a commit (e.g. at a tree) are left out.
+    ///
+    /// # Arguments
+    /// * `tag_pattern` - Optional tag pattern (e.g. "v{version}"); without one,
+    ///   every semver-like tag is listed
+    pub fn list_tag_details(&self, tag_pattern: Option<&str>) -> Result<Vec<TagDetails>> {
+        let mut details = Vec::new();
+        for tag_name in self.repo.tag_names(None)?.iter().flatten() {
+            if !tag_matches_pattern(tag_name, tag_pattern) {
+                continue;
             }
-            // Reverse to get chronological order
-            commits.reverse();
-            Ok(commits)
+            let Ok(tag_ref) = self.repo.find_reference(&format!("refs/tags/{}", tag_name)) else {
+                continue;
+            };
+            let Ok(commit) = tag_ref.peel_to_commit() else {
+                continue;
+            };
+            let tag = tag_ref.peel_to_tag().ok();
+            let time = tag
+                .as_ref()
+                .and_then(|tag| tag.tagger().map(|tagger| tagger.when().seconds()))
+                .unwrap_or_else(|| commit.time().seconds());
+            details.push(TagDetails {
+                name: tag_name.to_string(),
+                target: commit.id().to_string(),
+                time,
+                annotated: tag.is_some(),
+            });
         }
+        Ok(details)
     }
 
-    /// Get the current HEAD git hash (full 40-character SHA-1)
-    #[allow(dead_code)]
-    pub fn get_current_head_hash(&self) -> Result<String> {
-        let head = self.repo.head()?;
-        let oid = head
-            .target()
-            .ok_or_else(|| anyhow::anyhow!("HEAD is detached or invalid"))?;
-        Ok(oid.to_string())
+    /// Checks whether a local tag with the given name exists.
+    pub fn tag_exists(&self, tag_name: &str) -> bool {
+        self.repo
+            .find_reference(&format!("refs/tags/{}", tag_name))
+            .is_ok()
     }
 
-    /// Creates a lightweight tag on a specific branch's head commit.
-    ///
-    /// If a branch name is provided, the tag is created on that branch's head commit.
-    /// If no branch name is provided, falls back to tagging the current HEAD.
-    ///
-    /// # Arguments
-    /// * `tag_name` - Name of the tag to create
-    /// * `branch_name` - Optional name of the branch to tag; if not provided, uses current HEAD
-    ///
-    /// # Returns
-    /// * `Ok(())` - Tag created successfully
-    /// * `Err` - If tag creation fails
-    pub fn create_tag(&self, tag_name: &str, branch_name: Option<&str>) -> Result<()> {
+    /// Deletes a local tag.
+    pub fn delete_tag(&self, tag_name: &str) -> Result<()> {
+        self.repo.tag_delete(tag_name)?;
+        Ok(())
+    }
+
+    fn write_tag(
+        &self,
+        tag_name: &str,
+        branch_name: Option<&str>,
+        force: bool,
+        annotation: Option<&TagAnnotation>,
+    ) -> Result<()> {
         let target_oid = if let Some(branch) = branch_name {
             // Tag the specific branch's head
             self.get_branch_head_oid(branch)?
@@ -499,10 +2193,182 @@ impl GitRepo {
         };
 
         let target_object = self.repo.find_object(target_oid, None)?;
-        self.repo.tag_lightweight(tag_name, &target_object, false)?;
+        self.write_tag_object(tag_name, &target_object, force, annotation)
+    }
+
+    /// Tags `target_object`, a commit or another tag
+    fn write_tag_object(
+        &self,
+        tag_name: &str,
+        target_object: &git2::Object,
+        force: bool,
+        annotation: Option<&TagAnnotation>,
+    ) -> Result<()> {
+        match annotation {
+            None => {
+                self.repo.tag_lightweight(tag_name, target_object, force)?;
+            }
+            Some(annotation) if annotation.signed => {
+                self.write_signed_tag(tag_name, target_object, &annotation.message, force)?;
+            }
+            Some(annotation) => {
+                let tagger = self.repo.signature()?;
+                self.repo
+                    .tag(tag_name, target_object, &tagger, &annotation.message, force)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a signed tag object the way git does: the armored signature of
+    /// the tag's content is appended to its message.
+    fn write_signed_tag(
+        &self,
+        tag_name: &str,
+        target: &git2::Object,
+        message: &str,
+        force: bool,
+    ) -> Result<()> {
+        let config = self.repo.config()?;
+        if let Ok(format) = config.get_string("gpg.format") {
+            if format != "openpgp" {
+                return Err(anyhow::anyhow!(
+                    "gpg.format '{}' is not supported for signed tags, only openpgp",
+                    format
+                ));
+            }
+        }
+        let program = config
+            .get_string("gpg.openpgp.program")
+            .or_else(|_| config.get_string("gpg.program"))
+            .unwrap_or_else(|_| "gpg".to_string());
+
+        let tagger = self.repo.signature()?;
+        let identity = format!(
+            "{} <{}>",
+            tagger.name().unwrap_or_default(),
+            tagger.email().unwrap_or_default()
+        );
+        let key = config
+            .get_string("user.signingkey")
+            .unwrap_or_else(|_| identity.clone());
+        let when = tagger.when();
+        let offset = when.offset_minutes().abs();
+        let mut content = format!(
+            "object {}\ntype {}\ntag {}\ntagger {} {} {}{:02}{:02}\n\n{}",
+            target.id(),
+            target.kind().map_or("commit", |kind| kind.str()),
+            tag_name,
+            identity,
+            when.seconds(),
+            when.sign(),
+            offset / 60,
+            offset % 60,
+            message
+        );
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&gpg_sign(&program, &key, &content)?);
+
+        let oid = self
+            .repo
+            .odb()?
+            .write(git2::ObjectType::Tag, content.as_bytes())?;
+        self.repo.reference(
+            &format!("refs/tags/{}", tag_name),
+            oid,
+            force,
+            &format!("tag: {}", tag_name),
+        )?;
         Ok(())
     }
 
+    /// Commits the current contents of a single file on top of a branch.
+    ///
+    /// See [`GitRepo::commit_files`].
+    pub fn commit_file(&self, branch_name: &str, path: &Path, message: &str) -> Result<String> {
+        self.commit_files(branch_name, &[path], message)
+    }
+
+    /// Commits the current contents of some files on top of a branch.
+    ///
+    /// The branch must be checked out, since the files are taken from the
+    /// working tree. Other staged changes make this fail rather than sneak
+    /// into the commit. Files git ignores are left out unless already tracked,
+    /// like an ignored `Cargo.lock`.
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Hash of the new commit
+    /// * `Err` - If the branch isn't checked out or the index has other changes
+    pub fn commit_files(
+        &self,
+        branch_name: &str,
+        paths: &[&Path],
+        message: &str,
+    ) -> Result<String> {
+        let files = paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let head = self.repo.head()?;
+        if !head.is_branch() || head.shorthand() != Some(branch_name) {
+            return Err(anyhow::anyhow!(
+                "Branch '{}' must be checked out to commit {}",
+                branch_name,
+                files
+            ));
+        }
+        let workdir = self
+            .repo
+            .workdir()
+            .ok_or_else(|| anyhow::anyhow!("Cannot commit in a bare repository"))?;
+        let relative: Vec<&Path> = paths
+            .iter()
+            .map(|path| path.strip_prefix(workdir).unwrap_or(path))
+            .collect();
+
+        let staged = git2::Status::INDEX_NEW
+            | git2::Status::INDEX_MODIFIED
+            | git2::Status::INDEX_DELETED
+            | git2::Status::INDEX_RENAMED
+            | git2::Status::INDEX_TYPECHANGE;
+        let mut options = git2::StatusOptions::new();
+        options.include_untracked(false);
+        for entry in self.repo.statuses(Some(&mut options))?.iter() {
+            let committed = relative.iter().any(|path| entry.path() == path.to_str());
+            if entry.status().intersects(staged) && !committed {
+                return Err(anyhow::anyhow!(
+                    "Staged changes in {} would be committed along with {}",
+                    entry.path().unwrap_or_default(),
+                    files
+                ));
+            }
+        }
+
+        let mut index = self.repo.index()?;
+        for path in &relative {
+            if index.get_path(path, 0).is_none() && self.repo.status_should_ignore(path)? {
+                continue;
+            }
+            index.add_path(path)?;
+        }
+        index.write()?;
+        let tree = self.repo.find_tree(index.write_tree()?)?;
+        let parent = head.peel_to_commit()?;
+        let signature = self.repo.signature()?;
+        let oid = self.repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &[&parent],
+        )?;
+        Ok(oid.to_string())
+    }
+
     /// Pushes a tag to a specified remote.
     ///
     /// Attempts to authenticate using SSH credentials from ~/.ssh/id_rsa.
@@ -515,50 +2381,136 @@ impl GitRepo {
     /// * `Ok(())` - Tag pushed successfully
     /// * `Err` - If push fails (network, auth, or reference error)
     pub fn push_tag(&self, tag_name: &str, remote_name: &str) -> Result<()> {
-        let mut remote = match self.repo.find_remote(remote_name) {
-            Ok(remote) => remote,
-            Err(_) => return Err(anyhow::anyhow!("No remote named '{}' found", remote_name)),
+        self.push_tag_with_override(tag_name, remote_name, None)
+    }
+
+    /// Pushes a tag to a remote, applying an optional push override.
+    ///
+    /// When the override sets a `push_url`, the tag is pushed to that URL instead of
+    /// the remote's configured URL. The override's refspec template controls which
+    /// ref the tag lands in on the remote side (e.g. `refs/releases/v1.2.3`).
+    ///
+    /// # Arguments
+    /// * `tag_name` - Name of the tag to push
+    /// * `remote_name` - Name of the remote to push to (e.g., "origin", "upstream")
+    /// * `remote_override` - Optional push URL and refspec override for this remote
+    ///
+    /// # Returns
+    /// * `Ok(())` - Tag pushed successfully
+    /// * `Err` - If push fails (network, auth, or reference error)
+    pub fn push_tag_with_override(
+        &self,
+        tag_name: &str,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+    ) -> Result<()> {
+        self.push_tag_with_options(tag_name, remote_name, remote_override, false)
+    }
+
+    /// Pushes a tag like [`GitRepo::push_tag_with_override`], overwriting the
+    /// remote tag when `force` is set (for tags that were replaced locally).
+    pub fn push_tag_with_options(
+        &self,
+        tag_name: &str,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+        force: bool,
+    ) -> Result<()> {
+        self.push_tags_with_options(&[tag_name], remote_name, remote_override, force)
+    }
+
+    /// Pushes several tags in a single push, like
+    /// [`GitRepo::push_tag_with_options`] does for one.
+    ///
+    /// Used for compound releases (`--also-tag`), so the tags of all
+    /// environments reach the remote together.
+    pub fn push_tags_with_options(
+        &self,
+        tag_names: &[&str],
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+        force: bool,
+    ) -> Result<()> {
+        let push_url = remote_override.and_then(|o| o.push_url.as_deref());
+        let refspecs: Vec<String> = tag_names
+            .iter()
+            .map(|tag_name| {
+                let refspec = remote_override
+                    .map(|o| o.tag_refspec_for(tag_name))
+                    .unwrap_or_else(|| format!("refs/tags/{}", tag_name));
+                if force && !refspec.starts_with('+') {
+                    format!("+{}", refspec)
+                } else {
+                    refspec
+                }
+            })
+            .collect();
+        let what = match tag_names {
+            [tag_name] => format!("tag '{}'", tag_name),
+            _ => format!("tags '{}'", tag_names.join("', '")),
         };
+        self.push_refspecs(remote_name, push_url, &refspecs, &what)
+    }
+
+    /// Deletes a tag on a remote, like `git push <remote> :refs/tags/<tag>`.
+    ///
+    /// With a remote override, the tag is deleted where its refspec would
+    /// have pushed it.
+    pub fn delete_remote_tag(
+        &self,
+        tag_name: &str,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+    ) -> Result<()> {
+        let push_url = remote_override.and_then(|o| o.push_url.as_deref());
+        let destination = remote_override
+            .map(|o| o.tag_refspec_for(tag_name))
+            .map(|refspec| match refspec.split_once(':') {
+                Some((_, destination)) => destination.to_string(),
+                None => refspec.trim_start_matches('+').to_string(),
+            })
+            .unwrap_or_else(|| format!("refs/tags/{}", tag_name));
+        self.push_refspecs(
+            remote_name,
+            push_url,
+            &[format!(":{}", destination)],
+            &format!("deletion of tag '{}'", tag_name),
+        )
+    }
+
+    /// Pushes a local branch to the branch of the same name on a remote.
+    ///
+    /// Used for the release commit made when the manifest holds the version.
+    /// The push is rejected if the remote branch has moved on.
+    pub fn push_branch(&self, branch_name: &str, remote_name: &str) -> Result<()> {
+        let refspec = format!("refs/heads/{0}:refs/heads/{0}", branch_name);
+        self.push_refspecs(
+            remote_name,
+            None,
+            &[refspec],
+            &format!("branch '{}'", branch_name),
+        )
+    }
+
+    /// Pushes refspecs in one push; `what` names the pushed refs in error messages.
+    fn push_refspecs(
+        &self,
+        remote_name: &str,
+        push_url: Option<&str>,
+        refspecs: &[String],
+        what: &str,
+    ) -> Result<()> {
+        if push_url.is_none() && !self.remote_exists(remote_name)? {
+            return Err(anyhow::anyhow!("No remote named '{}' found", remote_name));
+        }
+        let mut remote = self.resolve_remote(remote_name, push_url, true)?;
 
         let mut push_options = git2::PushOptions::new();
 
         // Set credentials callback if needed
         let mut callbacks = git2::RemoteCallbacks::new();
-        callbacks.credentials(|_url, username_from_url, allowed_types| {
-            // SSH key authentication
-            if allowed_types.contains(git2::CredentialType::SSH_KEY) {
-                // Try different key types in order of preference
-                let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
-                let key_paths = vec![
-                    format!("{}/.ssh/id_ed25519", home),
-                    format!("{}/.ssh/id_rsa", home),
-                    format!("{}/.ssh/id_ecdsa", home),
-                ];
-
-                for key_path in key_paths {
-                    let path = std::path::Path::new(&key_path);
-                    if path.exists() {
-                        if let Ok(cred) = git2::Cred::ssh_key(
-                            username_from_url.unwrap_or("git"),
-                            None,
-                            path,
-                            None,
-                        ) {
-                            return Ok(cred);
-                        }
-                    }
-                }
-
-                // Try SSH agent as fallback
-                if let Ok(cred) = git2::Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"))
-                {
-                    return Ok(cred);
-                }
-            }
-
-            // Fall back to default credentials
-            git2::Cred::default()
-        });
+        let heartbeat = self.add_network_timeout(&mut callbacks, remote_name);
+        self.add_credentials(&mut callbacks, remote_name, heartbeat.clone());
 
         // Add a push update reference callback to catch errors during push
         callbacks.push_update_reference(|refname, status| {
@@ -576,18 +2528,24 @@ impl GitRepo {
             }
         });
 
+        let remote_url = remote
+            .pushurl()
+            .or(remote.url())
+            .unwrap_or_default()
+            .to_string();
+        self.add_host_key_check(&mut callbacks, &remote_url, heartbeat);
+
         push_options.remote_callbacks(callbacks);
 
-        match remote.push(
-            &[&format!("refs/tags/{}", tag_name)],
-            Some(&mut push_options),
-        ) {
+        match remote.push(refspecs, Some(&mut push_options)) {
             Ok(_) => Ok(()),
             Err(e) => {
                 // libgit2 has known issues with ODB lookups in some scenarios.
                 // Fall back to git CLI which handles these cases correctly.
                 let output = std::process::Command::new("git")
-                    .args(["push", remote_name, &format!("refs/tags/{}", tag_name)])
+                    .arg("push")
+                    .arg(push_url.unwrap_or(remote_name))
+                    .args(refspecs)
                     .current_dir(self.repo.workdir().unwrap_or(self.repo.path()))
                     .output();
 
@@ -595,19 +2553,27 @@ impl GitRepo {
                     Ok(result) if result.status.success() => Ok(()),
                     Ok(result) => {
                         let stderr = String::from_utf8_lossy(&result.stderr);
-                        Err(anyhow::anyhow!(
-                            "Failed to push tag '{}': libgit2: {}; git cli: {}",
-                            tag_name,
-                            e,
-                            stderr.trim()
-                        ))
+                        Err(GitPublishError::remote_access(
+                            remote_name,
+                            classify_remote_error(&e),
+                            format!(
+                                "Failed to push {}: libgit2: {}; git cli: {}",
+                                what,
+                                e,
+                                stderr.trim()
+                            ),
+                        )
+                        .into())
                     }
-                    Err(io_err) => Err(anyhow::anyhow!(
-                        "Failed to push tag '{}': libgit2: {}; git cli not available: {}",
-                        tag_name,
-                        e,
-                        io_err
-                    )),
+                    Err(io_err) => Err(GitPublishError::remote_access(
+                        remote_name,
+                        classify_remote_error(&e),
+                        format!(
+                            "Failed to push {}: libgit2: {}; git cli not available: {}",
+                            what, e, io_err
+                        ),
+                    )
+                    .into()),
                 }
             }
         }
@@ -618,6 +2584,213 @@ impl GitRepo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_classify_remote_error_by_code() {
+        let auth = git2::Error::new(
+            git2::ErrorCode::Auth,
+            git2::ErrorClass::Http,
+            "too many redirects or authentication replays",
+        );
+        assert_eq!(
+            classify_remote_error(&auth),
+            RemoteFailureKind::Authentication
+        );
+
+        let cert = git2::Error::new(
+            git2::ErrorCode::Certificate,
+            git2::ErrorClass::Ssh,
+            "invalid or unknown remote ssh hostkey",
+        );
+        assert_eq!(
+            classify_remote_error(&cert),
+            RemoteFailureKind::HostKeyVerification
+        );
+    }
+
+    #[test]
+    fn test_classify_remote_error_by_class() {
+        let net = git2::Error::new(
+            git2::ErrorCode::GenericError,
+            git2::ErrorClass::Net,
+            "failed to resolve address for example.invalid",
+        );
+        assert_eq!(
+            classify_remote_error(&net),
+            RemoteFailureKind::NetworkUnreachable
+        );
+
+        let ssh_auth = git2::Error::new(
+            git2::ErrorCode::GenericError,
+            git2::ErrorClass::Ssh,
+            "Failed to authenticate SSH session",
+        );
+        assert_eq!(
+            classify_remote_error(&ssh_auth),
+            RemoteFailureKind::Authentication
+        );
+
+        let other = git2::Error::new(
+            git2::ErrorCode::GenericError,
+            git2::ErrorClass::Reference,
+            "reference not found",
+        );
+        assert_eq!(classify_remote_error(&other), RemoteFailureKind::Other);
+    }
+
+    #[test]
+    fn test_ssh_host() {
+        assert_eq!(
+            ssh_host("ssh://git@example.com:2222/app.git"),
+            Some("example.com")
+        );
+        assert_eq!(ssh_host("ssh://example.com/app.git"), Some("example.com"));
+        assert_eq!(ssh_host("git@github.com:team/app.git"), Some("github.com"));
+        assert_eq!(ssh_host("https://github.com/team/app.git"), None);
+        assert_eq!(ssh_host("/srv/git/app.git"), None);
+    }
+
+    #[test]
+    fn test_ssh_identity_candidates_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let configured = dir.path().join("work_ed25519");
+        let from_ssh_config = dir.path().join("github_key");
+        std::fs::write(&configured, "key").unwrap();
+        std::fs::write(&from_ssh_config, "key").unwrap();
+
+        let ssh_config = SshConfig::parse(&format!(
+            "Host github.com\n  IdentityFile {}\n  IdentityFile {}\n  IdentityFile {}\n",
+            from_ssh_config.display(),
+            configured.display(),
+            dir.path().join("missing").display()
+        ));
+
+        let candidates =
+            ssh_identity_candidates(Some(&configured), &ssh_config, Some("github.com"));
+        assert_eq!(candidates[0], configured);
+        assert_eq!(candidates[1], from_ssh_config);
+        assert_eq!(
+            candidates.iter().filter(|p| **p == configured).count(),
+            1,
+            "duplicates should be removed"
+        );
+        assert!(!candidates.contains(&dir.path().join("missing")));
+
+        let other = ssh_identity_candidates(None, &ssh_config, Some("gitlab.com"));
+        assert!(!other.contains(&from_ssh_config));
+    }
+
+    #[test]
+    fn test_ssh_port() {
+        assert_eq!(ssh_port("ssh://git@example.com:2222/app.git"), Some(2222));
+        assert_eq!(ssh_port("ssh://git@example.com/app.git"), None);
+        assert_eq!(ssh_port("git@example.com:team/app.git"), None);
+        assert_eq!(ssh_port("https://example.com:8443/app.git"), None);
+    }
+
+    #[test]
+    fn test_web_url() {
+        for url in [
+            "git@github.com:team/app.git",
+            "ssh://git@github.com:22/team/app.git",
+            "https://github.com/team/app.git",
+            "https://token@github.com/team/app",
+            "git://github.com/team/app.git/",
+        ] {
+            assert_eq!(
+                web_url(url).as_deref(),
+                Some("https://github.com/team/app"),
+                "{}",
+                url
+            );
+        }
+        assert_eq!(
+            web_url("http://git.local:8080/group/sub/app.git").as_deref(),
+            Some("http://git.local:8080/group/sub/app")
+        );
+        assert_eq!(web_url("/srv/git/app.git"), None);
+        assert_eq!(web_url("file:///srv/git/app.git"), None);
+        assert_eq!(web_url("../app.git"), None);
+        assert_eq!(web_url("C:/repos/app.git"), None);
+    }
+
+    fn rule(base: &str, prefix: &str, push_only: bool) -> UrlRewrite {
+        UrlRewrite {
+            base: base.to_string(),
+            prefix: prefix.to_string(),
+            push_only,
+        }
+    }
+
+    #[test]
+    fn test_rewrite_url_no_rules() {
+        assert_eq!(
+            rewrite_url("https://github.com/user/repo.git", &[], false),
+            "https://github.com/user/repo.git"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_url_insteadof_applies_to_fetch_and_push() {
+        let rules = vec![rule("git@github.com:", "https://github.com/", false)];
+
+        assert_eq!(
+            rewrite_url("https://github.com/user/repo.git", &rules, false),
+            "git@github.com:user/repo.git"
+        );
+        assert_eq!(
+            rewrite_url("https://github.com/user/repo.git", &rules, true),
+            "git@github.com:user/repo.git"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_url_pushinsteadof_only_applies_to_push() {
+        let rules = vec![rule("git@github.com:", "https://github.com/", true)];
+
+        assert_eq!(
+            rewrite_url("https://github.com/user/repo.git", &rules, false),
+            "https://github.com/user/repo.git"
+        );
+        assert_eq!(
+            rewrite_url("https://github.com/user/repo.git", &rules, true),
+            "git@github.com:user/repo.git"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_url_longest_prefix_wins() {
+        let rules = vec![
+            rule("ssh://git@example.com/", "https://example.com/", false),
+            rule(
+                "ssh://git@mirror.example.com/",
+                "https://example.com/team/",
+                false,
+            ),
+        ];
+
+        assert_eq!(
+            rewrite_url("https://example.com/team/app.git", &rules, false),
+            "ssh://git@mirror.example.com/app.git"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_url_push_prefers_pushinsteadof() {
+        let rules = vec![
+            rule("https://fetch.example.com/", "gh:", false),
+            rule("ssh://push.example.com/", "gh:", true),
+        ];
+
+        assert_eq!(
+            rewrite_url("gh:app.git", &rules, true),
+            "ssh://push.example.com/app.git"
+        );
+        assert_eq!(
+            rewrite_url("gh:app.git", &rules, false),
+            "https://fetch.example.com/app.git"
+        );
+    }
+
     #[test]
     fn test_remote_exists_returns_true_for_valid_remote() {
         // Initialize a git repo and add a remote
@@ -626,8 +2799,156 @@ mod tests {
 
         // The repo should have "origin" if we configure it
         // For this test, we'll verify the function exists and can be called
-        let result = GitRepo { repo }.remote_exists("origin");
+        let result = GitRepo {
+            repo,
+            host_key_verifier: None,
+            ssh_identities: HashMap::new(),
+            network_timeout: None,
+            ignored_paths: None,
+            path_scope: None,
+            credential_prompt: None,
+            github_hosts: Vec::new(),
+        }
+        .remote_exists("origin");
         // This will fail initially because function doesn't exist
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_env_token_prefers_git_publish_token() {
+        let vars = |pairs: &'static [(&str, &str)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+        let github = "https://github.com/owner/repo.git";
+        let remote = ["github.com".to_string()];
+        assert_eq!(
+            env_token(
+                github,
+                &remote,
+                &[],
+                vars(&[("GITHUB_TOKEN", "gh"), ("GIT_PUBLISH_TOKEN", "gp")])
+            ),
+            Some("gp".to_string())
+        );
+        // An empty variable doesn't hide the next one
+        assert_eq!(
+            env_token(
+                github,
+                &remote,
+                &[],
+                vars(&[("GIT_PUBLISH_TOKEN", ""), ("GITHUB_TOKEN", "gh")])
+            ),
+            Some("gh".to_string())
+        );
+        assert_eq!(env_token(github, &remote, &[], vars(&[])), None);
+    }
+
+    #[test]
+    fn test_env_token_keeps_git_publish_token_to_the_remote_host() {
+        let vars = |name: &str| (name == "GIT_PUBLISH_TOKEN").then(|| "gp".to_string());
+        let remote = ["git.example.com".to_string()];
+        assert_eq!(
+            env_token("https://Git.Example.com/owner/repo.git", &remote, &[], vars),
+            Some("gp".to_string())
+        );
+        // A redirect to another host doesn't take the token along
+        assert_eq!(
+            env_token(
+                "https://mirror.example.org/owner/repo.git",
+                &remote,
+                &[],
+                vars
+            ),
+            None
+        );
+        assert_eq!(
+            env_token(
+                "https://git.example.com.evil.example/repo",
+                &remote,
+                &[],
+                vars
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_env_token_is_never_sent_over_http() {
+        let vars = |_: &str| Some("secret".to_string());
+        let remote = ["github.com".to_string()];
+        assert_eq!(
+            env_token("http://github.com/owner/repo.git", &remote, &[], vars),
+            None
+        );
+        assert_eq!(
+            env_token("http://github.com/owner/repo.git", &[], &[], vars),
+            None
+        );
+    }
+
+    #[test]
+    fn test_env_token_keeps_github_token_to_github_hosts() {
+        let vars = |name: &str| (name == "GITHUB_TOKEN").then(|| "gh".to_string());
+        let enterprise = ["GitHub.Example.com".to_string()];
+        assert_eq!(
+            env_token("https://git@GitHub.com:443/owner/repo", &[], &[], vars),
+            Some("gh".to_string())
+        );
+        assert_eq!(
+            env_token(
+                "https://github.example.com/owner/repo",
+                &[],
+                &enterprise,
+                vars
+            ),
+            Some("gh".to_string())
+        );
+        assert_eq!(
+            env_token("https://gitlab.com/owner/repo.git", &[], &enterprise, vars),
+            None
+        );
+        assert_eq!(
+            env_token("https://github.com.evil.example/owner/repo", &[], &[], vars),
+            None
+        );
+    }
+
+    #[test]
+    fn test_watch_calls_on_stall_without_progress() {
+        let (tx, rx) = mpsc::channel();
+        let mut stalled = false;
+        tx.send(Heartbeat::Progress).unwrap();
+        watch(rx, Duration::from_millis(20), || stalled = true);
+        assert!(stalled);
+        drop(tx);
+    }
+
+    #[test]
+    fn test_watch_stops_when_operation_ends() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(Heartbeat::Progress).unwrap();
+        drop(tx);
+        let mut stalled = false;
+        watch(rx, Duration::from_secs(5), || stalled = true);
+        assert!(!stalled);
+    }
+
+    #[test]
+    fn test_watch_ignores_time_spent_paused() {
+        let (tx, rx) = mpsc::channel();
+        let sender = std::thread::spawn(move || {
+            tx.send(Heartbeat::Pause).unwrap();
+            std::thread::sleep(Duration::from_millis(100));
+            tx.send(Heartbeat::Resume).unwrap();
+        });
+        let mut stalled = false;
+        watch(rx, Duration::from_millis(50), || stalled = true);
+        sender.join().unwrap();
+        assert!(!stalled);
+    }
 }
@@ -0,0 +1,181 @@
+//! Discovery of hook scripts in the `.gitpublish/hooks/` directory (or the
+//! directory set with `[hooks] path`).
+//!
+//! Executable files named after a hook (`pre-tag-create`, `post-tag-create`,
+//! `post-push`) run after the scripts configured in `[hooks]`, the same
+//! way `.git/hooks` works for git itself.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::{HookCommand, HooksConfig};
+use crate::hooks::executor::HookExecutor;
+use crate::hooks::lifecycle::HookType;
+
+/// Hooks directory, relative to the repository root
+pub const HOOKS_DIR: &str = ".gitpublish/hooks";
+
+/// Directory hooks are discovered in: `[hooks] path` if set, else [`HOOKS_DIR`]
+pub fn hooks_dir(hooks: &HooksConfig, repo_root: &Path) -> PathBuf {
+    match hooks.path {
+        Some(_) => hooks.scripts_dir(repo_root),
+        None => repo_root.join(HOOKS_DIR),
+    }
+}
+
+/// Path where a hook would be discovered in `dir`, whether or not it exists
+pub fn hook_path(dir: &Path, hook_type: HookType) -> PathBuf {
+    dir.join(hook_type.name())
+}
+
+/// Returns the discovered script for a hook, if it exists and is executable
+///
+/// Like git, non-executable files are ignored so a hook can be disabled with
+/// `chmod -x`.
+pub fn discover_hook(dir: &Path, hook_type: HookType) -> Option<PathBuf> {
+    let path = hook_path(dir, hook_type);
+    HookExecutor::check_script(&path.to_string_lossy())
+        .ok()
+        .map(|()| path)
+}
+
+/// All scripts to run for a hook, in order: the configured ones, then the
+/// discovered one
+///
+/// Relative configured paths are resolved against the repository root, or
+/// `[hooks] path` under it.
+///
+/// # Arguments
+/// * `hooks` - Hook configuration (already resolved for the branch)
+/// * `repo_root` - Repository root to discover hooks in, if any
+/// * `hook_type` - Hook being run
+pub fn resolve_scripts(
+    hooks: &HooksConfig,
+    repo_root: Option<&Path>,
+    hook_type: HookType,
+) -> Vec<HookCommand> {
+    let mut commands: Vec<HookCommand> = match repo_root {
+        Some(root) => hooks
+            .scripts(hook_type)
+            .iter()
+            .map(|command| command.relative_to(&hooks.scripts_dir(root)))
+            .collect(),
+        None => hooks.scripts(hook_type).to_vec(),
+    };
+    if let Some(discovered) =
+        repo_root.and_then(|root| discover_hook(&hooks_dir(hooks, root), hook_type))
+    {
+        let already_configured = commands
+            .iter()
+            .any(|command| same_file(Path::new(&command.script), &discovered));
+        if !already_configured {
+            commands.push(HookCommand::new(discovered.to_string_lossy()));
+        }
+    }
+    commands
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn write_hook(root: &Path, hook_type: HookType, mode: u32) -> PathBuf {
+        let path = hook_path(&root.join(HOOKS_DIR), hook_type);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "#!/bin/sh\nexit 0\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_discover_hook_requires_executable() {
+        let dir = tempfile::tempdir().unwrap();
+        let executable = write_hook(dir.path(), HookType::PostPush, 0o755);
+        write_hook(dir.path(), HookType::PreTagCreate, 0o644);
+
+        let hooks = dir.path().join(HOOKS_DIR);
+        assert_eq!(discover_hook(&hooks, HookType::PostPush), Some(executable));
+        assert_eq!(discover_hook(&hooks, HookType::PreTagCreate), None);
+        assert_eq!(discover_hook(&hooks, HookType::PostTagCreate), None);
+    }
+
+    #[test]
+    fn test_resolve_scripts_runs_configured_then_discovered() {
+        let dir = tempfile::tempdir().unwrap();
+        let discovered = write_hook(dir.path(), HookType::PostPush, 0o755);
+        let hooks = HooksConfig {
+            post_push: vec![HookCommand::new("./notify.sh")],
+            ..HooksConfig::default()
+        };
+
+        assert_eq!(
+            resolve_scripts(&hooks, Some(dir.path()), HookType::PostPush),
+            vec![
+                HookCommand::new(dir.path().join("notify.sh").to_string_lossy()),
+                HookCommand::new(discovered.to_string_lossy())
+            ]
+        );
+        assert!(resolve_scripts(&hooks, None, HookType::PreTagCreate).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_scripts_skips_discovered_hook_already_configured() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_hook(dir.path(), HookType::PostPush, 0o755);
+        let hooks = HooksConfig {
+            post_push: vec![HookCommand::new(path.to_string_lossy())],
+            ..HooksConfig::default()
+        };
+
+        assert_eq!(
+            resolve_scripts(&hooks, Some(dir.path()), HookType::PostPush),
+            hooks.post_push
+        );
+    }
+
+    #[test]
+    fn test_resolve_scripts_matches_relative_config_to_discovered_hook() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_hook(dir.path(), HookType::PostPush, 0o755);
+        let hooks = HooksConfig {
+            post_push: vec![HookCommand::new(".gitpublish/hooks/post-push")],
+            ..HooksConfig::default()
+        };
+
+        let commands = resolve_scripts(&hooks, Some(dir.path()), HookType::PostPush);
+        assert_eq!(commands, vec![HookCommand::new(path.to_string_lossy())]);
+    }
+
+    #[test]
+    fn test_resolve_scripts_uses_hooks_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let custom = dir.path().join("ci/release-hooks");
+        std::fs::create_dir_all(&custom).unwrap();
+        let discovered = hook_path(&custom, HookType::PostPush);
+        std::fs::rename(
+            write_hook(dir.path(), HookType::PostPush, 0o755),
+            &discovered,
+        )
+        .unwrap();
+        let hooks = HooksConfig {
+            post_push: vec![HookCommand::new("notify.sh")],
+            path: Some("ci/release-hooks".to_string()),
+            ..HooksConfig::default()
+        };
+
+        assert_eq!(
+            resolve_scripts(&hooks, Some(dir.path()), HookType::PostPush),
+            vec![
+                HookCommand::new(custom.join("notify.sh").to_string_lossy()),
+                HookCommand::new(discovered.to_string_lossy())
+            ]
+        );
+    }
+}
@@ -0,0 +1,515 @@
+use crate::config::HookCommand;
+use crate::error::{GitPublishError, Result};
+use crate::hooks::interpreter::resolve_interpreter;
+use crate::hooks::lifecycle::{HookContext, HookFailurePolicy, HookType};
+use crate::i18n::{tr, Msg};
+use serde::Serialize;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::Instant;
+
+/// Maximum bytes of hook output kept in a [`HookRun`]; the end is kept
+pub const MAX_HOOK_OUTPUT: usize = 4096;
+
+/// Variables kept when hooks don't inherit the environment, so scripts and
+/// interpreters can still be found and run
+const BASE_ENV: [&str; 8] = [
+    "PATH",
+    "HOME",
+    "SYSTEMROOT",
+    "COMSPEC",
+    "PATHEXT",
+    "TEMP",
+    "TMP",
+    "USERPROFILE",
+];
+
+/// Outcome of running one hook script
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HookRun {
+    /// Hook the script ran for
+    pub hook_type: HookType,
+    /// Path to the script
+    pub script: String,
+    /// Failure policy the script ran with
+    pub policy: HookFailurePolicy,
+    /// Exit code, if the script ran and exited normally
+    pub exit_code: Option<i32>,
+    /// Wall-clock run time in milliseconds
+    pub duration_ms: u64,
+    /// Combined stdout and stderr, truncated to the last [`MAX_HOOK_OUTPUT`] bytes
+    pub output: String,
+    /// Error message if the script failed
+    pub error: Option<String>,
+}
+
+impl HookRun {
+    /// Whether the script succeeded
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Whether this run failed and should stop the workflow
+    pub fn aborts(&self) -> bool {
+        !self.succeeded() && self.policy == HookFailurePolicy::Abort
+    }
+}
+
+/// Executes git-publish hooks
+///
+/// HookExecutor runs hook scripts with proper environment variable setup
+/// and error handling. It validates that scripts exist before execution
+/// and interprets exit codes according to hook semantics.
+pub struct HookExecutor;
+
+impl HookExecutor {
+    /// Check that a hook script exists and can be executed directly
+    ///
+    /// # Returns
+    /// * `Err` if the script is missing, not a file, or (on Unix) not executable
+    pub fn check_script(script_path: &str) -> Result<()> {
+        Self::check_file(script_path)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if Path::new(script_path).metadata()?.permissions().mode() & 0o111 == 0 {
+                return Err(GitPublishError::hook(format!(
+                    "Hook script is not executable: {} (make it executable or set an interpreter)",
+                    script_path
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_file(script_path: &str) -> Result<()> {
+        let path = Path::new(script_path);
+
+        if !path.exists() {
+            return Err(GitPublishError::hook(format!(
+                "Hook script not found: {}",
+                script_path
+            )));
+        }
+
+        if !path.is_file() {
+            return Err(GitPublishError::hook(format!(
+                "Hook path is not a file: {}",
+                script_path
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Resolve how a hook command will be run
+    ///
+    /// # Returns
+    /// * `Ok(Some(interpreter))` if the script runs through an interpreter
+    ///   (configured, from its shebang, or from its extension)
+    /// * `Ok(None)` if the script is executed directly
+    /// * `Err` if the script is missing, or can't be run either way
+    pub fn interpreter(command: &HookCommand) -> Result<Option<Vec<String>>> {
+        Self::check_file(&command.script)?;
+        let interpreter =
+            resolve_interpreter(Path::new(&command.script), command.interpreter.as_deref());
+        if interpreter.is_none() {
+            Self::check_script(&command.script)?;
+        }
+        Ok(interpreter)
+    }
+
+    /// Command line a hook command runs, for echoing and dry runs
+    ///
+    /// Falls back to the bare script path if it can't be resolved (e.g. the
+    /// script is missing); running it will report the actual problem.
+    pub fn command_line(command: &HookCommand) -> String {
+        match Self::interpreter(command) {
+            Ok(Some(mut interpreter)) => {
+                interpreter.push(command.script.clone());
+                interpreter.join(" ")
+            }
+            _ => command.script.clone(),
+        }
+    }
+
+    /// Execute a hook script
+    ///
+    /// Runs the script at the specified path with environment variables
+    /// from the hook context, detecting an interpreter if needed.
+    ///
+    /// # Arguments
+    /// * `script_path` - Path to the hook script to execute
+    /// * `context` - Hook context with environment variables
+    ///
+    /// # Returns
+    /// * `Ok(())` if hook succeeds (exit code 0)
+    /// * `Err` if hook fails (non-zero exit code), script not found, or not a file
+    pub fn execute(script_path: &str, context: &HookContext) -> Result<()> {
+        Self::execute_command(&HookCommand::new(script_path), context)
+    }
+
+    /// Execute a configured hook command
+    ///
+    /// Like [`HookExecutor::execute`], honoring the command's `interpreter`.
+    pub fn execute_command(command: &HookCommand, context: &HookContext) -> Result<()> {
+        let output = Self::spawn(command, context)?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            return Err(GitPublishError::hook(format!(
+                "Hook {} failed with exit code {}\nStdout: {}\nStderr: {}",
+                command.script,
+                output.status.code().unwrap_or(-1),
+                stdout,
+                stderr
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn spawn(command: &HookCommand, context: &HookContext) -> Result<Output> {
+        let script_path = command.script.as_str();
+        let mut cmd = match Self::interpreter(command)? {
+            Some(interpreter) => {
+                let mut cmd = Command::new(&interpreter[0]);
+                cmd.args(&interpreter[1..]).arg(script_path);
+                cmd
+            }
+            None => Command::new(script_path),
+        };
+
+        if let Some(ref root) = context.repo_root {
+            cmd.current_dir(root);
+        }
+
+        if !context.env.inherit {
+            cmd.env_clear();
+            let names = BASE_ENV
+                .iter()
+                .copied()
+                .chain(context.env.pass.iter().map(String::as_str));
+            for name in names {
+                if let Some(value) = std::env::var_os(name) {
+                    cmd.env(name, value);
+                }
+            }
+        }
+
+        cmd.envs(context.to_env_vars()).output().map_err(|e| {
+            GitPublishError::hook(format!(
+                "Failed to execute hook {} ({}): {}",
+                script_path,
+                cmd.get_program().to_string_lossy(),
+                e
+            ))
+        })
+    }
+
+    /// Execute a configured hook command and record the outcome
+    ///
+    /// Never returns an error: failures are captured in the returned
+    /// [`HookRun`], which also says whether the workflow should stop.
+    ///
+    /// # Arguments
+    /// * `command` - Script and failure policy to run
+    /// * `context` - Hook context with environment variables
+    pub fn run(command: &HookCommand, context: &HookContext) -> HookRun {
+        let started = Instant::now();
+        let result = Self::spawn(command, context);
+        let duration_ms = started.elapsed().as_millis() as u64;
+
+        let mut run = HookRun {
+            hook_type: context.hook_type,
+            script: command.script.clone(),
+            policy: command.failure_policy(context.hook_type),
+            exit_code: None,
+            duration_ms,
+            output: String::new(),
+            error: None,
+        };
+        match result {
+            Ok(output) => {
+                run.exit_code = output.status.code();
+                let mut combined = String::from_utf8_lossy(&output.stdout).into_owned();
+                combined.push_str(&String::from_utf8_lossy(&output.stderr));
+                run.output = truncate_output(&combined);
+                if !output.status.success() {
+                    run.error = Some(format!(
+                        "Hook {} failed with exit code {}",
+                        command.script,
+                        output.status.code().unwrap_or(-1)
+                    ));
+                }
+            }
+            Err(e) => run.error = Some(e.to_string()),
+        }
+        run
+    }
+
+    /// Try to execute a hook, logging warnings but not failing
+    ///
+    /// Used for post-push hooks where the push has already succeeded
+    /// and we don't want hook failures to affect the overall success.
+    ///
+    /// # Arguments
+    /// * `script_path` - Path to the hook script to execute
+    /// * `context` - Hook context with environment variables
+    pub fn execute_permissive(script_path: &str, context: &HookContext) {
+        match Self::execute(script_path, context) {
+            Ok(()) => println!(
+                "\x1b[32m✓\x1b[0m {}",
+                tr(Msg::HookSucceeded, &[("hook", &context.hook_type.name())])
+            ),
+            Err(e) => eprintln!(
+                "\x1b[33m⚠\x1b[0m {}",
+                tr(
+                    Msg::HookWarning,
+                    &[("hook", &context.hook_type.name()), ("error", &e)]
+                )
+            ),
+        }
+    }
+}
+
+/// Keep the last [`MAX_HOOK_OUTPUT`] bytes of hook output, where errors usually are
+fn truncate_output(output: &str) -> String {
+    if output.len() <= MAX_HOOK_OUTPUT {
+        return output.to_string();
+    }
+    let mut start = output.len() - MAX_HOOK_OUTPUT;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("…{}", &output[start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::lifecycle::HookEnv;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn context(hook_type: HookType) -> HookContext {
+        HookContext {
+            hook_type,
+            branch: "main".to_string(),
+            tag: "v1.2.3".to_string(),
+            version: "1.2.3".to_string(),
+            remote: "origin".to_string(),
+            previous_tag: Some("v1.2.2".to_string()),
+            version_bump: None,
+            commit_count: None,
+            commit_types: None,
+            env: HookEnv::default(),
+            repo_root: None,
+        }
+    }
+
+    fn script(lines: &[&str]) -> tempfile::TempPath {
+        let mut script = NamedTempFile::new().unwrap();
+        writeln!(script, "#!/bin/sh").unwrap();
+        for line in lines {
+            writeln!(script, "{}", line).unwrap();
+        }
+        script.flush().unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(script.path(), std::fs::Permissions::from_mode(0o755))
+                .unwrap();
+        }
+        script.into_temp_path()
+    }
+
+    #[test]
+    fn test_execute_nonexistent_hook_fails() {
+        let result =
+            HookExecutor::execute("/nonexistent/hook.sh", &context(HookType::PreTagCreate));
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_execute_directory_instead_of_file_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = HookExecutor::execute(
+            dir.path().to_str().unwrap(),
+            &context(HookType::PostTagCreate),
+        );
+        assert!(result.unwrap_err().to_string().contains("not a file"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_script_rejects_non_executable() {
+        let file = NamedTempFile::new().unwrap();
+        let result = HookExecutor::check_script(file.path().to_str().unwrap());
+        assert!(result.unwrap_err().to_string().contains("not executable"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_successful_hook() {
+        let path = script(&["exit 0"]);
+        let result =
+            HookExecutor::execute(path.to_str().unwrap(), &context(HookType::PreTagCreate));
+        assert!(result.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_hook_with_failing_exit_code() {
+        let path = script(&["exit 1"]);
+        let result = HookExecutor::execute(path.to_str().unwrap(), &context(HookType::PostPush));
+        assert!(result.unwrap_err().to_string().contains("failed"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_hook_receives_environment_variables() {
+        let path = script(&[
+            "[ \"$GITPUBLISH_BRANCH\" = \"main\" ] || exit 1",
+            "[ \"$GITPUBLISH_TAG_NAME\" = \"v1.2.3\" ] || exit 1",
+            "[ \"$GITPUBLISH_VERSION\" = \"1.2.3\" ] || exit 1",
+            "[ \"$GITPUBLISH_REMOTE\" = \"origin\" ] || exit 1",
+            "[ \"$GITPUBLISH_PREVIOUS_TAG\" = \"v1.2.2\" ] || exit 1",
+            "exit 0",
+        ]);
+        let result =
+            HookExecutor::execute(path.to_str().unwrap(), &context(HookType::PreTagCreate));
+        assert!(result.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_records_outcome_and_policy() {
+        let ok = script(&["exit 0"]);
+        let run = HookExecutor::run(
+            &HookCommand::new(ok.to_str().unwrap()),
+            &context(HookType::PreTagCreate),
+        );
+        assert!(run.succeeded());
+        assert!(!run.aborts());
+
+        assert_eq!(run.exit_code, Some(0));
+
+        let failing = script(&["echo deploying", "echo boom >&2", "exit 3"]);
+        let command = HookCommand::new(failing.to_str().unwrap());
+        let run = HookExecutor::run(&command, &context(HookType::PreTagCreate));
+        assert!(run.error.as_deref().unwrap().contains("exit code 3"));
+        assert_eq!(run.exit_code, Some(3));
+        assert_eq!(run.output, "deploying\nboom\n");
+        assert!(run.aborts());
+
+        let run = HookExecutor::run(&command, &context(HookType::PostPush));
+        assert_eq!(run.policy, HookFailurePolicy::Continue);
+        assert!(!run.aborts());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_non_executable_script_via_shebang() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "#!/bin/sh").unwrap();
+        writeln!(file, "[ \"$GITPUBLISH_BRANCH\" = \"main\" ] || exit 1").unwrap();
+        file.flush().unwrap();
+
+        let result =
+            HookExecutor::execute(file.path().to_str().unwrap(), &context(HookType::PostPush));
+        assert!(result.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_command_with_configured_interpreter() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "exit 4").unwrap();
+        file.flush().unwrap();
+
+        let command = HookCommand {
+            interpreter: Some("sh".to_string()),
+            ..HookCommand::new(file.path().to_str().unwrap())
+        };
+        let error = HookExecutor::execute_command(&command, &context(HookType::PostPush))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("exit code 4"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_line() {
+        let command = HookCommand {
+            interpreter: Some("sh -e".to_string()),
+            ..HookCommand::new("/nonexistent/hook.sh")
+        };
+        assert_eq!(HookExecutor::command_line(&command), "/nonexistent/hook.sh");
+
+        let path = script(&["exit 0"]);
+        let command = HookCommand {
+            interpreter: Some("sh -e".to_string()),
+            ..HookCommand::new(path.to_str().unwrap())
+        };
+        assert_eq!(
+            HookExecutor::command_line(&command),
+            format!("sh -e {}", path.to_str().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_truncate_output_keeps_tail() {
+        assert_eq!(truncate_output("short"), "short");
+
+        let long = format!("{}end", "é".repeat(MAX_HOOK_OUTPUT));
+        let truncated = truncate_output(&long);
+        assert!(truncated.starts_with('…'));
+        assert!(truncated.ends_with("end"));
+        assert!(truncated.len() <= MAX_HOOK_OUTPUT + '…'.len_utf8());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_without_inherited_env() {
+        std::env::set_var("GITPUBLISH_TEST_INHERITED", "1");
+        let path = script(&[
+            "[ -z \"$GITPUBLISH_TEST_INHERITED\" ] || exit 1",
+            "[ \"$DEPLOY_ENV\" = \"prod\" ] || exit 1",
+            "[ -n \"$PATH\" ] || exit 1",
+        ]);
+
+        let mut ctx = context(HookType::PostPush);
+        ctx.env = HookEnv {
+            vars: std::collections::HashMap::from([("DEPLOY_ENV".to_string(), "prod".to_string())]),
+            inherit: false,
+            pass: Vec::new(),
+        };
+        assert!(HookExecutor::execute(path.to_str().unwrap(), &ctx).is_ok());
+
+        ctx.env.pass = vec!["GITPUBLISH_TEST_INHERITED".to_string()];
+        assert!(HookExecutor::execute(path.to_str().unwrap(), &ctx).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_runs_in_repo_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let path = script(&[
+            "[ \"$(pwd -P)\" = \"$GITPUBLISH_REPO_ROOT\" ] || exit 1",
+            "touch marker",
+        ]);
+
+        let ctx = HookContext {
+            repo_root: Some(root.clone()),
+            ..context(HookType::PostTagCreate)
+        };
+        assert!(HookExecutor::execute(path.to_str().unwrap(), &ctx).is_ok());
+        assert!(root.join("marker").exists());
+    }
+}
@@ -0,0 +1,179 @@
+//! Interpreter detection for hook scripts.
+//!
+//! Executable scripts on Unix run directly. Everything else (any script on
+//! Windows, non-executable scripts on Unix) is run through an interpreter taken
+//! from, in order: the script's `interpreter` setting, its shebang line, or its
+//! file extension.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Interpreter command line (program and leading arguments) for a hook script
+///
+/// Returns `None` when the script should be executed directly.
+///
+/// # Arguments
+/// * `script` - Path to the hook script
+/// * `configured` - `interpreter` set for the script in the config, if any
+pub fn resolve_interpreter(script: &Path, configured: Option<&str>) -> Option<Vec<String>> {
+    if let Some(configured) = configured {
+        let words: Vec<String> = configured.split_whitespace().map(String::from).collect();
+        if !words.is_empty() {
+            return Some(words);
+        }
+    }
+    if is_directly_executable(script) {
+        return None;
+    }
+    read_shebang(script).or_else(|| extension_interpreter(script))
+}
+
+/// Whether the OS can run the script without an interpreter
+fn is_directly_executable(script: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        script
+            .metadata()
+            .map(|metadata| metadata.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        matches!(extension(script).as_deref(), Some("exe") | Some("com"))
+    }
+}
+
+fn extension(script: &Path) -> Option<String> {
+    script
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+}
+
+fn read_shebang(script: &Path) -> Option<Vec<String>> {
+    let mut first_line = String::new();
+    BufReader::new(File::open(script).ok()?)
+        .read_line(&mut first_line)
+        .ok()?;
+    parse_shebang(&first_line)
+}
+
+/// Parse a `#!` line into an interpreter command line
+///
+/// `#!/usr/bin/env bash` yields `bash`. On Windows, absolute interpreter paths
+/// such as `/bin/sh` are reduced to the program name so it's looked up on `PATH`.
+pub fn parse_shebang(line: &str) -> Option<Vec<String>> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let program = words.next()?;
+    let mut command: Vec<String> = Vec::new();
+
+    if program.ends_with("/env") {
+        let mut rest = words.skip_while(|word| word.starts_with('-'));
+        command.push(rest.next()?.to_string());
+        command.extend(rest.map(String::from));
+        return Some(command);
+    }
+
+    let program = if cfg!(windows) {
+        program.rsplit('/').next().unwrap_or(program)
+    } else {
+        program
+    };
+    command.push(program.to_string());
+    command.extend(words.map(String::from));
+    Some(command)
+}
+
+/// Interpreter for well-known script extensions
+pub fn extension_interpreter(script: &Path) -> Option<Vec<String>> {
+    let command: &[&str] = match extension(script)?.as_str() {
+        "ps1" if cfg!(windows) => &[
+            "powershell",
+            "-NoProfile",
+            "-ExecutionPolicy",
+            "Bypass",
+            "-File",
+        ],
+        "ps1" => &["pwsh", "-NoProfile", "-File"],
+        "bat" | "cmd" => &["cmd", "/C"],
+        "sh" => &["sh"],
+        "bash" => &["bash"],
+        "py" if cfg!(windows) => &["python"],
+        "py" => &["python3"],
+        "js" => &["node"],
+        _ => return None,
+    };
+    Some(command.iter().map(|word| word.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_parse_shebang_env() {
+        assert_eq!(
+            parse_shebang("#!/usr/bin/env bash\n"),
+            Some(vec!["bash".to_string()])
+        );
+        assert_eq!(
+            parse_shebang("#!/usr/bin/env -S python3 -u"),
+            Some(vec!["python3".to_string(), "-u".to_string()])
+        );
+        assert_eq!(parse_shebang("echo hello"), None);
+        assert_eq!(parse_shebang("#!"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_parse_shebang_absolute_path() {
+        assert_eq!(
+            parse_shebang("#!/bin/sh -e"),
+            Some(vec!["/bin/sh".to_string(), "-e".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_extension_interpreter() {
+        assert_eq!(
+            extension_interpreter(Path::new("hooks/notify.BAT")),
+            Some(vec!["cmd".to_string(), "/C".to_string()])
+        );
+        assert_eq!(
+            extension_interpreter(Path::new("deploy.sh")),
+            Some(vec!["sh".to_string()])
+        );
+        assert!(extension_interpreter(Path::new("deploy.ps1"))
+            .unwrap()
+            .contains(&"-File".to_string()));
+        assert_eq!(extension_interpreter(Path::new("deploy")), None);
+    }
+
+    #[test]
+    fn test_configured_interpreter_wins() {
+        let mut script = tempfile::NamedTempFile::new().unwrap();
+        writeln!(script, "#!/bin/sh").unwrap();
+        assert_eq!(
+            resolve_interpreter(script.path(), Some("bash -e")),
+            Some(vec!["bash".to_string(), "-e".to_string()])
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_executable_script_runs_directly() {
+        use std::os::unix::fs::PermissionsExt;
+        let mut script = tempfile::NamedTempFile::new().unwrap();
+        writeln!(script, "#!/bin/sh").unwrap();
+        assert_eq!(
+            resolve_interpreter(script.path(), None),
+            Some(vec!["/bin/sh".to_string()])
+        );
+
+        std::fs::set_permissions(script.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(resolve_interpreter(script.path(), None), None);
+    }
+}
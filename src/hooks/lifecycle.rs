@@ -0,0 +1,303 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::domain::commit::CommitTypeCounts;
+
+/// Types of hooks in the workflow
+///
+/// Defines the three key extension points in the git-publish workflow
+/// where users can run custom scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HookType {
+    /// Runs before creating the tag
+    PreTagCreate,
+    /// Runs after tag created locally, before push
+    PostTagCreate,
+    /// Runs after tag pushed to remote
+    PostPush,
+}
+
+impl HookType {
+    /// All hook types, in workflow order
+    pub const ALL: [HookType; 3] = [
+        HookType::PreTagCreate,
+        HookType::PostTagCreate,
+        HookType::PostPush,
+    ];
+
+    /// Get the canonical name of this hook type
+    ///
+    /// Returns the hook name as it appears in configuration files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookType::PreTagCreate => "pre-tag-create",
+            HookType::PostTagCreate => "post-tag-create",
+            HookType::PostPush => "post-push",
+        }
+    }
+
+    /// Failure policy for scripts that don't set `on_error`
+    ///
+    /// Post-push failures are only reported since the tag is already published.
+    pub fn default_failure_policy(&self) -> HookFailurePolicy {
+        match self {
+            HookType::PreTagCreate | HookType::PostTagCreate => HookFailurePolicy::Abort,
+            HookType::PostPush => HookFailurePolicy::Continue,
+        }
+    }
+}
+
+/// What to do when a hook script fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HookFailurePolicy {
+    /// Stop the workflow (fail fast)
+    Abort,
+    /// Report the failure and keep going
+    Continue,
+}
+
+/// Environment settings for hook processes beyond the `GITPUBLISH_*` variables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookEnv {
+    /// Extra static variables from `[hooks.env]`
+    pub vars: HashMap<String, String>,
+    /// Whether hooks inherit git-publish's environment
+    pub inherit: bool,
+    /// Variables passed through when not inheriting
+    pub pass: Vec<String>,
+}
+
+impl Default for HookEnv {
+    fn default() -> Self {
+        HookEnv {
+            vars: HashMap::new(),
+            inherit: true,
+            pass: Vec::new(),
+        }
+    }
+}
+
+/// Context passed to a hook script
+///
+/// Contains all information the hook needs to make decisions,
+/// which is passed as environment variables to the script.
+#[derive(Debug, Clone)]
+pub struct HookContext {
+    /// Type of hook being executed
+    pub hook_type: HookType,
+    /// Git branch being tagged
+    pub branch: String,
+    /// Tag name being created/pushed
+    pub tag: String,
+    /// Version of the new tag without the pattern's prefix/suffix (e.g. `1.2.3`)
+    pub version: String,
+    /// Remote name to push to
+    pub remote: String,
+    /// Latest tag on the branch before this release, if any
+    pub previous_tag: Option<String>,
+    /// Version bump type (Major/Minor/Patch), if applicable
+    pub version_bump: Option<String>,
+    /// Number of commits since last tag, if applicable
+    pub commit_count: Option<usize>,
+    /// Breaking/feat/fix counts of the released commits, if applicable
+    pub commit_types: Option<CommitTypeCounts>,
+    /// Extra variables and inheritance settings for the hook process
+    pub env: HookEnv,
+    /// Repository root; hooks run with it as their working directory
+    pub repo_root: Option<PathBuf>,
+}
+
+impl HookContext {
+    /// Convert hook context to environment variables
+    ///
+    /// Returns a HashMap of environment variable names and values
+    /// that will be passed to the hook script. Always includes:
+    /// - GITPUBLISH_BRANCH
+    /// - GITPUBLISH_TAG_NAME
+    /// - GITPUBLISH_VERSION
+    /// - GITPUBLISH_REMOTE
+    ///
+    /// Variables from `[hooks.env]` are included too; they can't override the
+    /// `GITPUBLISH_*` ones.
+    ///
+    /// Optionally includes (if Some):
+    /// - GITPUBLISH_PREVIOUS_TAG
+    /// - GITPUBLISH_VERSION_BUMP
+    /// - GITPUBLISH_COMMIT_COUNT
+    /// - GITPUBLISH_REPO_ROOT
+    /// - GITPUBLISH_BREAKING_COUNT, GITPUBLISH_FEAT_COUNT, GITPUBLISH_FIX_COUNT
+    pub fn to_env_vars(&self) -> HashMap<String, String> {
+        let mut env = self.env.vars.clone();
+
+        env.insert("GITPUBLISH_BRANCH".to_string(), self.branch.clone());
+        env.insert("GITPUBLISH_TAG_NAME".to_string(), self.tag.clone());
+        env.insert("GITPUBLISH_VERSION".to_string(), self.version.clone());
+        env.insert("GITPUBLISH_REMOTE".to_string(), self.remote.clone());
+
+        if let Some(ref previous_tag) = self.previous_tag {
+            env.insert("GITPUBLISH_PREVIOUS_TAG".to_string(), previous_tag.clone());
+        }
+
+        if let Some(ref bump) = self.version_bump {
+            env.insert("GITPUBLISH_VERSION_BUMP".to_string(), bump.clone());
+        }
+
+        if let Some(count) = self.commit_count {
+            env.insert("GITPUBLISH_COMMIT_COUNT".to_string(), count.to_string());
+        }
+
+        if let Some(ref root) = self.repo_root {
+            env.insert(
+                "GITPUBLISH_REPO_ROOT".to_string(),
+                root.to_string_lossy().into_owned(),
+            );
+        }
+
+        if let Some(counts) = self.commit_types {
+            env.insert(
+                "GITPUBLISH_BREAKING_COUNT".to_string(),
+                counts.breaking.to_string(),
+            );
+            env.insert("GITPUBLISH_FEAT_COUNT".to_string(), counts.feat.to_string());
+            env.insert("GITPUBLISH_FIX_COUNT".to_string(), counts.fix.to_string());
+        }
+
+        env
+    }
+}
+
+/// Name fragments that mark an environment variable as secret
+const SECRET_MARKERS: [&str; 6] = ["TOKEN", "SECRET", "PASSWORD", "PASSWD", "CREDENTIAL", "KEY"];
+
+/// Environment variables sorted by name, with secret-looking values masked
+///
+/// Used when showing a hook's environment (dry run, echo) rather than running it.
+pub fn masked_env(env: &HashMap<String, String>) -> Vec<(String, String)> {
+    let mut vars: Vec<(String, String)> = env
+        .iter()
+        .map(|(key, value)| {
+            let upper = key.to_ascii_uppercase();
+            let value = if SECRET_MARKERS.iter().any(|marker| upper.contains(marker)) {
+                "****".to_string()
+            } else {
+                value.clone()
+            };
+            (key.clone(), value)
+        })
+        .collect();
+    vars.sort();
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(hook_type: HookType) -> HookContext {
+        HookContext {
+            hook_type,
+            branch: "main".to_string(),
+            tag: "v1.2.3".to_string(),
+            version: "1.2.3".to_string(),
+            remote: "origin".to_string(),
+            previous_tag: None,
+            version_bump: None,
+            commit_count: None,
+            commit_types: None,
+            env: HookEnv::default(),
+            repo_root: None,
+        }
+    }
+
+    #[test]
+    fn test_hook_type_names() {
+        assert_eq!(HookType::PreTagCreate.name(), "pre-tag-create");
+        assert_eq!(HookType::PostTagCreate.name(), "post-tag-create");
+        assert_eq!(HookType::PostPush.name(), "post-push");
+    }
+
+    #[test]
+    fn test_hook_context_to_env_vars_basic() {
+        let env = context(HookType::PreTagCreate).to_env_vars();
+        assert_eq!(env.get("GITPUBLISH_BRANCH"), Some(&"main".to_string()));
+        assert_eq!(env.get("GITPUBLISH_TAG_NAME"), Some(&"v1.2.3".to_string()));
+        assert_eq!(env.get("GITPUBLISH_VERSION"), Some(&"1.2.3".to_string()));
+        assert_eq!(env.get("GITPUBLISH_REMOTE"), Some(&"origin".to_string()));
+        assert_eq!(env.len(), 4); // Only branch, tag, version, remote
+    }
+
+    #[test]
+    fn test_hook_context_to_env_vars_with_optional_fields() {
+        let ctx = HookContext {
+            previous_tag: Some("v1.1.0".to_string()),
+            version_bump: Some("Minor".to_string()),
+            commit_count: Some(5),
+            repo_root: Some(PathBuf::from("/repo")),
+            commit_types: Some(CommitTypeCounts {
+                breaking: 0,
+                feat: 2,
+                fix: 3,
+            }),
+            ..context(HookType::PostPush)
+        };
+
+        let env = ctx.to_env_vars();
+        assert_eq!(
+            env.get("GITPUBLISH_PREVIOUS_TAG"),
+            Some(&"v1.1.0".to_string())
+        );
+        assert_eq!(
+            env.get("GITPUBLISH_VERSION_BUMP"),
+            Some(&"Minor".to_string())
+        );
+        assert_eq!(env.get("GITPUBLISH_COMMIT_COUNT"), Some(&"5".to_string()));
+        assert_eq!(env.get("GITPUBLISH_BREAKING_COUNT"), Some(&"0".to_string()));
+        assert_eq!(env.get("GITPUBLISH_FEAT_COUNT"), Some(&"2".to_string()));
+        assert_eq!(env.get("GITPUBLISH_FIX_COUNT"), Some(&"3".to_string()));
+        assert_eq!(env.get("GITPUBLISH_REPO_ROOT"), Some(&"/repo".to_string()));
+    }
+
+    #[test]
+    fn test_masked_env() {
+        let env = HashMap::from([
+            ("GITPUBLISH_TAG_NAME".to_string(), "v1.2.3".to_string()),
+            ("DEPLOY_TOKEN".to_string(), "abc123".to_string()),
+            ("api_key".to_string(), "xyz".to_string()),
+        ]);
+        assert_eq!(
+            masked_env(&env),
+            vec![
+                ("DEPLOY_TOKEN".to_string(), "****".to_string()),
+                ("GITPUBLISH_TAG_NAME".to_string(), "v1.2.3".to_string()),
+                ("api_key".to_string(), "****".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hook_type_serializes_as_config_name() {
+        for hook_type in HookType::ALL {
+            assert_eq!(
+                serde_json::to_value(hook_type).unwrap(),
+                serde_json::Value::from(hook_type.name())
+            );
+        }
+    }
+
+    #[test]
+    fn test_hook_context_extra_env_cannot_override_builtins() {
+        let mut ctx = context(HookType::PostPush);
+        ctx.env.vars = HashMap::from([
+            ("DEPLOY_ENV".to_string(), "prod".to_string()),
+            ("GITPUBLISH_TAG_NAME".to_string(), "bogus".to_string()),
+        ]);
+
+        let env = ctx.to_env_vars();
+        assert_eq!(env.get("DEPLOY_ENV"), Some(&"prod".to_string()));
+        assert_eq!(env.get("GITPUBLISH_TAG_NAME"), Some(&"v1.2.3".to_string()));
+    }
+}
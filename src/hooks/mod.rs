@@ -0,0 +1,27 @@
+//! Git hooks system for extensibility
+//!
+//! Provides a flexible system for users to run custom scripts at key points
+//! in the git-publish workflow: before tag creation, after tag creation, and
+//! after successful push to remote. Scripts come from `[hooks]` in the config
+//! and from the `.gitpublish/hooks/` directory.
+
+pub mod discovery;
+pub mod executor;
+pub mod interpreter;
+pub mod lifecycle;
+
+pub use discovery::resolve_scripts;
+pub use executor::{HookExecutor, HookRun};
+pub use lifecycle::{masked_env, HookContext, HookEnv, HookFailurePolicy, HookType};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_type_variants_exist() {
+        let _hook = lifecycle::HookType::PreTagCreate;
+        let _hook = lifecycle::HookType::PostTagCreate;
+        let _hook = lifecycle::HookType::PostPush;
+    }
+}
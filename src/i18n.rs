@@ -0,0 +1,646 @@
+//! Message catalog for user-facing prompts, warnings and status lines.
+//!
+//! Every translatable string has a [`Msg`] key with one entry per [`Locale`].
+//! Placeholders are written as `{name}` and filled in by [`tr`]. The active
+//! locale is process-wide and is chosen once at startup from `--lang`, the
+//! `[behavior] language` setting or the `LC_ALL`/`LC_MESSAGES`/`LANG` environment.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// Supported display languages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Locale {
+    /// English (default)
+    #[default]
+    En,
+    /// Simplified Chinese
+    ZhCn,
+}
+
+impl Locale {
+    /// Canonical tag for the locale (e.g. "zh-CN")
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::ZhCn => "zh-CN",
+        }
+    }
+
+    /// Detect the locale from `LC_ALL`, `LC_MESSAGES` or `LANG` (e.g. "zh_CN.UTF-8")
+    pub fn from_env() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| {
+                let lang = value.split('.').next().unwrap_or_default();
+                lang.parse().ok()
+            })
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tag())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().replace('_', "-").to_ascii_lowercase();
+        match normalized.as_str() {
+            "en" | "c" | "posix" => Ok(Locale::En),
+            lang if lang.starts_with("en-") => Ok(Locale::En),
+            "zh" | "zh-cn" | "zh-hans" | "zh-sg" => Ok(Locale::ZhCn),
+            _ => Err(format!(
+                "Unsupported language '{}' (supported: en, zh-CN)",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for Locale {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Locale> for String {
+    fn from(locale: Locale) -> Self {
+        locale.tag().to_string()
+    }
+}
+
+impl schemars::JsonSchema for Locale {
+    fn schema_name() -> String {
+        "Locale".to_string()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        // Aliases such as "zh" or "en-US" are accepted too, so only suggest the tags
+        schemars::schema::SchemaObject {
+            instance_type: Some(schemars::schema::InstanceType::String.into()),
+            metadata: Some(Box::new(schemars::schema::Metadata {
+                examples: vec![Locale::En.tag().into(), Locale::ZhCn.tag().into()],
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+static CURRENT_LOCALE: AtomicU8 = AtomicU8::new(0);
+
+/// Set the process-wide display language
+pub fn set_locale(locale: Locale) {
+    CURRENT_LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+/// Current display language
+pub fn locale() -> Locale {
+    match CURRENT_LOCALE.load(Ordering::Relaxed) {
+        1 => Locale::ZhCn,
+        _ => Locale::En,
+    }
+}
+
+macro_rules! messages {
+    ($($key:ident => $en:expr, $zh_cn:expr;)*) => {
+        /// Keys of translatable user-facing strings
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Msg {
+            $($key,)*
+        }
+
+        impl Msg {
+            /// Every message key, for catalog completeness checks
+            pub const ALL: &'static [Msg] = &[$(Msg::$key,)*];
+
+            /// Raw template for this message in the given locale
+            pub fn template(self, locale: Locale) -> &'static str {
+                match (self, locale) {
+                    $(
+                        (Msg::$key, Locale::En) => $en,
+                        (Msg::$key, Locale::ZhCn) => $zh_cn,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+messages! {
+    // Prompts
+    AvailableBranches => "Available branches for tagging:", "可打标签的分支：";
+    SelectBranchPrompt => "Select a branch (1-{max}) [default: 1]: ", "选择分支 (1-{max}) [默认: 1]: ";
+    InvalidSelection => "Invalid selection", "无效的选择";
+    AvailableRemotes => "Available remotes:", "可用的远程仓库：";
+    SelectRemotePrompt => "Select a remote for fetch/push (1-{max}) [default: 1]: ",
+        "选择用于拉取/推送的远程仓库 (1-{max}) [默认: 1]: ";
+    InvalidRemoteSelection => "Invalid remote selection", "无效的远程仓库选择";
+    TagOptions => "Tag options:\n  (press Enter to use recommended)\n  (enter custom tag)\n  (enter 'e' to edit)\n\nTag [{tag}]: ",
+        "标签选项：\n  （按回车使用推荐标签）\n  （输入自定义标签）\n  （输入 'e' 编辑）\n\n标签 [{tag}]: ";
+    EditTagPrompt => "Edit tag [{tag}]: ", "编辑标签 [{tag}]: ";
+    SuggestedTags => "Suggested tags:", "推荐的标签：";
+    Recommended => "(recommended)", "（推荐）";
+    CustomTagOption => "Custom tag", "自定义标签";
+    SelectTagPrompt => "Select a tag (1-{max}, c) [default: 1]: ", "选择标签 (1-{max}, c) [默认: 1]: ";
+    EnterCustomTag => "Enter custom tag: ", "输入自定义标签: ";
+    RecentTags => "Recent tags (Tab completes): {tags}", "最近的标签（Tab 补全）：{tags}";
+    ContinueFromVersion => "Continue from which version?", "以哪个版本为基础继续？";
+    SelectVersionSourcePrompt => "Select 1-2 [default: {default}]: ", "选择 1-2 [默认: {default}]: ";
+    InvalidVersionSourceSelection => "Invalid version selection", "无效的版本选择";
+    InvalidTagSelection => "Invalid tag selection", "无效的标签选择";
+    EmptyTag => "The tag can't be empty", "标签不能为空";
+    TagRejectedTryAgain => "{error}. Please enter another tag.", "{error}。请输入其他标签。";
+    ConfirmTagCreation => "Confirm tag creation: {tag} (Y/n): ", "确认创建标签：{tag} (Y/n): ";
+    ConfirmPushTag => "Tag '{tag}' created locally. Push to remote '{remote}'",
+        "已在本地创建标签 '{tag}'。推送到远程仓库 '{remote}'？";
+    UnknownHostKey => "The authenticity of host '{host}' can't be established.\nKey fingerprint is {fingerprint}.",
+        "无法确认主机 '{host}' 的真实性。\n密钥指纹为 {fingerprint}。";
+    AcceptHostKey => "Accept and save to known_hosts? (y/N): ", "接受并保存到 known_hosts？(y/N): ";
+    HttpsCredentialsNeeded => "{url} needs a username and password (or access token).",
+        "{url} 需要用户名和密码（或访问令牌）。";
+    HttpsUsername => "Username: ", "用户名: ";
+    HttpsUsernameWithDefault => "Username [{username}]: ", "用户名 [{username}]: ";
+    HttpsPassword => "Password or token: ", "密码或令牌: ";
+    ContinueWithLocalData => "Continue using local data?", "继续使用本地数据？";
+    ContinueWithNoNewCommits => "Continue with no new commits?", "没有新的提交，是否继续？";
+    ContinueWithUnreachableTag => "Continue with the rewritten history?", "基于改写后的历史继续？";
+    ContinueWithLightweightTags => "Create lightweight tags instead?", "改为创建轻量标签？";
+    ContinueWithOlderVersion => "Create a tag that is not newer than the current version?", "创建不比当前版本新的标签？";
+    VersionDowngradeBlocked => "Refusing to create a tag that is not newer than the current version (behavior.on_version_downgrade = \"block\")",
+        "拒绝创建不比当前版本新的标签（behavior.on_version_downgrade = \"block\"）";
+    WarningIsFatal => "Stopping on this warning (warnings.{key} = \"fail\")",
+        "因此警告而停止（warnings.{key} = \"fail\"）";
+    UseInitialVersion => "Use initial version v0.1.0 and continue?", "使用初始版本 v0.1.0 并继续？";
+    ConfirmMajorVersion => "Type the new major version ({major}) to confirm this breaking release: ",
+        "输入新的主版本号（{major}）以确认此不兼容发布：";
+
+    // Tag format validation
+    PatternMultiplePlaceholders => "Invalid pattern '{pattern}': should have exactly one {version} placeholder",
+        "无效的模式 '{pattern}'：必须且只能包含一个 {version} 占位符";
+    TagMissingPrefix => "Tag '{tag}' does not match pattern '{pattern}': missing prefix '{prefix}'",
+        "标签 '{tag}' 与模式 '{pattern}' 不匹配：缺少前缀 '{prefix}'";
+    TagMissingSuffix => "Tag '{tag}' does not match pattern '{pattern}': missing suffix '{suffix}'",
+        "标签 '{tag}' 与模式 '{pattern}' 不匹配：缺少后缀 '{suffix}'";
+    TagInvalidVersion => "Tag '{tag}' has invalid version format '{version}'",
+        "标签 '{tag}' 的版本格式 '{version}' 无效";
+    TagPolicyViolation => "Tag '{tag}' does not match behavior.tag_validation_regex '{regex}'",
+        "标签 '{tag}' 不符合 behavior.tag_validation_regex '{regex}'";
+
+    // Output formatting
+    ErrorLabel => "ERROR:", "错误：";
+    WarningLabel => "WARNING:", "警告：";
+    HintLabel => "Hint:", "提示：";
+    AnalyzingCommits => "Analyzing commits on branch '{branch}'", "正在分析分支 '{branch}' 上的提交";
+    LastCommits => "Last {count} commits:", "最近 {count} 个提交：";
+    MoreCommits => "... and {count} more commits", "……以及另外 {count} 个提交";
+    ProposedTagChange => "Proposed Tag Change:", "建议的标签变更：";
+    TagFrom => "From:", "从：";
+    TagTo => "To:  ", "到：";
+    InitialTag => "Initial Tag:", "初始标签：";
+    NewTag => "New tag:", "新标签：";
+    ConfiguredBranches => "Configured branches:", "已配置的分支：";
+    BranchDashboardLine => "latest {tag}, {count} commit(s) since, next {next}",
+        "最新 {tag}，此后 {count} 个提交，下一个 {next}";
+    BranchMissing => "not in this repository", "不在此仓库中";
+    ManualPushInstruction => "To push this tag later, run:", "稍后推送此标签，请运行：";
+    BreakingChangesHeader => "Breaking changes (major version bump):", "不兼容变更（主版本号升级）：";
+    NonConformingHeader => "Non-conforming commits (warning only):", "不符合规范的提交（仅警告）：";
+    SubjectTooLong => "subject is {length} characters (max {max})", "标题长度为 {length} 个字符（上限 {max}）";
+    MissingType => "missing conventional commit type", "缺少约定式提交类型";
+    UnknownType => "unknown type '{type}'", "未知的类型 '{type}'";
+    RuleSemverTrailer => "Semver: trailer", "Semver: 尾注";
+    RuleBreakingChange => "breaking change marker (! or BREAKING CHANGE:)", "不兼容变更标记（! 或 BREAKING CHANGE:）";
+    RuleDeprecation => "deprecation (conventional_commits.deprecation_bump)", "弃用（conventional_commits.deprecation_bump）";
+    RuleScope => "scope '{scope}' (conventional_commits.scopes)", "范围 '{scope}'（conventional_commits.scopes）";
+    RuleCommitType => "commit type '{type}'", "提交类型 '{type}'";
+    RuleMajorKeyword => "major keyword '{keyword}' (counted as a feature)", "major 关键字 '{keyword}'（按新功能计）";
+    RuleMinorKeyword => "minor keyword '{keyword}'", "minor 关键字 '{keyword}'";
+    ExplainHeader => "Classification of: {subject}", "提交分类：{subject}";
+    ExplainNotConventional => "not a conventional commit, parsed as type '{type}'", "不是约定式提交，按类型 '{type}' 解析";
+    ExplainType => "type: {type}", "类型：{type}";
+    ExplainScope => "scope: {scope}", "范围：{scope}";
+    ExplainBreaking => "breaking: {breaking}", "不兼容变更：{breaking}";
+    ExplainDeprecation => "deprecates: {note}", "弃用：{note}";
+    ExplainBump => "bump: {bump}, decided by {rule}", "版本变更：{bump}，依据 {rule}";
+    ExplainNoBump => "bump: none on its own (a range of such commits is a patch release)",
+        "版本变更：本身不影响版本（仅含此类提交的范围按补丁版本发布）";
+    ExplainScopeIgnored => "scope '{scope}' is ignored (conventional_commits.scopes.ignore)",
+        "范围 '{scope}' 已被忽略（conventional_commits.scopes.ignore）";
+    SimulateNoBranch => "No configured branch has {commit} in its history; choose one with --branch",
+        "没有已配置的分支包含 {commit}，请用 --branch 指定分支";
+    ExplainIgnoredPaths => "only changes ignored paths, so it doesn't count toward a release",
+        "仅修改了被忽略的路径，不计入发布";
+    BatchReleasingBranch => "Releasing branch '{branch}' ({index}/{total})", "正在发布分支 '{branch}'（{index}/{total}）";
+    BatchConfirm => "Release branches {branches} one after the other?", "依次发布分支 {branches}？";
+    ReleaseOptionsBeforeSubcommand => "Release options go after `publish` or `preview` (e.g. git-publish publish --dry-run)",
+        "发布选项需放在 `publish` 或 `preview` 之后（例如 git-publish publish --dry-run）";
+    SeveralBranchesNotSupported => "Only releases can take --branch more than once", "只有发布可以多次指定 --branch";
+    TuiNotBuilt => "This git-publish was built without --tui (the \"tui\" cargo feature)",
+        "此 git-publish 构建时未包含 --tui（\"tui\" cargo 特性）";
+    SeveralBranchesConflict => "--branch can't be given more than once with --tui, --json, --push-only, --package or --from-tag",
+        "多次指定 --branch 时不能同时使用 --tui、--json、--push-only、--package 或 --from-tag";
+    BatchSummaryHeader => "Release summary:", "发布汇总：";
+    BatchColumnBranch => "Branch", "分支";
+    BatchColumnTag => "Tag", "标签";
+    BatchColumnResult => "Result", "结果";
+    SelfUpdateUpToDate => "git-publish {version} is the latest release", "git-publish {version} 已是最新版本";
+    SelfUpdateAvailable => "git-publish {latest} is available (installed: {current}): {url}",
+        "git-publish {latest} 已发布（当前安装：{current}）：{url}";
+    SelfUpdateHowTo => "Install it with: git-publish self-update --install (release binaries) or cargo install --git https://github.com/chenmijiang/git-publish-rust",
+        "安装方式：git-publish self-update --install（发布的二进制文件）或 cargo install --git https://github.com/chenmijiang/git-publish-rust";
+    SelfUpdateNoAsset => "Release {version} has no binary for {platform}; install it with cargo install --git https://github.com/chenmijiang/git-publish-rust",
+        "版本 {version} 没有适用于 {platform} 的二进制文件；请使用 cargo install --git https://github.com/chenmijiang/git-publish-rust 安装";
+    SelfUpdateNoChecksum => "Release {version} publishes no checksum for {asset}, so it can't be verified; install it with cargo install --git https://github.com/chenmijiang/git-publish-rust",
+        "版本 {version} 未发布 {asset} 的校验和，无法验证；请使用 cargo install --git https://github.com/chenmijiang/git-publish-rust 安装";
+    SelfUpdateInstalled => "Installed git-publish {version} to {path}", "已将 git-publish {version} 安装到 {path}";
+    AnalysisBump => "Version bump: {bump} ({count} commits)", "版本变更：{bump}（{count} 个提交）";
+    AnalysisNoMessages => "No commit messages to analyze", "没有可分析的提交信息";
+    DashboardWritten => "Wrote the release dashboard to {path}", "已将发布看板写入 {path}";
+    DoctorSummary => "{passed} passed, {warnings} warnings, {failed} failed",
+        "{passed} 项通过，{warnings} 项警告，{failed} 项失败";
+    CheckPass => "PASS", "通过";
+    CheckWarn => "WARN", "警告";
+    CheckFail => "FAIL", "失败";
+    DoctorConfig => "config", "配置";
+    DoctorConfigValid => "configuration is valid", "配置有效";
+    DoctorRepository => "repository", "仓库";
+    DoctorInsideRepository => "inside a git repository", "位于 git 仓库中";
+    DoctorHook => "hook '{hook}'", "钩子 '{hook}'";
+    DoctorBranchHook => "hook '{hook}' on '{branch}'", "分支 '{branch}' 上的钩子 '{hook}'";
+    DoctorHookExecutable => "discovered, executable", "已发现，可执行";
+    DoctorHookNotExecutable => "not executable, will be skipped", "不可执行，将被跳过";
+    DoctorScriptExecutable => "{script} is executable", "{script} 可执行";
+    DoctorScriptInterpreter => "{script} runs with {interpreter}", "{script} 使用 {interpreter} 运行";
+    DoctorRemotes => "remotes", "远程仓库";
+    DoctorNoRemotes => "no remotes configured", "未配置远程仓库";
+    DoctorRemote => "remote '{remote}'", "远程仓库 '{remote}'";
+    DoctorRemoteReachable => "reachable, credentials accepted", "可访问，凭据有效";
+    DoctorBranches => "branches", "分支";
+    DoctorNoBranches => "no branches configured for tagging", "未配置用于打标签的分支";
+    DoctorBranch => "branch '{branch}'", "分支 '{branch}'";
+    DoctorBranchNotConfigured => "not configured for tagging", "未配置打标签";
+    DoctorBranchMissing => "configured but not found locally", "已配置但本地不存在";
+    DoctorBranchExists => "exists, pattern {pattern}", "存在，模式为 {pattern}";
+    DoctorTags => "tags on '{branch}'", "分支 '{branch}' 上的标签";
+    DoctorNoTags => "no tags yet", "尚无标签";
+    DoctorLatestTag => "latest tag {tag} parses as {version}", "最新标签 {tag} 解析为 {version}";
+    DoctorUnparsableTag => "latest tag {tag} is not parseable: {reason}", "最新标签 {tag} 无法解析：{reason}";
+    TimingsHeader => "Timings:", "耗时：";
+    TuiBranches => "Branches", "分支";
+    TuiNoTag => "none", "无";
+    TuiBump => "Bump: {bump} (commits suggest {suggested})", "升级：{bump}（提交建议 {suggested}）";
+    TuiPreRelease => "Pre-release: {identifier}", "预发布：{identifier}";
+    TuiCommits => "Commits since {tag} ({count})", "自 {tag} 以来的提交（{count}）";
+    TuiKeys => "←/→ branch  ↑/↓ scroll  M/m/p bump  a auto  r pre-release  Enter release  q quit",
+        "←/→ 分支  ↑/↓ 滚动  M/m/p 升级  a 自动  r 预发布  Enter 发布  q 退出";
+
+    // Boundary warnings
+    NoNewCommits => "No new commits since tag '{tag}' (current: {commit})",
+        "自标签 '{tag}' 以来没有新的提交（当前：{commit}）";
+    UnparsableTag => "Cannot parse tag '{tag}': {reason}", "无法解析标签 '{tag}'：{reason}";
+    UnrecognizedVersionFormat => "Version number format not recognized", "无法识别的版本号格式";
+    FetchAuthenticationFailed => "Authentication failed when fetching from remote '{remote}'",
+        "从远程仓库 '{remote}' 拉取时认证失败";
+    FetchNetworkUnreachable => "Remote '{remote}' is unreachable", "无法连接远程仓库 '{remote}'";
+    FetchHostKeyVerificationFailed => "Host key verification failed when fetching from remote '{remote}'",
+        "从远程仓库 '{remote}' 拉取时主机密钥验证失败";
+    RemoteConnectionFailed => "Could not connect to remote '{remote}': {error}",
+        "无法连接远程仓库 '{remote}'：{error}";
+    ManifestVersionMismatch => "{manifest} declares version {manifest_version}, but the latest tag {tag} is version {tag_version}",
+        "{manifest} 声明的版本为 {manifest_version}，但最新标签 {tag} 的版本为 {tag_version}";
+    HintManifestMismatch => "Choose which version to continue from; with version_source = \"manifest\" the manifest is then updated to match the new tag.",
+        "请选择以哪个版本为基础继续；若 version_source = \"manifest\"，清单文件随后会更新为与新标签一致。";
+    TagMetadataMismatch => "Tag {tag} is version {tag_version}, but its message records version {recorded_version}",
+        "标签 {tag} 的版本为 {tag_version}，但其消息中记录的版本为 {recorded_version}";
+    HintTagMetadataMismatch => "The new version is bumped from the tag name; check that the tag was created for the intended release.",
+        "新版本将基于标签名递增；请确认该标签是为预期的版本创建的。";
+    TagUnreachable => "Tag {tag} is not in the history of branch '{branch}'; the branch was rewritten after the tag was created, so already released commits may count as new",
+        "标签 {tag} 不在分支 '{branch}' 的历史中；该分支在创建标签后被改写，已发布的提交可能会被视为新提交";
+    HintTagUnreachable => "The branch was likely rebased or force-pushed. Check that the right branch is selected, fetch the latest history, or move the tag onto the rewritten commit.",
+        "该分支可能经过了变基或强制推送。请确认选择了正确的分支并拉取最新历史，或将标签移动到改写后的提交上。";
+    CommitLimitExceeded => "{count} commits since the latest tag; only the most recent {limit} are analyzed",
+        "自最新标签以来有 {count} 个提交；仅分析最近的 {limit} 个";
+    HintCommitLimit => "Tag releases more often, or raise [behavior] max_commits_analyzed (0 analyzes every commit).",
+        "请更频繁地发布标签，或调高 [behavior] max_commits_analyzed（设为 0 则分析所有提交）。";
+    VersionNotIncreased => "Tag '{tag}' has version {version}, which is not newer than the current version {current}",
+        "标签 '{tag}' 的版本 {version} 并不比当前版本 {current} 新";
+    HintVersionNotIncreased => "Versions compare numerically (1.10.0 is newer than 1.2.3). Pick a higher version, or set [behavior] on_version_downgrade = \"block\" to always refuse.",
+        "版本按数值比较（1.10.0 比 1.2.3 新）。请选择更高的版本，或设置 [behavior] on_version_downgrade = \"block\" 以始终拒绝。";
+    AnonymousCommits => "{count} commit(s) since the latest tag have no author name or email: {commits}",
+        "自最新标签以来有 {count} 个提交缺少作者名称或邮箱：{commits}";
+    HintAnonymousCommits => "Such commits usually come from bots or squash tools. Check their configured identity, or amend the commits before releasing.",
+        "这类提交通常来自机器人或压缩合并工具。请检查其配置的身份，或在发布前修改这些提交。";
+    MissingGitIdentity => "No git identity is configured (user.name / user.email), so annotated tags would have no tagger",
+        "未配置 git 身份（user.name / user.email），附注标签将缺少标签创建者";
+    HintMissingGitIdentity => "Set it with: git config user.name \"Your Name\" and git config user.email you@example.com (add --global for every repository).",
+        "请通过以下命令设置：git config user.name \"Your Name\" 以及 git config user.email you@example.com（加上 --global 可应用于所有仓库）。";
+    PreviousBreakingChanges => "{tag} recorded {count} breaking change(s):", "{tag} 记录了 {count} 项不兼容变更：";
+    HintAuthentication => "Check that your SSH key is loaded (ssh-add -l), or for HTTPS that GIT_PUBLISH_TOKEN (or GITHUB_TOKEN) or your credential helper has a valid token.",
+        "请确认 SSH 密钥已加载（ssh-add -l）；若使用 HTTPS，请确认 GIT_PUBLISH_TOKEN（或 GITHUB_TOKEN）或凭据助手中的令牌仍然有效。";
+    HintNetwork => "Check your network connection, VPN, or proxy settings and that the remote URL is correct.",
+        "请检查网络连接、VPN 或代理设置，并确认远程仓库地址正确。";
+    HintHostKey => "Verify the host fingerprint and add it to ~/.ssh/known_hosts (e.g. ssh -T git@<host>).",
+        "请核对主机指纹并将其加入 ~/.ssh/known_hosts（例如 ssh -T git@<host>）。";
+
+    // config migrate
+    NoConfigToMigrate => "No configuration file found, nothing to migrate", "未找到配置文件，无需迁移";
+    ConfigUpToDate => "{path} is already up to date", "{path} 已是最新";
+    ConfigKeyRenamed => "Renamed `{key}` to `{replacement}`", "已将 `{key}` 重命名为 `{replacement}`";
+    ConfigMigrated => "Migrated {path} from config_version {from} to {to}",
+        "已将 {path} 从 config_version {from} 迁移到 {to}";
+
+    // init
+    ConfigAlreadyExists => "{path} already exists, nothing to initialize", "{path} 已存在，无需初始化";
+    ConfigCreated => "Created {path}, edit it to adjust the branches and tag patterns",
+        "已创建 {path}，可编辑其中的分支与标签模式";
+    NoConfigFound => "No configuration found, the built-in defaults would be used",
+        "未找到配置，将使用内置默认配置";
+    OfferInit => "Create gitpublish.toml for this repository now?", "现在为此仓库创建 gitpublish.toml？";
+    NotInRepository => "Not inside a git repository", "当前不在 git 仓库中";
+    InitDetectedPattern => "Branch '{branch}': latest release tag {tag}, pattern {pattern}",
+        "分支 '{branch}'：最新发布标签 {tag}，模式 {pattern}";
+    InitTagBranch => "Tag releases on branch '{branch}'?", "是否在分支 '{branch}' 上打发布标签？";
+    InitTagPrefix => "Tag prefix for '{branch}' (text before the version, may be empty)",
+        "'{branch}' 的标签前缀（版本号前的文字，可为空）";
+    InitInvalidPrefix => "Invalid prefix: {error}", "无效的前缀：{error}";
+    InitNoBranches => "No branch selected, nothing written", "未选择任何分支，未写入任何内容";
+
+    // Publish workflow status
+    AnalyzingSinceMergeBase => "Analyzing only the commits since the merge base with {branch} ({commit})",
+        "仅分析与 {branch} 的合并基点（{commit}）之后的提交";
+    MergeBaseNotFound => "No merge base of {branch} and {other}: {error}", "找不到 {branch} 与 {other} 的合并基点：{error}";
+    AnalyzingSinceTag => "Analyzing the commits since {tag} instead of the latest tag",
+        "分析 {tag} 之后的提交，而非最新标签之后的提交";
+    FromTagNotFound => "Tag '{tag}' given with --from-tag not found", "未找到 --from-tag 指定的标签 '{tag}'";
+    AnalyzingSince => "Analyzing the commits after {since} instead of those since the latest tag",
+        "分析 {since} 之后的提交，而非最新标签之后的提交";
+    SinceNotFound => "Can't start the analysis at '{since}': {error}", "无法从 '{since}' 开始分析：{error}";
+    PreReleaseDisabled => "Pre-releases are disabled; set [prerelease] enabled = true to publish one",
+        "预发布功能未启用；请设置 [prerelease] enabled = true 后再发布预发布版本";
+    NothingToPromote => "The latest tag ({tag}) is not a pre-release, so there is nothing to promote",
+        "最新标签（{tag}）不是预发布版本，没有可升级为正式版的内容";
+    BumpOverridden => "Using a {bump} bump (--bump) instead of the {analyzed} bump the commits call for",
+        "使用 --bump 指定的 {bump} 递增，而非提交所需的 {analyzed} 递增";
+    ChannelPreReleaseOnly => "Only {channel} channel commits since the latest tag, proposing a {channel} pre-release",
+        "自最新标签以来只有 {channel} 通道的提交，建议发布 {channel} 预发布版本";
+    ConfigLoadError => "Error loading config: {error}", "加载配置出错：{error}";
+    RepositoryError => "Git repository error: {error}", "Git 仓库错误：{error}";
+    IgnoredConfigKey => "Configuration entry ignored: {key}", "已忽略配置项：{key}";
+    DeprecatedConfigKey => "`{key}` is deprecated, use `{replacement}` (run `git-publish config migrate` to update the configuration)",
+        "`{key}` 已弃用，请改用 `{replacement}`（运行 `git-publish config migrate` 更新配置）";
+    NoBranchesConfigured => "No branches configured for tagging in gitpublish.toml",
+        "gitpublish.toml 中没有配置可打标签的分支";
+    ManifestNotFound => "version_source is \"manifest\" but no Cargo.toml or package.json was found in the repository root",
+        "version_source 为 \"manifest\"，但仓库根目录中没有 Cargo.toml 或 package.json";
+    ManifestReadFailed => "Failed to read the version from {path}: {error}", "无法从 {path} 读取版本：{error}";
+    ManifestNotUpdated => "Tag {tag} has no semantic version; {path} is left unchanged",
+        "标签 {tag} 不是语义化版本；{path} 保持不变";
+    UpdatingManifest => "Setting version in {path} to {version}", "正在将 {path} 中的版本设为 {version}";
+    ManifestUpdateFailed => "Failed to update {path}: {error}", "更新 {path} 失败：{error}";
+    UpdatingChangelog => "Adding the release notes to {path}", "正在将发布说明写入 {path}";
+    ChangelogUpdateFailed => "Failed to update {path}: {error}", "更新 {path} 失败：{error}";
+    NoWorkingTree => "the repository has no working tree", "仓库没有工作区";
+    ReleaseCommitted => "Committed {paths} as {commit}", "已提交 {paths}：{commit}";
+    ReleaseCommitFailed => "Failed to commit {paths}: {error}", "提交 {paths} 失败：{error}";
+    PushingBranch => "Pushing branch: {branch} to remote '{remote}'", "正在推送分支 {branch} 到远程仓库 '{remote}'";
+    ReleaseCommitNotPushed => "The release commit on {branch} is not pushed yet; push the branch to '{remote}' as well",
+        "{branch} 上的发布提交尚未推送；请同时将该分支推送到 '{remote}'";
+    TagNotFoundLocally => "Tag '{tag}' does not exist locally; create it first, e.g. with --tag-only",
+        "本地不存在标签 '{tag}'；请先创建，例如使用 --tag-only";
+    TagBranchUnknown => "Tag '{tag}' matches no configured branch's tag pattern; pass --branch",
+        "标签 '{tag}' 不匹配任何已配置分支的标签格式；请使用 --branch 指定分支";
+    TagAlreadyExists => "Tag '{tag}' already exists (set behavior.on_existing_tag to skip, increment or replace)",
+        "标签 '{tag}' 已存在（可将 behavior.on_existing_tag 设为 skip、increment 或 replace）";
+    ExistingTagSkipped => "Tag '{tag}' already exists, nothing to do", "标签 '{tag}' 已存在，无需操作";
+    ExistingTagIncremented => "Tag '{tag}' already exists, using {next} instead", "标签 '{tag}' 已存在，改用 {next}";
+    ExistingTagReplaced => "Tag '{tag}' already exists and will be moved to the branch head and force-pushed",
+        "标签 '{tag}' 已存在，将移动到分支最新提交并强制推送";
+    ReplacingTags => "{count} existing tag(s) will be moved to the branch head:",
+        "{count} 个已存在的标签将被移动到分支最新提交：";
+    ReplacedTagLocal => "local", "本地";
+    ConfirmReplaceTags => "Move these tags and force-push them to {remote}?", "移动这些标签并强制推送到 {remote}？";
+    ReplaceTagsNeedsFlag => "Existing tags are only replaced after confirmation; pass --replace-tags to replace them without asking",
+        "已存在的标签需要确认后才会被替换；如需无提示替换，请使用 --replace-tags";
+    AutoSelectedBranch => "Using branch '{branch}', the only configured branch in this repository",
+        "使用分支 '{branch}'，它是本仓库中唯一已配置的分支";
+    PackageNotConfigured => "Package '{package}' is not configured under [packages]", "包 '{package}' 未在 [packages] 中配置";
+    NoCommitsYet => "The repository has no commits yet: commit something on '{branch}' first, then run git-publish to create the first tag",
+        "仓库还没有任何提交：请先在 '{branch}' 上提交，再运行 git-publish 创建第一个标签";
+    BranchNotFound => "Branch '{branch}' does not exist locally or on any remote. Existing branches: {branches}",
+        "分支 '{branch}' 在本地和所有远程仓库中都不存在。现有分支：{branches}";
+    BranchNotConfigured => "Branch '{branch}' is not configured for tagging", "分支 '{branch}' 未配置打标签";
+    AlsoTagging => "Also tagging the same commit: {tags}", "同时为同一提交打标签：{tags}";
+    NoRemotesConfigured => "No remotes configured in this repository", "此仓库没有配置远程仓库";
+    CheckingRemote => "Checking connection to '{remote}'...", "正在检查与 '{remote}' 的连接……";
+    ContinueOffline => "Continue offline? Fetching is skipped and pushes are queued for `git-publish flush`.",
+        "是否离线继续？将跳过拉取，推送会排队等待 `git-publish flush`。";
+    WorkingOffline => "Working offline: using local data for '{remote}'", "离线模式：使用 '{remote}' 的本地数据";
+    Fetching => "Fetching latest data from '{remote}'...", "正在从 '{remote}' 拉取最新数据……";
+    AlreadyFetched => "Using the data fetched from '{remote}' at the start of the batch", "使用批量发布开始时从 '{remote}' 拉取的数据";
+    Fetched => "Successfully fetched latest data from '{remote}'", "已从 '{remote}' 拉取最新数据";
+    FetchFailedUsingLocal => "Warning: Could not fetch from remote '{remote}': {error}. Using local branch data.",
+        "警告：无法从远程仓库 '{remote}' 拉取：{error}。将使用本地分支数据。";
+    BreakingNeedsAcceptance => "Major version bump requires --accept-breaking or typing the new major version",
+        "主版本号升级需要 --accept-breaking 或输入新的主版本号确认";
+    BreakingForceWithoutAccept => "Refusing major version bump with --force; pass --accept-breaking to confirm",
+        "在 --force 模式下拒绝主版本号升级；请添加 --accept-breaking 以确认";
+    OperationCancelled => "Operation cancelled by user.", "操作已被用户取消。";
+    TagCreationCancelled => "Tag creation cancelled by user.", "用户已取消创建标签。";
+    DryRunHeader => "Dry run mode:", "演练模式：";
+    DryRunCreateTag => "  Step 1: Will create local tag: {tag}", "  第 1 步：将创建本地标签：{tag}";
+    DryRunAskPush => "  Step 2: Will ask whether to push tag to remote", "  第 2 步：将询问是否推送标签到远程仓库";
+    DryRunPush => "  Step 3: (Optional) Push {tag} to '{remote}'", "  第 3 步：（可选）推送 {tag} 到 '{remote}'";
+    DryRunTagOnly => "  Step 2: Stop without pushing (--tag-only)", "  第 2 步：不推送，直接结束（--tag-only）";
+    DryRunUpdateManifest => "  Before tagging: Will set the version in {path} to {version} and commit it",
+        "  打标签前：将把 {path} 中的版本设为 {version} 并提交";
+    DryRunUpdateChangelog => "  Before tagging: Will add the release notes to {path} and commit them",
+        "  打标签前：将把发布说明写入 {path} 并提交";
+    CreatingTag => "Creating tag: {tag}", "正在创建标签：{tag}";
+    CreatedTag => "Created tag: {tag}", "已创建标签：{tag}";
+    CreateTagFailed => "Failed to create tag '{tag}': {error}", "创建标签 '{tag}' 失败：{error}";
+    PushingTag => "Pushing tag: {tag} to remote '{remote}'", "正在推送标签 {tag} 到远程仓库 '{remote}'";
+    PushedTag => "Pushed tag: {tag} to remote", "已推送标签 {tag} 到远程仓库";
+    PushTagFailed => "Failed to push tag '{tag}': {error}", "推送标签 '{tag}' 失败：{error}";
+
+    // annotate
+    LayerTargetNotFound => "Tag '{tag}' does not exist locally", "本地不存在标签 '{tag}'";
+    LayerTagExists => "Tag '{tag}' already exists; pass --replace to move it", "标签 '{tag}' 已存在；使用 --replace 移动它";
+    LayerTargetLightweight => "Tag '{tag}' is lightweight and has no tag object; the new tag points at its commit",
+        "标签 '{tag}' 是轻量标签，没有标签对象；新标签将指向其提交";
+    LayerTagDefaultMessage => "{tag}: metadata for {target}", "{tag}：{target} 的附加信息";
+    LayerTagFailed => "Failed to create tag '{tag}': {error}", "创建标签 '{tag}' 失败：{error}";
+    LayerTagCreated => "Created tag {tag} on {target}", "已在 {target} 上创建标签 {tag}";
+    RollbackNoTags => "No release tags to roll back", "没有可回滚的发布标签";
+    RollbackTagNotFound => "Tag '{tag}' does not exist locally; pass --remote-only to delete it on the remote only",
+        "本地不存在标签 '{tag}'；使用 --remote-only 仅删除远程标签";
+    RollbackNotLatest => "Tag '{tag}' is older than the latest tag '{latest}'; pass --force to delete it anyway",
+        "标签 '{tag}' 早于最新标签 '{latest}'；使用 --force 强制删除";
+    RollbackRefRemote => "To delete: {ref} on remote '{remote}'", "将删除：远程仓库 '{remote}' 上的 {ref}";
+    RollbackRefLocal => "To delete: local {ref}", "将删除：本地 {ref}";
+    RollbackConfirm => "Delete tag {tag} locally and on remote '{remote}'?", "删除本地及远程仓库 '{remote}' 上的标签 {tag}？";
+    RollbackConfirmLocal => "Delete local tag {tag}?", "删除本地标签 {tag}？";
+    RollbackConfirmRemote => "Delete tag {tag} on remote '{remote}' (keeping the local tag)?",
+        "删除远程仓库 '{remote}' 上的标签 {tag}（保留本地标签）？";
+    RollbackCancelled => "Rollback cancelled", "已取消回滚";
+    RollbackRemoteFailed => "Failed to delete tag '{tag}' on remote '{remote}': {error}",
+        "删除远程仓库 '{remote}' 上的标签 '{tag}' 失败：{error}";
+    RollbackRemoteDeleted => "Deleted tag {tag} on remote '{remote}'", "已删除远程仓库 '{remote}' 上的标签 {tag}";
+    RollbackLocalFailed => "Failed to delete local tag '{tag}': {error}", "删除本地标签 '{tag}' 失败：{error}";
+    RollbackLocalDeleted => "Deleted local tag {tag}", "已删除本地标签 {tag}";
+    PublishedTag => "Successfully published tag {tag} for branch {branch}", "已为分支 {branch} 发布标签 {tag}";
+    CreatedTagLocally => "Tag {tag} created locally for branch {branch}", "已在本地为分支 {branch} 创建标签 {tag}";
+    PushQueued => "Remote '{remote}' is unreachable; queued push of {tag}. Run `git-publish flush` once back online.",
+        "无法连接远程仓库 '{remote}'，已将 {tag} 的推送加入队列。恢复联网后请运行 `git-publish flush`。";
+    CreatedTagPushQueued => "Tag {tag} created locally for branch {branch} (push queued)",
+        "已在本地为分支 {branch} 创建标签 {tag}（推送已排队）";
+    StepSummaryFailed => "Could not write the step summary to {path}: {error}",
+        "无法将步骤摘要写入 {path}：{error}";
+    RunningHook => "Running {hook} hook: {script}", "正在运行 {hook} 钩子：{script}";
+    HookSucceeded => "{hook} hook completed", "{hook} 钩子已完成";
+    HookWarning => "{hook} hook failed (continuing): {error}", "{hook} 钩子失败（继续执行）：{error}";
+    HookFailed => "{hook} hook failed: {error}", "{hook} 钩子失败：{error}";
+    HookCommandLine => "$ {command}", "$ {command}";
+    DryRunHooks => "  Hooks that would run:", "  将运行的钩子：";
+    DryRunHookEnv => "  Hook environment:", "  钩子环境变量：";
+    HookSummaryHeader => "Hooks:", "钩子：";
+    HookSummaryFailed => "failed", "失败";
+    HookSummaryIgnored => "failed, continued", "失败，已继续";
+    Interrupted => "Interrupted", "已中断";
+    PublishCancelled => "Publishing was cancelled", "发布已取消";
+    InterruptRolledBack => "Removed tag {tag} created by this run", "已删除本次运行创建的标签 {tag}";
+    InterruptKeptTag => "Kept unpublished tag {tag}", "已保留未发布的标签 {tag}";
+    NetworkTimeout => "Remote '{remote}' made no progress for {secs}s, giving up", "远程仓库 '{remote}' 在 {secs} 秒内没有任何进展，已放弃";
+    InterruptRollbackFailed => "Failed to remove the tag created by this run: {error}", "删除本次运行创建的标签失败：{error}";
+    NoQueuedPushes => "No queued tag pushes", "没有待推送的标签";
+    DryRunQueuedPushes => "Dry run mode: would push queued tags:", "演练模式：将推送以下排队的标签：";
+    PushingQueuedTag => "Pushing queued tag: {tag} to remote '{remote}'", "正在推送排队的标签 {tag} 到远程仓库 '{remote}'";
+    AllQueuedPushed => "All queued tags pushed", "所有排队的标签均已推送";
+    QueuedPushesPending => "{count} queued tag push(es) still pending", "仍有 {count} 个排队的标签推送未完成";
+    LatestTagFailed => "Failed to get latest tag on branch '{branch}': {error}",
+        "获取分支 '{branch}' 上的最新标签失败：{error}";
+    CommitsSinceTagFailed => "Failed to get commits since tag on branch '{branch}': {error}",
+        "获取分支 '{branch}' 上自该标签以来的提交失败：{error}";
+    ListRemotesFailed => "Failed to list remotes: {error}", "列出远程仓库失败：{error}";
+    ValidateRemoteFailed => "Failed to validate remote", "校验远程仓库失败";
+    RemoteNotFound => "Remote '{remote}' not found. Available remotes: {available}",
+        "未找到远程仓库 '{remote}'。可用的远程仓库：{available}";
+
+    // Step summary and release dashboard
+    SummaryReleased => "Released `{tag}`", "已发布 `{tag}`";
+    SummaryQueued => "Tagged `{tag}` (push queued)", "已创建标签 `{tag}`（推送已排队）";
+    SummaryCreatedLocally => "Tagged `{tag}` (not pushed)", "已创建标签 `{tag}`（未推送）";
+    SummaryPreviousNotes => "Previous release notes", "上一版本的发布说明";
+    ColumnBranch => "Branch", "分支";
+    ColumnRemote => "Remote", "远程仓库";
+    ColumnPreviousTag => "Previous tag", "上一个标签";
+    ColumnBump => "Bump", "版本变更";
+    ColumnCommits => "Commits", "提交数";
+    ColumnTag => "Tag", "标签";
+    ColumnDate => "Date", "日期";
+    ColumnContributors => "Contributors", "贡献者";
+    ColumnReleases => "Releases", "发布次数";
+    ColumnFirst => "First", "首次";
+    ColumnLatest => "Latest", "最近";
+    ColumnDaysBetween => "Days between", "间隔天数";
+    ColumnCommitsPerRelease => "Commits per release", "每次发布的提交数";
+    ColumnMajor => "Major", "主版本";
+    ColumnMinor => "Minor", "次版本";
+    ColumnPatch => "Patch", "修订版本";
+    ColumnPrerelease => "Pre-release", "预发布";
+    DashboardTitle => "Release dashboard", "发布看板";
+    DashboardMeta => "Releases from {since} to {until}: {releases} releases, {commits} commits, {contributors} contributors",
+        "{since} 至 {until} 的发布：{releases} 次发布，{commits} 个提交，{contributors} 位贡献者";
+    DashboardStatistics => "Statistics", "统计";
+    DashboardNoBranches => "No branches.", "没有分支。";
+    DashboardReleases => "Releases", "发布";
+    DashboardNoReleases => "No releases.", "没有发布。";
+    DashboardChangelogs => "Recent changelogs", "最近的变更日志";
+    DashboardNoChanges => "No changes.", "没有变更。";
+}
+
+/// Look up a message in the current locale without substituting placeholders
+pub fn text(msg: Msg) -> &'static str {
+    msg.template(locale())
+}
+
+/// Look up a message in the current locale and fill in `{name}` placeholders
+///
+/// # Arguments
+/// * `msg` - Message key
+/// * `args` - Placeholder names and their values
+pub fn tr(msg: Msg, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut rendered = text(msg).to_string();
+    for (name, value) in args {
+        rendered = rendered.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_catalog_complete_and_consistent() {
+        for msg in Msg::ALL {
+            let en = msg.template(Locale::En);
+            let zh = msg.template(Locale::ZhCn);
+            assert!(!en.is_empty(), "{:?} has no English text", msg);
+            assert!(!zh.is_empty(), "{:?} has no zh-CN text", msg);
+            assert_eq!(
+                placeholders(en),
+                placeholders(zh),
+                "{:?} placeholders differ between locales",
+                msg
+            );
+        }
+    }
+
+    #[test]
+    fn test_locale_from_str() {
+        assert_eq!("en".parse::<Locale>(), Ok(Locale::En));
+        assert_eq!("en_US".parse::<Locale>(), Ok(Locale::En));
+        assert_eq!("zh-CN".parse::<Locale>(), Ok(Locale::ZhCn));
+        assert_eq!("zh_CN".parse::<Locale>(), Ok(Locale::ZhCn));
+        assert!("fr".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn test_tr_substitutes_placeholders() {
+        let rendered = Msg::ConfirmPushTag.template(Locale::En);
+        assert!(rendered.contains("{tag}"));
+
+        let text = tr(
+            Msg::PushingTag,
+            &[("tag", &"v1.2.3"), ("remote", &"origin")],
+        );
+        if locale() == Locale::En {
+            assert_eq!(text, "Pushing tag: v1.2.3 to remote 'origin'");
+        }
+        assert!(text.contains("v1.2.3") && text.contains("origin"));
+    }
+}
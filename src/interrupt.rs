@@ -0,0 +1,184 @@
+//! Clean shutdown when the workflow is interrupted with Ctrl-C.
+//!
+//! Once a tag has been created, it is tracked here until it is pushed (or
+//! deliberately kept, e.g. queued in the outbox). Interrupting in between
+//! removes the tag again (all of them for a compound release), unless
+//! `keep_tag_on_interrupt` is set, so a cancelled run doesn't leave a local
+//! tag that was never published.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::error::Result;
+use crate::i18n::{text, tr, Msg};
+
+/// Exit code after an interrupt, following the shell convention of 128 + SIGINT
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Tags created by this run that haven't been published yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingTag {
+    git_dir: PathBuf,
+    tags: Vec<String>,
+}
+
+static PENDING_TAG: Mutex<Option<PendingTag>> = Mutex::new(None);
+
+static ROLLBACK: AtomicBool = AtomicBool::new(true);
+
+/// Install the Ctrl-C handler
+///
+/// On interrupt, the pending tags (if any) are rolled back when `rollback` is
+/// true, and the process exits with [`INTERRUPTED_EXIT_CODE`].
+pub fn install(rollback: bool) -> Result<()> {
+    ROLLBACK.store(rollback, Ordering::SeqCst);
+    ctrlc::set_handler(|| {
+        let _ = console::Term::stderr().show_cursor();
+        eprintln!("\n\x1b[33m⚠\x1b[0m {}", text(Msg::Interrupted));
+        abort(INTERRUPTED_EXIT_CODE);
+    })
+    .map_err(|e| std::io::Error::other(e.to_string()))?;
+    Ok(())
+}
+
+/// Clean up the pending tags like an interrupt would, then exit with `code`
+///
+/// For failures that leave the workflow stuck, such as a remote that stops
+/// responding in the middle of a push.
+pub fn abort(code: i32) -> ! {
+    if ROLLBACK.load(Ordering::SeqCst) {
+        match rollback_pending_tag() {
+            Ok(tags) => {
+                for tag in tags {
+                    eprintln!(
+                        "\x1b[33m→\x1b[0m {}",
+                        tr(Msg::InterruptRolledBack, &[("tag", &tag)])
+                    );
+                }
+            }
+            Err(e) => eprintln!(
+                "\x1b[31m{}\x1b[0m {}",
+                text(Msg::ErrorLabel),
+                tr(Msg::InterruptRollbackFailed, &[("error", &e)])
+            ),
+        }
+    } else if let Some(pending) = take_pending_tag() {
+        for tag in &pending.tags {
+            eprintln!(
+                "\x1b[33m→\x1b[0m {}",
+                tr(Msg::InterruptKeptTag, &[("tag", tag)])
+            );
+        }
+    }
+    std::process::exit(code);
+}
+
+/// Record a tag that was just created and isn't published yet, next to the
+/// ones already tracked in the same repository
+pub fn track_tag(git_dir: &Path, tag: &str) {
+    let mut pending = lock();
+    match pending
+        .as_mut()
+        .filter(|pending| pending.git_dir == git_dir)
+    {
+        Some(pending) => pending.tags.push(tag.to_string()),
+        None => {
+            *pending = Some(PendingTag {
+                git_dir: git_dir.to_path_buf(),
+                tags: vec![tag.to_string()],
+            })
+        }
+    }
+}
+
+/// Stop tracking the pending tags: they were pushed or are kept on purpose
+pub fn release_tag() {
+    lock().take();
+}
+
+/// Delete the pending tags, if any
+///
+/// # Returns
+/// * `Ok(tags)` - The deleted tags, in the order they were created; empty
+///   if no tag was pending
+pub fn rollback_pending_tag() -> Result<Vec<String>> {
+    let Some(pending) = take_pending_tag() else {
+        return Ok(Vec::new());
+    };
+    let repo = git2::Repository::open(&pending.git_dir)?;
+    for tag in &pending.tags {
+        repo.tag_delete(tag)?;
+    }
+    Ok(pending.tags)
+}
+
+fn take_pending_tag() -> Option<PendingTag> {
+    lock().take()
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Option<PendingTag>> {
+    // A panic while holding the lock can't leave the Option half-written
+    PENDING_TAG.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    fn repo_with_tag(tag: &str) -> (tempfile::TempDir, git2::Repository) {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        {
+            let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+            let tree_id = repo.index().unwrap().write_tree().unwrap();
+            let tree = repo.find_tree(tree_id).unwrap();
+            let commit = repo
+                .commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+                .unwrap();
+            let object = repo.find_object(commit, None).unwrap();
+            repo.tag_lightweight(tag, &object, false).unwrap();
+        }
+        (dir, repo)
+    }
+
+    #[test]
+    #[serial]
+    fn test_rollback_deletes_tracked_tag() {
+        let (_dir, repo) = repo_with_tag("v1.0.0");
+        track_tag(repo.path(), "v1.0.0");
+
+        assert_eq!(rollback_pending_tag().unwrap(), vec!["v1.0.0".to_string()]);
+        assert!(repo.find_reference("refs/tags/v1.0.0").is_err());
+        assert!(rollback_pending_tag().unwrap().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_released_tag_is_kept() {
+        let (_dir, repo) = repo_with_tag("v1.0.0");
+        track_tag(repo.path(), "v1.0.0");
+        release_tag();
+
+        assert!(rollback_pending_tag().unwrap().is_empty());
+        assert!(repo.find_reference("refs/tags/v1.0.0").is_ok());
+    }
+
+    #[test]
+    #[serial]
+    fn test_rollback_deletes_all_tags_of_compound_release() {
+        let (_dir, repo) = repo_with_tag("v1.0.0");
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag_lightweight("g1.0.0", head.as_object(), false)
+            .unwrap();
+        track_tag(repo.path(), "v1.0.0");
+        track_tag(repo.path(), "g1.0.0");
+
+        assert_eq!(
+            rollback_pending_tag().unwrap(),
+            vec!["v1.0.0".to_string(), "g1.0.0".to_string()]
+        );
+        assert!(repo.find_reference("refs/tags/g1.0.0").is_err());
+    }
+}
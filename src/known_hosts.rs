@@ -0,0 +1,392 @@
+//! SSH host key verification against OpenSSH `known_hosts` files.
+//!
+//! Supports plain and hashed (`|1|salt|hash`) host entries, comma-separated host
+//! lists, `[host]:port` syntax, `*`/`?` wildcards, negated patterns and
+//! `@revoked` markers. `@cert-authority` lines are ignored.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+use crate::error::Result;
+
+/// Outcome of looking up a host key in `known_hosts`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostKeyStatus {
+    /// A matching entry with the same key exists
+    Trusted,
+    /// The host is known but with a different key, or the key is revoked
+    Mismatch,
+    /// The host has no entry for this key type
+    Unknown,
+}
+
+/// Host part of a `known_hosts` line
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HostMatcher {
+    /// Comma-separated list of (possibly wildcarded or negated) patterns
+    Patterns(Vec<String>),
+    /// Hashed hostname: HMAC-SHA1 of the hostname keyed with the salt
+    Hashed { salt: Vec<u8>, hash: Vec<u8> },
+}
+
+impl HostMatcher {
+    fn matches(&self, host: &str) -> bool {
+        match self {
+            HostMatcher::Patterns(patterns) => {
+                let mut matched = false;
+                for pattern in patterns {
+                    if let Some(negated) = pattern.strip_prefix('!') {
+                        if wildcard_match(negated, host) {
+                            return false;
+                        }
+                    } else if wildcard_match(pattern, host) {
+                        matched = true;
+                    }
+                }
+                matched
+            }
+            HostMatcher::Hashed { salt, hash } => {
+                let Ok(mut mac) = Hmac::<Sha1>::new_from_slice(salt) else {
+                    return false;
+                };
+                mac.update(host.as_bytes());
+                mac.verify_slice(hash).is_ok()
+            }
+        }
+    }
+}
+
+/// A single host key line from `known_hosts`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct KnownHostEntry {
+    hosts: HostMatcher,
+    key_type: String,
+    key: String,
+    revoked: bool,
+}
+
+/// Parsed contents of a `known_hosts` file
+#[derive(Debug, Clone, Default)]
+pub struct KnownHosts {
+    entries: Vec<KnownHostEntry>,
+}
+
+impl KnownHosts {
+    /// Returns the default user `known_hosts` path (`~/.ssh/known_hosts`)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".ssh").join("known_hosts"))
+    }
+
+    /// Load a `known_hosts` file; a missing file yields an empty set of entries
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(KnownHosts::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(KnownHosts::parse(&content))
+    }
+
+    /// Parse `known_hosts` content, skipping malformed and unsupported lines
+    pub fn parse(content: &str) -> Self {
+        let entries = content.lines().filter_map(parse_line).collect();
+        KnownHosts { entries }
+    }
+
+    /// Look up a host key
+    ///
+    /// # Arguments
+    /// * `host` - Host name as used for the connection (e.g. "github.com")
+    /// * `port` - Connection port; non-22 ports are looked up as `[host]:port`
+    /// * `key_type` - Key algorithm name (e.g. "ssh-ed25519")
+    /// * `key` - Raw public key blob presented by the server
+    pub fn check(
+        &self,
+        host: &str,
+        port: Option<u16>,
+        key_type: &str,
+        key: &[u8],
+    ) -> HostKeyStatus {
+        let lookup = host_lookup_name(host, port);
+        let encoded = STANDARD.encode(key);
+        let mut status = HostKeyStatus::Unknown;
+
+        for entry in self.entries.iter().filter(|e| e.hosts.matches(&lookup)) {
+            if entry.revoked {
+                if entry.key == encoded {
+                    return HostKeyStatus::Mismatch;
+                }
+                continue;
+            }
+            if entry.key_type != key_type {
+                continue;
+            }
+            if entry.key == encoded {
+                status = HostKeyStatus::Trusted;
+            } else if status == HostKeyStatus::Unknown {
+                status = HostKeyStatus::Mismatch;
+            }
+        }
+
+        status
+    }
+
+    /// Append a host key to a `known_hosts` file, creating it if necessary
+    pub fn append(
+        path: &Path,
+        host: &str,
+        port: Option<u16>,
+        key_type: &str,
+        key: &[u8],
+    ) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(
+            file,
+            "{} {} {}",
+            host_lookup_name(host, port),
+            key_type,
+            STANDARD.encode(key)
+        )?;
+        Ok(())
+    }
+}
+
+/// Format a SHA-256 host key hash the way OpenSSH displays it (`SHA256:...`)
+pub fn format_fingerprint(sha256: &[u8]) -> String {
+    format!("SHA256:{}", STANDARD_NO_PAD.encode(sha256))
+}
+
+fn host_lookup_name(host: &str, port: Option<u16>) -> String {
+    match port {
+        Some(port) if port != 22 => format!("[{}]:{}", host, port),
+        _ => host.to_string(),
+    }
+}
+
+fn parse_line(line: &str) -> Option<KnownHostEntry> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let mut fields = line.split_whitespace();
+    let mut hosts_field = fields.next()?;
+    let mut revoked = false;
+
+    if let Some(marker) = hosts_field.strip_prefix('@') {
+        match marker {
+            "revoked" => revoked = true,
+            _ => return None,
+        }
+        hosts_field = fields.next()?;
+    }
+
+    let key_type = fields.next()?.to_string();
+    let key = fields.next()?.to_string();
+
+    let hosts = if let Some(hashed) = hosts_field.strip_prefix("|1|") {
+        let (salt, hash) = hashed.split_once('|')?;
+        HostMatcher::Hashed {
+            salt: STANDARD.decode(salt).ok()?,
+            hash: STANDARD.decode(hash).ok()?,
+        }
+    } else {
+        HostMatcher::Patterns(hosts_field.split(',').map(|h| h.to_string()).collect())
+    };
+
+    Some(KnownHostEntry {
+        hosts,
+        key_type,
+        key,
+        revoked,
+    })
+}
+
+/// Matches OpenSSH host patterns, where `*` matches any run of characters and `?` one character
+pub(crate) fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const KEY: &[u8] = b"example-ed25519-key";
+    const OTHER_KEY: &[u8] = b"another-key";
+
+    fn line(hosts: &str, key_type: &str, key: &[u8]) -> String {
+        format!("{} {} {}\n", hosts, key_type, STANDARD.encode(key))
+    }
+
+    fn hashed_host(host: &str, salt: &[u8]) -> String {
+        let mut mac = Hmac::<Sha1>::new_from_slice(salt).unwrap();
+        mac.update(host.as_bytes());
+        format!(
+            "|1|{}|{}",
+            STANDARD.encode(salt),
+            STANDARD.encode(mac.finalize().into_bytes())
+        )
+    }
+
+    #[test]
+    fn test_check_plain_host_trusted() {
+        let known = KnownHosts::parse(&line("github.com,140.82.112.3", "ssh-ed25519", KEY));
+        assert_eq!(
+            known.check("github.com", None, "ssh-ed25519", KEY),
+            HostKeyStatus::Trusted
+        );
+    }
+
+    #[test]
+    fn test_check_unknown_host() {
+        let known = KnownHosts::parse(&line("github.com", "ssh-ed25519", KEY));
+        assert_eq!(
+            known.check("gitlab.com", None, "ssh-ed25519", KEY),
+            HostKeyStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_check_key_mismatch() {
+        let known = KnownHosts::parse(&line("github.com", "ssh-ed25519", KEY));
+        assert_eq!(
+            known.check("github.com", None, "ssh-ed25519", OTHER_KEY),
+            HostKeyStatus::Mismatch
+        );
+    }
+
+    #[test]
+    fn test_check_other_key_type_is_unknown() {
+        let known = KnownHosts::parse(&line("github.com", "ssh-rsa", OTHER_KEY));
+        assert_eq!(
+            known.check("github.com", None, "ssh-ed25519", KEY),
+            HostKeyStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_check_hashed_host() {
+        let hosts = hashed_host("github.com", b"0123456789abcdefghij");
+        let known = KnownHosts::parse(&line(&hosts, "ssh-ed25519", KEY));
+        assert_eq!(
+            known.check("github.com", None, "ssh-ed25519", KEY),
+            HostKeyStatus::Trusted
+        );
+        assert_eq!(
+            known.check("gitlab.com", None, "ssh-ed25519", KEY),
+            HostKeyStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_check_non_default_port() {
+        let known = KnownHosts::parse(&line("[git.example.com]:2222", "ssh-ed25519", KEY));
+        assert_eq!(
+            known.check("git.example.com", Some(2222), "ssh-ed25519", KEY),
+            HostKeyStatus::Trusted
+        );
+        assert_eq!(
+            known.check("git.example.com", Some(22), "ssh-ed25519", KEY),
+            HostKeyStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_check_wildcard_and_negation() {
+        let known = KnownHosts::parse(&line("*.example.com,!evil.example.com", "ssh-ed25519", KEY));
+        assert_eq!(
+            known.check("git.example.com", None, "ssh-ed25519", KEY),
+            HostKeyStatus::Trusted
+        );
+        assert_eq!(
+            known.check("evil.example.com", None, "ssh-ed25519", KEY),
+            HostKeyStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_check_revoked_key() {
+        let content = format!(
+            "@revoked * ssh-ed25519 {}\n{}",
+            STANDARD.encode(KEY),
+            line("github.com", "ssh-ed25519", KEY)
+        );
+        let known = KnownHosts::parse(&content);
+        assert_eq!(
+            known.check("github.com", None, "ssh-ed25519", KEY),
+            HostKeyStatus::Mismatch
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_comments_and_malformed_lines() {
+        let content = format!(
+            "# comment\n\nmalformed-line\n@cert-authority *.example.com ssh-rsa AAAA\n{}",
+            line("github.com", "ssh-ed25519", KEY)
+        );
+        let known = KnownHosts::parse(&content);
+        assert_eq!(known.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_append_then_load_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(".ssh").join("known_hosts");
+
+        KnownHosts::append(&path, "git.example.com", Some(2222), "ssh-ed25519", KEY).unwrap();
+        let known = KnownHosts::load(&path).unwrap();
+
+        assert_eq!(
+            known.check("git.example.com", Some(2222), "ssh-ed25519", KEY),
+            HostKeyStatus::Trusted
+        );
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let known = KnownHosts::load(&temp_dir.path().join("missing")).unwrap();
+        assert_eq!(
+            known.check("github.com", None, "ssh-ed25519", KEY),
+            HostKeyStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_format_fingerprint() {
+        assert_eq!(format_fingerprint(&[0u8; 3]), "SHA256:AAAA");
+    }
+}
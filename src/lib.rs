@@ -1,10 +1,32 @@
 pub mod analyzer;
 pub mod boundary;
+pub mod changelog;
 pub mod config;
+pub mod config_diagnostics;
+pub mod config_migrate;
+pub mod dashboard;
+pub mod doctor;
 pub mod domain;
 pub mod error;
 pub mod git_ops;
+pub mod hooks;
+pub mod i18n;
+pub mod interrupt;
+pub mod known_hosts;
+pub mod manifest;
+pub mod outbox;
+pub mod path_ignore;
+pub mod report;
+#[cfg(feature = "self-update")]
+pub mod self_update;
+pub mod simulation;
+pub mod ssh_config;
+pub mod state;
+pub mod stats;
+pub mod tag_list;
+pub mod timings;
 pub mod ui;
+pub mod workflow;
 
 pub use domain::VersionBump;
 pub use error::{GitPublishError, Result};
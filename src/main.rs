@@ -1,11 +1,35 @@
-use anyhow::{Context, Result};
+use std::io::Read;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
 use clap::Parser;
 
-use git_publish::boundary::BoundaryWarning;
-use git_publish::config;
-use git_publish::domain::Version;
+use git_publish::analyzer::message_analysis::{self, MessageAnalysis};
+use git_publish::config::{self, HostKeyChecking};
+use git_publish::config_migrate;
+use git_publish::dashboard;
+use git_publish::doctor;
+use git_publish::domain::commit::{explain_commit, BumpAnalysis};
+use git_publish::domain::{BumpOverride, TagPattern, Version, VersionBump};
 use git_publish::git_ops;
+use git_publish::i18n::{self, text, tr, Locale, Msg};
+use git_publish::interrupt;
+use git_publish::known_hosts::KnownHosts;
+use git_publish::outbox::Outbox;
+use git_publish::path_ignore::PathIgnore;
+use git_publish::report;
+#[cfg(feature = "self-update")]
+use git_publish::self_update;
+use git_publish::simulation;
+use git_publish::stats;
+use git_publish::tag_list::TagList;
+use git_publish::timings::{self, Phase};
 use git_publish::ui;
+use git_publish::workflow::{
+    self, BranchOutcome, LayerTagArgs, Notifier, Prompter, PublishWorkflowArgs, ReportedFailure,
+    RollbackArgs, RollbackScope, TerminalNotifier, TerminalPrompter,
+};
 
 #[derive(clap::Parser, Debug, Clone, PartialEq)]
 #[command(
@@ -13,30 +37,392 @@ use git_publish::ui;
     about = "Create and push git tags based on conventional commits"
 )]
 struct Args {
-    #[arg(short, long, help = "Custom configuration file path")]
+    #[arg(short, long, global = true, help = "Custom configuration file path")]
     config: Option<String>,
 
-    #[arg(short, long, help = "Explicitly specify branch to tag")]
-    branch: Option<String>,
+    #[arg(
+        short,
+        long,
+        global = true,
+        value_name = "BRANCH",
+        help = "Explicitly specify branch to tag; repeat to release several configured branches in turn"
+    )]
+    branch: Vec<String>,
 
     #[arg(
         short,
         long,
+        global = true,
         help = "Specify which git remote to fetch from and push to"
     )]
     remote: Option<String>,
 
-    #[arg(short, long, help = "Skip confirmation prompts")]
+    #[arg(
+        long,
+        conflicts_with_all = ["all_branches", "tui", "json"],
+        help = "Show configured branches with their latest tag, pending commits and next tag, then exit (same as `list`)"
+    )]
+    list: bool,
+
+    #[arg(short, long, help = "Print version information")]
+    version: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Display language for prompts and messages (en, zh-CN)"
+    )]
+    lang: Option<Locale>,
+
+    #[arg(
+        long,
+        global = true,
+        hide = true,
+        help = "Report time spent in fetch, tag scan, revwalk and analysis"
+    )]
+    profile_timings: bool,
+
+    #[command(flatten)]
+    publish: PublishArgs,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+impl Args {
+    /// The branch given with `--branch`; only releases take several
+    fn branch(&self) -> Option<&str> {
+        self.branch.first().map(String::as_str)
+    }
+
+    /// Options of the release to run: the subcommand's, or the bare ones
+    ///
+    /// Release options given before `publish` or `preview` would be ignored,
+    /// so they are rejected instead.
+    fn release_options(&self) -> Result<PublishArgs, &'static str> {
+        match &self.command {
+            Some(Command::Publish(_) | Command::Preview(_))
+                if self.publish != PublishArgs::default() =>
+            {
+                Err(text(Msg::ReleaseOptionsBeforeSubcommand))
+            }
+            Some(Command::Publish(publish)) => Ok(publish.clone()),
+            Some(Command::Preview(publish)) => Ok(PublishArgs {
+                dry_run: true,
+                ..publish.clone()
+            }),
+            _ => Ok(self.publish.clone()),
+        }
+    }
+}
+
+/// Options of a release, given to `publish` and `preview` or directly to
+/// `git-publish`
+#[derive(clap::Args, Debug, Clone, Default, PartialEq)]
+struct PublishArgs {
+    #[arg(
+        long,
+        conflicts_with_all = ["branch", "push_only", "tui", "json"],
+        help = "Release every configured branch in turn, then show a summary"
+    )]
+    all_branches: bool,
+
+    #[arg(
+        short,
+        long,
+        visible_alias = "yes",
+        visible_short_alias = 'y',
+        help = "Skip confirmation prompts"
+    )]
     force: bool,
 
     #[arg(long, help = "Preview what would happen without making changes")]
     dry_run: bool,
 
-    #[arg(long, help = "Show available configured branches and exit")]
-    list: bool,
+    #[arg(
+        long,
+        help = "Allow a major version bump without typing the new major version"
+    )]
+    accept_breaking: bool,
 
-    #[arg(short, long, help = "Print version information")]
-    version: bool,
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with = "tui",
+        help = "Apply this bump instead of the one the commits call for"
+    )]
+    bump: Option<BumpArg>,
+
+    #[arg(
+        long,
+        value_name = "IDENTIFIER",
+        num_args = 0..=1,
+        default_missing_value = "",
+        conflicts_with = "tui",
+        help = "Publish a pre-release such as v1.3.0-beta.1 ([prerelease] default_identifier without IDENTIFIER)"
+    )]
+    prerelease: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["tui", "prerelease", "bump"],
+        help = "Release the latest pre-release as its stable version (v1.3.0-beta.2 -> v1.3.0)"
+    )]
+    promote: bool,
+
+    #[arg(
+        long,
+        value_name = "BRANCH",
+        help = "Also tag the same commit with the new version in this configured branch's tag pattern (repeatable)"
+    )]
+    also_tag: Vec<String>,
+
+    #[arg(
+        long,
+        value_name = "BRANCH",
+        help = "Only analyze the commits since the branch's merge base with this other branch"
+    )]
+    merge_base: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "TAG",
+        conflicts_with = "all_branches",
+        help = "Analyze the commits since this tag and bump its version, instead of the latest tag on the branch"
+    )]
+    from_tag: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "COMMIT|DATE",
+        conflicts_with = "merge_base",
+        help = "Analyze the commits after this commit or date (YYYY-MM-DD, UTC) instead of those since the latest tag"
+    )]
+    since: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["all_branches", "tui", "push_only"],
+        help = "Release only this [packages] entry: the commits changing its files, tagged with its pattern"
+    )]
+    package: Option<String>,
+
+    #[arg(
+        long,
+        help = "Add the release notes to the changelog file before tagging (CHANGELOG.md unless [changelog] file says otherwise)"
+    )]
+    changelog: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "changelog",
+        help = "Don't update the changelog file, even when [changelog] file is set"
+    )]
+    no_changelog: bool,
+
+    #[arg(
+        long,
+        help = "Move existing tags without asking when [behavior] on_existing_tag is \"replace\""
+    )]
+    replace_tags: bool,
+
+    #[arg(long, help = "Stop after creating the tag, without pushing it")]
+    tag_only: bool,
+
+    #[arg(
+        long,
+        value_name = "TAG",
+        conflicts_with_all = ["tag_only", "bump", "prerelease", "promote", "also_tag", "merge_base", "from_tag", "since", "changelog"],
+        help = "Only push this existing local tag, skipping the analysis and tag creation"
+    )]
+    push_only: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["force", "dry_run", "push_only"],
+        help = "Plan the release on a full-screen view of branches, tags and commits"
+    )]
+    tui: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "tui",
+        help = "Don't ask anything and print the release decision and outcome as JSON on stdout"
+    )]
+    json: bool,
+}
+
+#[derive(clap::Subcommand, Debug, Clone, PartialEq)]
+enum Command {
+    /// Analyze the commits of a branch, then create and push its next tag
+    /// (what `git-publish` without a subcommand does)
+    Publish(PublishArgs),
+    /// Show the tag a release would create, without changing anything
+    /// (`publish --dry-run`)
+    Preview(PublishArgs),
+    /// Show configured branches with their latest tag, pending commits and next tag
+    List,
+    /// Create a gitpublish.toml for the repository's release branches
+    Init {
+        /// Ask which branches to tag and the tag prefix of each
+        #[arg(short, long)]
+        interactive: bool,
+    },
+    /// Push tags queued while the remote was unreachable
+    Flush,
+    /// Create an annotated tag pointing at an existing tag, to add metadata
+    /// to a release (e.g. v1.2.3-signed over v1.2.3)
+    Annotate {
+        /// Existing tag to point at
+        target: String,
+
+        /// Name of the new tag
+        tag: String,
+
+        /// Message of the new tag
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Sign the new tag with GPG as configured for git
+        #[arg(long)]
+        sign: bool,
+
+        /// Move the new tag if it already exists (force-pushing it with --push)
+        #[arg(long)]
+        replace: bool,
+
+        /// Push the new tag to the remote (--remote, or origin)
+        #[arg(long)]
+        push: bool,
+    },
+    /// Delete the latest tag (or the given one) locally and on the remote
+    /// (--remote, or origin)
+    Rollback {
+        /// Tag to delete; the latest tag (of --branch's tag pattern with --branch) by default
+        tag: Option<String>,
+
+        /// Only delete the tag on the remote, keeping the local tag
+        #[arg(long, conflicts_with = "local_only")]
+        remote_only: bool,
+
+        /// Only delete the local tag
+        #[arg(long)]
+        local_only: bool,
+
+        /// Delete the tag even though a newer tag exists
+        #[arg(long)]
+        force: bool,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Show how one commit is classified and which rule decides its bump
+    Explain {
+        /// A commit (SHA, branch, HEAD~2, ...) or a commit message
+        commit: String,
+    },
+    /// Show the baseline tag, bump and tag a release would have got with the
+    /// branch head at a past commit
+    Simulate {
+        /// The commit to pretend the head is at (SHA, HEAD~2, a tag, ...)
+        #[arg(long, value_name = "COMMIT")]
+        at: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+    },
+    /// Print the version bump of commit messages, without a repository
+    Analyze {
+        /// Read the messages from stdin: one per line, or NUL-separated to
+        /// keep multi-line messages (git log -z --format=%B)
+        #[arg(long, required = true)]
+        stdin: bool,
+
+        /// Print the analysis as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show release cadence, commits per release and bump distribution from local tags
+    Stats {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+    },
+    /// List release tags by version, newest first, with their date and commit
+    /// (only the --branch's tag pattern with --branch)
+    ListTags {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+    },
+    /// Diagnose configuration, repository and remote access problems
+    Doctor,
+    /// Check the project's GitHub releases for a newer git-publish
+    #[cfg(feature = "self-update")]
+    SelfUpdate {
+        /// Only report whether a newer release exists (the default)
+        #[arg(long, conflicts_with = "install")]
+        check: bool,
+
+        /// Replace this executable with the newer release's binary for this platform
+        #[arg(long)]
+        install: bool,
+    },
+    /// Manage the configuration file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Summarize the releases made in a recent period
+    Report {
+        /// Start of the period: a duration such as 7d, 2w, 12h, or a date (YYYY-MM-DD)
+        #[arg(long, default_value = "7d")]
+        since: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+
+        /// Write a static HTML dashboard with statistics and recent changelogs to this directory
+        #[arg(long, value_name = "DIR", conflicts_with = "format")]
+        html: Option<PathBuf>,
+    },
+}
+
+#[derive(clap::Subcommand, Debug, Clone, PartialEq)]
+enum ConfigCommand {
+    /// Rewrite the configuration for the current config_version, renaming deprecated keys
+    Migrate,
+    /// Print the JSON Schema of the configuration file
+    Schema,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum BumpArg {
+    Major,
+    Minor,
+    Patch,
+    Prerelease,
+}
+
+impl From<BumpArg> for BumpOverride {
+    fn from(bump: BumpArg) -> Self {
+        match bump {
+            BumpArg::Major => BumpOverride::Release(VersionBump::Major),
+            BumpArg::Minor => BumpOverride::Release(VersionBump::Minor),
+            BumpArg::Patch => BumpOverride::Release(VersionBump::Patch),
+            BumpArg::Prerelease => BumpOverride::PreRelease,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum ReportFormat {
+    Markdown,
+    Json,
 }
 
 fn main() -> Result<()> {
@@ -47,356 +433,916 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    if args.list {
+    // Display language: --lang, then [behavior] language, then the environment
+    let locale = args
+        .lang
+        .or_else(|| {
+            config::load_config(args.config.as_deref())
+                .ok()
+                .and_then(|cfg| cfg.behavior.language)
+        })
+        .or_else(Locale::from_env)
+        .unwrap_or_default();
+    i18n::set_locale(locale);
+
+    if args.profile_timings {
+        timings::enable();
+    }
+
+    // Other commands look at a single branch
+    let releases = matches!(
+        args.command,
+        None | Some(Command::Publish(_) | Command::Preview(_))
+    );
+    if args.branch.len() > 1 && (args.list || !releases) {
+        ui::display_error(text(Msg::SeveralBranchesNotSupported));
+        std::process::exit(1);
+    }
+
+    if args.list || args.command == Some(Command::List) {
         list_configured_branches(args.config.as_deref())?;
+        report_timings(&args);
         return Ok(());
     }
 
-    // Load configuration
-    let config = match config::load_config(args.config.as_deref()) {
-        Ok(cfg) => cfg,
-        Err(e) => {
-            eprintln!("Error loading config: {}", e);
-            std::process::exit(1);
+    match &args.command {
+        Some(Command::Init { interactive }) => return init_config(*interactive),
+        Some(Command::Flush) => return flush_outbox(&args),
+        Some(Command::Annotate {
+            target,
+            tag,
+            message,
+            sign,
+            replace,
+            push,
+        }) => {
+            let layer_args = LayerTagArgs {
+                target_tag: target.clone(),
+                tag: tag.clone(),
+                message: message.clone(),
+                signed: *sign,
+                replace: *replace,
+                push: *push,
+                remote: args.remote.clone(),
+            };
+            return annotate_tag(&args, &layer_args);
         }
-    };
+        Some(Command::Rollback {
+            tag,
+            remote_only,
+            local_only,
+            force,
+            yes,
+        }) => {
+            let scope = match (*remote_only, *local_only) {
+                (true, _) => RollbackScope::RemoteOnly,
+                (_, true) => RollbackScope::LocalOnly,
+                _ => RollbackScope::Everywhere,
+            };
+            let rollback_args = RollbackArgs {
+                tag: tag.clone(),
+                branch: args.branch().map(str::to_string),
+                scope,
+                force: *force,
+                yes: *yes,
+                remote: args.remote.clone(),
+            };
+            return rollback_tag(&args, &rollback_args);
+        }
+        Some(Command::Explain { commit }) => return print_explanation(&args, commit),
+        Some(Command::Simulate { at, format }) => return print_simulation(&args, at, *format),
+        #[cfg(feature = "self-update")]
+        Some(Command::SelfUpdate { install, .. }) => return self_update(*install),
+        Some(Command::Analyze { json, .. }) => return print_message_analysis(&args, *json),
+        Some(Command::Stats { format }) => return print_stats(&args, *format),
+        Some(Command::ListTags { format }) => return print_tag_list(&args, *format),
+        Some(Command::Report {
+            since,
+            format,
+            html,
+        }) => return print_report(&args, since, *format, html.as_deref()),
+        Some(Command::Config {
+            command: ConfigCommand::Migrate,
+        }) => return migrate_config(&args),
+        Some(Command::Config {
+            command: ConfigCommand::Schema,
+        }) => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&config::Config::schema())?
+            );
+            return Ok(());
+        }
+        Some(Command::Doctor) => {
+            let report = doctor::run_doctor(args.config.as_deref(), args.branch());
+            ui::display_doctor_report(&report);
+            if report.has_failures() {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Command::Publish(_) | Command::Preview(_) | Command::List) | None => {}
+    }
 
-    // Select branch to tag
-    let branch_to_tag = if let Some(branch) = args.branch {
-        branch
-    } else {
-        // Get configured branches as a sorted vector
-        let mut configured_branches: Vec<String> = config.branches.keys().cloned().collect();
-        configured_branches.sort();
-        if configured_branches.is_empty() {
-            ui::display_error("No branches configured for tagging in gitpublish.toml");
+    let publish = match args.release_options() {
+        Ok(publish) => publish,
+        Err(message) => {
+            ui::display_error(message);
             std::process::exit(1);
         }
-
-        ui::select_branch(&configured_branches)?
     };
 
-    // Verify the selected branch exists in config
-    if !config.branches.contains_key(&branch_to_tag) {
-        eprintln!(
-            "Error: Branch '{}' is not configured for tagging",
-            branch_to_tag
-        );
+    // `--json` runs unattended, like `--force`
+    let force = publish.force || publish.json;
+
+    // Several branches are released one by one, like `--all-branches`
+    if args.branch.len() > 1
+        && (publish.tui
+            || publish.json
+            || publish.push_only.is_some()
+            || publish.package.is_some()
+            || publish.from_tag.is_some())
+    {
+        ui::display_error(text(Msg::SeveralBranchesConflict));
         std::process::exit(1);
     }
 
-    // Initialize git operations
-    let git_repo = match git_ops::GitRepo::new() {
-        Ok(repo) => repo,
-        Err(e) => {
-            ui::display_error(&format!("Git repository error: {}", e));
-            std::process::exit(1);
+    // First run: offer the `init` scaffolding instead of silently using the defaults
+    if args.config.is_none()
+        && !force
+        && !publish.dry_run
+        && console::Term::stdout().is_term()
+        && matches!(config::find_config_source(None), Ok(None))
+    {
+        ui::display_status(text(Msg::NoConfigFound));
+        if ui::confirm_action(text(Msg::OfferInit))? {
+            init_config(false)?;
         }
-    };
+    }
 
-    // Validate specified remote if provided
-    if let Some(ref specified_remote) = args.remote {
-        if !git_repo
-            .remote_exists(specified_remote)
-            .context("Failed to validate remote")?
-        {
-            let available = git_repo.list_remotes()?;
-            anyhow::bail!(
-                "Remote '{}' not found. Available remotes: {}",
-                specified_remote,
-                available.join(", ")
-            );
-        }
+    let (config, mut git_repo) = open_config_and_repo(&args);
+
+    // Roll back an unpublished tag if the run is interrupted
+    interrupt::install(!config.behavior.keep_tag_on_interrupt)?;
+
+    configure_remote_access(&mut git_repo, &config, force);
+    git_repo.add_ignored_paths(&config.behavior.ignore_paths);
+
+    // Only the package's commits count; the workflow reports unknown packages
+    if let Some(package) = publish
+        .package
+        .as_ref()
+        .and_then(|package| config.packages.get(package))
+    {
+        git_repo.set_path_scope(PathIgnore::parse(&package.path));
     }
 
-    // Get available remotes for selection
-    let available_remotes = match git_repo.list_remotes() {
-        Ok(remotes) => {
-            if remotes.is_empty() {
-                ui::display_error("No remotes configured in this repository");
-                std::process::exit(1);
-            }
-            remotes
+    // The planner only picks the branch and tag; the workflow does the rest
+    #[cfg(feature = "tui")]
+    let plan = if publish.tui {
+        match ui::tui::run(&git_repo, &config)? {
+            Some(plan) => Some(plan),
+            None => return Ok(()),
         }
-        Err(e) => {
-            ui::display_error(&format!("Failed to list remotes: {}", e));
+    } else {
+        None
+    };
+    #[cfg(feature = "tui")]
+    let planned_prompter = plan
+        .as_ref()
+        .map(|plan| ui::tui::PlannedPrompter::new(plan, &TerminalPrompter));
+    #[cfg(feature = "tui")]
+    let (prompter, planned_branch): (&dyn Prompter, _) = match &planned_prompter {
+        Some(prompter) => (prompter, plan.as_ref().map(|plan| plan.branch.clone())),
+        None => (&TerminalPrompter, None),
+    };
+    #[cfg(not(feature = "tui"))]
+    let (prompter, planned_branch): (&dyn Prompter, Option<String>) = {
+        if publish.tui {
+            ui::display_error(text(Msg::TuiNotBuilt));
             std::process::exit(1);
         }
+        (&TerminalPrompter, None)
     };
 
-    // Determine which remote to use with three-tier precedence:
-    // 1. CLI flag (--remote) - takes absolute precedence if provided
-    // 2. Config option (skip_remote_selection) - applies only to single-remote case
-    //    - If true and single remote exists: auto-select without prompting
-    //    - If false (default): always prompt user even for single remote
-    // 3. Interactive prompt - used for multiple remotes or when no CLI flag
-    let selected_remote = if let Some(ref cli_remote) = args.remote {
-        // CLI flag takes precedence
-        cli_remote.clone()
+    let workflow_args = PublishWorkflowArgs {
+        branch: planned_branch.or_else(|| args.branch().map(str::to_string)),
+        remote: args.remote.clone(),
+        force,
+        dry_run: publish.dry_run,
+        accept_breaking: publish.accept_breaking,
+        also_tag: publish.also_tag.clone(),
+        merge_base: publish.merge_base.clone(),
+        from_tag: publish.from_tag.clone(),
+        since: publish.since.clone(),
+        bump: publish.bump.map(BumpOverride::from),
+        prerelease: match publish.prerelease.as_deref() {
+            Some("") => Some(config.prerelease.default_identifier.parse()?),
+            Some(identifier) => Some(identifier.parse()?),
+            None => None,
+        },
+        promote: publish.promote,
+        replace_tags: publish.replace_tags,
+        tag_only: publish.tag_only,
+        push_only: publish.push_only.clone(),
+        changelog: match (publish.changelog, publish.no_changelog) {
+            (true, _) => Some(true),
+            (_, true) => Some(false),
+            _ => None,
+        },
+        step_summary: std::env::var_os(workflow::step_summary::STEP_SUMMARY_VAR)
+            .filter(|path| !path.is_empty())
+            .map(std::path::PathBuf::from),
+        package: publish.package.clone(),
+        already_fetched: false,
+    };
+    let notifier: &dyn Notifier = if publish.json {
+        &ui::json::JsonNotifier
     } else {
-        // Check available remotes
-        if available_remotes.len() == 1 {
-            // Single remote case
-            let should_skip = config.behavior.skip_remote_selection;
-            if should_skip {
-                // Auto-select the single remote
-                available_remotes[0].clone()
-            } else {
-                // Prompt even though there's only one
-                ui::select_remote(&available_remotes)?
-            }
-        } else {
-            // Multiple remotes - always prompt (config only applies to single remote case)
-            ui::select_remote(&available_remotes)?
-        }
+        &TerminalNotifier
     };
-
-    // Fetch latest from remote to ensure we have the latest tags and commits
-    ui::display_status(&format!(
-        "Fetching latest data from '{}'...",
-        selected_remote
-    ));
-    match git_repo.fetch_from_remote(&selected_remote, &branch_to_tag) {
-        Ok(_) => {
-            ui::display_success(&format!(
-                "Successfully fetched latest data from '{}'",
-                selected_remote
-            ));
-        }
-        Err(e) => {
-            // Check if it's an authentication error
-            let error_msg = e.to_string();
-            if error_msg.contains("auth")
-                || error_msg.contains("Auth")
-                || error_msg.contains("permission")
-                || error_msg.contains("Permission")
-            {
-                let warning = BoundaryWarning::FetchAuthenticationFailed {
-                    remote: selected_remote.clone(),
-                };
-                ui::display_boundary_warning(&warning);
-
-                if !args.force
-                    && !args.dry_run
-                    && !ui::confirm_action("Continue using local data?")?
-                {
-                    println!("Operation cancelled by user.");
-                    return Ok(());
+    if publish.all_branches || args.branch.len() > 1 {
+        let branches: Vec<String> = if publish.all_branches {
+            let mut branches: Vec<String> = config.branches.keys().cloned().collect();
+            branches.sort();
+            branches
+        } else {
+            // In the order given, each once
+            let mut branches: Vec<String> = Vec::new();
+            for branch in &args.branch {
+                if !config.branches.contains_key(branch) {
+                    ui::display_error(&tr(Msg::BranchNotConfigured, &[("branch", branch)]));
+                    std::process::exit(1);
+                }
+                if !branches.contains(branch) {
+                    branches.push(branch.clone());
                 }
-            } else {
-                // Non-auth errors are still warnings
-                ui::display_status(&format!(
-                    "Warning: Could not fetch from remote '{}': {}. Using local branch data.",
-                    selected_remote, e
-                ));
             }
+            branches
+        };
+        if branches.is_empty() {
+            ui::display_error(text(Msg::NoBranchesConfigured));
+            std::process::exit(1);
+        }
+        if !force
+            && !publish.dry_run
+            && !ui::confirm_action(&tr(
+                Msg::BatchConfirm,
+                &[("branches", &branches.join(", "))],
+            ))?
+        {
+            ui::display_status(text(Msg::OperationCancelled));
+            return Ok(());
+        }
+        let outcomes = workflow::publish_branches(
+            &git_repo,
+            prompter,
+            notifier,
+            &config,
+            &workflow_args,
+            &branches,
+        );
+        report_timings(&args);
+        ui::display_batch_summary(&outcomes);
+        if outcomes.iter().any(BranchOutcome::is_failure) {
+            std::process::exit(1);
         }
+        return Ok(());
     }
+    let result = workflow::publish(&git_repo, prompter, notifier, &config, &workflow_args);
+    report_timings(&args);
+    // Failures were shown by the notifier
+    if result.is_failure() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
 
-    // Get the tag pattern for this branch from config
-    let tag_pattern = config.branches.get(&branch_to_tag).map(|s| s.as_str());
+/// Show the recorded timings when `--profile-timings` is set
+fn report_timings(args: &Args) {
+    if args.profile_timings {
+        ui::display_timings(&timings::report());
+    }
+}
 
-    // Get the latest tag on the selected branch, checking both local and remote-tracking branches
-    let latest_tag = match git_repo.get_latest_tag_on_branch_with_remote(
-        &branch_to_tag,
-        Some(&selected_remote),
-        tag_pattern,
-    ) {
-        Ok(tag) => tag,
+/// Set up SSH host key verification and per-remote identities for fetch and push.
+fn configure_remote_access(git_repo: &mut git_ops::GitRepo, config: &config::Config, force: bool) {
+    // Verify SSH host keys against known_hosts (unknown hosts can't be confirmed when forced)
+    if let Some(known_hosts_path) = KnownHosts::default_path() {
+        let mode = match config.behavior.host_key_checking {
+            HostKeyChecking::Ask if force => HostKeyChecking::Strict,
+            mode => mode,
+        };
+        git_repo.set_host_key_verifier(git_ops::HostKeyVerifier {
+            known_hosts_path,
+            mode,
+            confirm_unknown: |host, fingerprint| {
+                ui::confirm_unknown_host_key(host, fingerprint).unwrap_or(false)
+            },
+        });
+    }
+
+    // Don't let a hung connection block the release indefinitely
+    if let Some(secs) = config
+        .behavior
+        .network_timeout_secs
+        .filter(|&secs| secs > 0)
+    {
+        git_repo.set_network_timeout(git_ops::NetworkTimeout {
+            duration: std::time::Duration::from_secs(secs),
+            on_stall: |remote, duration| {
+                ui::display_error(&tr(
+                    Msg::NetworkTimeout,
+                    &[("remote", &remote), ("secs", &duration.as_secs())],
+                ));
+                interrupt::abort(1);
+            },
+        });
+    }
+
+    // HTTPS credentials: tokens and credential helpers come first, then ask
+    if !force && console::Term::stdout().is_term() {
+        git_repo.set_credential_prompt(|url, username| {
+            ui::prompt_https_credentials(url, username).ok().flatten()
+        });
+    }
+
+    git_repo.set_github_hosts(config.auth.github_hosts.clone());
+
+    // Per-remote SSH identities; ~/.ssh/config IdentityFile entries are used as a fallback
+    for (remote_name, auth) in &config.auth.remotes {
+        if let Some(key_path) = auth.ssh_key_path() {
+            git_repo.set_ssh_identity(remote_name, key_path);
+        }
+    }
+}
+
+/// Load the configuration, showing its warnings, or exit when it can't be read.
+fn load_config_or_exit(config_path: Option<&str>) -> config::Config {
+    match config::load_config_with_warnings(config_path) {
+        Ok((cfg, warnings)) => {
+            for warning in &warnings {
+                ui::display_warning(&warning.message());
+            }
+            cfg
+        }
         Err(e) => {
-            ui::display_error(&format!(
-                "Failed to get latest tag on branch '{}': {}",
-                branch_to_tag, e
-            ));
+            eprintln!("{}", tr(Msg::ConfigLoadError, &[("error", &e)]));
             std::process::exit(1);
         }
-    };
+    }
+}
 
-    // Get commits since the latest tag
-    let commits = match git_repo.get_commits_since_tag(&branch_to_tag, latest_tag.as_deref()) {
-        Ok(commits) => commits,
+/// Load the configuration and open the repository of the current directory,
+/// exiting when either fails.
+fn open_config_and_repo(args: &Args) -> (config::Config, git_ops::GitRepo) {
+    let config = load_config_or_exit(args.config.as_deref());
+    match git_ops::GitRepo::new() {
+        Ok(repo) => (config, repo),
         Err(e) => {
-            ui::display_error(&format!(
-                "Failed to get commits since tag on branch '{}': {}",
-                branch_to_tag, e
-            ));
+            ui::display_error(&tr(Msg::RepositoryError, &[("error", &e)]));
             std::process::exit(1);
         }
-    };
+    }
+}
 
-    // Extract commit messages for analysis
-    let commit_messages: Vec<String> = commits
-        .iter()
-        .filter_map(|commit| commit.message().map(|msg| msg.to_string()))
-        .collect();
+/// Create a tag layered over an existing tag.
+fn annotate_tag(args: &Args, layer_args: &LayerTagArgs) -> Result<()> {
+    let (config, mut git_repo) = open_config_and_repo(args);
+    configure_remote_access(&mut git_repo, &config, args.publish.force);
 
-    if commits.is_empty() {
-        let head_hash = git_repo.get_current_head_hash()?;
-        let warning = BoundaryWarning::NoNewCommits {
-            latest_tag: latest_tag.clone().unwrap_or_else(|| "unknown".to_string()),
-            current_commit_hash: head_hash,
-        };
+    match workflow::layer_tag(&git_repo, &TerminalNotifier, &config, layer_args) {
+        Ok(()) => Ok(()),
+        // Already shown to the user
+        Err(e) if e.is::<ReportedFailure>() => std::process::exit(1),
+        Err(e) => Err(e),
+    }
+}
 
-        ui::display_boundary_warning(&warning);
+fn rollback_tag(args: &Args, rollback_args: &RollbackArgs) -> Result<()> {
+    let (config, mut git_repo) = open_config_and_repo(args);
+    configure_remote_access(&mut git_repo, &config, rollback_args.yes);
 
-        if !args.force && !args.dry_run && !ui::confirm_action("Continue with no new commits?")? {
-            println!("Operation cancelled by user.");
-            return Ok(());
-        }
+    match workflow::rollback(
+        &git_repo,
+        &TerminalNotifier,
+        &TerminalPrompter,
+        &config,
+        rollback_args,
+    ) {
+        Ok(_) => Ok(()),
+        // Already shown to the user
+        Err(e) if e.is::<ReportedFailure>() => std::process::exit(1),
+        Err(e) => Err(e),
     }
+}
 
-    // Display commit analysis
-    ui::display_commit_analysis(&commit_messages, &branch_to_tag);
+/// Push every queued tag, keeping the ones that still fail in the outbox.
+fn flush_outbox(args: &Args) -> Result<()> {
+    let (config, mut git_repo) = open_config_and_repo(args);
+    configure_remote_access(&mut git_repo, &config, args.publish.force);
 
-    // Determine the version bump based on commits using domain module
-    let version_bump = git_publish::domain::commit::analyze_version_bump(
-        &commit_messages,
-        &config.conventional_commits,
-    );
+    let outbox = Outbox::in_git_dir(git_repo.git_dir());
+    let queued = outbox.load()?;
+    if queued.is_empty() {
+        ui::display_status(text(Msg::NoQueuedPushes));
+        return Ok(());
+    }
 
-    // Format the new tag using the configured pattern
-    let new_tag_pattern = config
-        .branches
-        .get(&branch_to_tag)
-        .cloned()
-        .unwrap_or_else(|| "v{version}".to_string());
-    let final_tag = match latest_tag.as_ref() {
-        Some(tag) => match Version::parse(tag) {
-            Ok(current_version) => {
-                let candidate_tags: Vec<String> = current_version
-                    .bump_options(&version_bump)
-                    .into_iter()
-                    .map(|version| new_tag_pattern.replace("{version}", &version.to_string()))
-                    .collect();
-                let recommended_tag = candidate_tags
-                    .first()
-                    .cloned()
-                    .unwrap_or_else(|| new_tag_pattern.replace("{version}", "0.1.0"));
-
-                ui::display_proposed_tag(latest_tag.as_deref(), &recommended_tag);
-
-                if !args.force && !args.dry_run {
-                    ui::select_tag_from_candidates(&recommended_tag, &candidate_tags)?
-                } else {
-                    recommended_tag
-                }
+    if args.publish.dry_run {
+        ui::display_status(text(Msg::DryRunQueuedPushes));
+        for entry in &queued {
+            ui::display_success(&format!("  {} -> '{}'", entry.tag, entry.remote));
+        }
+        return Ok(());
+    }
+
+    let mut remaining = Vec::new();
+    for entry in queued {
+        ui::display_status(&tr(
+            Msg::PushingQueuedTag,
+            &[("tag", &entry.tag), ("remote", &entry.remote)],
+        ));
+        let remote_override = config.remote_overrides.get(&entry.remote);
+        match git_repo.push_tag_with_override(&entry.tag, &entry.remote, remote_override) {
+            Ok(()) => ui::display_success(&tr(Msg::PushedTag, &[("tag", &entry.tag)])),
+            Err(e) => {
+                ui::display_error(&tr(
+                    Msg::PushTagFailed,
+                    &[("tag", &entry.tag), ("error", &e)],
+                ));
+                remaining.push(entry);
             }
-            Err(_) => {
-                let warning = BoundaryWarning::UnparsableTag {
-                    tag: tag.clone(),
-                    reason: "Version number format not recognized".to_string(),
-                };
-                ui::display_boundary_warning(&warning);
-
-                if !args.force
-                    && !args.dry_run
-                    && !ui::confirm_action("Use initial version v0.1.0 and continue?")?
-                {
-                    println!("Operation cancelled by user.");
-                    return Ok(());
-                }
+        }
+    }
+    outbox.save(&remaining)?;
 
-                let new_version = Version::new(0, 1, 0);
-                let new_tag = new_tag_pattern.replace("{version}", &new_version.to_string());
-                ui::display_proposed_tag(latest_tag.as_deref(), &new_tag);
+    if remaining.is_empty() {
+        println!("\n\x1b[32m✓\x1b[0m {}\n", text(Msg::AllQueuedPushed));
+        Ok(())
+    } else {
+        anyhow::bail!(tr(Msg::QueuedPushesPending, &[("count", &remaining.len())]))
+    }
+}
 
-                if !args.force && !args.dry_run {
-                    ui::select_or_customize_tag(&new_tag, &new_tag_pattern)?
-                } else {
-                    new_tag
-                }
-            }
-        },
-        None => {
-            let new_version = Version::new(0, 1, 0);
-            let new_tag = new_tag_pattern.replace("{version}", &new_version.to_string());
-            ui::display_proposed_tag(latest_tag.as_deref(), &new_tag);
-
-            if !args.force && !args.dry_run {
-                ui::select_or_customize_tag(&new_tag, &new_tag_pattern)?
-            } else {
-                new_tag
-            }
+/// Rewrite the configuration for the current `config_version`.
+fn migrate_config(args: &Args) -> Result<()> {
+    let source = match config::find_config_source(args.config.as_deref()) {
+        Ok(Some(source)) => source,
+        Ok(None) => {
+            ui::display_status(text(Msg::NoConfigToMigrate));
+            return Ok(());
         }
+        Err(e) => anyhow::bail!(e.to_string()),
     };
+    let path = source.path().display().to_string();
 
-    // Confirm tag use (checks format and gets user confirmation)
-    if !args.force && !args.dry_run && !ui::confirm_tag_use(&final_tag, &new_tag_pattern)? {
-        println!("Tag creation cancelled by user.");
+    let report = config_migrate::migrate_source(&source)?;
+    if report.is_up_to_date() {
+        ui::display_success(&tr(Msg::ConfigUpToDate, &[("path", &path)]));
         return Ok(());
     }
+    for deprecation in &report.deprecations {
+        ui::display_status(&tr(
+            Msg::ConfigKeyRenamed,
+            &[
+                ("key", &deprecation.key),
+                ("replacement", &deprecation.replacement),
+            ],
+        ));
+    }
+    ui::display_success(&tr(
+        Msg::ConfigMigrated,
+        &[
+            ("path", &path),
+            ("from", &report.from_version),
+            ("to", &config_migrate::CURRENT_CONFIG_VERSION),
+        ],
+    ));
+    Ok(())
+}
 
-    if args.dry_run {
-        ui::display_status("Dry run mode:");
-        ui::display_success(&format!("  Step 1: Will create local tag: {}", final_tag));
-        ui::display_success("  Step 2: Will ask whether to push tag to remote");
-        ui::display_success(&format!(
-            "  Step 3: (Optional) Push {} to '{}'",
-            final_tag, selected_remote
+/// Write a starter gitpublish.toml to the repository root, unless a
+/// configuration is already found. With `interactive`, ask which branches
+/// to tag and their tag prefixes instead of using the detected defaults.
+fn init_config(interactive: bool) -> Result<()> {
+    if let Ok(Some(source)) = config::find_config_source(None) {
+        ui::display_warning(&tr(
+            Msg::ConfigAlreadyExists,
+            &[("path", &source.path().display())],
         ));
         return Ok(());
     }
-
-    // Create the tag on the target branch (not on current HEAD)
-    ui::display_status(&format!("Creating tag: {}", final_tag));
-    if let Err(e) = git_repo.create_tag(&final_tag, Some(&branch_to_tag)) {
-        ui::display_error(&format!("Failed to create tag '{}': {}", final_tag, e));
+    let (Some(path), Ok(git_repo)) = (config::init_config_path(), git_ops::GitRepo::new()) else {
+        ui::display_error(text(Msg::NotInRepository));
         std::process::exit(1);
-    }
-    ui::display_success(&format!("Created tag: {}", final_tag));
+    };
 
-    // Step 2: Ask user whether to push the tag
-    let should_push = if !args.force {
-        ui::confirm_push_tag(&final_tag, &selected_remote)?
+    let branches = git_repo.list_branches()?;
+    let defaults = config::default_release_branches(&branches);
+    // Branches that already have release tags keep their tag format
+    let detected = |branch: &str| {
+        let tag = git_repo.get_latest_version_tag_on_branch(branch).ok()??;
+        let pattern = TagPattern::detect(&tag)?;
+        ui::display_status(&tr(
+            Msg::InitDetectedPattern,
+            &[
+                ("branch", &branch),
+                ("tag", &tag),
+                ("pattern", &pattern.as_str()),
+            ],
+        ));
+        Some(pattern.pattern)
+    };
+
+    let release_branches = if interactive {
+        let mut release_branches = Vec::new();
+        for branch in &branches {
+            let default = defaults.iter().find(|(name, _)| name == branch);
+            if !ui::confirm_with_default(
+                &tr(Msg::InitTagBranch, &[("branch", branch)]),
+                default.is_some(),
+            )? {
+                continue;
+            }
+            let pattern = detected(branch)
+                .or_else(|| default.map(|(_, pattern)| pattern.clone()))
+                .unwrap_or_else(|| "v{version}".to_string());
+            let default_prefix = pattern.split("{version}").next().unwrap_or_default();
+            let pattern = loop {
+                let prefix = ui::input_with_default(
+                    &tr(Msg::InitTagPrefix, &[("branch", branch)]),
+                    default_prefix,
+                )?;
+                match TagPattern::parse(&format!("{}{{version}}", prefix)) {
+                    Ok(pattern) => break pattern.pattern,
+                    Err(e) => ui::display_error(&tr(Msg::InitInvalidPrefix, &[("error", &e)])),
+                }
+            };
+            release_branches.push((branch.clone(), pattern));
+        }
+        if release_branches.is_empty() {
+            ui::display_warning(text(Msg::InitNoBranches));
+            return Ok(());
+        }
+        release_branches
     } else {
-        true // In force mode, push automatically
+        defaults
+            .into_iter()
+            .map(|(branch, pattern)| {
+                let pattern = detected(&branch).unwrap_or(pattern);
+                (branch, pattern)
+            })
+            .collect()
     };
 
-    // Step 3: Push if user confirmed (or in force mode)
-    if should_push {
-        ui::display_status(&format!(
-            "Pushing tag: {} to remote '{}'",
-            final_tag, selected_remote
-        ));
-        if let Err(e) = git_repo.push_tag(&final_tag, &selected_remote) {
-            ui::display_error(&format!("Failed to push tag '{}': {}", final_tag, e));
-            std::process::exit(1);
+    std::fs::write(&path, config::scaffold_config(&release_branches))?;
+    ui::display_success(&tr(Msg::ConfigCreated, &[("path", &path.display())]));
+    Ok(())
+}
+
+/// Print how a commit, or a message when no commit goes by that name, is
+/// classified.
+fn print_explanation(args: &Args, commit: &str) -> Result<()> {
+    let config = load_config_or_exit(args.config.as_deref());
+    let mut git_repo = git_ops::GitRepo::new().ok();
+    if let Some(repo) = git_repo.as_mut() {
+        repo.add_ignored_paths(&config.behavior.ignore_paths);
+    }
+    let message = git_repo
+        .as_ref()
+        .and_then(|repo| repo.commit_message(commit))
+        .unwrap_or_else(|| commit.to_string());
+    let explanation = explain_commit(&message, &config.conventional_commits);
+    ui::display_commit_explanation(message.lines().next().unwrap_or_default(), &explanation);
+
+    // A commit only changing ignored paths doesn't count, whatever its type
+    let files = git_repo
+        .as_ref()
+        .and_then(|repo| Some((repo, repo.changed_files(commit).ok()?)));
+    if let Some((repo, files)) = files {
+        if !files.is_empty() && files.iter().all(|file| repo.is_path_ignored(file)) {
+            ui::display_warning(text(Msg::ExplainIgnoredPaths));
         }
-        ui::display_success(&format!("Pushed tag: {} to remote", final_tag));
+    }
+    Ok(())
+}
 
-        println!(
-            "\n\x1b[32m✓\x1b[0m Successfully published tag {} for branch {}\n",
-            final_tag, branch_to_tag
-        );
+/// Report a newer release of git-publish, installing it with `install`.
+#[cfg(feature = "self-update")]
+fn self_update(install: bool) -> Result<()> {
+    let current = Version::parse(env!("CARGO_PKG_VERSION"))?;
+    let release = self_update::latest_release()?;
+    let Some(latest) = release.newer_than(&current)? else {
+        ui::display_success(&tr(Msg::SelfUpdateUpToDate, &[("version", &current)]));
+        return Ok(());
+    };
+    ui::display_status(&tr(
+        Msg::SelfUpdateAvailable,
+        &[
+            ("latest", &latest),
+            ("current", &current),
+            ("url", &release.html_url),
+        ],
+    ));
+    if !install {
+        println!("{}", text(Msg::SelfUpdateHowTo));
+        return Ok(());
+    }
+
+    let (os, arch) = (std::env::consts::OS, std::env::consts::ARCH);
+    let Some(asset) = release.asset_for(os, arch) else {
+        ui::display_error(&tr(
+            Msg::SelfUpdateNoAsset,
+            &[
+                ("version", &latest),
+                ("platform", &format!("{}-{}", arch, os)),
+            ],
+        ));
+        std::process::exit(1);
+    };
+    let Some(checksum) = release.checksum_for(asset) else {
+        ui::display_error(&tr(
+            Msg::SelfUpdateNoChecksum,
+            &[("version", &latest), ("asset", &asset.name)],
+        ));
+        std::process::exit(1);
+    };
+    let exe = std::env::current_exe()?;
+    self_update::install(asset, checksum, &exe)?;
+    ui::display_success(&tr(
+        Msg::SelfUpdateInstalled,
+        &[("version", &latest), ("path", &exe.display())],
+    ));
+    Ok(())
+}
+
+/// Print the version bump of the commit messages read from stdin.
+fn print_message_analysis(args: &Args, json: bool) -> Result<()> {
+    let config = load_config_or_exit(args.config.as_deref());
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    let messages = message_analysis::split_messages(&input);
+    let analysis = MessageAnalysis::new(&messages, &config.conventional_commits);
+
+    if json {
+        println!("{}", analysis.to_json()?);
     } else {
-        // Tag created locally, but not pushed
-        ui::display_manual_push_instruction(&final_tag, &selected_remote);
+        ui::display_message_analysis(&analysis);
+    }
+    Ok(())
+}
 
-        println!(
-            "\n\x1b[32m✓\x1b[0m Tag {} created locally for branch {}\n",
-            final_tag, branch_to_tag
-        );
+/// Print a summary of the releases made since `since`, or write it as an
+/// HTML dashboard to `html`.
+fn print_report(args: &Args, since: &str, format: ReportFormat, html: Option<&Path>) -> Result<()> {
+    let (config, mut git_repo) = open_config_and_repo(args);
+    git_repo.add_ignored_paths(&config.behavior.ignore_paths);
+
+    let now = chrono::Utc::now();
+    let since = report::parse_since(since, now)?;
+    let release_report = report::build_report(&git_repo, &config, args.branch(), since, now)?;
+
+    if let Some(dir) = html {
+        // Pull request links need the repository's web URL
+        let repo_url = git_repo
+            .remote_url(args.remote.as_deref().unwrap_or("origin"))
+            .ok()
+            .flatten()
+            .and_then(|url| git_ops::web_url(&url));
+        let dashboard = dashboard::build_dashboard(
+            &git_repo,
+            &config,
+            args.branch(),
+            release_report,
+            repo_url.as_deref(),
+        )?;
+        let path = dashboard.write_to(dir)?;
+        ui::display_success(&tr(Msg::DashboardWritten, &[("path", &path.display())]));
+        return Ok(());
+    }
+
+    match format {
+        ReportFormat::Markdown => print!("{}", release_report.to_markdown()),
+        ReportFormat::Json => println!("{}", release_report.to_json()?),
     }
+    Ok(())
+}
 
+/// Print the release statistics of the configured branches.
+fn print_stats(args: &Args, format: ReportFormat) -> Result<()> {
+    let (config, git_repo) = open_config_and_repo(args);
+
+    let release_stats = stats::build_stats(&git_repo, &config, args.branch())?;
+    match format {
+        ReportFormat::Markdown => print!("{}", release_stats.to_markdown()),
+        ReportFormat::Json => println!("{}", release_stats.to_json()?),
+    }
     Ok(())
 }
 
-fn list_configured_branches(config_path: Option<&str>) -> Result<()> {
-    let config = match config::load_config(config_path) {
-        Ok(cfg) => cfg,
-        Err(e) => {
-            eprintln!("Error loading config: {}", e);
-            std::process::exit(1);
+fn print_tag_list(args: &Args, format: ReportFormat) -> Result<()> {
+    let (config, git_repo) = open_config_and_repo(args);
+
+    let pattern = args
+        .branch()
+        .and_then(|branch| config.branches.get(branch))
+        .map(TagPattern::new);
+    let tags = git_repo.list_tag_details(pattern.as_ref().map(|p| p.pattern.as_str()))?;
+    let tag_list = TagList::new(tags, pattern.as_ref());
+    match format {
+        ReportFormat::Markdown => print!("{}", tag_list.to_markdown()),
+        ReportFormat::Json => println!("{}", tag_list.to_json()?),
+    }
+    Ok(())
+}
+
+/// Show what a release would have been with the branch head at `at`.
+fn print_simulation(args: &Args, at: &str, format: ReportFormat) -> Result<()> {
+    let (config, mut git_repo) = open_config_and_repo(args);
+    git_repo.add_ignored_paths(&config.behavior.ignore_paths);
+
+    if let Err(e) = git_repo.resolve_commit(at) {
+        ui::display_error(&e.to_string());
+        std::process::exit(1);
+    }
+    // Without --branch, the first configured branch with the commit in its history
+    let branch = match args.branch() {
+        Some(branch) => branch.to_string(),
+        None => {
+            let mut branches: Vec<&String> = config.branches.keys().collect();
+            branches.sort();
+            match branches
+                .into_iter()
+                .find(|branch| git_repo.is_commit_on_branch(branch, at).unwrap_or(false))
+            {
+                Some(branch) => branch.clone(),
+                None => {
+                    ui::display_error(&tr(Msg::SimulateNoBranch, &[("commit", &at)]));
+                    std::process::exit(1);
+                }
+            }
         }
     };
+
+    let simulation = simulation::simulate(&git_repo, &config, &branch, at)?;
+    match format {
+        ReportFormat::Markdown => print!("{}", simulation.to_markdown()),
+        ReportFormat::Json => println!("{}", simulation.to_json()?),
+    }
+    Ok(())
+}
+
+fn list_configured_branches(config_path: Option<&str>) -> Result<()> {
+    let config = load_config_or_exit(config_path);
     let mut branches: Vec<String> = config.branches.keys().cloned().collect();
     branches.sort();
 
     if branches.is_empty() {
-        ui::display_error("No branches configured for tagging in gitpublish.toml");
+        ui::display_error(text(Msg::NoBranchesConfigured));
         std::process::exit(1);
     }
 
-    ui::display_available_branches(&branches);
+    // Outside a repository there is nothing to query: just list the branches
+    let Ok(git_repo) = git_ops::GitRepo::new() else {
+        ui::display_available_branches(&branches);
+        return Ok(());
+    };
+
+    let existing_branches = git_repo.list_branches()?;
+    let overviews: Vec<ui::BranchOverview> = branches
+        .into_iter()
+        .map(|branch| branch_overview(&git_repo, &config, &existing_branches, branch))
+        .collect();
+    ui::display_branch_dashboard(&overviews);
     Ok(())
 }
+
+/// Latest tag, pending commits and tentative next tag of a configured branch,
+/// from local data only.
+fn branch_overview(
+    git_repo: &git_ops::GitRepo,
+    config: &config::Config,
+    existing_branches: &[String],
+    branch: String,
+) -> ui::BranchOverview {
+    let pattern = config.branches.get(&branch).cloned().unwrap_or_default();
+    let mut overview = ui::BranchOverview {
+        exists: existing_branches.contains(&branch),
+        branch,
+        pattern,
+        latest_tag: None,
+        pending_commits: 0,
+        next_tag: None,
+    };
+    if !overview.exists {
+        return overview;
+    }
+
+    overview.latest_tag = timings::measure(Phase::TagScan, || {
+        git_repo.get_latest_tag_on_branch(&overview.branch, Some(&overview.pattern))
+    })
+    .ok()
+    .flatten();
+    // Count and classify the pending commits without collecting them
+    let mut pending_commits = 0;
+    let mut bump_analysis = BumpAnalysis::default();
+    let _ = timings::measure(Phase::Revwalk, || {
+        git_repo.walk_commits_since_tag(
+            &overview.branch,
+            overview.latest_tag.as_deref(),
+            |commit| {
+                pending_commits += 1;
+                if let Some(message) = commit.message() {
+                    timings::measure(Phase::Analysis, || {
+                        bump_analysis.add(message, &config.conventional_commits)
+                    });
+                }
+                ControlFlow::Continue(())
+            },
+        )
+    });
+    overview.pending_commits = pending_commits;
+
+    let next_version = match overview.latest_tag.as_deref() {
+        Some(tag) => Version::parse(tag)
+            .ok()
+            .map(|version| version.bump(&bump_analysis.bump())),
+        None => Some(Version::new(0, 1, 0)),
+    };
+    overview.next_tag = next_version
+        .zip(config.branches.tag_pattern(&overview.branch))
+        .map(|(version, pattern)| pattern.format(&version.to_string()));
+    overview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Args::command().debug_assert();
+    }
+
+    #[test]
+    fn test_publish_subcommands_keep_bare_flags() {
+        let bare = Args::try_parse_from(["git-publish", "--dry-run", "-b", "main"]).unwrap();
+        assert!(bare.publish.dry_run);
+        assert_eq!(bare.branch(), Some("main"));
+        assert_eq!(bare.command, None);
+
+        let publish =
+            Args::try_parse_from(["git-publish", "publish", "-b", "main", "--force"]).unwrap();
+        assert_eq!(publish.branch(), Some("main"));
+
+        let several =
+            Args::try_parse_from(["git-publish", "-b", "main", "--branch", "develop"]).unwrap();
+        assert_eq!(several.branch, ["main", "develop"]);
+        assert!(matches!(
+            publish.command,
+            Some(Command::Publish(PublishArgs { force: true, .. }))
+        ));
+
+        let preview = Args::try_parse_from(["git-publish", "preview", "--bump", "minor"]).unwrap();
+        assert!(matches!(
+            preview.command,
+            Some(Command::Preview(PublishArgs {
+                bump: Some(BumpArg::Minor),
+                ..
+            }))
+        ));
+
+        assert!(
+            Args::try_parse_from(["git-publish", "publish", "--all-branches", "-b", "main"])
+                .is_err()
+        );
+        assert!(Args::try_parse_from(["git-publish", "--list", "--json"]).is_err());
+    }
+
+    #[test]
+    fn test_release_options_before_the_subcommand_are_rejected() {
+        for argv in [
+            &["git-publish", "--dry-run", "-b", "main"][..],
+            &["git-publish", "publish", "--dry-run", "-b", "main"],
+            &["git-publish", "preview", "-b", "main"],
+        ] {
+            let args = Args::try_parse_from(argv).unwrap();
+            assert!(args.release_options().unwrap().dry_run, "{:?}", argv);
+        }
+
+        let before = Args::try_parse_from([
+            "git-publish",
+            "--dry-run",
+            "publish",
+            "-b",
+            "main",
+            "--force",
+        ])
+        .unwrap();
+        assert!(before.release_options().is_err());
+        let before = Args::try_parse_from(["git-publish", "--force", "preview"]).unwrap();
+        assert!(before.release_options().is_err());
+    }
+}
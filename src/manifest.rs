@@ -0,0 +1,468 @@
+//! Package manifests as the source of truth for the current version.
+//!
+//! With `version_source = "manifest"`, the current version is read from the
+//! repository's `Cargo.toml` or `package.json` instead of the latest tag, and
+//! the bumped version is written back so the manifest and the tag agree.
+//!
+//! Edits are made in place on the file's text, so formatting, comments and key
+//! order are left untouched. A Cargo package's `Cargo.lock` entry is kept in
+//! step with its manifest.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use toml_edit::{DocumentMut, Item};
+
+use crate::domain::Version;
+use crate::error::{GitPublishError, Result};
+
+/// Supported manifest formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestKind {
+    /// Rust `Cargo.toml` (`package.version`, or `workspace.package.version`)
+    Cargo,
+    /// Node.js `package.json` (top-level `version`)
+    Npm,
+}
+
+impl ManifestKind {
+    /// Manifest kinds in lookup order
+    pub const ALL: [ManifestKind; 2] = [ManifestKind::Cargo, ManifestKind::Npm];
+
+    /// File name of this manifest in the repository root
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ManifestKind::Cargo => "Cargo.toml",
+            ManifestKind::Npm => "package.json",
+        }
+    }
+}
+
+/// A manifest file holding the package version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    pub kind: ManifestKind,
+    pub path: PathBuf,
+}
+
+impl Manifest {
+    /// Find the manifest in a repository root, preferring `Cargo.toml`
+    pub fn find(root: &Path) -> Option<Manifest> {
+        ManifestKind::ALL.into_iter().find_map(|kind| {
+            let path = root.join(kind.file_name());
+            path.is_file().then_some(Manifest { kind, path })
+        })
+    }
+
+    /// Read the version currently declared in the manifest
+    pub fn read_version(&self) -> Result<Version> {
+        let content = fs::read_to_string(&self.path)?;
+        let version = match self.kind {
+            ManifestKind::Cargo => cargo_version(&content)?.1,
+            ManifestKind::Npm => npm_version(&content)?,
+        };
+        Version::parse(&version)
+            .map_err(|e| GitPublishError::manifest(format!("{}: {}", self.path.display(), e)))
+    }
+
+    /// Replace the declared version with `version`
+    ///
+    /// For `Cargo.toml`, the package's entries in the `Cargo.lock` next to it
+    /// are updated too. Nothing is left half-written: when a file can't be
+    /// written, the ones already changed are restored.
+    pub fn write_version(&self, version: &Version) -> Result<ManifestUpdate> {
+        let version = version.to_string();
+        let content = fs::read_to_string(&self.path)?;
+        let mut files = Vec::new();
+        match self.kind {
+            ManifestKind::Cargo => {
+                files.push((
+                    self.path.clone(),
+                    set_cargo_version(&content, &version)?,
+                    content.clone(),
+                ));
+                let lock_path = self.path.with_file_name(CARGO_LOCK);
+                if let Some(lock) = read_optional(&lock_path)? {
+                    let root = self.path.parent().unwrap_or(Path::new("."));
+                    let names = cargo_package_names(root, &content)?;
+                    let updated = set_lock_version(&lock, &names, &version)?;
+                    if updated != lock {
+                        files.push((lock_path, updated, lock));
+                    }
+                }
+            }
+            ManifestKind::Npm => {
+                files.push((
+                    self.path.clone(),
+                    set_npm_version(&content, &version)?,
+                    content,
+                ));
+            }
+        }
+
+        let mut update = ManifestUpdate::default();
+        for (path, updated, original) in files {
+            if let Err(e) = fs::write(&path, updated) {
+                // Best effort: the write error is the one worth reporting
+                let _ = update.restore();
+                return Err(e.into());
+            }
+            update.files.push((path, original));
+        }
+        Ok(update)
+    }
+}
+
+/// Files rewritten by [`Manifest::write_version`], with their previous content
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestUpdate {
+    files: Vec<(PathBuf, String)>,
+}
+
+impl ManifestUpdate {
+    /// The rewritten files, the manifest first
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+
+    /// Put the rewritten files back as they were
+    pub fn restore(&self) -> Result<()> {
+        for (path, original) in &self.files {
+            fs::write(path, original)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lock file of a Cargo package or workspace
+const CARGO_LOCK: &str = "Cargo.lock";
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Find the section holding the version and the version itself
+///
+/// A package that inherits its version (`version.workspace = true`) is
+/// versioned through `[workspace.package]`.
+fn cargo_version(content: &str) -> Result<(&'static str, String)> {
+    let value: toml::Value =
+        toml::from_str(content).map_err(|e| GitPublishError::manifest(e.to_string()))?;
+    let package_version = value.get("package").and_then(|p| p.get("version"));
+    if let Some(version) = package_version.and_then(|v| v.as_str()) {
+        return Ok(("package", version.to_string()));
+    }
+    value
+        .get("workspace")
+        .and_then(|w| w.get("package"))
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str())
+        .map(|version| ("workspace.package", version.to_string()))
+        .ok_or_else(|| GitPublishError::manifest("Cargo.toml does not declare a version"))
+}
+
+fn set_cargo_version(content: &str, version: &str) -> Result<String> {
+    let (section, _) = cargo_version(content)?;
+    let mut document: DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| GitPublishError::manifest(e.to_string()))?;
+    let table = section
+        .split('.')
+        .try_fold(document.as_item_mut(), |item, key| item.get_mut(key))
+        .ok_or_else(|| {
+            GitPublishError::manifest(format!("could not find the [{}] section", section))
+        })?;
+    let value = table
+        .get_mut("version")
+        .and_then(Item::as_value_mut)
+        .ok_or_else(|| {
+            GitPublishError::manifest(format!("could not find the version in [{}]", section))
+        })?;
+    replace_keeping_decor(value, version);
+    Ok(document.to_string())
+}
+
+/// Replace a string value, keeping the whitespace and comments around it
+fn replace_keeping_decor(value: &mut toml_edit::Value, version: &str) {
+    let decor = value.decor().clone();
+    *value = version.into();
+    *value.decor_mut() = decor;
+}
+
+/// Names of the packages versioned by the manifest at `root`
+///
+/// With a `[workspace.package]` version, these are the packages inheriting it:
+/// the root package and the members listed in `workspace.members`, either as
+/// paths or as `dir/*` globs.
+fn cargo_package_names(root: &Path, content: &str) -> Result<Vec<String>> {
+    let (section, _) = cargo_version(content)?;
+    let value: toml::Value =
+        toml::from_str(content).map_err(|e| GitPublishError::manifest(e.to_string()))?;
+    let package_name = |value: &toml::Value| {
+        value
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+            .map(str::to_string)
+    };
+    let inherits_version = |value: &toml::Value| {
+        value
+            .get("package")
+            .and_then(|p| p.get("version"))
+            .and_then(|v| v.get("workspace"))
+            .and_then(|w| w.as_bool())
+            == Some(true)
+    };
+    if section == "package" {
+        return Ok(package_name(&value).into_iter().collect());
+    }
+
+    let mut names = Vec::new();
+    if inherits_version(&value) {
+        names.extend(package_name(&value));
+    }
+    let members = value
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|member| member.as_str());
+    for member in members {
+        let dirs = match member.strip_suffix("/*") {
+            Some(parent) => match fs::read_dir(root.join(parent)) {
+                Ok(entries) => entries
+                    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                    .collect(),
+                Err(_) => Vec::new(),
+            },
+            None => vec![root.join(member)],
+        };
+        for dir in dirs {
+            let Some(content) = read_optional(&dir.join(ManifestKind::Cargo.file_name()))? else {
+                continue;
+            };
+            let Ok(member) = toml::from_str::<toml::Value>(&content) else {
+                continue;
+            };
+            if inherits_version(&member) {
+                names.extend(package_name(&member));
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Set the version of the local packages named `names` in a `Cargo.lock`
+///
+/// Packages from a registry or git have a `source`; only the workspace's own
+/// packages are touched.
+fn set_lock_version(content: &str, names: &[String], version: &str) -> Result<String> {
+    let mut document: DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| GitPublishError::manifest(e.to_string()))?;
+    let packages = document
+        .get_mut("package")
+        .and_then(Item::as_array_of_tables_mut);
+    for package in packages
+        .into_iter()
+        .flat_map(|packages| packages.iter_mut())
+    {
+        let local = !package.contains_key("source");
+        let named = package
+            .get("name")
+            .and_then(Item::as_str)
+            .is_some_and(|name| names.iter().any(|n| n == name));
+        if local && named {
+            if let Some(value) = package.get_mut("version").and_then(Item::as_value_mut) {
+                replace_keeping_decor(value, version);
+            }
+        }
+    }
+    Ok(document.to_string())
+}
+
+fn npm_version(content: &str) -> Result<String> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| GitPublishError::manifest(e.to_string()))?;
+    value
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| GitPublishError::manifest("package.json does not declare a version"))
+}
+
+fn set_npm_version(content: &str, version: &str) -> Result<String> {
+    npm_version(content)?;
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            b'"' => {
+                let end = json_string_end(bytes, i)?;
+                if depth == 1 && &content[i + 1..end] == "version" {
+                    let value = content[end + 1..].trim_start();
+                    if let Some(value) = value.strip_prefix(':').map(str::trim_start) {
+                        if value.starts_with('"') {
+                            let start = content.len() - value.len();
+                            let close = json_string_end(bytes, start)?;
+                            return Ok(format!(
+                                "{}\"{}\"{}",
+                                &content[..start],
+                                version,
+                                &content[close + 1..]
+                            ));
+                        }
+                    }
+                }
+                i = end;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    Err(GitPublishError::manifest(
+        "could not find the version field in package.json",
+    ))
+}
+
+/// Index of the quote closing the JSON string that starts at `start`
+fn json_string_end(bytes: &[u8], start: usize) -> Result<usize> {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Ok(i),
+            _ => i += 1,
+        }
+    }
+    Err(GitPublishError::manifest(
+        "unterminated string in package.json",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_cargo_version_keeps_formatting() {
+        let content = "[package]\nname = \"demo\"\nversion   = \"0.3.1\" # bumped on release\n\n[dependencies]\nserde = { version = \"1.0\" }\n";
+        let updated = set_cargo_version(content, "0.4.0").unwrap();
+        assert_eq!(
+            updated,
+            "[package]\nname = \"demo\"\nversion   = \"0.4.0\" # bumped on release\n\n[dependencies]\nserde = { version = \"1.0\" }\n"
+        );
+    }
+
+    #[test]
+    fn test_cargo_workspace_version() {
+        let content = "[package]\nname = \"demo\"\nversion.workspace = true\n\n[workspace.package]\nversion = \"2.0.0\"\n";
+        assert_eq!(
+            cargo_version(content).unwrap(),
+            ("workspace.package", "2.0.0".to_string())
+        );
+        let updated = set_cargo_version(content, "2.1.0").unwrap();
+        assert!(updated.contains("version.workspace = true"));
+        assert!(updated.ends_with("[workspace.package]\nversion = \"2.1.0\"\n"));
+    }
+
+    #[test]
+    fn test_set_lock_version_only_touches_local_packages() {
+        let content = "version = 3\n\n[[package]]\nname = \"demo\"\nversion = \"0.3.1\"\n\n\
+                       [[package]]\nname = \"demo\"\nversion = \"0.3.1\"\n\
+                       source = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n\
+                       [[package]]\nname = \"other\"\nversion = \"0.3.1\"\n";
+        let updated = set_lock_version(content, &["demo".to_string()], "0.4.0").unwrap();
+        assert_eq!(
+            updated,
+            content.replacen("version = \"0.3.1\"", "version = \"0.4.0\"", 1)
+        );
+    }
+
+    #[test]
+    fn test_write_version_updates_workspace_members_in_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\", \"tool\"]\n\n[workspace.package]\nversion = \"1.0.0\"\n",
+        )
+        .unwrap();
+        for (dir_name, manifest) in [
+            (
+                "crates/core",
+                "[package]\nname = \"core\"\nversion.workspace = true\n",
+            ),
+            ("tool", "[package]\nname = \"tool\"\nversion = \"0.1.0\"\n"),
+        ] {
+            fs::create_dir_all(dir.path().join(dir_name)).unwrap();
+            fs::write(dir.path().join(dir_name).join("Cargo.toml"), manifest).unwrap();
+        }
+        let lock = "version = 3\n\n[[package]]\nname = \"core\"\nversion = \"1.0.0\"\n\n\
+                    [[package]]\nname = \"tool\"\nversion = \"0.1.0\"\n";
+        fs::write(dir.path().join("Cargo.lock"), lock).unwrap();
+
+        let manifest = Manifest::find(dir.path()).unwrap();
+        let update = manifest.write_version(&Version::new(1, 1, 0)).unwrap();
+        assert_eq!(
+            update.paths().collect::<Vec<_>>(),
+            vec![dir.path().join("Cargo.toml"), dir.path().join("Cargo.lock")]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("Cargo.lock")).unwrap(),
+            lock.replace("\"1.0.0\"", "\"1.1.0\"")
+        );
+
+        update.restore().unwrap();
+        assert_eq!(manifest.read_version().unwrap(), Version::new(1, 0, 0));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("Cargo.lock")).unwrap(),
+            lock
+        );
+    }
+
+    #[test]
+    fn test_set_npm_version_only_touches_top_level() {
+        let content = "{\n  \"name\": \"version\",\n  \"engines\": { \"version\": \"18\" },\n  \"version\": \"1.2.3\",\n  \"dependencies\": {}\n}\n";
+        let updated = set_npm_version(content, "1.3.0").unwrap();
+        assert_eq!(
+            updated,
+            "{\n  \"name\": \"version\",\n  \"engines\": { \"version\": \"18\" },\n  \"version\": \"1.3.0\",\n  \"dependencies\": {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_manifest_without_version() {
+        assert!(cargo_version("[workspace]\nmembers = []\n").is_err());
+        assert!(npm_version("{\"name\": \"demo\"}").is_err());
+    }
+
+    #[test]
+    fn test_find_prefers_cargo() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(Manifest::find(dir.path()), None);
+
+        fs::write(dir.path().join("package.json"), "{\"version\": \"1.0.0\"}").unwrap();
+        let manifest = Manifest::find(dir.path()).unwrap();
+        assert_eq!(manifest.kind, ManifestKind::Npm);
+        assert_eq!(manifest.read_version().unwrap(), Version::new(1, 0, 0));
+
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.2.0\"\n",
+        )
+        .unwrap();
+        let manifest = Manifest::find(dir.path()).unwrap();
+        assert_eq!(manifest.kind, ManifestKind::Cargo);
+        manifest.write_version(&Version::new(0, 3, 0)).unwrap();
+        assert_eq!(manifest.read_version().unwrap(), Version::new(0, 3, 0));
+    }
+}
@@ -0,0 +1,140 @@
+//! Queue of tag pushes deferred while the remote was unreachable.
+//!
+//! Entries are stored one per line as `<remote>\t<tag>` in `.git/gitpublish-outbox`,
+//! so queued pushes survive across runs until `git-publish flush` delivers them.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+
+/// File name of the outbox inside the repository's git directory
+pub const OUTBOX_FILE: &str = "gitpublish-outbox";
+
+/// A tag push waiting to be delivered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedPush {
+    /// Remote the tag should be pushed to
+    pub remote: String,
+    /// Name of the local tag
+    pub tag: String,
+}
+
+/// Persistent queue of pending tag pushes
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    path: PathBuf,
+}
+
+impl Outbox {
+    /// Outbox stored in the given git directory (e.g. `.git`)
+    pub fn in_git_dir(git_dir: &Path) -> Self {
+        Outbox {
+            path: git_dir.join(OUTBOX_FILE),
+        }
+    }
+
+    /// Path of the outbox file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read all queued pushes in the order they were queued
+    pub fn load(&self) -> Result<Vec<QueuedPush>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)?;
+        Ok(content
+            .lines()
+            .filter_map(|line| {
+                let (remote, tag) = line.split_once('\t')?;
+                if remote.is_empty() || tag.is_empty() {
+                    return None;
+                }
+                Some(QueuedPush {
+                    remote: remote.to_string(),
+                    tag: tag.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// Queue a push; pushes already queued are not duplicated
+    pub fn enqueue(&self, remote: &str, tag: &str) -> Result<()> {
+        let mut entries = self.load()?;
+        let entry = QueuedPush {
+            remote: remote.to_string(),
+            tag: tag.to_string(),
+        };
+        if !entries.contains(&entry) {
+            entries.push(entry);
+        }
+        self.save(&entries)
+    }
+
+    /// Replace the queue contents; an empty queue removes the outbox file
+    pub fn save(&self, entries: &[QueuedPush]) -> Result<()> {
+        if entries.is_empty() {
+            if self.path.exists() {
+                fs::remove_file(&self.path)?;
+            }
+            return Ok(());
+        }
+        let content: String = entries
+            .iter()
+            .map(|e| format!("{}\t{}\n", e.remote, e.tag))
+            .collect();
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_enqueue_and_load() {
+        let dir = TempDir::new().unwrap();
+        let outbox = Outbox::in_git_dir(dir.path());
+        assert!(outbox.load().unwrap().is_empty());
+
+        outbox.enqueue("origin", "v1.0.0").unwrap();
+        outbox.enqueue("upstream", "v1.0.0").unwrap();
+        outbox.enqueue("origin", "v1.0.0").unwrap();
+
+        let entries = outbox.load().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].remote, "origin");
+        assert_eq!(entries[1].remote, "upstream");
+        assert_eq!(outbox.path(), dir.path().join(OUTBOX_FILE));
+    }
+
+    #[test]
+    fn test_save_empty_removes_file() {
+        let dir = TempDir::new().unwrap();
+        let outbox = Outbox::in_git_dir(dir.path());
+        outbox.enqueue("origin", "v1.0.0").unwrap();
+        assert!(outbox.path().exists());
+
+        outbox.save(&[]).unwrap();
+        assert!(!outbox.path().exists());
+        assert!(outbox.load().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_load_skips_malformed_lines() {
+        let dir = TempDir::new().unwrap();
+        let outbox = Outbox::in_git_dir(dir.path());
+        fs::write(
+            outbox.path(),
+            "origin\tv1.0.0\ngarbage\n\tv2\norigin\tv1.1.0\n",
+        )
+        .unwrap();
+
+        let tags: Vec<String> = outbox.load().unwrap().into_iter().map(|e| e.tag).collect();
+        assert_eq!(tags, vec!["v1.0.0", "v1.1.0"]);
+    }
+}
@@ -0,0 +1,227 @@
+//! `.gitpublishignore`: paths whose changes never count toward a release.
+//!
+//! The file sits in the repository root and lists paths in gitignore syntax,
+//! typically generated code and vendored dependencies. A commit that only
+//! changes such paths is left out of the commit walk, so it neither bumps the
+//! version nor shows up in the changelog. Commits that also change other
+//! files, merge commits and empty commits still count.
+//!
+//! Supported syntax: `#` comments, `!` to re-include, `*`, `?`, `**` and
+//! `[...]` wildcards, a trailing `/` for directories only, and a leading or
+//! inner `/` to anchor a pattern to the repository root (otherwise it matches
+//! a file or directory name at any depth).
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Component, Path};
+
+use regex::Regex;
+
+/// Name of the ignore file in the repository root
+pub const IGNORE_FILE: &str = ".gitpublishignore";
+
+/// One pattern line of the ignore file
+#[derive(Debug, Clone)]
+struct Rule {
+    regex: Regex,
+    /// `!pattern`: re-include what earlier patterns excluded
+    negated: bool,
+    /// `pattern/`: only matches directories
+    dir_only: bool,
+    /// Matched against the whole path rather than the last name
+    anchored: bool,
+}
+
+/// The patterns of a `.gitpublishignore` file
+#[derive(Debug, Clone, Default)]
+pub struct PathIgnore {
+    rules: Vec<Rule>,
+}
+
+impl PathIgnore {
+    /// Parse the content of an ignore file; invalid patterns are skipped
+    pub fn parse(content: &str) -> Self {
+        PathIgnore {
+            rules: content.lines().filter_map(parse_rule).collect(),
+        }
+    }
+
+    /// The ignore file of the repository at `root`, if there is one
+    pub fn load(root: &Path) -> io::Result<Option<Self>> {
+        match fs::read_to_string(root.join(IGNORE_FILE)) {
+            Ok(content) => Ok(Some(Self::parse(&content))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Add the patterns of `other` after these, so they take precedence
+    pub fn extend(&mut self, other: PathIgnore) {
+        self.rules.extend(other.rules);
+    }
+
+    /// Whether the file at `path`, relative to the repository root, is
+    /// ignored: by a pattern matching it or one of its parent directories,
+    /// the last matching pattern deciding
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let names: Vec<&str> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+
+        let mut ignored = false;
+        for depth in 1..=names.len() {
+            let is_dir = depth < names.len();
+            let prefix = names[..depth].join("/");
+            let name = names[depth - 1];
+            for rule in &self.rules {
+                if rule.dir_only && !is_dir {
+                    continue;
+                }
+                let candidate = if rule.anchored { prefix.as_str() } else { name };
+                if rule.regex.is_match(candidate) {
+                    ignored = !rule.negated;
+                }
+            }
+        }
+        ignored
+    }
+
+    /// Whether a commit changing `paths` only changes ignored files; never
+    /// when it changes nothing
+    pub fn ignores_all(&self, paths: &[&Path]) -> bool {
+        !paths.is_empty() && paths.iter().all(|path| self.is_ignored(path))
+    }
+
+    /// Whether the patterns match the file at `path`, for pattern lists that
+    /// select paths rather than ignore them (such as a package's paths)
+    pub fn matches(&self, path: &Path) -> bool {
+        self.is_ignored(path)
+    }
+}
+
+/// The rule of one line, `None` for blank lines and comments
+fn parse_rule(line: &str) -> Option<Rule> {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (negated, pattern) = match line.strip_prefix('!') {
+        Some(pattern) => (true, pattern),
+        None => (false, line.strip_prefix('\\').unwrap_or(line)),
+    };
+    let (dir_only, pattern) = match pattern.strip_suffix('/') {
+        Some(pattern) => (true, pattern),
+        None => (false, pattern),
+    };
+    let anchored = pattern.contains('/');
+    let pattern = pattern.trim_start_matches('/');
+    if pattern.is_empty() {
+        return None;
+    }
+    Some(Rule {
+        regex: Regex::new(&format!("^{}$", glob_to_regex(pattern))).ok()?,
+        negated,
+        dir_only,
+        anchored,
+    })
+}
+
+/// Regular expression of a glob pattern, where only `**` crosses `/`
+fn glob_to_regex(pattern: &str) -> String {
+    let mut regex = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    // `**/` also matches no directory at all
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                let class: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                regex.push('[');
+                match class.strip_prefix('!') {
+                    Some(negated) => {
+                        regex.push('^');
+                        regex.push_str(&negated.replace('\\', "\\\\"));
+                    }
+                    None => regex.push_str(&class.replace('\\', "\\\\")),
+                }
+                regex.push(']');
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignored(patterns: &str, path: &str) -> bool {
+        PathIgnore::parse(patterns).is_ignored(Path::new(path))
+    }
+
+    #[test]
+    fn test_names_match_at_any_depth() {
+        let patterns = "# generated\nvendor/\n*.gen.rs\n";
+        assert!(ignored(patterns, "vendor/lib.rs"));
+        assert!(ignored(patterns, "crates/app/vendor/lib.rs"));
+        assert!(ignored(patterns, "src/schema.gen.rs"));
+        assert!(!ignored(patterns, "src/schema.rs"));
+        // A directory pattern doesn't match a file of that name
+        assert!(!ignored(patterns, "docs/vendor"));
+    }
+
+    #[test]
+    fn test_anchored_patterns_and_wildcards() {
+        let patterns = "/build\ndocs/**/*.png\nassets/icon?.svg\nlogs/[!a]*.txt\n";
+        assert!(ignored(patterns, "build/out.js"));
+        assert!(!ignored(patterns, "src/build/out.js"));
+        assert!(ignored(patterns, "docs/a/b/shot.png"));
+        assert!(ignored(patterns, "docs/shot.png"));
+        assert!(ignored(patterns, "assets/icon1.svg"));
+        assert!(!ignored(patterns, "assets/icon10.svg"));
+        assert!(ignored(patterns, "logs/b.txt"));
+        assert!(!ignored(patterns, "logs/a.txt"));
+    }
+
+    #[test]
+    fn test_negation_re_includes() {
+        let patterns = "generated/\n!generated/api.rs\n";
+        assert!(ignored(patterns, "generated/model.rs"));
+        assert!(!ignored(patterns, "generated/api.rs"));
+    }
+
+    #[test]
+    fn test_extend_takes_precedence() {
+        let mut ignore = PathIgnore::parse("docs/**\n*.md\n");
+        ignore.extend(PathIgnore::parse("!CHANGELOG.md\n"));
+        assert!(ignore.is_ignored(Path::new("README.md")));
+        assert!(!ignore.is_ignored(Path::new("CHANGELOG.md")));
+        assert!(ignore.ignores_all(&[Path::new("docs/a.txt"), Path::new("README.md")]));
+        assert!(!ignore.ignores_all(&[Path::new("docs/a.txt"), Path::new("src/lib.rs")]));
+        assert!(!ignore.ignores_all(&[]));
+    }
+
+    #[test]
+    fn test_load_without_file() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(PathIgnore::load(dir.path()).unwrap().is_none());
+        fs::write(dir.path().join(IGNORE_FILE), "vendor/\n").unwrap();
+        let ignore = PathIgnore::load(dir.path()).unwrap().unwrap();
+        assert!(ignore.is_ignored(Path::new("vendor/x.rs")));
+    }
+}
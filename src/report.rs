@@ -0,0 +1,299 @@
+//! Periodic release summaries for `git-publish report`.
+//!
+//! Collects the release tags created within a time window on each configured
+//! branch, with commit counts and contributors, and renders them as Markdown
+//! or JSON for status updates.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::error::GitPublishError;
+use crate::git_ops::GitRepo;
+
+/// One release tag in the report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseSummary {
+    pub tag: String,
+    /// Release date (`YYYY-MM-DD`, UTC)
+    pub date: String,
+    /// Commits since the previous release on the branch
+    pub commit_count: usize,
+    pub contributors: Vec<String>,
+}
+
+/// Releases made on a single branch during the period
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BranchReport {
+    pub branch: String,
+    pub releases: Vec<ReleaseSummary>,
+}
+
+/// Release summary for a time window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseReport {
+    /// Start of the period (`YYYY-MM-DD`, UTC)
+    pub since: String,
+    /// End of the period (`YYYY-MM-DD`, UTC)
+    pub until: String,
+    pub branches: Vec<BranchReport>,
+}
+
+impl ReleaseReport {
+    /// Total number of releases across all branches
+    pub fn release_count(&self) -> usize {
+        self.branches.iter().map(|b| b.releases.len()).sum()
+    }
+
+    /// Total number of released commits across all branches
+    pub fn commit_count(&self) -> usize {
+        self.branches
+            .iter()
+            .flat_map(|b| &b.releases)
+            .map(|r| r.commit_count)
+            .sum()
+    }
+
+    /// Distinct contributors across all branches, in first-seen order
+    pub fn contributors(&self) -> Vec<String> {
+        let mut contributors: Vec<String> = Vec::new();
+        for release in self.branches.iter().flat_map(|b| &b.releases) {
+            for name in &release.contributors {
+                if !contributors.contains(name) {
+                    contributors.push(name.clone());
+                }
+            }
+        }
+        contributors
+    }
+
+    /// Render the report as Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Release report ({} to {})\n", self.since, self.until);
+
+        for branch in &self.branches {
+            let _ = writeln!(out, "## {}\n", branch.branch);
+            if branch.releases.is_empty() {
+                let _ = writeln!(out, "No releases.\n");
+                continue;
+            }
+            let _ = writeln!(out, "| Tag | Date | Commits | Contributors |");
+            let _ = writeln!(out, "|-----|------|---------|--------------|");
+            for release in &branch.releases {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} | {} |",
+                    release.tag,
+                    release.date,
+                    release.commit_count,
+                    release.contributors.join(", ")
+                );
+            }
+            out.push('\n');
+        }
+
+        let _ = writeln!(
+            out,
+            "**Total:** {} releases, {} commits, {} contributors",
+            self.release_count(),
+            self.commit_count(),
+            self.contributors().len()
+        );
+        out
+    }
+
+    /// Render the report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Parse a `--since` value into the start of the reporting period
+///
+/// Accepts relative durations (`12h`, `30d`, `2w`) counted back from `now`,
+/// or an absolute date (`2024-01-31`, midnight UTC).
+pub fn parse_since(spec: &str, now: DateTime<Utc>) -> crate::error::Result<DateTime<Utc>> {
+    let spec = spec.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(spec, "%Y-%m-%d") {
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+        return Ok(Utc.from_utc_datetime(&midnight));
+    }
+
+    let invalid = || {
+        GitPublishError::config(format!(
+            "Invalid --since value '{}': expected e.g. 12h, 30d, 2w or YYYY-MM-DD",
+            spec
+        ))
+    };
+    let unit = spec.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = spec[..spec.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    let duration = match unit {
+        'h' => Duration::hours(amount),
+        'd' => Duration::days(amount),
+        'w' => Duration::weeks(amount),
+        _ => return Err(invalid()),
+    };
+    Ok(now - duration)
+}
+
+/// Collect the releases made since `since` on the configured branches
+///
+/// Branches that don't exist locally are skipped.
+///
+/// # Arguments
+/// * `git_repo` - Repository to read tags and history from
+/// * `config` - Configuration providing branches and their tag patterns
+/// * `branch` - Only report on this branch instead of every configured branch
+/// * `since` - Start of the reporting period
+/// * `now` - End of the reporting period
+pub fn build_report(
+    git_repo: &GitRepo,
+    config: &Config,
+    branch: Option<&str>,
+    since: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<ReleaseReport> {
+    let mut branch_names: Vec<String> = match branch {
+        Some(branch) => vec![branch.to_string()],
+        None => config.branches.keys().cloned().collect(),
+    };
+    branch_names.sort();
+
+    let mut branches = Vec::new();
+    for branch_name in branch_names {
+        if git_repo.get_branch_head_oid(&branch_name).is_err() {
+            continue;
+        }
+        let pattern = config.branches.get(&branch_name).map(|s| s.as_str());
+        let releases = git_repo
+            .get_tag_releases_on_branch(&branch_name, pattern)?
+            .into_iter()
+            .filter(|release| release.time >= since.timestamp() && release.time <= now.timestamp())
+            .map(|release| ReleaseSummary {
+                tag: release.tag,
+                date: format_date(release.time),
+                commit_count: release.commit_count,
+                contributors: release.authors,
+            })
+            .collect();
+        branches.push(BranchReport {
+            branch: branch_name,
+            releases,
+        });
+    }
+
+    Ok(ReleaseReport {
+        since: since.format("%Y-%m-%d").to_string(),
+        until: now.format("%Y-%m-%d").to_string(),
+        branches,
+    })
+}
+
+fn format_date(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 31, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_since_relative() {
+        assert_eq!(
+            parse_since("30d", now()).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_since("2w", now()).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 17, 12, 0, 0).unwrap()
+        );
+        assert_eq!(
+            parse_since("12h", now()).unwrap(),
+            Utc.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_since_absolute_date() {
+        assert_eq!(
+            parse_since("2024-01-15", now()).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_since_invalid() {
+        assert!(parse_since("", now()).is_err());
+        assert!(parse_since("30", now()).is_err());
+        assert!(parse_since("d", now()).is_err());
+        assert!(parse_since("3y", now()).is_err());
+    }
+
+    fn sample_report() -> ReleaseReport {
+        ReleaseReport {
+            since: "2024-03-01".to_string(),
+            until: "2024-03-31".to_string(),
+            branches: vec![
+                BranchReport {
+                    branch: "develop".to_string(),
+                    releases: vec![],
+                },
+                BranchReport {
+                    branch: "main".to_string(),
+                    releases: vec![
+                        ReleaseSummary {
+                            tag: "v1.1.0".to_string(),
+                            date: "2024-03-10".to_string(),
+                            commit_count: 4,
+                            contributors: vec!["Alice".to_string(), "Bob".to_string()],
+                        },
+                        ReleaseSummary {
+                            tag: "v1.1.1".to_string(),
+                            date: "2024-03-20".to_string(),
+                            commit_count: 1,
+                            contributors: vec!["Bob".to_string()],
+                        },
+                    ],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_report_totals() {
+        let report = sample_report();
+        assert_eq!(report.release_count(), 2);
+        assert_eq!(report.commit_count(), 5);
+        assert_eq!(report.contributors(), vec!["Alice", "Bob"]);
+    }
+
+    #[test]
+    fn test_report_markdown() {
+        let markdown = sample_report().to_markdown();
+        assert!(markdown.starts_with("# Release report (2024-03-01 to 2024-03-31)"));
+        assert!(markdown.contains("## develop\n\nNo releases."));
+        assert!(markdown.contains("| v1.1.0 | 2024-03-10 | 4 | Alice, Bob |"));
+        assert!(markdown.contains("**Total:** 2 releases, 5 commits, 2 contributors"));
+    }
+
+    #[test]
+    fn test_report_json() {
+        let json: serde_json::Value =
+            serde_json::from_str(&sample_report().to_json().unwrap()).unwrap();
+        assert_eq!(json["since"], "2024-03-01");
+        assert_eq!(json["branches"][1]["releases"][0]["tag"], "v1.1.0");
+        assert_eq!(json["branches"][1]["releases"][0]["commit_count"], 4);
+    }
+}
@@ -0,0 +1,296 @@
+//! `git-publish self-update`: newer releases of git-publish itself.
+//!
+//! The latest release is looked up on the project's GitHub releases. With
+//! `--install`, the release asset built for this platform (a plain binary
+//! named after it, such as `git-publish-x86_64-linux`) replaces the running
+//! executable; installations from source are pointed to `cargo install`.
+//! The binary is only installed once its SHA-256 matches the checksum the
+//! release publishes for it (`<binary>.sha256` or a `SHA256SUMS` file).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::domain::Version;
+
+/// GitHub API endpoint of the latest (non-pre-release) release
+pub const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/chenmijiang/git-publish-rust/releases/latest";
+
+/// How long to wait for GitHub before giving up
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Archives would need unpacking, and checksums and signatures describe
+/// other assets: only plain binaries are installed
+const ARCHIVE_EXTENSIONS: [&str; 5] = [".tar.gz", ".tgz", ".zip", ".sha256", ".sig"];
+
+/// Extension of an asset holding the checksum of the asset it is named after
+const CHECKSUM_EXTENSION: &str = ".sha256";
+
+/// Name (lowercased, without extension) of an asset listing the checksums
+/// of several assets, as written by `sha256sum`
+const CHECKSUMS_FILE: &str = "sha256sums";
+
+/// A published release, as returned by the GitHub API
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    /// Release page
+    pub html_url: String,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+/// A file attached to a release
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// The version of the release, from its tag
+    pub fn version(&self) -> Result<Version> {
+        Ok(Version::parse(&self.tag_name)?)
+    }
+
+    /// The release's version when it is newer than `current`
+    pub fn newer_than(&self, current: &Version) -> Result<Option<Version>> {
+        let version = self.version()?;
+        Ok((version > *current).then_some(version))
+    }
+
+    /// The binary built for the platform `os`/`arch` (as in
+    /// [`std::env::consts`]), if the release has one
+    pub fn asset_for(&self, os: &str, arch: &str) -> Option<&Asset> {
+        // Target triples name macOS "darwin"
+        let os_names: &[&str] = match os {
+            "macos" => &["macos", "darwin"],
+            os => &[os],
+        };
+        self.assets.iter().find(|asset| {
+            let name = asset.name.to_lowercase();
+            name.contains(arch)
+                && os_names.iter().any(|os| name.contains(os))
+                && !ARCHIVE_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+        })
+    }
+
+    /// The asset holding the SHA-256 checksum of `asset`: `<asset>.sha256`,
+    /// otherwise a `SHA256SUMS` file
+    pub fn checksum_for(&self, asset: &Asset) -> Option<&Asset> {
+        let own = format!("{}{}", asset.name, CHECKSUM_EXTENSION);
+        self.assets
+            .iter()
+            .find(|candidate| candidate.name == own)
+            .or_else(|| {
+                self.assets.iter().find(|candidate| {
+                    let name = candidate.name.to_lowercase();
+                    name.split('.').next() == Some(CHECKSUMS_FILE)
+                })
+            })
+    }
+}
+
+/// Fetch the latest release from GitHub
+pub fn latest_release() -> Result<Release> {
+    let response = attohttpc::get(LATEST_RELEASE_URL)
+        .header("Accept", "application/vnd.github+json")
+        .header(
+            "User-Agent",
+            concat!("git-publish/", env!("CARGO_PKG_VERSION")),
+        )
+        .timeout(TIMEOUT)
+        .send()
+        .context("Failed to reach GitHub releases")?
+        .error_for_status()
+        .context("GitHub releases returned an error")?;
+    response
+        .json()
+        .context("Unexpected response from GitHub releases")
+}
+
+/// Download `asset`, check it against the checksum in `checksum` and
+/// replace the executable at `exe` with it
+///
+/// The download goes to a file next to `exe` first, so a failed download or
+/// a checksum mismatch leaves the installed binary untouched.
+pub fn install(asset: &Asset, checksum: &Asset, exe: &Path) -> Result<()> {
+    let listing = download(checksum, TIMEOUT)?;
+    let expected = expected_checksum(&String::from_utf8_lossy(&listing), &asset.name)
+        .with_context(|| format!("{} has no checksum for {}", checksum.name, asset.name))?;
+    let binary = download(asset, TIMEOUT * 8)?;
+
+    let staged = staging_path(exe);
+    fs::write(&staged, binary).with_context(|| format!("Failed to write {}", staged.display()))?;
+    if let Err(e) = verify(&staged, &expected) {
+        let _ = fs::remove_file(&staged);
+        return Err(e.context(format!("Refusing to install {}", asset.name)));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+    if let Err(e) = fs::rename(&staged, exe) {
+        let _ = fs::remove_file(&staged);
+        return Err(e).with_context(|| format!("Failed to replace {}", exe.display()));
+    }
+    Ok(())
+}
+
+fn download(asset: &Asset, timeout: Duration) -> Result<Vec<u8>> {
+    attohttpc::get(&asset.browser_download_url)
+        .header(
+            "User-Agent",
+            concat!("git-publish/", env!("CARGO_PKG_VERSION")),
+        )
+        .timeout(timeout)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.bytes())
+        .with_context(|| format!("Failed to download {}", asset.name))
+}
+
+/// The lowercase hex checksum of `name` in a checksum asset: either a lone
+/// hash (`<binary>.sha256`) or `sha256sum` lines (`<hash>  <name>`)
+fn expected_checksum(listing: &str, name: &str) -> Option<String> {
+    let is_hash = |word: &str| word.len() == 64 && word.chars().all(|c| c.is_ascii_hexdigit());
+    listing.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        let hash = words.next().filter(|word| is_hash(word))?;
+        match words.next() {
+            // `*` marks a file hashed in binary mode
+            Some(file) if file.trim_start_matches('*') != name => None,
+            _ => Some(hash.to_lowercase()),
+        }
+    })
+}
+
+/// Check that the SHA-256 of the file at `path` is `expected`
+fn verify(path: &Path, expected: &str) -> Result<()> {
+    let contents = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let actual: String = Sha256::digest(&contents)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    if actual != expected {
+        anyhow::bail!("checksum mismatch: expected {}, got {}", expected, actual);
+    }
+    Ok(())
+}
+
+/// Where the new binary is written before it replaces `exe`
+fn staging_path(exe: &Path) -> PathBuf {
+    let mut name = exe.file_name().unwrap_or_default().to_os_string();
+    name.push(".new");
+    exe.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release() -> Release {
+        serde_json::from_str(
+            r#"{
+                "tag_name": "v0.3.0",
+                "html_url": "https://github.com/chenmijiang/git-publish-rust/releases/tag/v0.3.0",
+                "assets": [
+                    {"name": "git-publish-x86_64-linux.tar.gz", "browser_download_url": "https://example.com/a"},
+                    {"name": "git-publish-x86_64-linux", "browser_download_url": "https://example.com/b"},
+                    {"name": "git-publish-x86_64-linux.sha256", "browser_download_url": "https://example.com/d"},
+                    {"name": "git-publish-aarch64-apple-darwin", "browser_download_url": "https://example.com/c"}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_newer_release() {
+        let release = release();
+        assert_eq!(
+            release.newer_than(&Version::new(0, 2, 9)).unwrap(),
+            Some(Version::new(0, 3, 0))
+        );
+        assert_eq!(release.newer_than(&Version::new(0, 3, 0)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_asset_for_platform() {
+        let release = release();
+        assert_eq!(
+            release.asset_for("linux", "x86_64").unwrap().name,
+            "git-publish-x86_64-linux"
+        );
+        assert_eq!(
+            release.asset_for("macos", "aarch64").unwrap().name,
+            "git-publish-aarch64-apple-darwin"
+        );
+        assert!(release.asset_for("windows", "x86_64").is_none());
+        assert_eq!(
+            staging_path(Path::new("/usr/local/bin/git-publish")),
+            Path::new("/usr/local/bin/git-publish.new")
+        );
+    }
+
+    #[test]
+    fn test_checksum_for_asset() {
+        let mut release = release();
+        let linux = release.asset_for("linux", "x86_64").unwrap().clone();
+        let darwin = release.asset_for("macos", "aarch64").unwrap().clone();
+        assert_eq!(
+            release.checksum_for(&linux).unwrap().name,
+            "git-publish-x86_64-linux.sha256"
+        );
+        assert!(release.checksum_for(&darwin).is_none());
+
+        release.assets.push(Asset {
+            name: "SHA256SUMS.txt".to_string(),
+            browser_download_url: "https://example.com/e".to_string(),
+        });
+        assert_eq!(
+            release.checksum_for(&darwin).unwrap().name,
+            "SHA256SUMS.txt"
+        );
+    }
+
+    #[test]
+    fn test_expected_checksum() {
+        let hash = "a".repeat(64);
+        assert_eq!(
+            expected_checksum(&format!("{}\n", hash), "git-publish"),
+            Some(hash.clone())
+        );
+        let listing = format!(
+            "{}  git-publish-x86_64-linux\n{} *git-publish-aarch64-apple-darwin\n",
+            "b".repeat(64),
+            hash.to_uppercase()
+        );
+        assert_eq!(
+            expected_checksum(&listing, "git-publish-aarch64-apple-darwin"),
+            Some(hash)
+        );
+        assert_eq!(
+            expected_checksum(&listing, "git-publish-x86_64-windows"),
+            None
+        );
+        assert_eq!(expected_checksum("not a checksum", "git-publish"), None);
+    }
+
+    #[test]
+    fn test_verify_staged_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("git-publish.new");
+        fs::write(&path, b"abc").unwrap();
+        // sha256("abc")
+        let expected = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+        assert!(verify(&path, expected).is_ok());
+        assert!(verify(&path, &"0".repeat(64)).is_err());
+    }
+}
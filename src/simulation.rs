@@ -0,0 +1,158 @@
+//! What a release from a past commit would have been, for
+//! `git-publish simulate --at <commit>`.
+//!
+//! The commit is taken as the branch head: the baseline is the latest tag in
+//! its history, and the commits between the two decide the bump, as a dry run
+//! at that point in time would have. Tags made later on older commits are
+//! still seen, so the result can differ from what was really released then.
+
+use std::fmt::Write as _;
+use std::ops::ControlFlow;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::domain::commit::BumpAnalysis;
+use crate::domain::{TagPattern, Version, VersionBump};
+use crate::git_ops::GitRepo;
+
+/// Characters of the commit hash shown in the Markdown output
+const SHORT_HASH_LEN: usize = 7;
+
+/// The release computed for a past commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Simulation {
+    /// Full hash of the simulated head
+    pub commit: String,
+    /// Subject line of the simulated head
+    pub subject: String,
+    /// Branch whose tag pattern was used
+    pub branch: String,
+    /// Latest tag in the history of the commit
+    pub baseline_tag: Option<String>,
+    /// Commits since the baseline tag, up to and including the commit
+    pub commit_count: usize,
+    /// `None` without any commit since the baseline tag
+    pub bump: Option<VersionBump>,
+    /// Recommended tag; `None` without any commit since the baseline tag
+    pub next_tag: Option<String>,
+}
+
+impl Simulation {
+    /// Render the simulation as a Markdown list
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let short = &self.commit[..self.commit.len().min(SHORT_HASH_LEN)];
+        let _ = writeln!(out, "# Simulated release at {}\n", short);
+        let _ = writeln!(out, "- Commit: {} {}", short, self.subject);
+        let _ = writeln!(out, "- Branch: {}", self.branch);
+        let _ = writeln!(
+            out,
+            "- Baseline tag: {}",
+            self.baseline_tag.as_deref().unwrap_or("none")
+        );
+        let _ = writeln!(out, "- Commits since baseline: {}", self.commit_count);
+        let _ = writeln!(
+            out,
+            "- Bump: {}",
+            self.bump.map_or_else(
+                || "none".to_string(),
+                |bump| format!("{:?}", bump).to_lowercase()
+            )
+        );
+        let _ = writeln!(
+            out,
+            "- Next tag: {}",
+            self.next_tag.as_deref().unwrap_or("none")
+        );
+        out
+    }
+
+    /// Render the simulation as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Compute the release of `branch` as if its head were at `revision`
+///
+/// # Arguments
+/// * `git_repo` - Repository to read tags and history from
+/// * `config` - Configuration providing the tag pattern and commit rules
+/// * `branch` - Branch whose tag pattern applies (`v{version}` when not configured)
+/// * `revision` - The past commit (e.g. a SHA, `HEAD~2` or a tag)
+pub fn simulate(
+    git_repo: &GitRepo,
+    config: &Config,
+    branch: &str,
+    revision: &str,
+) -> Result<Simulation> {
+    let pattern = config
+        .branches
+        .tag_pattern(branch)
+        .cloned()
+        .unwrap_or_else(|| TagPattern::new("v{version}"));
+    let commit = git_repo.resolve_commit(revision)?;
+    let baseline_tag = git_repo.get_latest_tag_at(&commit, Some(pattern.as_str()))?;
+
+    let mut commit_count = 0;
+    let mut analysis = BumpAnalysis::default();
+    git_repo.walk_commits_since_tag_at(&commit, baseline_tag.as_deref(), |commit| {
+        commit_count += 1;
+        if let Some(message) = commit.message() {
+            analysis.add(message, &config.conventional_commits);
+        }
+        ControlFlow::Continue(())
+    })?;
+
+    let bump = (commit_count > 0).then(|| analysis.bump());
+    // Without a usable baseline the workflow proposes the initial version
+    let next_tag = bump.map(|bump| {
+        let version = baseline_tag
+            .as_deref()
+            .and_then(|tag| pattern.extract_version(tag))
+            .and_then(|version| Version::parse(&version).ok())
+            .map_or_else(|| Version::new(0, 1, 0), |version| version.bump(&bump));
+        pattern.format(&version.to_string())
+    });
+
+    Ok(Simulation {
+        subject: git_repo
+            .commit_message(&commit)
+            .and_then(|message| message.lines().next().map(str::to_string))
+            .unwrap_or_default(),
+        commit,
+        branch: branch.to_string(),
+        baseline_tag,
+        commit_count,
+        bump,
+        next_tag,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulation_markdown() {
+        let simulation = Simulation {
+            commit: "0123456789abcdef0123456789abcdef01234567".to_string(),
+            subject: "feat: add export".to_string(),
+            branch: "main".to_string(),
+            baseline_tag: Some("v1.2.0".to_string()),
+            commit_count: 3,
+            bump: Some(VersionBump::Minor),
+            next_tag: Some("v1.3.0".to_string()),
+        };
+        let markdown = simulation.to_markdown();
+        assert!(markdown.starts_with("# Simulated release at 0123456\n"));
+        assert!(markdown.contains("- Commit: 0123456 feat: add export\n"));
+        assert!(markdown.contains("- Baseline tag: v1.2.0\n"));
+        assert!(markdown.contains("- Bump: minor\n- Next tag: v1.3.0\n"));
+
+        let json = simulation.to_json().unwrap();
+        assert!(json.contains("\"bump\": \"minor\""));
+    }
+}
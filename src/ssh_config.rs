@@ -0,0 +1,227 @@
+//! Minimal OpenSSH client config (`~/.ssh/config`) reader.
+//!
+//! Only `Host` blocks and the `IdentityFile` option are interpreted, which is what
+//! git-publish needs to pick the same key `ssh` would. `Match` blocks are skipped
+//! and `Include` directives are not followed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::expand_home;
+use crate::error::Result;
+use crate::known_hosts::wildcard_match;
+
+/// One `Host` block (or the implicit global block before the first `Host`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct HostBlock {
+    /// Host patterns; `None` for the global block and for `Match` blocks
+    patterns: Option<Vec<String>>,
+    /// Whether this block came from a `Match` line and never applies
+    skipped: bool,
+    identity_files: Vec<String>,
+}
+
+impl HostBlock {
+    fn matches(&self, host: &str) -> bool {
+        if self.skipped {
+            return false;
+        }
+        let Some(patterns) = &self.patterns else {
+            return true;
+        };
+        let mut matched = false;
+        for pattern in patterns {
+            if let Some(negated) = pattern.strip_prefix('!') {
+                if wildcard_match(negated, host) {
+                    return false;
+                }
+            } else if wildcard_match(pattern, host) {
+                matched = true;
+            }
+        }
+        matched
+    }
+}
+
+/// Parsed contents of an SSH client config file
+#[derive(Debug, Clone, Default)]
+pub struct SshConfig {
+    blocks: Vec<HostBlock>,
+}
+
+impl SshConfig {
+    /// Returns the default user SSH config path (`~/.ssh/config`)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".ssh").join("config"))
+    }
+
+    /// Load an SSH config file; a missing file yields an empty config
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(SshConfig::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(SshConfig::parse(&content))
+    }
+
+    /// Parse SSH config content, ignoring options git-publish does not use
+    pub fn parse(content: &str) -> Self {
+        let mut blocks = vec![HostBlock::default()];
+
+        for line in content.lines() {
+            let Some((keyword, value)) = split_option(line) else {
+                continue;
+            };
+            match keyword.to_ascii_lowercase().as_str() {
+                "host" => blocks.push(HostBlock {
+                    patterns: Some(value.split_whitespace().map(|p| p.to_string()).collect()),
+                    ..HostBlock::default()
+                }),
+                "match" => blocks.push(HostBlock {
+                    skipped: true,
+                    ..HostBlock::default()
+                }),
+                "identityfile" => {
+                    if let Some(block) = blocks.last_mut() {
+                        block.identity_files.push(unquote(value).to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        SshConfig { blocks }
+    }
+
+    /// Identity files that apply to `host`, in the order `ssh` would try them
+    ///
+    /// `~` and the `%h` (host), `%d` (home directory) and `%%` tokens are expanded.
+    pub fn identity_files(&self, host: &str) -> Vec<PathBuf> {
+        self.blocks
+            .iter()
+            .filter(|block| block.matches(host))
+            .flat_map(|block| block.identity_files.iter())
+            .map(|file| expand_home(&expand_tokens(file, host)))
+            .collect()
+    }
+}
+
+/// Splits a config line into keyword and value (`Keyword value` or `Keyword=value`)
+fn split_option(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let split_at = line.find(|c: char| c.is_whitespace() || c == '=')?;
+    let keyword = &line[..split_at];
+    let value = line[split_at..]
+        .trim_start()
+        .strip_prefix('=')
+        .unwrap_or(&line[split_at..])
+        .trim();
+    if value.is_empty() {
+        return None;
+    }
+    Some((keyword, value))
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+fn expand_tokens(value: &str, host: &str) -> String {
+    let home = dirs::home_dir()
+        .map(|h| h.display().to_string())
+        .unwrap_or_default();
+    let mut expanded = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            expanded.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('h') => expanded.push_str(host),
+            Some('d') => expanded.push_str(&home),
+            Some('%') => expanded.push('%'),
+            Some(other) => {
+                expanded.push('%');
+                expanded.push(other);
+            }
+            None => expanded.push('%'),
+        }
+    }
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+IdentityFile /keys/global
+
+Host github.com gitlab.*
+    User git
+    IdentityFile ~/.ssh/work_ed25519
+
+Host *.internal !build.internal
+    IdentityFile=/keys/%h
+
+Match host example.com
+    IdentityFile /keys/never
+"#;
+
+    #[test]
+    fn test_identity_files_for_matching_host() {
+        let config = SshConfig::parse(CONFIG);
+        let files = config.identity_files("github.com");
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0], PathBuf::from("/keys/global"));
+        assert!(files[1].ends_with(".ssh/work_ed25519"));
+    }
+
+    #[test]
+    fn test_identity_files_wildcard_and_tokens() {
+        let config = SshConfig::parse(CONFIG);
+        assert_eq!(
+            config.identity_files("git.internal"),
+            vec![
+                PathBuf::from("/keys/global"),
+                PathBuf::from("/keys/git.internal")
+            ]
+        );
+        assert!(config.identity_files("gitlab.example.org").len() == 2);
+    }
+
+    #[test]
+    fn test_identity_files_negation_and_match_blocks() {
+        let config = SshConfig::parse(CONFIG);
+        assert_eq!(
+            config.identity_files("build.internal"),
+            vec![PathBuf::from("/keys/global")]
+        );
+        assert_eq!(
+            config.identity_files("example.com"),
+            vec![PathBuf::from("/keys/global")]
+        );
+    }
+
+    #[test]
+    fn test_quoted_identity_file() {
+        let config = SshConfig::parse("Host a\n  IdentityFile \"/keys/with space\"\n");
+        assert_eq!(
+            config.identity_files("a"),
+            vec![PathBuf::from("/keys/with space")]
+        );
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let config = SshConfig::load(Path::new("/nonexistent/ssh_config")).unwrap();
+        assert!(config.identity_files("github.com").is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+//! Answers from the previous interactive release, offered as defaults.
+//!
+//! Stored as `key=value` lines (`branch`, `remote`, `push`) in
+//! `.git/gitpublish-state`, so releasing the same branch to the same remote
+//! again only takes Enter presses.
+
+use std::fs;
+use std::path::Path;
+
+use crate::error::Result;
+
+/// File name of the state inside the repository's git directory
+pub const STATE_FILE: &str = "gitpublish-state";
+
+/// The branch, remote and push decision chosen last time
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LastAnswers {
+    pub branch: Option<String>,
+    pub remote: Option<String>,
+    /// Whether the tag was pushed right away
+    pub push: Option<bool>,
+}
+
+impl LastAnswers {
+    /// Read the answers stored in the given git directory (e.g. `.git`)
+    ///
+    /// A missing or unreadable file gives no answers; unknown keys and
+    /// malformed lines are skipped.
+    pub fn load(git_dir: &Path) -> Self {
+        let Ok(content) = fs::read_to_string(git_dir.join(STATE_FILE)) else {
+            return LastAnswers::default();
+        };
+        let mut answers = LastAnswers::default();
+        for (key, value) in content.lines().filter_map(|line| line.split_once('=')) {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            match key.trim() {
+                "branch" => answers.branch = Some(value.to_string()),
+                "remote" => answers.remote = Some(value.to_string()),
+                "push" => answers.push = value.parse().ok(),
+                _ => {}
+            }
+        }
+        answers
+    }
+
+    /// Write the answers to the given git directory, replacing earlier ones
+    pub fn save(&self, git_dir: &Path) -> Result<()> {
+        let mut content = String::new();
+        if let Some(branch) = &self.branch {
+            content.push_str(&format!("branch={}\n", branch));
+        }
+        if let Some(remote) = &self.remote {
+            content.push_str(&format!("remote={}\n", remote));
+        }
+        if let Some(push) = self.push {
+            content.push_str(&format!("push={}\n", push));
+        }
+        fs::write(git_dir.join(STATE_FILE), content)?;
+        Ok(())
+    }
+}
+
+/// Move `last` to the front of `choices`, where prompts take it as the
+/// default; `choices` stays as is when `last` isn't one of them
+pub fn put_first(choices: &mut [String], last: Option<&str>) {
+    if let Some(index) = choices
+        .iter()
+        .position(|choice| Some(choice.as_str()) == last)
+    {
+        choices[..=index].rotate_right(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(LastAnswers::load(dir.path()), LastAnswers::default());
+
+        let answers = LastAnswers {
+            branch: Some("release/2.x".to_string()),
+            remote: Some("upstream".to_string()),
+            push: Some(false),
+        };
+        answers.save(dir.path()).unwrap();
+        assert_eq!(LastAnswers::load(dir.path()), answers);
+
+        fs::write(
+            dir.path().join(STATE_FILE),
+            "garbage\nremote=\npush=maybe\nbranch=main\n",
+        )
+        .unwrap();
+        assert_eq!(
+            LastAnswers::load(dir.path()),
+            LastAnswers {
+                branch: Some("main".to_string()),
+                ..LastAnswers::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_put_first() {
+        let mut remotes: Vec<String> = ["origin", "fork", "upstream"]
+            .iter()
+            .map(|remote| remote.to_string())
+            .collect();
+        put_first(&mut remotes, Some("upstream"));
+        assert_eq!(remotes, vec!["upstream", "origin", "fork"]);
+
+        put_first(&mut remotes, Some("gone"));
+        put_first(&mut remotes, None);
+        assert_eq!(remotes, vec!["upstream", "origin", "fork"]);
+    }
+}
@@ -0,0 +1,229 @@
+//! Local release statistics for `git-publish stats`.
+//!
+//! Computed from the release tags of each configured branch only, with nothing
+//! sent anywhere: how often releases happen, how many commits they carry and
+//! which version bumps they were.
+
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use chrono::DateTime;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::domain::{TagPattern, Version};
+use crate::git_ops::{GitRepo, TagRelease};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// How many releases were each kind of version bump
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct BumpCounts {
+    pub major: usize,
+    pub minor: usize,
+    pub patch: usize,
+    pub prerelease: usize,
+}
+
+/// Release statistics of one branch
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BranchStats {
+    pub branch: String,
+    pub release_count: usize,
+    /// Date of the first release (`YYYY-MM-DD`, UTC)
+    pub first_release: Option<String>,
+    /// Date of the latest release (`YYYY-MM-DD`, UTC)
+    pub latest_release: Option<String>,
+    /// Average time between two releases; needs two releases
+    pub average_days_between_releases: Option<f64>,
+    /// Average commits since the previous release; the first release, which
+    /// covers the whole earlier history, isn't counted
+    pub average_commits_per_release: Option<f64>,
+    /// Bumps from one release to the next, by comparing their versions
+    pub bumps: BumpCounts,
+}
+
+impl BranchStats {
+    /// Statistics of the `releases` of a branch, oldest first, whose tags
+    /// follow `pattern`
+    pub fn new(branch: &str, releases: &[TagRelease], pattern: &TagPattern) -> Self {
+        let span_days = match (releases.first(), releases.last()) {
+            (Some(first), Some(last)) => (last.time - first.time) as f64 / SECONDS_PER_DAY,
+            _ => 0.0,
+        };
+        let later = releases.get(1..).unwrap_or_default();
+        let later_commits: usize = later.iter().map(|release| release.commit_count).sum();
+
+        let versions = releases.iter().filter_map(|release| {
+            pattern
+                .extract_version(&release.tag)
+                .and_then(|version| Version::parse(&version).ok())
+        });
+        // A stable release is compared with the previous stable one, so a
+        // promoted `2.0.0-rc.1` still makes `2.0.0` a major release
+        let mut bumps = BumpCounts::default();
+        let (mut first, mut last_stable): (bool, Option<Version>) = (true, None);
+        for version in versions {
+            if version.prerelease.is_some() {
+                if !first {
+                    bumps.prerelease += 1;
+                }
+            } else {
+                match &last_stable {
+                    Some(previous) if version.major > previous.major => bumps.major += 1,
+                    Some(previous) if version.minor > previous.minor => bumps.minor += 1,
+                    Some(_) => bumps.patch += 1,
+                    None => {}
+                }
+                last_stable = Some(version);
+            }
+            first = false;
+        }
+
+        BranchStats {
+            branch: branch.to_string(),
+            release_count: releases.len(),
+            first_release: releases.first().map(|release| format_date(release.time)),
+            latest_release: releases.last().map(|release| format_date(release.time)),
+            average_days_between_releases: (!later.is_empty())
+                .then(|| span_days / later.len() as f64),
+            average_commits_per_release: (!later.is_empty())
+                .then(|| later_commits as f64 / later.len() as f64),
+            bumps,
+        }
+    }
+}
+
+/// Release statistics of the configured branches
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReleaseStats {
+    pub branches: Vec<BranchStats>,
+}
+
+impl ReleaseStats {
+    /// Render the statistics as a Markdown table
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Release statistics\n");
+        let _ = writeln!(
+            out,
+            "| Branch | Releases | First | Latest | Days between | Commits per release | Major | Minor | Patch | Pre-release |"
+        );
+        let _ = writeln!(
+            out,
+            "|--------|----------|-------|--------|--------------|---------------------|-------|-------|-------|-------------|"
+        );
+        let average =
+            |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{:.1}", v));
+        for branch in &self.branches {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+                branch.branch,
+                branch.release_count,
+                branch.first_release.as_deref().unwrap_or("-"),
+                branch.latest_release.as_deref().unwrap_or("-"),
+                average(branch.average_days_between_releases),
+                average(branch.average_commits_per_release),
+                branch.bumps.major,
+                branch.bumps.minor,
+                branch.bumps.patch,
+                branch.bumps.prerelease
+            );
+        }
+        out
+    }
+
+    /// Render the statistics as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Compute the statistics of the configured branches, or only of `branch`
+///
+/// Branches that don't exist locally are skipped.
+pub fn build_stats(
+    git_repo: &GitRepo,
+    config: &Config,
+    branch: Option<&str>,
+) -> Result<ReleaseStats> {
+    let mut branch_names: Vec<String> = match branch {
+        Some(branch) => vec![branch.to_string()],
+        None => config.branches.keys().cloned().collect(),
+    };
+    branch_names.sort();
+
+    let mut branches = Vec::new();
+    for branch_name in branch_names {
+        if git_repo.get_branch_head_oid(&branch_name).is_err() {
+            continue;
+        }
+        let pattern = config.branches.get(&branch_name).map(|s| s.as_str());
+        let releases = git_repo.get_tag_releases_on_branch(&branch_name, pattern)?;
+        let pattern = TagPattern::new(pattern.unwrap_or("v{version}"));
+        branches.push(BranchStats::new(&branch_name, &releases, &pattern));
+    }
+    Ok(ReleaseStats { branches })
+}
+
+fn format_date(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, day: i64, commit_count: usize) -> TagRelease {
+        TagRelease {
+            tag: tag.to_string(),
+            // 2024-01-01 plus `day` days
+            time: 1_704_067_200 + day * 86_400,
+            commit_count,
+            authors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_branch_stats() {
+        let releases = [
+            release("v1.0.0", 0, 40),
+            release("v1.1.0", 10, 6),
+            release("v1.1.1", 15, 1),
+            release("v2.0.0-rc.1", 25, 5),
+            release("v2.0.0", 30, 0),
+        ];
+        let stats = BranchStats::new("main", &releases, &TagPattern::new("v{version}"));
+
+        assert_eq!(stats.release_count, 5);
+        assert_eq!(stats.first_release.as_deref(), Some("2024-01-01"));
+        assert_eq!(stats.latest_release.as_deref(), Some("2024-01-31"));
+        assert_eq!(stats.average_days_between_releases, Some(7.5));
+        assert_eq!(stats.average_commits_per_release, Some(3.0));
+        assert_eq!(
+            stats.bumps,
+            BumpCounts {
+                major: 1,
+                minor: 1,
+                patch: 1,
+                prerelease: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_without_releases() {
+        let stats = BranchStats::new("develop", &[], &TagPattern::new("d{version}"));
+        assert_eq!(stats.release_count, 0);
+        assert_eq!(stats.average_days_between_releases, None);
+
+        let markdown = ReleaseStats {
+            branches: vec![stats],
+        }
+        .to_markdown();
+        assert!(markdown.contains("| develop | 0 | - | - | - | - | 0 | 0 | 0 | 0 |"));
+    }
+}
@@ -0,0 +1,162 @@
+//! Local release tags for `git-publish list-tags`.
+//!
+//! Tags are sorted by version, newest first, with the date they were made and
+//! the commit they point at, so old releases can be reviewed (or pruned)
+//! without `git tag` and `git log` side by side.
+
+use std::cmp::Ordering;
+use std::fmt::Write as _;
+
+use anyhow::Result;
+use chrono::DateTime;
+use serde::Serialize;
+
+use crate::domain::{TagPattern, Version};
+use crate::git_ops::TagDetails;
+
+/// Characters of the commit hash shown in the Markdown table
+const SHORT_HASH_LEN: usize = 7;
+
+/// One listed tag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ListedTag {
+    pub tag: String,
+    /// Version in the tag, `None` when it isn't valid semver
+    pub version: Option<String>,
+    /// Tag date (`YYYY-MM-DD`, UTC): tagger date, or commit date for lightweight tags
+    pub date: String,
+    /// Full hash of the tagged commit
+    pub commit: String,
+    pub annotated: bool,
+}
+
+/// Tags sorted by version, newest first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TagList {
+    pub tags: Vec<ListedTag>,
+}
+
+impl TagList {
+    /// Sort `tags` by the version they carry, newest first
+    ///
+    /// Versions are read with `pattern` when given, otherwise from whatever
+    /// prefix each tag has. Tags without a valid version come last, by name.
+    pub fn new(tags: Vec<TagDetails>, pattern: Option<&TagPattern>) -> Self {
+        let mut tags: Vec<(Option<Version>, TagDetails)> = tags
+            .into_iter()
+            .map(|details| (tag_version(&details.name, pattern), details))
+            .collect();
+        tags.sort_by(|(a, a_details), (b, b_details)| match (a, b) {
+            (Some(a), Some(b)) => b.cmp(a).then_with(|| a_details.name.cmp(&b_details.name)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a_details.name.cmp(&b_details.name),
+        });
+
+        TagList {
+            tags: tags
+                .into_iter()
+                .map(|(version, details)| ListedTag {
+                    version: version.map(|version| version.to_string()),
+                    date: format_date(details.time),
+                    tag: details.name,
+                    commit: details.target,
+                    annotated: details.annotated,
+                })
+                .collect(),
+        }
+    }
+
+    /// Render the tags as a Markdown table
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "| Tag | Version | Date | Commit | Annotated |");
+        let _ = writeln!(out, "|-----|---------|------|--------|-----------|");
+        for tag in &self.tags {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                tag.tag,
+                tag.version.as_deref().unwrap_or("-"),
+                tag.date,
+                &tag.commit[..tag.commit.len().min(SHORT_HASH_LEN)],
+                if tag.annotated { "yes" } else { "no" }
+            );
+        }
+        out
+    }
+
+    /// Render the tags as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// The version in `tag`, read with `pattern` when given, otherwise after
+/// whatever prefix the tag has
+pub fn tag_version(tag: &str, pattern: Option<&TagPattern>) -> Option<Version> {
+    let version = match pattern {
+        Some(pattern) => pattern.extract_version(tag),
+        None => TagPattern::detect(tag).and_then(|pattern| pattern.extract_version(tag)),
+    };
+    version.and_then(|version| Version::parse(&version).ok())
+}
+
+fn format_date(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(name: &str, day: i64, annotated: bool) -> TagDetails {
+        TagDetails {
+            name: name.to_string(),
+            target: format!("{:040x}", day),
+            // 2024-01-01 plus `day` days
+            time: 1_704_067_200 + day * 86_400,
+            annotated,
+        }
+    }
+
+    #[test]
+    fn test_tags_sorted_by_version() {
+        let list = TagList::new(
+            vec![
+                details("v1.10.0", 20, true),
+                details("v1.2.0", 5, false),
+                details("v2.0.0-rc.1", 25, true),
+                details("v1.9.1", 15, true),
+                details("vnext", 30, false),
+            ],
+            None,
+        );
+        let names: Vec<&str> = list.tags.iter().map(|tag| tag.tag.as_str()).collect();
+        assert_eq!(
+            names,
+            ["v2.0.0-rc.1", "v1.10.0", "v1.9.1", "v1.2.0", "vnext"]
+        );
+        assert_eq!(list.tags[1].date, "2024-01-21");
+        assert_eq!(list.tags[4].version, None);
+
+        let markdown = list.to_markdown();
+        assert!(markdown.contains("| v1.2.0 | 1.2.0 | 2024-01-06 | 0000000 | no |"));
+    }
+
+    #[test]
+    fn test_tags_with_pattern() {
+        let pattern = TagPattern::new("release-{version}");
+        let list = TagList::new(
+            vec![
+                details("release-1.0.0", 0, true),
+                details("v3.0.0", 1, true),
+            ],
+            Some(&pattern),
+        );
+        assert_eq!(list.tags[0].tag, "release-1.0.0");
+        assert_eq!(list.tags[1].version, None);
+    }
+}
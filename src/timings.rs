@@ -0,0 +1,113 @@
+//! Wall-clock time spent in the hot paths of a run, for `--profile-timings`.
+//!
+//! Meant for tuning on large repositories (100k+ commits, 10k+ tags). Timing
+//! is off unless [`enable`] was called; [`measure`] then only runs the closure.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A measured part of the publish workflow
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Fetching the branch and tags from the remote
+    Fetch,
+    /// Finding the latest release tag on the branch
+    TagScan,
+    /// Walking the commits since that tag; includes the analysis of the
+    /// commits as the walk yields them
+    Revwalk,
+    /// Parsing the commits into a version bump and changelog
+    Analysis,
+}
+
+impl Phase {
+    /// All phases in workflow order
+    pub const ALL: [Phase; 4] = [
+        Phase::Fetch,
+        Phase::TagScan,
+        Phase::Revwalk,
+        Phase::Analysis,
+    ];
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Phase::Fetch => "fetch",
+            Phase::TagScan => "tag scan",
+            Phase::Revwalk => "revwalk",
+            Phase::Analysis => "analysis",
+        };
+        f.pad(name)
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static TIMINGS: Mutex<Vec<(Phase, Duration)>> = Mutex::new(Vec::new());
+
+/// Start recording timings
+pub fn enable() {
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Run `f`, adding its duration to `phase` when timing is enabled
+pub fn measure<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return f();
+    }
+    let start = Instant::now();
+    let result = f();
+    record(phase, start.elapsed());
+    result
+}
+
+fn record(phase: Phase, elapsed: Duration) {
+    let mut timings = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+    match timings.iter_mut().find(|(p, _)| *p == phase) {
+        Some((_, total)) => *total += elapsed,
+        None => timings.push((phase, elapsed)),
+    }
+}
+
+/// Total time per phase recorded so far, in workflow order; phases that
+/// never ran are left out
+pub fn totals() -> Vec<(Phase, Duration)> {
+    let timings = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+    Phase::ALL
+        .iter()
+        .filter_map(|phase| timings.iter().find(|(p, _)| p == phase).copied())
+        .collect()
+}
+
+/// One aligned `phase  12.3 ms` line per recorded phase
+pub fn report() -> Vec<String> {
+    totals()
+        .into_iter()
+        .map(|(phase, elapsed)| format!("{:<10}{:>10.1} ms", phase, elapsed.as_secs_f64() * 1000.0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timings_accumulate_per_phase() {
+        record(Phase::Revwalk, Duration::from_millis(2));
+        record(Phase::TagScan, Duration::from_millis(1));
+        record(Phase::Revwalk, Duration::from_millis(3));
+
+        let totals = totals();
+        assert_eq!(
+            totals,
+            vec![
+                (Phase::TagScan, Duration::from_millis(1)),
+                (Phase::Revwalk, Duration::from_millis(5)),
+            ]
+        );
+        assert_eq!(report()[1], "revwalk          5.0 ms");
+    }
+}
@@ -3,11 +3,17 @@
 //! This module contains all display/formatting logic separated from user interaction.
 //! Functions here are pure (no I/O side effects beyond printing) and testable.
 
+use crate::analyzer::MessageAnalysis;
 use crate::boundary::BoundaryWarning;
+use crate::doctor::{CheckStatus, DoctorReport};
+use crate::domain::commit::{CommitExplanation, NonConformingCommit};
+use crate::hooks::HookRun;
+use crate::i18n::{text, tr, Msg};
+use crate::workflow::BranchOutcome;
 
 /// Format and print an error message in red.
 pub fn display_error(message: &str) {
-    eprintln!("\x1b[31mERROR:\x1b[0m {}", message);
+    eprintln!("\x1b[31m{}\x1b[0m {}", text(Msg::ErrorLabel), message);
 }
 
 /// Format and print a success message with green checkmark.
@@ -15,6 +21,11 @@ pub fn display_success(message: &str) {
     println!("\x1b[32m✓\x1b[0m {}", message);
 }
 
+/// Format and print a warning message with yellow warning sign.
+pub fn display_warning(message: &str) {
+    eprintln!("\x1b[33m⚠\x1b[0m {}", message);
+}
+
 /// Format and print a status message with yellow arrow.
 pub fn display_status(message: &str) {
     println!("\x1b[33m→\x1b[0m {}", message);
@@ -30,10 +41,13 @@ pub fn display_status(message: &str) {
 /// * `branch_name` - The name of the branch being analyzed
 pub fn display_commit_analysis(commit_messages: &[String], branch_name: &str) {
     println!(
-        "\n\x1b[1mAnalyzing commits on branch '{}'\x1b[0m",
-        branch_name
+        "\n\x1b[1m{}\x1b[0m",
+        tr(Msg::AnalyzingCommits, &[("branch", &branch_name)])
+    );
+    println!(
+        "\x1b[4m{}\x1b[0m",
+        tr(Msg::LastCommits, &[("count", &commit_messages.len())])
     );
-    println!("\x1b[4mLast {} commits:\x1b[0m", commit_messages.len());
 
     for (i, message) in commit_messages.iter().take(10).enumerate() {
         let short_msg = if message.len() > 60 {
@@ -45,7 +59,13 @@ pub fn display_commit_analysis(commit_messages: &[String], branch_name: &str) {
     }
 
     if commit_messages.len() > 10 {
-        println!("  ... and {} more commits", commit_messages.len() - 10);
+        println!(
+            "  {}",
+            tr(
+                Msg::MoreCommits,
+                &[("count", &(commit_messages.len() - 10))]
+            )
+        );
     }
 }
 
@@ -61,25 +81,134 @@ pub fn display_commit_analysis(commit_messages: &[String], branch_name: &str) {
 pub fn display_proposed_tag(old_tag: Option<&str>, new_tag: &str) {
     match old_tag {
         Some(old) => {
-            println!("\n\x1b[1mProposed Tag Change:\x1b[0m");
-            println!("  From: \x1b[31m{}\x1b[0m", old);
-            println!("  To:   \x1b[32m{}\x1b[0m", new_tag);
+            println!("\n\x1b[1m{}\x1b[0m", text(Msg::ProposedTagChange));
+            println!("  {} \x1b[31m{}\x1b[0m", text(Msg::TagFrom), old);
+            println!("  {} \x1b[32m{}\x1b[0m", text(Msg::TagTo), new_tag);
         }
         None => {
-            println!("\n\x1b[1mInitial Tag:\x1b[0m");
-            println!("  New tag: \x1b[32m{}\x1b[0m", new_tag);
+            println!("\n\x1b[1m{}\x1b[0m", text(Msg::InitialTag));
+            println!("  {} \x1b[32m{}\x1b[0m", text(Msg::NewTag), new_tag);
         }
     }
 }
 
 /// Display a boundary warning to the user.
 ///
-/// Shows a yellow warning icon followed by the warning message.
+/// Shows a yellow warning icon followed by the warning message, plus a
+/// remediation hint when the warning has one.
 ///
 /// # Arguments
 /// * `warning` - The boundary warning to display
 pub fn display_boundary_warning(warning: &BoundaryWarning) {
-    eprintln!("\x1b[33m⚠ WARNING:\x1b[0m {}", warning);
+    eprintln!("\x1b[33m⚠ {}\x1b[0m {}", text(Msg::WarningLabel), warning);
+    if let Some(hint) = warning.remediation_hint() {
+        eprintln!("  \x1b[36m{}\x1b[0m {}", text(Msg::HintLabel), hint);
+    }
+}
+
+/// Display commits that don't follow the configured commit conventions.
+///
+/// Purely informational: flagged commits still take part in the analysis.
+///
+/// # Arguments
+/// * `commits` - Flagged commits with their issues
+pub fn display_non_conforming_commits(commits: &[NonConformingCommit]) {
+    println!("\n\x1b[33m{}\x1b[0m", text(Msg::NonConformingHeader));
+    for commit in commits {
+        let issues: Vec<String> = commit.issues.iter().map(|i| i.to_string()).collect();
+        println!(
+            "  - {} \x1b[2m({})\x1b[0m",
+            commit.subject,
+            issues.join("; ")
+        );
+    }
+}
+
+/// Display the bump of commit messages read by `git-publish analyze`, with
+/// the bump each message calls for on its own.
+pub fn display_message_analysis(analysis: &MessageAnalysis) {
+    let Some(bump) = analysis.bump else {
+        println!("{}", text(Msg::AnalysisNoMessages));
+        return;
+    };
+    println!(
+        "\x1b[1m{}\x1b[0m",
+        tr(
+            Msg::AnalysisBump,
+            &[
+                ("bump", &format!("{:?}", bump).to_lowercase()),
+                ("count", &analysis.commit_count)
+            ]
+        )
+    );
+    for commit in &analysis.commits {
+        let bump = commit.bump.map_or_else(
+            || "-".to_string(),
+            |bump| format!("{:?}", bump).to_lowercase(),
+        );
+        println!("  {:<5}  {}", bump, commit.subject);
+    }
+    if !analysis.breaking_changes.is_empty() {
+        display_breaking_changes(&analysis.breaking_changes);
+    }
+}
+
+/// Display one row per branch released by `--all-branches`: its tag and how
+/// the release ended.
+pub fn display_batch_summary(outcomes: &[BranchOutcome]) {
+    let rows: Vec<(&str, &str, &str)> = outcomes
+        .iter()
+        .map(|branch| match &branch.outcome {
+            Ok(outcome) => (
+                branch.branch.as_str(),
+                outcome.tag().unwrap_or("-"),
+                outcome.status(),
+            ),
+            Err(_) => (branch.branch.as_str(), "-", "failed"),
+        })
+        .collect();
+    let branch_width = rows
+        .iter()
+        .map(|(branch, _, _)| branch.len())
+        .chain([text(Msg::BatchColumnBranch).len()])
+        .max()
+        .unwrap_or_default();
+    let tag_width = rows
+        .iter()
+        .map(|(_, tag, _)| tag.len())
+        .chain([text(Msg::BatchColumnTag).len()])
+        .max()
+        .unwrap_or_default();
+
+    println!("\n\x1b[1m{}\x1b[0m", text(Msg::BatchSummaryHeader));
+    println!(
+        "  \x1b[4m{:<branch_width$}  {:<tag_width$}  {}\x1b[0m",
+        text(Msg::BatchColumnBranch),
+        text(Msg::BatchColumnTag),
+        text(Msg::BatchColumnResult),
+    );
+    for (branch, tag, status) in rows {
+        let color = match status {
+            "failed" | "aborted" => "31",
+            "published" => "32",
+            _ => "33",
+        };
+        println!(
+            "  {:<branch_width$}  {:<tag_width$}  \x1b[{}m{}\x1b[0m",
+            branch, tag, color, status
+        );
+    }
+}
+
+/// Display the commits responsible for a major version bump.
+///
+/// # Arguments
+/// * `subjects` - Subject lines of the breaking commits
+pub fn display_breaking_changes(subjects: &[String]) {
+    println!("\n\x1b[1;31m{}\x1b[0m", text(Msg::BreakingChangesHeader));
+    for subject in subjects {
+        println!("  - {}", subject);
+    }
 }
 
 /// Display available branches configured for tagging.
@@ -87,24 +216,222 @@ pub fn display_boundary_warning(warning: &BoundaryWarning) {
 /// # Arguments
 /// * `branches` - List of branch names to display
 pub fn display_available_branches(branches: &[String]) {
-    println!("\x1b[1mConfigured branches:\x1b[0m");
+    println!("\x1b[1m{}\x1b[0m", text(Msg::ConfiguredBranches));
     for branch in branches {
         println!("  - {}", branch);
     }
 }
 
+/// Release status of a configured branch, as shown by `--list`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchOverview {
+    /// Branch name
+    pub branch: String,
+    /// Configured tag pattern
+    pub pattern: String,
+    /// Whether the branch exists in the repository
+    pub exists: bool,
+    /// Latest tag matching the pattern, if any
+    pub latest_tag: Option<String>,
+    /// Number of commits since the latest tag
+    pub pending_commits: usize,
+    /// Tag the next release would get, if it can be computed
+    pub next_tag: Option<String>,
+}
+
+/// Display the release dashboard of configured branches.
+///
+/// # Arguments
+/// * `overviews` - Status of each configured branch, in display order
+pub fn display_branch_dashboard(overviews: &[BranchOverview]) {
+    println!("\x1b[1m{}\x1b[0m", text(Msg::ConfiguredBranches));
+    for overview in overviews {
+        if !overview.exists {
+            println!(
+                "  - {} ({}) \x1b[2m{}\x1b[0m",
+                overview.branch,
+                overview.pattern,
+                text(Msg::BranchMissing)
+            );
+            continue;
+        }
+        let latest = overview.latest_tag.as_deref().unwrap_or("-");
+        let next = match (&overview.next_tag, overview.pending_commits) {
+            (Some(next), count) if count > 0 => format!("\x1b[32m{}\x1b[0m", next),
+            _ => "-".to_string(),
+        };
+        println!(
+            "  - {} ({}): {}",
+            overview.branch,
+            overview.pattern,
+            tr(
+                Msg::BranchDashboardLine,
+                &[
+                    ("tag", &latest),
+                    ("count", &overview.pending_commits),
+                    ("next", &next),
+                ]
+            )
+        );
+    }
+}
+
 /// Display manual push instruction for a tag.
 ///
-/// Shows the git command needed to push the tag to a remote.
+/// Shows the command needed to push the tag to a remote.
+///
+/// # Arguments
+/// * `command` - The rendered push command (e.g., "git push origin v1.2.3")
+pub fn display_manual_push_instruction(command: &str) {
+    println!(
+        "\n\x1b[33m→\x1b[0m {}\n  \x1b[36m{}\x1b[0m",
+        text(Msg::ManualPushInstruction),
+        command
+    );
+}
+
+/// Display the hooks a dry run would execute and the environment they'd get.
+///
+/// Prints nothing when no hooks are configured or discovered.
+///
+/// # Arguments
+/// * `hooks` - Hook names with the command lines that would run, in order
+/// * `env` - Environment passed to the hooks, with secrets already masked
+pub fn display_hook_dry_run(hooks: &[(&str, Vec<String>)], env: &[(String, String)]) {
+    if hooks.iter().all(|(_, commands)| commands.is_empty()) {
+        return;
+    }
+    println!("{}", text(Msg::DryRunHooks));
+    for (hook, commands) in hooks {
+        for command in commands {
+            println!("    {}: {}", hook, command);
+        }
+    }
+    println!("{}", text(Msg::DryRunHookEnv));
+    for (key, value) in env {
+        println!("    {}={}", key, value);
+    }
+}
+
+/// Display one line per executed hook script with its outcome.
+///
+/// Prints nothing when no hooks ran.
+///
+/// # Arguments
+/// * `runs` - Executed hook scripts, in execution order
+pub fn display_hook_summary(runs: &[HookRun]) {
+    if runs.is_empty() {
+        return;
+    }
+    println!("\n\x1b[1m{}\x1b[0m", text(Msg::HookSummaryHeader));
+    for run in runs {
+        let duration = format!("{:.2}s", run.duration_ms as f64 / 1000.0);
+        let (mark, note) = if run.succeeded() {
+            ("\x1b[32m✓\x1b[0m", duration)
+        } else if run.aborts() {
+            let failed = text(Msg::HookSummaryFailed);
+            ("\x1b[31m✗\x1b[0m", format!("{}, {}", failed, duration))
+        } else {
+            let ignored = text(Msg::HookSummaryIgnored);
+            ("\x1b[33m⚠\x1b[0m", format!("{}, {}", ignored, duration))
+        };
+        println!(
+            "  {} {} {} ({})",
+            mark,
+            run.hook_type.name(),
+            run.script,
+            note
+        );
+    }
+}
+
+/// Display the results of `git-publish doctor` as a pass/fail report.
+///
+/// # Arguments
+/// * `report` - Collected diagnostic results
+pub fn display_doctor_report(report: &DoctorReport) {
+    println!("\x1b[1mgit-publish doctor\x1b[0m");
+    for check in &report.checks {
+        let color = match check.status {
+            CheckStatus::Pass => "32",
+            CheckStatus::Warn => "33",
+            CheckStatus::Fail => "31",
+        };
+        println!(
+            "  \x1b[{}m[{}]\x1b[0m {}: {}",
+            color, check.status, check.name, check.detail
+        );
+    }
+    println!(
+        "\n{}",
+        tr(
+            Msg::DoctorSummary,
+            &[
+                ("passed", &report.count(CheckStatus::Pass)),
+                ("warnings", &report.count(CheckStatus::Warn)),
+                ("failed", &report.count(CheckStatus::Fail)),
+            ]
+        )
+    );
+}
+
+/// Display how `git-publish explain` classified a commit.
 ///
 /// # Arguments
-/// * `tag` - The tag that was created locally
-/// * `remote` - The remote name (e.g., "origin")
-pub fn display_manual_push_instruction(tag: &str, remote: &str) {
+/// * `subject` - First line of the explained commit
+/// * `explanation` - The parse result and the rule that decided the bump
+pub fn display_commit_explanation(subject: &str, explanation: &CommitExplanation) {
+    let parsed = &explanation.parsed;
     println!(
-        "\n\x1b[33m→\x1b[0m To push this tag later, run:\n  \x1b[36mgit push {} {}\x1b[0m",
-        remote, tag
+        "\x1b[1m{}\x1b[0m",
+        tr(Msg::ExplainHeader, &[("subject", &subject)])
     );
+    if explanation.conventional {
+        println!("  {}", tr(Msg::ExplainType, &[("type", &parsed.r#type)]));
+    } else {
+        println!(
+            "  \x1b[33m{}\x1b[0m",
+            tr(Msg::ExplainNotConventional, &[("type", &parsed.r#type)])
+        );
+    }
+    if let Some(scope) = &parsed.scope {
+        println!("  {}", tr(Msg::ExplainScope, &[("scope", scope)]));
+    }
+    println!(
+        "  {}",
+        tr(
+            Msg::ExplainBreaking,
+            &[("breaking", &parsed.is_breaking_change)]
+        )
+    );
+    if let Some(note) = &parsed.deprecation {
+        println!("  {}", tr(Msg::ExplainDeprecation, &[("note", note)]));
+    }
+    if let Some(scope) = &explanation.ignored_scope {
+        println!("  {}", tr(Msg::ExplainScopeIgnored, &[("scope", scope)]));
+    }
+    match &explanation.bump {
+        Some((bump, rule)) => println!(
+            "  \x1b[1m{}\x1b[0m",
+            tr(
+                Msg::ExplainBump,
+                &[
+                    ("bump", &format!("{:?}", bump).to_lowercase()),
+                    ("rule", rule)
+                ]
+            )
+        ),
+        None => println!("  {}", text(Msg::ExplainNoBump)),
+    }
+}
+
+/// Print the time spent per phase (`--profile-timings`) to stderr, keeping
+/// stdout free for the command's own output.
+pub fn display_timings(lines: &[String]) {
+    eprintln!("\x1b[1m{}\x1b[0m", text(Msg::TimingsHeader));
+    for line in lines {
+        eprintln!("  {}", line);
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,60 @@
+//! Machine-readable output for `--json`.
+//!
+//! Instead of the progress lines, a run prints its [`WorkflowResult`] as a
+//! single JSON document on stdout once it is over. Warnings and errors are
+//! still shown on stderr, so CI logs keep them.
+
+use crate::boundary::BoundaryWarning;
+use crate::domain::commit::NonConformingCommit;
+use crate::hooks::HookRun;
+use crate::ui;
+use crate::workflow::{Notifier, ReleaseDecision, WorkflowResult};
+
+/// Notifier for `--json`: keeps stdout for the final [`WorkflowResult`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonNotifier;
+
+impl Notifier for JsonNotifier {
+    fn status(&self, _: &str) {}
+
+    fn success(&self, _: &str) {}
+
+    fn warning(&self, message: &str) {
+        ui::display_warning(message);
+    }
+
+    fn error(&self, message: &str) {
+        ui::display_error(message);
+    }
+
+    fn message(&self, _: &str) {}
+
+    fn finished(&self, _: &str) {}
+
+    fn boundary_warning(&self, warning: &BoundaryWarning) {
+        ui::display_boundary_warning(warning);
+    }
+
+    fn commit_analysis(&self, _: &[String], _: &str) {}
+
+    fn non_conforming_commits(&self, _: &[NonConformingCommit]) {}
+
+    fn proposed_tag(&self, _: Option<&str>, _: &str) {}
+
+    fn breaking_changes(&self, _: &[String]) {}
+
+    fn hook_dry_run(&self, _: &[(&str, Vec<String>)], _: &[(String, String)]) {}
+
+    fn hook_summary(&self, _: &[HookRun]) {}
+
+    fn manual_push_instruction(&self, _: &str) {}
+
+    fn release_decision(&self, _: &ReleaseDecision) {}
+
+    fn workflow_result(&self, result: &WorkflowResult) {
+        match result.to_json() {
+            Ok(json) => println!("{}", json),
+            Err(e) => ui::display_error(&e.to_string()),
+        }
+    }
+}
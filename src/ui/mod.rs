@@ -2,18 +2,31 @@
 //!
 //! Separates concerns:
 //! - `formatter` - Pure formatting functions
+//! - `json` - Machine-readable run report for `--json`
+//! - `tui` - Full-screen release planner for `--tui`
 //! - This module - Interactive prompts and user input handling
 
 use std::io::{self, Write};
 
 use anyhow::Result;
 
+use crate::config::VersionSource;
+use crate::domain::{PreRelease, Version};
+use crate::i18n::{text, tr, Msg};
+
 pub mod formatter;
+pub mod json;
+#[cfg(feature = "tui")]
+pub mod tui;
 
 // Re-export formatter functions for convenience
 pub use formatter::{
-    display_available_branches, display_boundary_warning, display_commit_analysis, display_error,
-    display_manual_push_instruction, display_proposed_tag, display_status, display_success,
+    display_available_branches, display_batch_summary, display_boundary_warning,
+    display_branch_dashboard, display_breaking_changes, display_commit_analysis,
+    display_commit_explanation, display_doctor_report, display_error, display_hook_dry_run,
+    display_hook_summary, display_manual_push_instruction, display_message_analysis,
+    display_non_conforming_commits, display_proposed_tag, display_status, display_success,
+    display_timings, display_warning, BranchOverview,
 };
 
 /// Prompts user to select a branch from available options.
@@ -33,14 +46,17 @@ pub fn select_branch(available_branches: &[String]) -> Result<String> {
         return Ok(available_branches[0].clone());
     }
 
-    println!("\n\x1b[1mAvailable branches for tagging:\x1b[0m");
+    println!("\n\x1b[1m{}\x1b[0m", text(Msg::AvailableBranches));
     for (i, branch) in available_branches.iter().enumerate() {
         println!("  {}. {}", i + 1, branch);
     }
 
     print!(
-        "\nSelect a branch (1-{}) [default: 1]: ",
-        available_branches.len()
+        "\n{}",
+        tr(
+            Msg::SelectBranchPrompt,
+            &[("max", &available_branches.len())]
+        )
     );
     io::stdout().flush()?;
 
@@ -58,7 +74,7 @@ pub fn select_branch(available_branches: &[String]) -> Result<String> {
     if index > 0 && index <= available_branches.len() {
         Ok(available_branches[index - 1].clone())
     } else {
-        Err(anyhow::anyhow!("Invalid selection"))
+        Err(anyhow::anyhow!(text(Msg::InvalidSelection)))
     }
 }
 
@@ -78,14 +94,17 @@ pub fn select_remote(available_remotes: &[String]) -> Result<String> {
         return Ok(available_remotes[0].clone());
     }
 
-    println!("\n\x1b[1mAvailable remotes:\x1b[0m");
+    println!("\n\x1b[1m{}\x1b[0m", text(Msg::AvailableRemotes));
     for (i, remote) in available_remotes.iter().enumerate() {
         println!("  {}. {}", i + 1, remote);
     }
 
     print!(
-        "\nSelect a remote for fetch/push (1-{}) [default: 1]: ",
-        available_remotes.len()
+        "\n{}",
+        tr(
+            Msg::SelectRemotePrompt,
+            &[("max", &available_remotes.len())]
+        )
     );
     io::stdout().flush()?;
 
@@ -103,7 +122,7 @@ pub fn select_remote(available_remotes: &[String]) -> Result<String> {
     if index > 0 && index <= available_remotes.len() {
         Ok(available_remotes[index - 1].clone())
     } else {
-        Err(anyhow::anyhow!("Invalid remote selection"))
+        Err(anyhow::anyhow!(text(Msg::InvalidRemoteSelection)))
     }
 }
 
@@ -130,11 +149,51 @@ pub fn confirm_action(prompt: &str) -> Result<bool> {
     Ok(response == "y" || response == "yes")
 }
 
+/// Asks a yes/no question, Enter answering `default`.
+///
+/// # Returns
+/// * `Ok(true)` - If the user entered "y" or "yes", or pressed Enter with a `true` default
+/// * `Ok(false)` - Otherwise
+/// * `Err` - If input error occurs
+pub fn confirm_with_default(prompt: &str, default: bool) -> Result<bool> {
+    print!("\n{} {}: ", prompt, if default { "(Y/n)" } else { "(y/N)" });
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    let response = input.trim().to_lowercase();
+    Ok(match response.as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        _ => false,
+    })
+}
+
+/// Asks for a line of text, Enter keeping `default`.
+///
+/// # Returns
+/// * `Ok(text)` - The trimmed input, or `default` if nothing was entered
+/// * `Err` - If input error occurs
+pub fn input_with_default(prompt: &str, default: &str) -> Result<String> {
+    print!("{} [{}]: ", prompt, default);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(match input.trim() {
+        "" => default.to_string(),
+        entered => entered.to_string(),
+    })
+}
+
 /// Validates that a tag matches the configured pattern.
 ///
 /// Checks if the tag conforms to the pattern (e.g., "v{version}" -> "v1.2.3").
 /// If pattern has no {version} placeholder, any tag is valid.
-/// Validates that the version part contains only digits and dots.
+/// Validates that the version part contains only digits and dots, optionally
+/// followed by a pre-release such as `-beta.1`.
 ///
 /// # Arguments
 /// * `tag` - The tag to validate (e.g., "v1.2.3")
@@ -161,10 +220,10 @@ pub fn validate_tag_format(tag: &str, pattern: &str) -> Result<()> {
     // Extract prefix and suffix from pattern around {version}
     let parts: Vec<&str> = pattern.split("{version}").collect();
     if parts.len() != 2 {
-        return Err(anyhow::anyhow!(
-            "Invalid pattern '{}': should have exactly one {{version}} placeholder",
-            pattern
-        ));
+        return Err(anyhow::anyhow!(tr(
+            Msg::PatternMultiplePlaceholders,
+            &[("pattern", &pattern)]
+        )));
     }
 
     let prefix = parts[0];
@@ -172,39 +231,139 @@ pub fn validate_tag_format(tag: &str, pattern: &str) -> Result<()> {
 
     // Check if tag starts with prefix
     if !tag.starts_with(prefix) {
-        return Err(anyhow::anyhow!(
-            "Tag '{}' does not match pattern '{}': missing prefix '{}'",
-            tag,
-            pattern,
-            prefix
-        ));
+        return Err(anyhow::anyhow!(tr(
+            Msg::TagMissingPrefix,
+            &[("tag", &tag), ("pattern", &pattern), ("prefix", &prefix)]
+        )));
     }
 
     // Check if tag ends with suffix
     if !tag.ends_with(suffix) {
-        return Err(anyhow::anyhow!(
-            "Tag '{}' does not match pattern '{}': missing suffix '{}'",
-            tag,
-            pattern,
-            suffix
-        ));
+        return Err(anyhow::anyhow!(tr(
+            Msg::TagMissingSuffix,
+            &[("tag", &tag), ("pattern", &pattern), ("suffix", &suffix)]
+        )));
     }
 
     // Extract version part
     let version_part = &tag[prefix.len()..tag.len() - suffix.len()];
 
-    // Validate it looks like a version (basic check: contains only digits and dots)
-    if !version_part.chars().all(|c| c.is_ascii_digit() || c == '.') {
-        return Err(anyhow::anyhow!(
-            "Tag '{}' has invalid version format '{}'",
-            tag,
-            version_part
-        ));
+    // Validate it looks like a version (basic check: contains only digits and
+    // dots), with an optional pre-release of a release channel
+    let (core, prerelease) = match version_part.split_once('-') {
+        Some((core, prerelease)) => (core, Some(prerelease)),
+        None => (version_part, None),
+    };
+    if !core.chars().all(|c| c.is_ascii_digit() || c == '.')
+        || prerelease.is_some_and(|prerelease| PreRelease::parse(prerelease).is_err())
+    {
+        return Err(anyhow::anyhow!(tr(
+            Msg::TagInvalidVersion,
+            &[("tag", &tag), ("version", &version_part)]
+        )));
     }
 
     Ok(())
 }
 
+/// Validates a tag against the organization's `behavior.tag_validation_regex`.
+///
+/// Applies on top of [`validate_tag_format`], so custom-entered tags follow
+/// the same policy as recommended ones. Without a regex every tag passes.
+///
+/// # Returns
+/// * `Ok(())` - If the tag matches (or no regex is configured)
+/// * `Err(anyhow::Error)` - If the tag doesn't match or the regex is invalid
+pub fn validate_tag_policy(tag: &str, regex: Option<&str>) -> Result<()> {
+    let Some(regex) = regex else {
+        return Ok(());
+    };
+    if regex::Regex::new(regex)?.is_match(tag) {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(tr(
+            Msg::TagPolicyViolation,
+            &[("tag", &tag), ("regex", &regex)]
+        )))
+    }
+}
+
+/// Completes `input` to the longest prefix shared by the tags starting with it.
+///
+/// # Returns
+/// * `Some(completed)` - If that prefix is longer than `input`
+/// * `None` - If no tag starts with `input` or there is nothing to add
+///
+/// # Examples
+/// ```ignore
+/// complete_tag("v", &["v1.10.0", "v1.11.0"])     // Some("v1.1")
+/// complete_tag("v1.10", &["v1.10.0", "v1.11.0"]) // Some("v1.10.0")
+/// complete_tag("v1.1", &["v1.10.0", "v1.11.0"])  // None - nothing to add
+/// ```
+pub fn complete_tag(input: &str, tags: &[String]) -> Option<String> {
+    let mut matching = tags.iter().filter(|tag| tag.starts_with(input));
+    let mut common = matching.next()?.as_str();
+    for tag in matching {
+        let shared = common
+            .char_indices()
+            .zip(tag.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(common.len().min(tag.len()), |((index, _), _)| index);
+        common = &common[..shared];
+    }
+    (common.len() > input.len()).then(|| common.to_string())
+}
+
+/// Shows the latest existing tags as context for typing a custom one.
+fn display_recent_tags(recent_tags: &[String]) {
+    if !recent_tags.is_empty() {
+        println!(
+            "\x1b[2m{}\x1b[0m",
+            tr(Msg::RecentTags, &[("tags", &recent_tags.join(", "))])
+        );
+    }
+}
+
+/// Reads a tag typed by the user, completing it with Tab.
+///
+/// Tab extends the input to the longest prefix shared by the `recent_tags`
+/// starting with it. When stdin is not a terminal, reads a plain line.
+fn read_tag_input(recent_tags: &[String]) -> Result<String> {
+    let term = console::Term::stdout();
+    if !term.is_term() {
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        return Ok(input.trim().to_string());
+    }
+
+    let mut input = String::new();
+    loop {
+        match term.read_key()? {
+            console::Key::Enter => {
+                term.write_line("")?;
+                return Ok(input.trim().to_string());
+            }
+            console::Key::Tab => {
+                if let Some(completed) = complete_tag(&input, recent_tags) {
+                    term.write_str(&completed[input.len()..])?;
+                    input = completed;
+                }
+            }
+            console::Key::Backspace if input.pop().is_some() => term.clear_chars(1)?,
+            console::Key::Char(c) if !c.is_control() => {
+                input.push(c);
+                term.write_str(c.encode_utf8(&mut [0; 4]))?;
+            }
+            // Raw mode swallows the signal, so interrupt like the Ctrl-C handler
+            console::Key::CtrlC => {
+                eprintln!("\n\x1b[33m⚠\x1b[0m {}", text(Msg::Interrupted));
+                crate::interrupt::abort(crate::interrupt::INTERRUPTED_EXIT_CODE);
+            }
+            _ => {}
+        }
+    }
+}
+
 /// Prompts user to select or customize a tag.
 ///
 /// Provides three options:
@@ -212,9 +371,12 @@ pub fn validate_tag_format(tag: &str, pattern: &str) -> Result<()> {
 /// 2. Enter a custom tag
 /// 3. Enter 'e' to edit the recommended tag
 ///
+/// The latest existing tags are shown first, and typed tags complete with Tab.
+///
 /// # Arguments
 /// * `recommended_tag` - The default recommended tag
 /// * `_pattern` - The tag pattern for validation (currently unused but kept for API compatibility)
+/// * `recent_tags` - Latest existing tags of the pattern, lowest version first
 ///
 /// # Returns
 /// * `Ok(String)` - The selected or customized tag
@@ -227,81 +389,123 @@ pub fn validate_tag_format(tag: &str, pattern: &str) -> Result<()> {
 /// // Returns custom tag if user enters one
 /// // Returns edited tag if user enters 'e'
 /// ```
-pub fn select_or_customize_tag(recommended_tag: &str, _pattern: &str) -> Result<String> {
-    print!(
-        "\nTag options:\n  (press Enter to use recommended)\n  (enter custom tag)\n  (enter 'e' to edit)\n\nTag [{}]: ",
-        recommended_tag
-    );
+pub fn select_or_customize_tag(
+    recommended_tag: &str,
+    _pattern: &str,
+    recent_tags: &[String],
+) -> Result<String> {
+    println!();
+    display_recent_tags(recent_tags);
+    print!("{}", tr(Msg::TagOptions, &[("tag", &recommended_tag)]));
     io::stdout().flush()?;
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    let input = input.trim();
+    let input = read_tag_input(recent_tags)?;
 
-    match input {
+    match input.as_str() {
         "" => Ok(recommended_tag.to_string()),
         "e" => {
-            print!("Edit tag [{}]: ", recommended_tag);
+            print!("{}", tr(Msg::EditTagPrompt, &[("tag", &recommended_tag)]));
             io::stdout().flush()?;
 
-            let mut edited = String::new();
-            io::stdin().read_line(&mut edited)?;
-            Ok(edited.trim().to_string())
+            read_tag_input(recent_tags)
         }
-        custom => Ok(custom.to_string()),
+        _ => Ok(input),
     }
 }
 
 /// Prompts user to select from candidate tags or enter a custom tag.
 ///
-/// The first item is treated as the default recommendation.
+/// The first item is treated as the default recommendation. An invalid
+/// selection is reported and asked again. For a custom tag, the latest
+/// existing tags are shown and the input completes with Tab.
 pub fn select_tag_from_candidates(
     recommended_tag: &str,
     candidate_tags: &[String],
+    recent_tags: &[String],
 ) -> Result<String> {
     if candidate_tags.is_empty() {
         return Ok(recommended_tag.to_string());
     }
 
-    println!("\n\x1b[1mSuggested tags:\x1b[0m");
+    println!("\n\x1b[1m{}\x1b[0m", text(Msg::SuggestedTags));
     for (index, tag) in candidate_tags.iter().enumerate() {
         if index == 0 {
-            println!("  {}. {} (recommended)", index + 1, tag);
+            println!("  {}. {} {}", index + 1, tag, text(Msg::Recommended));
         } else {
             println!("  {}. {}", index + 1, tag);
         }
     }
-    println!("  c. Custom tag");
+    println!("  c. {}", text(Msg::CustomTagOption));
 
-    print!(
-        "\nSelect a tag (1-{}, c) [default: 1]: ",
-        candidate_tags.len()
-    );
-    io::stdout().flush()?;
+    loop {
+        print!(
+            "\n{}",
+            tr(Msg::SelectTagPrompt, &[("max", &candidate_tags.len())])
+        );
+        io::stdout().flush()?;
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
-    let selection = input.trim();
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let selection = input.trim();
 
-    if selection.is_empty() {
-        return Ok(candidate_tags[0].clone());
-    }
+        if selection.is_empty() {
+            return Ok(candidate_tags[0].clone());
+        }
 
-    if selection.eq_ignore_ascii_case("c") {
-        print!("Enter custom tag: ");
-        io::stdout().flush()?;
+        if selection.eq_ignore_ascii_case("c") {
+            display_recent_tags(recent_tags);
+            print!("{}", text(Msg::EnterCustomTag));
+            io::stdout().flush()?;
 
-        let mut custom = String::new();
-        io::stdin().read_line(&mut custom)?;
-        return Ok(custom.trim().to_string());
-    }
+            return read_tag_input(recent_tags);
+        }
 
-    let index = selection.parse::<usize>().unwrap_or(0);
-    if index == 0 || index > candidate_tags.len() {
-        return Err(anyhow::anyhow!("Invalid tag selection"));
+        match selection.parse::<usize>() {
+            Ok(index) if index > 0 && index <= candidate_tags.len() => {
+                return Ok(candidate_tags[index - 1].clone());
+            }
+            _ => display_warning(text(Msg::InvalidTagSelection)),
+        }
     }
+}
 
-    Ok(candidate_tags[index - 1].clone())
+/// Prompts user to choose between the latest tag and the manifest as the
+/// version to continue from, when the two disagree.
+///
+/// # Arguments
+/// * `tag` - The latest tag and its version
+/// * `manifest` - The manifest file name and the version it declares
+/// * `default` - Source used when the user presses Enter
+pub fn select_version_source(
+    tag: (&str, &Version),
+    manifest: (&str, &Version),
+    default: VersionSource,
+) -> Result<VersionSource> {
+    println!("\n\x1b[1m{}\x1b[0m", text(Msg::ContinueFromVersion));
+    println!("  1. {} ({})", tag.0, tag.1);
+    println!("  2. {} ({})", manifest.0, manifest.1);
+
+    let default_index = match default {
+        VersionSource::Tag => 1,
+        VersionSource::Manifest => 2,
+    };
+    print!(
+        "\n{}",
+        tr(
+            Msg::SelectVersionSourcePrompt,
+            &[("default", &default_index)]
+        )
+    );
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    match input.trim() {
+        "" => Ok(default),
+        "1" => Ok(VersionSource::Tag),
+        "2" => Ok(VersionSource::Manifest),
+        _ => Err(anyhow::anyhow!(text(Msg::InvalidVersionSourceSelection))),
+    }
 }
 
 /// Confirms tag use with format validation.
@@ -330,7 +534,7 @@ pub fn confirm_tag_use(tag: &str, pattern: &str) -> Result<bool> {
 
     // If validation passed, confirm with user
     // Default is Y (confirm) - user needs to enter 'n' or 'no' to decline
-    print!("\nConfirm tag creation: {} (Y/n): ", tag);
+    print!("\n{}", tr(Msg::ConfirmTagCreation, &[("tag", &tag)]));
     io::stdout().flush()?;
 
     let mut input = String::new();
@@ -344,27 +548,29 @@ pub fn confirm_tag_use(tag: &str, pattern: &str) -> Result<bool> {
 /// Prompts user to confirm pushing a locally created tag to a remote.
 ///
 /// Asks if the user wants to push the tag to the specified remote.
-/// Default is not to push (user must enter "y" or "yes" to confirm).
+/// Pressing Enter gives `default`, shown capitalized in the (Y/n) hint.
 ///
 /// # Arguments
 /// * `tag` - The tag that was created locally
 /// * `remote` - The remote name (e.g., "origin")
+/// * `default` - Answer when the user just presses Enter
 ///
 /// # Returns
-/// * `Ok(true)` - If user enters "y" or "yes"
-/// * `Ok(false)` - Otherwise (including Enter or "n"/"no")
+/// * `Ok(true)` - If user enters "y" or "yes", or presses Enter with `default` set
+/// * `Ok(false)` - If user enters "n" or "no", or presses Enter without `default`
 /// * `Err` - If input error occurs
 ///
 /// # Examples
 /// ```ignore
-/// if confirm_push_tag("v1.2.3", "origin")? {
+/// if confirm_push_tag("v1.2.3", "origin", true)? {
 ///     // Push the tag to remote
 /// }
 /// ```
-pub fn confirm_push_tag(tag: &str, remote: &str) -> Result<bool> {
+pub fn confirm_push_tag(tag: &str, remote: &str, default: bool) -> Result<bool> {
     print!(
-        "\nTag '{}' created locally. Push to remote '{}' (Y/n): ",
-        tag, remote
+        "\n{} {}: ",
+        tr(Msg::ConfirmPushTag, &[("tag", &tag), ("remote", &remote)]),
+        if default { "(Y/n)" } else { "(y/N)" }
     );
     io::stdout().flush()?;
 
@@ -372,7 +578,94 @@ pub fn confirm_push_tag(tag: &str, remote: &str) -> Result<bool> {
     io::stdin().read_line(&mut input)?;
 
     let response = input.trim().to_lowercase();
-    Ok(response.is_empty() || response == "y" || response == "yes")
+    Ok(match response.as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        _ => false,
+    })
+}
+
+/// Asks the user to type the new major version to acknowledge a breaking release.
+///
+/// Guards against accidental major bumps caused by a stray `!` or footer: a plain
+/// Enter or "y" is not enough, the exact major number must be typed.
+///
+/// # Arguments
+/// * `major` - The major version number the release would bump to
+///
+/// # Returns
+/// * `Ok(true)` - If the user typed the major version
+/// * `Ok(false)` - Otherwise
+/// * `Err` - If input error occurs
+pub fn confirm_major_version(major: u32) -> Result<bool> {
+    print!("\n{}", tr(Msg::ConfirmMajorVersion, &[("major", &major)]));
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(input.trim() == major.to_string())
+}
+
+/// Ask whether to trust an SSH host that is not yet in `known_hosts`
+///
+/// # Arguments
+/// * `host` - Host name presented for the connection
+/// * `fingerprint` - SHA-256 fingerprint of the host key
+///
+/// # Returns
+/// true if the user accepts the key (it is then saved), false otherwise (default)
+pub fn confirm_unknown_host_key(host: &str, fingerprint: &str) -> Result<bool> {
+    println!(
+        "\n{}",
+        tr(
+            Msg::UnknownHostKey,
+            &[("host", &host), ("fingerprint", &fingerprint)]
+        )
+    );
+    print!("{}", text(Msg::AcceptHostKey));
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    let response = input.trim().to_lowercase();
+    Ok(response == "y" || response == "yes")
+}
+
+/// Ask for the HTTPS username and password (or token) of a remote
+///
+/// # Arguments
+/// * `url` - URL of the remote asking for credentials
+/// * `username` - Username named in the URL, offered as the default
+///
+/// # Returns
+/// The username and password, or `None` when no password is entered
+pub fn prompt_https_credentials(
+    url: &str,
+    username: Option<&str>,
+) -> Result<Option<(String, String)>> {
+    println!("\n{}", tr(Msg::HttpsCredentialsNeeded, &[("url", &url)]));
+    match username {
+        Some(username) => print!(
+            "{}",
+            tr(Msg::HttpsUsernameWithDefault, &[("username", &username)])
+        ),
+        None => print!("{}", text(Msg::HttpsUsername)),
+    }
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let username = match input.trim() {
+        "" => username.unwrap_or_default().to_string(),
+        entered => entered.to_string(),
+    };
+
+    print!("{}", text(Msg::HttpsPassword));
+    io::stdout().flush()?;
+    let password = console::Term::stdout().read_secure_line()?;
+    Ok((!username.is_empty() && !password.is_empty()).then_some((username, password)))
 }
 
 #[cfg(test)]
@@ -384,11 +677,30 @@ mod tests {
         assert!(validate_tag_format("v1.2.3", "v{version}").is_ok());
     }
 
+    #[test]
+    fn test_complete_tag_to_common_prefix() {
+        let tags: Vec<String> = vec!["v1.9.0".into(), "v1.10.0".into(), "v1.11.0".into()];
+        assert_eq!(complete_tag("", &tags).as_deref(), Some("v1."));
+        assert_eq!(complete_tag("v1.1", &tags), None);
+        assert_eq!(complete_tag("v1.10", &tags).as_deref(), Some("v1.10.0"));
+        assert_eq!(complete_tag("g", &tags), None);
+        assert_eq!(complete_tag("v", &[]), None);
+    }
+
     #[test]
     fn test_validate_tag_format_no_constraint() {
         assert!(validate_tag_format("anything", "free-form").is_ok());
     }
 
+    #[test]
+    fn test_validate_tag_policy() {
+        let policy = Some("^[a-z0-9.-]{1,12}$");
+        assert!(validate_tag_policy("v1.2.3", policy).is_ok());
+        assert!(validate_tag_policy("V1.2.3", policy).is_err());
+        assert!(validate_tag_policy("v1.2.3-hotfix-final", policy).is_err());
+        assert!(validate_tag_policy("V1.2.3", None).is_ok());
+    }
+
     #[test]
     fn test_validate_tag_format_with_suffix() {
         assert!(validate_tag_format("v1.2.3-release", "v{version}-release").is_ok());
@@ -412,9 +724,16 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_validate_tag_format_prerelease() {
+        assert!(validate_tag_format("v1.3.0-beta.2", "v{version}").is_ok());
+        assert!(validate_tag_format("v1.3.0-beta.x", "v{version}").is_err());
+        assert!(validate_tag_format("v1.3.0-", "v{version}").is_err());
+    }
+
     #[test]
     fn test_select_tag_from_candidates_empty_defaults_to_recommended() {
-        let selected = select_tag_from_candidates("v1.2.3", &[]).unwrap();
+        let selected = select_tag_from_candidates("v1.2.3", &[], &[]).unwrap();
         assert_eq!(selected, "v1.2.3");
     }
 
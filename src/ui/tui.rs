@@ -0,0 +1,552 @@
+//! Full-screen release planner for `--tui`.
+//!
+//! Shows each configured branch with its latest tag, the commits since and how
+//! each one is classified, and the proposed tag, from local data. Keys adjust
+//! the bump and pre-release; the confirmed tag is then handed to the regular
+//! publish workflow through [`PlannedPrompter`], so fetching, hooks and
+//! pushing work exactly as without the TUI.
+
+use std::ops::ControlFlow;
+
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Tabs};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::config::{Config, VersionSource};
+use crate::domain::commit::BumpAnalysis;
+use crate::domain::{ParsedCommit, PreRelease, PreReleaseType, TagPattern, Version, VersionBump};
+use crate::git_ops::GitRepo;
+use crate::i18n::{text, tr, Msg};
+use crate::workflow::Prompter;
+
+/// A commit since the latest tag, as listed on screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitView {
+    /// First line of the message
+    pub subject: String,
+    /// What the commit alone calls for; `None` for e.g. `docs:` commits
+    pub bump: Option<VersionBump>,
+}
+
+/// A configured branch and what releasing it would contain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchView {
+    pub name: String,
+    pub pattern: TagPattern,
+    /// Whether the branch exists in this repository
+    pub exists: bool,
+    pub latest_tag: Option<String>,
+    /// Commits since the latest tag, newest first
+    pub commits: Vec<CommitView>,
+    /// Bump the commits call for
+    pub suggested_bump: VersionBump,
+}
+
+impl BranchView {
+    /// Read the latest tag and the commits since from local data, up to
+    /// `max_commits_analyzed` of them
+    pub fn load(repo: &GitRepo, cfg: &Config, name: &str, exists: bool) -> Self {
+        let pattern = cfg
+            .branches
+            .tag_pattern(name)
+            .cloned()
+            .unwrap_or_else(|| TagPattern::new("v{version}"));
+        let mut view = BranchView {
+            name: name.to_string(),
+            pattern,
+            exists,
+            latest_tag: None,
+            commits: Vec::new(),
+            suggested_bump: VersionBump::Patch,
+        };
+        if !exists {
+            return view;
+        }
+
+        view.latest_tag = repo
+            .get_latest_tag_on_branch(name, Some(view.pattern.as_str()))
+            .ok()
+            .flatten();
+        let limit = cfg.behavior.max_commits_analyzed;
+        let mut analysis = BumpAnalysis::default();
+        let _ = repo.walk_commits_since_tag(name, view.latest_tag.as_deref(), |commit| {
+            let message = commit.message().unwrap_or_default();
+            analysis.add(message, &cfg.conventional_commits);
+            view.commits.push(CommitView {
+                subject: message.lines().next().unwrap_or_default().to_string(),
+                bump: ParsedCommit::parse_with_indicators(
+                    message,
+                    &cfg.conventional_commits.breaking_change_indicators,
+                )
+                .version_bump(),
+            });
+            if limit > 0 && view.commits.len() >= limit {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        view.suggested_bump = analysis.bump();
+        view
+    }
+}
+
+/// The branch and tag confirmed on the planner screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleasePlan {
+    pub branch: String,
+    pub tag: String,
+}
+
+/// What a key press asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Keep showing the screen
+    None,
+    /// Release the proposed tag
+    Confirm,
+    /// Leave without releasing
+    Quit,
+}
+
+/// Pre-release identifiers `r` cycles through, after "none"
+const PRERELEASE_CYCLE: [PreReleaseType; 3] = [
+    PreReleaseType::Alpha,
+    PreReleaseType::Beta,
+    PreReleaseType::ReleaseCandidate,
+];
+
+/// State of the planner screen, independent of the terminal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TuiState {
+    pub branches: Vec<BranchView>,
+    /// Index of the shown branch
+    pub selected: usize,
+    /// Bump chosen with the keys; `None` follows the commits
+    pub bump: Option<VersionBump>,
+    pub prerelease: Option<PreReleaseType>,
+    /// First commit shown in the list
+    pub scroll: usize,
+}
+
+impl TuiState {
+    pub fn new(branches: Vec<BranchView>) -> Self {
+        TuiState {
+            branches,
+            selected: 0,
+            bump: None,
+            prerelease: None,
+            scroll: 0,
+        }
+    }
+
+    /// The shown branch
+    pub fn branch(&self) -> Option<&BranchView> {
+        self.branches.get(self.selected)
+    }
+
+    /// The chosen bump, or else the one the commits call for
+    pub fn effective_bump(&self) -> Option<VersionBump> {
+        let branch = self.branch()?;
+        Some(self.bump.unwrap_or(branch.suggested_bump))
+    }
+
+    /// Tag a release of the shown branch would get; `None` when the branch
+    /// doesn't exist here
+    pub fn proposed_tag(&self) -> Option<String> {
+        let branch = self.branch().filter(|branch| branch.exists)?;
+        let bump = self.effective_bump()?;
+        let prerelease = |iteration| {
+            self.prerelease
+                .clone()
+                .map(|identifier| PreRelease::new(identifier, Some(iteration)))
+        };
+
+        let version = match branch.latest_tag.as_deref().map(Version::parse) {
+            Some(Ok(current)) => match (&current.prerelease, &self.prerelease) {
+                // Another round of the same pre-release, e.g. beta.1 -> beta.2
+                (Some(current_pre), Some(identifier))
+                    if current_pre.identifier == *identifier && self.bump.is_none() =>
+                {
+                    Version {
+                        prerelease: Some(current_pre.increment_iteration()),
+                        ..current.clone()
+                    }
+                }
+                _ => Version {
+                    prerelease: prerelease(1),
+                    ..current.bump(&bump)
+                },
+            },
+            _ => Version {
+                prerelease: prerelease(1),
+                ..Version::new(0, 1, 0)
+            },
+        };
+        Some(branch.pattern.format(&version.to_string()))
+    }
+
+    /// The confirmed release, if the shown branch can be released
+    pub fn plan(&self) -> Option<ReleasePlan> {
+        Some(ReleasePlan {
+            branch: self.branch()?.name.clone(),
+            tag: self.proposed_tag()?,
+        })
+    }
+
+    /// Apply a key press
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Enter if self.plan().is_some() => return Action::Confirm,
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::BackTab => {
+                let count = self.branches.len().max(1);
+                self.select((self.selected + count - 1) % count);
+            }
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => {
+                self.select((self.selected + 1) % self.branches.len().max(1));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let commits = self.branch().map_or(0, |branch| branch.commits.len());
+                self.scroll = (self.scroll + 1).min(commits.saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Char('M') => self.bump = Some(VersionBump::Major),
+            KeyCode::Char('m') => self.bump = Some(VersionBump::Minor),
+            KeyCode::Char('p') => self.bump = Some(VersionBump::Patch),
+            KeyCode::Char('a') => self.bump = None,
+            KeyCode::Char('r') => {
+                self.prerelease = match &self.prerelease {
+                    None => Some(PRERELEASE_CYCLE[0].clone()),
+                    Some(current) => PRERELEASE_CYCLE
+                        .iter()
+                        .position(|identifier| identifier == current)
+                        .and_then(|index| PRERELEASE_CYCLE.get(index + 1))
+                        .cloned(),
+                };
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    /// Show another branch, starting over with the bump its commits call for
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.bump = None;
+        self.scroll = 0;
+    }
+}
+
+/// Lowercase name of a bump, as in the configuration
+fn bump_name(bump: VersionBump) -> &'static str {
+    match bump {
+        VersionBump::Major => "major",
+        VersionBump::Minor => "minor",
+        VersionBump::Patch => "patch",
+    }
+}
+
+fn bump_color(bump: Option<VersionBump>) -> Color {
+    match bump {
+        Some(VersionBump::Major) => Color::Red,
+        Some(VersionBump::Minor) => Color::Green,
+        Some(VersionBump::Patch) => Color::Yellow,
+        None => Color::DarkGray,
+    }
+}
+
+/// Draw the planner screen
+pub fn render(frame: &mut Frame, state: &TuiState) {
+    let [tabs_area, summary_area, commits_area, help_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(5),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let names: Vec<&str> = state.branches.iter().map(|b| b.name.as_str()).collect();
+    frame.render_widget(
+        Tabs::new(names)
+            .select(state.selected)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(text(Msg::TuiBranches)),
+            ),
+        tabs_area,
+    );
+    frame.render_widget(
+        Paragraph::new(Line::from(text(Msg::TuiKeys))).style(Style::default().fg(Color::DarkGray)),
+        help_area,
+    );
+
+    let Some(branch) = state.branch() else {
+        return;
+    };
+    if !branch.exists {
+        frame.render_widget(
+            Paragraph::new(text(Msg::BranchMissing)).block(Block::default().borders(Borders::ALL)),
+            summary_area,
+        );
+        return;
+    }
+
+    let latest = branch.latest_tag.as_deref().unwrap_or(text(Msg::TuiNoTag));
+    let bump = state.effective_bump().unwrap_or(branch.suggested_bump);
+    let prerelease = state
+        .prerelease
+        .as_ref()
+        .map_or_else(|| text(Msg::TuiNoTag).to_string(), ToString::to_string);
+    let summary = vec![
+        Line::from(vec![
+            Span::raw(format!("{} ", text(Msg::TagFrom))),
+            Span::styled(latest.to_string(), Style::default().fg(Color::Red)),
+            Span::raw(format!("   {} ", text(Msg::TagTo))),
+            Span::styled(
+                state.proposed_tag().unwrap_or_default(),
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(tr(
+            Msg::TuiBump,
+            &[
+                ("bump", &bump_name(bump)),
+                ("suggested", &bump_name(branch.suggested_bump)),
+            ],
+        )),
+        Line::from(tr(Msg::TuiPreRelease, &[("identifier", &prerelease)])),
+    ];
+    frame.render_widget(
+        Paragraph::new(summary).block(Block::default().borders(Borders::ALL)),
+        summary_area,
+    );
+
+    let items: Vec<ListItem> = branch
+        .commits
+        .iter()
+        .skip(state.scroll)
+        .map(|commit| {
+            let label = commit.bump.map_or("-", bump_name);
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<6} ", label),
+                    Style::default().fg(bump_color(commit.bump)),
+                ),
+                Span::raw(commit.subject.clone()),
+            ]))
+        })
+        .collect();
+    let title = tr(
+        Msg::TuiCommits,
+        &[("tag", &latest), ("count", &branch.commits.len())],
+    );
+    frame.render_widget(
+        List::new(items).block(Block::default().borders(Borders::ALL).title(title)),
+        commits_area,
+    );
+}
+
+/// Show the planner for the configured branches
+///
+/// # Returns
+/// * `Ok(Some(plan))` - The user confirmed a release
+/// * `Ok(None)` - The user quit
+pub fn run(repo: &GitRepo, cfg: &Config) -> Result<Option<ReleasePlan>> {
+    let existing = repo.list_branches()?;
+    let mut names: Vec<String> = cfg.branches.keys().cloned().collect();
+    names.sort();
+    let branches = names
+        .iter()
+        .map(|name| BranchView::load(repo, cfg, name, existing.contains(name)))
+        .collect();
+    let mut state = TuiState::new(branches);
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut state);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, state: &mut TuiState) -> Result<Option<ReleasePlan>> {
+    loop {
+        terminal.draw(|frame| render(frame, state))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match state.handle_key(key) {
+                Action::Confirm => return Ok(state.plan()),
+                Action::Quit => return Ok(None),
+                Action::None => {}
+            }
+        }
+    }
+}
+
+/// Answers the branch and tag questions of the workflow from a
+/// [`ReleasePlan`], and forwards everything else
+pub struct PlannedPrompter<'a> {
+    plan: &'a ReleasePlan,
+    fallback: &'a dyn Prompter,
+}
+
+impl<'a> PlannedPrompter<'a> {
+    pub fn new(plan: &'a ReleasePlan, fallback: &'a dyn Prompter) -> Self {
+        PlannedPrompter { plan, fallback }
+    }
+}
+
+impl Prompter for PlannedPrompter<'_> {
+    fn select_branch(&self, _: &[String]) -> Result<String> {
+        Ok(self.plan.branch.clone())
+    }
+
+    fn select_remote(&self, remotes: &[String]) -> Result<String> {
+        self.fallback.select_remote(remotes)
+    }
+
+    fn confirm(&self, prompt: &str) -> Result<bool> {
+        self.fallback.confirm(prompt)
+    }
+
+    fn select_version_source(
+        &self,
+        tag: (&str, &Version),
+        manifest: (&str, &Version),
+        default: VersionSource,
+    ) -> Result<VersionSource> {
+        self.fallback.select_version_source(tag, manifest, default)
+    }
+
+    fn select_tag_from_candidates(&self, _: &str, _: &[String], _: &[String]) -> Result<String> {
+        Ok(self.plan.tag.clone())
+    }
+
+    fn select_or_customize_tag(&self, _: &str, _: &str, _: &[String]) -> Result<String> {
+        Ok(self.plan.tag.clone())
+    }
+
+    fn confirm_major_version(&self, major: u32) -> Result<bool> {
+        self.fallback.confirm_major_version(major)
+    }
+
+    fn confirm_tag_use(&self, tag: &str, pattern: &str) -> Result<bool> {
+        self.fallback.confirm_tag_use(tag, pattern)
+    }
+
+    fn confirm_push_tag(&self, tag: &str, remote: &str, default: bool) -> Result<bool> {
+        self.fallback.confirm_push_tag(tag, remote, default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn branch(latest_tag: Option<&str>) -> BranchView {
+        BranchView {
+            name: "main".to_string(),
+            pattern: TagPattern::new("v{version}"),
+            exists: true,
+            latest_tag: latest_tag.map(str::to_string),
+            commits: vec![
+                CommitView {
+                    subject: "feat: add export".to_string(),
+                    bump: Some(VersionBump::Minor),
+                },
+                CommitView {
+                    subject: "docs: typo".to_string(),
+                    bump: None,
+                },
+            ],
+            suggested_bump: VersionBump::Minor,
+        }
+    }
+
+    fn press(state: &mut TuiState, code: KeyCode) -> Action {
+        state.handle_key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_tui_adjusts_bump_and_prerelease() {
+        let mut state = TuiState::new(vec![branch(Some("v1.2.0"))]);
+        assert_eq!(state.proposed_tag().as_deref(), Some("v1.3.0"));
+
+        press(&mut state, KeyCode::Char('M'));
+        assert_eq!(state.proposed_tag().as_deref(), Some("v2.0.0"));
+        press(&mut state, KeyCode::Char('p'));
+        assert_eq!(state.proposed_tag().as_deref(), Some("v1.2.1"));
+        press(&mut state, KeyCode::Char('a'));
+        assert_eq!(state.proposed_tag().as_deref(), Some("v1.3.0"));
+
+        press(&mut state, KeyCode::Char('r'));
+        assert_eq!(state.proposed_tag().as_deref(), Some("v1.3.0-alpha.1"));
+        press(&mut state, KeyCode::Char('r'));
+        press(&mut state, KeyCode::Char('r'));
+        assert_eq!(state.proposed_tag().as_deref(), Some("v1.3.0-rc.1"));
+        press(&mut state, KeyCode::Char('r'));
+        assert_eq!(state.prerelease, None);
+
+        // Another round of the current pre-release
+        let mut state = TuiState::new(vec![branch(Some("v1.3.0-beta.1"))]);
+        press(&mut state, KeyCode::Char('r'));
+        press(&mut state, KeyCode::Char('r'));
+        assert_eq!(state.proposed_tag().as_deref(), Some("v1.3.0-beta.2"));
+    }
+
+    #[test]
+    fn test_tui_confirms_plan() {
+        let missing = BranchView {
+            name: "release".to_string(),
+            exists: false,
+            ..branch(None)
+        };
+        let mut state = TuiState::new(vec![branch(None), missing]);
+        assert_eq!(state.proposed_tag().as_deref(), Some("v0.1.0"));
+
+        press(&mut state, KeyCode::Right);
+        assert_eq!(press(&mut state, KeyCode::Enter), Action::None);
+        press(&mut state, KeyCode::Right);
+        assert_eq!(press(&mut state, KeyCode::Enter), Action::Confirm);
+        assert_eq!(
+            state.plan(),
+            Some(ReleasePlan {
+                branch: "main".to_string(),
+                tag: "v0.1.0".to_string(),
+            })
+        );
+        assert_eq!(press(&mut state, KeyCode::Esc), Action::Quit);
+    }
+
+    #[test]
+    fn test_tui_renders_branch() {
+        let state = TuiState::new(vec![branch(Some("v1.2.0"))]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 16)).unwrap();
+        terminal.draw(|frame| render(frame, &state)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("v1.3.0"));
+        assert!(screen.contains("minor  feat: add export"));
+        assert!(screen.contains("-      docs: typo"));
+    }
+}
@@ -0,0 +1,106 @@
+//! Releasing several branches in one run, for `--all-branches` and a
+//! repeated `--branch`.
+//!
+//! The remote is chosen and fetched once for the batch, which brings every
+//! configured branch up to date at the same time, so all branches are
+//! released from the same view of the remote. Each branch then goes through
+//! the rest of the workflow on its own: commit analysis, bump and its own tag
+//! pattern. A failure on one branch is reported and the next one is released
+//! anyway; the outcomes are collected for a summary at the end.
+
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::i18n::{tr, Msg};
+use crate::state::LastAnswers;
+use crate::workflow::{
+    fetch_remote, publish, select_remote, Notifier, Prompter, PublishOutcome, PublishWorkflowArgs,
+    ReportedFailure, Repository,
+};
+
+/// How the release of one branch of a batch ended
+#[derive(Debug)]
+pub struct BranchOutcome {
+    pub branch: String,
+    pub outcome: Result<PublishOutcome>,
+}
+
+impl BranchOutcome {
+    /// Whether the release of the branch failed
+    pub fn is_failure(&self) -> bool {
+        self.outcome.is_err()
+    }
+}
+
+/// Run the publish workflow for each branch in turn, as [`publish`] with
+/// `--branch` set to it
+pub fn publish_branches(
+    repo: &dyn Repository,
+    prompter: &dyn Prompter,
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    args: &PublishWorkflowArgs,
+    branches: &[String],
+) -> Vec<BranchOutcome> {
+    let mut args = args.clone();
+    // Without remotes each branch reports the problem itself
+    if !args.already_fetched && repo.list_remotes().is_ok_and(|remotes| !remotes.is_empty()) {
+        let last_answers = LastAnswers::load(repo.git_dir());
+        if let (Some(branch), Ok(remote)) = (
+            branches.first(),
+            select_remote(repo, prompter, notifier, cfg, &args, &last_answers),
+        ) {
+            let fetched = fetch_remote(repo, prompter, notifier, cfg, &args, &remote, branch);
+            match fetched {
+                Ok(None) => {}
+                Ok(Some(outcome)) => return every_branch(branches, || Ok(outcome.clone())),
+                Err(e) => {
+                    if !e.is::<ReportedFailure>() {
+                        notifier.error(&e.to_string());
+                    }
+                    let failure = e.to_string();
+                    return every_branch(branches, || Err(ReportedFailure(failure.clone()).into()));
+                }
+            }
+            args.remote = Some(remote);
+            args.already_fetched = true;
+        }
+    }
+
+    branches
+        .iter()
+        .enumerate()
+        .map(|(index, branch)| {
+            notifier.status(&tr(
+                Msg::BatchReleasingBranch,
+                &[
+                    ("branch", branch),
+                    ("index", &(index + 1)),
+                    ("total", &branches.len()),
+                ],
+            ));
+            let branch_args = PublishWorkflowArgs {
+                branch: Some(branch.clone()),
+                ..args.clone()
+            };
+            BranchOutcome {
+                branch: branch.clone(),
+                outcome: publish(repo, prompter, notifier, cfg, &branch_args).into_outcome(),
+            }
+        })
+        .collect()
+}
+
+/// The same outcome for each branch, when the batch stopped before releasing any
+fn every_branch(
+    branches: &[String],
+    outcome: impl Fn() -> Result<PublishOutcome>,
+) -> Vec<BranchOutcome> {
+    branches
+        .iter()
+        .map(|branch| BranchOutcome {
+            branch: branch.clone(),
+            outcome: outcome(),
+        })
+        .collect()
+}
@@ -0,0 +1,44 @@
+//! Cooperative cancellation of a running publish.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Shared flag for stopping [`publish_with_cancellation`](super::publish_with_cancellation)
+/// from another thread
+///
+/// The workflow checks it between steps, so a step that is already running
+/// (a fetch, a hook, a prompt) finishes first.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// A token that is not cancelled yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask the workflow to stop at the next step
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether [`CancellationToken::cancel`] was called on this token or a clone
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_is_shared_between_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+
+        clone.cancel();
+        assert!(token.is_cancelled());
+    }
+}
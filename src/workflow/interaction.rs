@@ -0,0 +1,260 @@
+//! How the publish workflow talks to the user.
+//!
+//! [`Prompter`] asks questions and [`Notifier`] reports progress. The terminal
+//! implementations forward to the [`ui`](crate::ui) module; a GUI or a bot can
+//! provide its own.
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::boundary::BoundaryWarning;
+use crate::config::VersionSource;
+use crate::domain::commit::NonConformingCommit;
+use crate::domain::{Version, VersionBump};
+use crate::hooks::HookRun;
+use crate::ui;
+use crate::workflow::WorkflowResult;
+
+/// Questions asked during the workflow
+///
+/// Not consulted with `force` or `dry_run`, except [`Prompter::confirm_push_tag`]
+/// which is skipped only with `force`.
+pub trait Prompter {
+    /// Pick the branch to tag from the configured ones
+    fn select_branch(&self, branches: &[String]) -> Result<String>;
+
+    /// Pick the remote to fetch from and push to
+    fn select_remote(&self, remotes: &[String]) -> Result<String>;
+
+    /// Yes/no question, defaulting to no
+    fn confirm(&self, prompt: &str) -> Result<bool>;
+
+    /// Pick the version to continue from when the manifest and latest tag disagree
+    fn select_version_source(
+        &self,
+        tag: (&str, &Version),
+        manifest: (&str, &Version),
+        default: VersionSource,
+    ) -> Result<VersionSource>;
+
+    /// Pick one of the suggested tags, or enter a custom one; `recent_tags`
+    /// are the latest existing tags of the branch pattern, lowest version
+    /// first, as context for custom entry
+    fn select_tag_from_candidates(
+        &self,
+        recommended_tag: &str,
+        candidate_tags: &[String],
+        recent_tags: &[String],
+    ) -> Result<String>;
+
+    /// Accept the recommended tag or edit it, with `recent_tags` as in
+    /// [`Prompter::select_tag_from_candidates`]
+    fn select_or_customize_tag(
+        &self,
+        recommended_tag: &str,
+        pattern: &str,
+        recent_tags: &[String],
+    ) -> Result<String>;
+
+    /// Acknowledge a major bump by typing the new major version
+    fn confirm_major_version(&self, major: u32) -> Result<bool>;
+
+    /// Validate the tag against the pattern and confirm its creation
+    fn confirm_tag_use(&self, tag: &str, pattern: &str) -> Result<bool>;
+
+    /// Confirm pushing the created tag; `default` is the answer for just
+    /// pressing Enter, the previous decision in this repository
+    fn confirm_push_tag(&self, tag: &str, remote: &str, default: bool) -> Result<bool>;
+}
+
+/// What a run decided to release, reported once the tag is settled
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseDecision {
+    pub branch: String,
+    pub remote: String,
+    pub previous_tag: Option<String>,
+    /// The new version, without the tag's prefix
+    pub version: String,
+    pub bump: VersionBump,
+    /// Commits since the previous tag
+    pub commit_count: usize,
+    /// Of those, the ones taken into account for the bump (see
+    /// `max_commits_analyzed`)
+    pub commits_analyzed: usize,
+    pub tag: String,
+    /// Every tag created for the release, `tag` first (see `--also-tag`)
+    pub tags: Vec<String>,
+}
+
+/// Progress reported during the workflow
+pub trait Notifier {
+    /// A step is starting
+    fn status(&self, message: &str);
+
+    /// A step succeeded
+    fn success(&self, message: &str);
+
+    /// Something needs attention but the workflow goes on
+    fn warning(&self, message: &str);
+
+    /// The workflow cannot go on
+    fn error(&self, message: &str);
+
+    /// Plain informational line
+    fn message(&self, message: &str);
+
+    /// The workflow finished with this result
+    fn finished(&self, message: &str);
+
+    /// A boundary condition, with its remediation hint
+    fn boundary_warning(&self, warning: &BoundaryWarning);
+
+    /// The commits that will be released
+    fn commit_analysis(&self, commit_messages: &[String], branch: &str);
+
+    /// Commits that don't follow the configured conventions
+    fn non_conforming_commits(&self, commits: &[NonConformingCommit]);
+
+    /// The tag that would follow the latest one
+    fn proposed_tag(&self, old_tag: Option<&str>, new_tag: &str);
+
+    /// Subjects of the commits causing a major bump
+    fn breaking_changes(&self, subjects: &[String]);
+
+    /// Hooks a dry run would execute and the environment they'd get
+    fn hook_dry_run(&self, hooks: &[(&str, Vec<String>)], env: &[(String, String)]);
+
+    /// Outcome of every hook script that ran
+    fn hook_summary(&self, runs: &[HookRun]);
+
+    /// Command for pushing the tag by hand
+    fn manual_push_instruction(&self, command: &str);
+
+    /// The release the run goes on to create (or would, in a dry run)
+    fn release_decision(&self, decision: &ReleaseDecision);
+
+    /// Everything the run did, once it is over
+    fn workflow_result(&self, _: &WorkflowResult) {}
+}
+
+/// Interactive prompts on the terminal
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TerminalPrompter;
+
+impl Prompter for TerminalPrompter {
+    fn select_branch(&self, branches: &[String]) -> Result<String> {
+        ui::select_branch(branches)
+    }
+
+    fn select_remote(&self, remotes: &[String]) -> Result<String> {
+        ui::select_remote(remotes)
+    }
+
+    fn confirm(&self, prompt: &str) -> Result<bool> {
+        ui::confirm_action(prompt)
+    }
+
+    fn select_version_source(
+        &self,
+        tag: (&str, &Version),
+        manifest: (&str, &Version),
+        default: VersionSource,
+    ) -> Result<VersionSource> {
+        ui::select_version_source(tag, manifest, default)
+    }
+
+    fn select_tag_from_candidates(
+        &self,
+        recommended_tag: &str,
+        candidate_tags: &[String],
+        recent_tags: &[String],
+    ) -> Result<String> {
+        ui::select_tag_from_candidates(recommended_tag, candidate_tags, recent_tags)
+    }
+
+    fn select_or_customize_tag(
+        &self,
+        recommended_tag: &str,
+        pattern: &str,
+        recent_tags: &[String],
+    ) -> Result<String> {
+        ui::select_or_customize_tag(recommended_tag, pattern, recent_tags)
+    }
+
+    fn confirm_major_version(&self, major: u32) -> Result<bool> {
+        ui::confirm_major_version(major)
+    }
+
+    fn confirm_tag_use(&self, tag: &str, pattern: &str) -> Result<bool> {
+        ui::confirm_tag_use(tag, pattern)
+    }
+
+    fn confirm_push_tag(&self, tag: &str, remote: &str, default: bool) -> Result<bool> {
+        ui::confirm_push_tag(tag, remote, default)
+    }
+}
+
+/// Colored progress output on the terminal
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TerminalNotifier;
+
+impl Notifier for TerminalNotifier {
+    fn status(&self, message: &str) {
+        ui::display_status(message);
+    }
+
+    fn success(&self, message: &str) {
+        ui::display_success(message);
+    }
+
+    fn warning(&self, message: &str) {
+        ui::display_warning(message);
+    }
+
+    fn error(&self, message: &str) {
+        ui::display_error(message);
+    }
+
+    fn message(&self, message: &str) {
+        println!("{}", message);
+    }
+
+    fn finished(&self, message: &str) {
+        println!("\n\x1b[32m✓\x1b[0m {}\n", message);
+    }
+
+    fn boundary_warning(&self, warning: &BoundaryWarning) {
+        ui::display_boundary_warning(warning);
+    }
+
+    fn commit_analysis(&self, commit_messages: &[String], branch: &str) {
+        ui::display_commit_analysis(commit_messages, branch);
+    }
+
+    fn non_conforming_commits(&self, commits: &[NonConformingCommit]) {
+        ui::display_non_conforming_commits(commits);
+    }
+
+    fn proposed_tag(&self, old_tag: Option<&str>, new_tag: &str) {
+        ui::display_proposed_tag(old_tag, new_tag);
+    }
+
+    fn breaking_changes(&self, subjects: &[String]) {
+        ui::display_breaking_changes(subjects);
+    }
+
+    fn hook_dry_run(&self, hooks: &[(&str, Vec<String>)], env: &[(String, String)]) {
+        ui::display_hook_dry_run(hooks, env);
+    }
+
+    fn hook_summary(&self, runs: &[HookRun]) {
+        ui::display_hook_summary(runs);
+    }
+
+    fn manual_push_instruction(&self, command: &str) {
+        ui::display_manual_push_instruction(command);
+    }
+
+    // Already shown step by step
+    fn release_decision(&self, _: &ReleaseDecision) {}
+}
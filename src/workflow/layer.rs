@@ -0,0 +1,110 @@
+//! Tags layered over an existing release tag, for `git-publish annotate`.
+//!
+//! A second annotated tag pointing at the release's tag object adds metadata
+//! to a release, such as the approval of another team (`v1.2.3-signed`),
+//! without moving or rewriting the original tag. Git keeps the chain: the new
+//! tag peels to the release tag, which peels to the released commit.
+
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::git_ops::{TagAnnotation, TagTarget};
+use crate::i18n::{text, tr, Msg};
+use crate::workflow::{fail, Notifier, Repository};
+
+/// What to layer over which tag
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LayerTagArgs {
+    /// Existing tag the new one points at
+    pub target_tag: String,
+    /// Name of the new tag
+    pub tag: String,
+    /// Message of the new tag; a default naming the target without one
+    pub message: Option<String>,
+    /// Sign the new tag (as well as when `[tags] signed` is set)
+    pub signed: bool,
+    /// Move the new tag if it already exists
+    pub replace: bool,
+    /// Push the new tag after creating it
+    pub push: bool,
+    /// Remote to push to; the first configured remote (`origin` first) without one
+    pub remote: Option<String>,
+}
+
+/// Create the annotated tag `args.tag` pointing at `args.target_tag`, and
+/// push it when asked
+pub fn layer_tag(
+    repo: &dyn Repository,
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    args: &LayerTagArgs,
+) -> Result<()> {
+    if !repo.tag_exists(&args.target_tag) {
+        return Err(fail(
+            notifier,
+            &tr(Msg::LayerTargetNotFound, &[("tag", &args.target_tag)]),
+        ));
+    }
+    if repo.tag_exists(&args.tag) && !args.replace {
+        return Err(fail(
+            notifier,
+            &tr(Msg::LayerTagExists, &[("tag", &args.tag)]),
+        ));
+    }
+    match repo.tag_target(&args.target_tag)? {
+        TagTarget::Tag => {}
+        // Without a tag object there is nothing to layer over
+        TagTarget::Commit | TagTarget::Other => notifier.warning(&tr(
+            Msg::LayerTargetLightweight,
+            &[("tag", &args.target_tag)],
+        )),
+    }
+
+    let annotation = TagAnnotation {
+        message: args.message.clone().unwrap_or_else(|| {
+            tr(
+                Msg::LayerTagDefaultMessage,
+                &[("tag", &args.tag), ("target", &args.target_tag)],
+            )
+        }),
+        signed: args.signed || cfg.tags.signed,
+    };
+    if let Err(e) = repo.write_tag_on_tag(&args.tag, &args.target_tag, args.replace, &annotation) {
+        return Err(fail(
+            notifier,
+            &tr(Msg::LayerTagFailed, &[("tag", &args.tag), ("error", &e)]),
+        ));
+    }
+    notifier.success(&tr(
+        Msg::LayerTagCreated,
+        &[("tag", &args.tag), ("target", &args.target_tag)],
+    ));
+
+    if !args.push {
+        return Ok(());
+    }
+    let remote = match &args.remote {
+        Some(remote) => remote.clone(),
+        None => match repo.list_remotes()?.into_iter().next() {
+            Some(remote) => remote,
+            None => return Err(fail(notifier, text(Msg::NoRemotesConfigured))),
+        },
+    };
+    notifier.status(&tr(
+        Msg::PushingTag,
+        &[("tag", &args.tag), ("remote", &remote)],
+    ));
+    if let Err(e) = repo.push_tags_with_options(
+        &[&args.tag],
+        &remote,
+        cfg.remote_overrides.get(&remote),
+        args.replace,
+    ) {
+        return Err(fail(
+            notifier,
+            &tr(Msg::PushTagFailed, &[("tag", &args.tag), ("error", &e)]),
+        ));
+    }
+    notifier.success(&tr(Msg::PushedTag, &[("tag", &args.tag)]));
+    Ok(())
+}
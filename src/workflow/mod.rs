@@ -0,0 +1,4160 @@
+//! The publish workflow: from picking a branch to pushing the new tag.
+//!
+//! [`publish`] runs the whole flow against injected components, so the CLI is
+//! just one front-end: a bot or a GUI can supply its own [`Repository`],
+//! [`Prompter`] and [`Notifier`].
+
+pub mod batch;
+pub mod cancellation;
+pub mod interaction;
+pub mod layer;
+pub mod repository;
+pub mod result;
+pub mod rollback;
+pub mod step_summary;
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::boundary::BoundaryWarning;
+use crate::changelog::ChangelogFile;
+use crate::config::{
+    CherryPicks, Config, DowngradePolicy, ExistingTagPolicy, HooksConfig, VersionSource,
+    WarningPolicy,
+};
+use crate::domain::channel::ChannelAnalysis;
+use crate::domain::{
+    BumpOverride, Changelog, ParsedCommit, PreReleaseType, ReleaseNote, TagMetadata, TagPattern,
+    Version, VersionBump,
+};
+use crate::error::{GitPublishError, RemoteFailureKind};
+use crate::git_ops::{web_url, TagAnnotation};
+use crate::hooks::{masked_env, resolve_scripts, HookContext, HookExecutor, HookRun, HookType};
+use crate::i18n::{text, tr, Msg};
+use crate::interrupt;
+use crate::manifest::{Manifest, ManifestUpdate};
+use crate::outbox::Outbox;
+use crate::state::{self, LastAnswers};
+use crate::timings::{self, Phase};
+
+pub use batch::{publish_branches, BranchOutcome};
+pub use cancellation::CancellationToken;
+pub use interaction::{Notifier, Prompter, ReleaseDecision, TerminalNotifier, TerminalPrompter};
+pub use layer::{layer_tag, LayerTagArgs};
+pub use repository::Repository;
+use result::Recording;
+pub use result::{WorkflowResult, WorkflowWarning};
+pub use rollback::{rollback, RollbackArgs, RollbackScope};
+pub use step_summary::{StepSummary, SummaryStatus};
+
+/// Options of a single publish run, usually from the command line
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublishWorkflowArgs {
+    /// Branch to tag; asked for when not set
+    pub branch: Option<String>,
+    /// Remote to fetch from and push to; asked for when not set
+    pub remote: Option<String>,
+    /// Skip confirmation prompts
+    pub force: bool,
+    /// Show what would happen without changing anything
+    pub dry_run: bool,
+    /// Allow a major bump without typing the new major version
+    pub accept_breaking: bool,
+    /// Other configured branches whose tag pattern also gets the new version,
+    /// on the same commit: `gray` adds `g1.2.0` next to `v1.2.0`
+    pub also_tag: Vec<String>,
+    /// Only analyze the commits unique to the branch: those since its merge
+    /// base with this other branch (and since the latest tag)
+    pub merge_base: Option<String>,
+    /// Take this tag as the latest one instead of looking it up on the
+    /// branch: the commits since it are analyzed and its version is bumped
+    pub from_tag: Option<String>,
+    /// Analyze the commits after this commit (`a1b2c3d`, `HEAD~5`...) or
+    /// date (`YYYY-MM-DD`, UTC) instead of those since the latest tag, even
+    /// when that reaches past the tag; the version is still bumped from the
+    /// latest tag (or `from_tag`)
+    pub since: Option<String>,
+    /// Whether to add the release notes to the changelog file; `None`
+    /// follows `[changelog] file`
+    pub changelog: Option<bool>,
+    /// Bump to apply whatever the commits call for; they are still analyzed
+    /// and shown
+    pub bump: Option<BumpOverride>,
+    /// Publish a pre-release with this identifier (`v1.3.0-beta.1`), or the
+    /// next iteration when the latest tag already is one; needs
+    /// `[prerelease] enabled`
+    pub prerelease: Option<PreReleaseType>,
+    /// Release the latest pre-release as its stable version
+    /// (`v1.3.0-beta.2` → `v1.3.0`)
+    pub promote: bool,
+    /// Move existing tags when `on_existing_tag` is `replace` without asking;
+    /// otherwise they are only moved after a confirmation, which `force`
+    /// can't give
+    pub replace_tags: bool,
+    /// Stop once the tags are created, without pushing or asking to
+    pub tag_only: bool,
+    /// Only push this existing local tag: no analysis and no tag creation
+    pub push_only: Option<String>,
+    /// Markdown file to append a release summary to, like `GITHUB_STEP_SUMMARY`
+    pub step_summary: Option<PathBuf>,
+    /// Release this `[packages]` entry: the branch is tagged with the
+    /// package's pattern instead of its own
+    ///
+    /// Only the commits changing the package's files should count, so the
+    /// repository is expected to be scoped to them, see
+    /// [`GitRepo::set_path_scope`](crate::git_ops::GitRepo::set_path_scope).
+    pub package: Option<String>,
+    /// The remote was already fetched earlier in the same run, bringing every
+    /// configured branch up to date, so it isn't fetched again; set by
+    /// [`publish_branches`] for the branches of a batch
+    pub already_fetched: bool,
+}
+
+/// How a publish run ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublishOutcome {
+    /// The user declined to go on
+    Cancelled,
+    /// The tag already existed and `on_existing_tag` is `skip`
+    Skipped { tag: String },
+    /// Dry run: nothing was changed
+    DryRun { tag: String },
+    /// The tag was created and pushed
+    Published { tag: String },
+    /// The tag was created and its push queued until the remote is reachable
+    Queued { tag: String },
+    /// The tag was created but not pushed
+    CreatedLocally { tag: String },
+    /// Stopped through the [`CancellationToken`]; `removed_tag` is the tag
+    /// created by this run and removed again, if any
+    Aborted { removed_tag: Option<String> },
+}
+
+impl PublishOutcome {
+    /// The tag the run settled on, if it got that far
+    pub fn tag(&self) -> Option<&str> {
+        match self {
+            PublishOutcome::Skipped { tag }
+            | PublishOutcome::DryRun { tag }
+            | PublishOutcome::Published { tag }
+            | PublishOutcome::Queued { tag }
+            | PublishOutcome::CreatedLocally { tag } => Some(tag),
+            PublishOutcome::Cancelled | PublishOutcome::Aborted { .. } => None,
+        }
+    }
+
+    /// `published`, `queued`, `created_locally`, `dry_run`, `skipped`,
+    /// `cancelled` or `aborted`
+    pub fn status(&self) -> &'static str {
+        match self {
+            PublishOutcome::Published { .. } => "published",
+            PublishOutcome::Queued { .. } => "queued",
+            PublishOutcome::CreatedLocally { .. } => "created_locally",
+            PublishOutcome::DryRun { .. } => "dry_run",
+            PublishOutcome::Skipped { .. } => "skipped",
+            PublishOutcome::Cancelled => "cancelled",
+            PublishOutcome::Aborted { .. } => "aborted",
+        }
+    }
+}
+
+/// A failure that was already reported through the [`Notifier`]
+///
+/// Front-ends can check for it (`err.is::<ReportedFailure>()`) to avoid
+/// showing the same error twice.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{0}")]
+pub struct ReportedFailure(pub String);
+
+/// Report a failure and turn it into an error
+fn fail(notifier: &dyn Notifier, message: &str) -> anyhow::Error {
+    notifier.error(message);
+    ReportedFailure(message.to_string()).into()
+}
+
+/// Run the publish workflow: select the branch and remote, fetch, analyze the
+/// commits since the latest tag, then create and push the new tag.
+///
+/// Questions go through `prompter` (unless `force` or `dry_run` is set) and
+/// progress through `notifier`, which renders the [`WorkflowResult`] at the end.
+/// Failures are reported through `notifier` too.
+///
+/// # Returns
+/// What the run did: how it ended, including cancellation by the user or a
+/// failure, the release decision, the hooks that ran and the warnings
+pub fn publish(
+    repo: &dyn Repository,
+    prompter: &dyn Prompter,
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    args: &PublishWorkflowArgs,
+) -> WorkflowResult {
+    publish_with_cancellation(
+        repo,
+        prompter,
+        notifier,
+        cfg,
+        args,
+        &CancellationToken::new(),
+    )
+}
+
+/// Run the publish workflow like [`publish`], stopping between steps once
+/// `token` is cancelled
+///
+/// A tag created by the run but not pushed yet is removed again, unless
+/// `keep_tag_on_interrupt` is set; the run then ends with
+/// [`PublishOutcome::Aborted`].
+pub fn publish_with_cancellation(
+    repo: &dyn Repository,
+    prompter: &dyn Prompter,
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    args: &PublishWorkflowArgs,
+    token: &CancellationToken,
+) -> WorkflowResult {
+    let started = Instant::now();
+    let recording = Recording::new(notifier);
+    let outcome = run_publish(repo, prompter, &recording, cfg, args, token);
+    if let Err(e) = &outcome {
+        // Reported failures were shown as they happened
+        if !e.is::<ReportedFailure>() {
+            recording.error(&format!("{:#}", e));
+        }
+    }
+    let result = recording.into_result(&outcome, started.elapsed());
+    notifier.workflow_result(&result);
+    result
+}
+
+/// Take the value a step goes on with, or end the run with the outcome it
+/// stopped at
+macro_rules! proceed {
+    ($step:expr) => {
+        match $step? {
+            ControlFlow::Continue(value) => value,
+            ControlFlow::Break(outcome) => return Ok(outcome),
+        }
+    };
+}
+
+/// What a step of the workflow returns: the value to go on with, or the
+/// outcome the run stops at (e.g. the user declined)
+type Step<T> = Result<ControlFlow<PublishOutcome, T>>;
+
+/// Components and options of a publish run, shared by its steps
+struct Run<'a> {
+    repo: &'a dyn Repository,
+    prompter: &'a dyn Prompter,
+    notifier: &'a dyn Notifier,
+    cfg: &'a Config,
+    args: &'a PublishWorkflowArgs,
+    token: &'a CancellationToken,
+}
+
+impl Run<'_> {
+    /// Report `warning`, see [`report_warning`]
+    fn warn(&self, warning: &BoundaryWarning) -> Result<bool> {
+        report_warning(self.notifier, self.cfg, self.args, warning)
+    }
+
+    /// Stop if the run was cancelled, see [`checkpoint`]
+    fn checkpoint(&self, created_tags: &[String]) -> Option<PublishOutcome> {
+        checkpoint(self.token, self.repo, self.notifier, self.cfg, created_tags)
+    }
+}
+
+/// The branch to release and the remote it goes to
+struct Target {
+    branch: String,
+    remote: String,
+    /// The remote couldn't be reached, so pushes are queued
+    offline: bool,
+}
+
+/// The commits since the latest tag and the bump they call for
+struct Analysis {
+    latest_tag: Option<String>,
+    /// Release metadata from the latest tag's message
+    previous_metadata: Option<TagMetadata>,
+    /// Messages of the analyzed commits, oldest first
+    commit_messages: Vec<String>,
+    /// Every commit since the latest tag, also those past `max_commits_analyzed`
+    commit_count: usize,
+    bump: VersionBump,
+    /// Only commits of channel scopes: a pre-release of that channel
+    channel: Option<PreReleaseType>,
+}
+
+/// The tag chosen for the release, before checking for existing tags
+struct ProposedTag {
+    pattern: TagPattern,
+    tag: String,
+    /// Version of `tag` as read through `pattern`
+    version: Option<Version>,
+    /// The project's manifest and the version it records, if any
+    manifest: Option<(Manifest, Version)>,
+}
+
+/// The tags the release creates
+struct ReleaseTags {
+    /// The branch's own tag
+    tag: String,
+    version: String,
+    /// `tag` followed by the tags of the `--also-tag` branches
+    tags: Vec<String>,
+    /// The tags exist already and are moved
+    replace_existing: bool,
+}
+
+/// Files the release commit changes
+struct ReleaseFiles<'a> {
+    /// Manifest to write the new version to
+    manifest: Option<(Manifest, Version)>,
+    /// Changelog file to add the release notes to, and its configured name
+    changelog: Option<(ChangelogFile, &'a str)>,
+}
+
+impl ReleaseFiles<'_> {
+    fn is_empty(&self) -> bool {
+        self.manifest.is_none() && self.changelog.is_none()
+    }
+}
+
+/// The release's hooks and the scripts they ran so far
+struct ReleaseHooks {
+    config: HooksConfig,
+    context: HookContext,
+    runs: Vec<HookRun>,
+}
+
+impl ReleaseHooks {
+    /// Run the scripts of `hook_type`, see [`run_hook`]
+    fn run(&mut self, run: &Run, hook_type: HookType) -> Result<()> {
+        run_hook(
+            run.notifier,
+            &self.config,
+            run.repo.workdir(),
+            hook_type,
+            &self.context,
+            run.cfg.behavior.hook_echo,
+            &mut self.runs,
+        )
+    }
+}
+
+fn run_publish(
+    repo: &dyn Repository,
+    prompter: &dyn Prompter,
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    args: &PublishWorkflowArgs,
+    token: &CancellationToken,
+) -> Result<PublishOutcome> {
+    // Offered as defaults: the previous interactive release's answers
+    let last_answers = LastAnswers::load(repo.git_dir());
+    if let Some(tag) = &args.push_only {
+        return push_existing_tag(repo, prompter, notifier, cfg, args, &last_answers, tag);
+    }
+    if args.prerelease.is_some() && !cfg.prerelease.enabled {
+        return Err(fail(notifier, text(Msg::PreReleaseDisabled)));
+    }
+    let branch_to_tag = select_branch(repo, prompter, notifier, cfg, args, &last_answers)?;
+
+    // A package is released from the branch under its own tag pattern
+    let package_cfg;
+    let cfg = match &args.package {
+        Some(package) => {
+            let Some(package_config) = cfg.packages.get(package) else {
+                return Err(fail(
+                    notifier,
+                    &tr(Msg::PackageNotConfigured, &[("package", package)]),
+                ));
+            };
+            let mut with_package = cfg.clone();
+            with_package.branches.insert(
+                branch_to_tag.clone(),
+                TagPattern::new(package_config.tag.as_str()),
+            );
+            package_cfg = with_package;
+            &package_cfg
+        }
+        None => cfg,
+    };
+
+    // Compound release: the extra tags only borrow the branches' patterns
+    if let Some(branch) = args
+        .also_tag
+        .iter()
+        .find(|branch| !cfg.branches.contains_key(*branch))
+    {
+        return Err(fail(
+            notifier,
+            &tr(Msg::BranchNotConfigured, &[("branch", branch)]),
+        ));
+    }
+
+    let run = Run {
+        repo,
+        prompter,
+        notifier,
+        cfg,
+        args,
+        token,
+    };
+    if let Some(outcome) = run.checkpoint(&[]) {
+        return Ok(outcome);
+    }
+    let target = proceed!(connect_remote(&run, &last_answers, branch_to_tag));
+    if let Some(outcome) = run.checkpoint(&[]) {
+        return Ok(outcome);
+    }
+
+    let analysis = proceed!(analyze_commits(&run, &target));
+    let proposed = proceed!(compute_tag(&run, &target, &analysis));
+    let release = proceed!(resolve_collisions(&run, &target, &proposed));
+    if let Some(outcome) = run.checkpoint(&[]) {
+        return Ok(outcome);
+    }
+
+    let hook_config = cfg.hooks_for(&target.branch);
+    let hook_env = hook_config.hook_env();
+    let mut hooks = ReleaseHooks {
+        config: hook_config,
+        context: HookContext {
+            hook_type: HookType::PreTagCreate,
+            branch: target.branch.clone(),
+            tag: release.tag.clone(),
+            version: release.version.clone(),
+            remote: target.remote.clone(),
+            previous_tag: analysis.latest_tag.clone(),
+            version_bump: Some(format!("{:?}", analysis.bump)),
+            commit_count: Some(analysis.commit_count),
+            commit_types: Some(crate::domain::commit::count_commit_types(
+                &analysis.commit_messages,
+                &cfg.conventional_commits.breaking_change_indicators,
+            )),
+            env: hook_env,
+            repo_root: repo.workdir().map(|root| root.components().collect()),
+        },
+        runs: Vec::new(),
+    };
+    let step_summary = release_summary(&run, &target, &analysis, &release);
+    let files = release_files(&run, proposed.manifest, &release)?;
+    let release_commit = !files.is_empty();
+
+    // Annotated tags need a tagger; without a git identity only lightweight
+    // tags can be created
+    let mut annotate = cfg.tags.annotated || cfg.tags.signed;
+    if annotate && !repo.has_identity() {
+        if run.warn(&BoundaryWarning::MissingGitIdentity)?
+            && !prompter.confirm(text(Msg::ContinueWithLightweightTags))?
+        {
+            notifier.message(text(Msg::OperationCancelled));
+            return Ok(PublishOutcome::Cancelled);
+        }
+        annotate = false;
+    }
+
+    notifier.release_decision(&ReleaseDecision {
+        branch: target.branch.clone(),
+        remote: target.remote.clone(),
+        previous_tag: analysis.latest_tag.clone(),
+        version: release.version.clone(),
+        bump: analysis.bump,
+        commit_count: analysis.commit_count,
+        commits_analyzed: analysis.commit_messages.len(),
+        tag: release.tag.clone(),
+        tags: release.tags.clone(),
+    });
+
+    if args.dry_run {
+        return Ok(report_dry_run(&run, &target, &release, &files, &hooks));
+    }
+
+    hooks.run(&run, HookType::PreTagCreate)?;
+    commit_release(&run, &target.branch, &release, &files, &step_summary)?;
+    if let Some(outcome) = run.checkpoint(&[]) {
+        return Ok(outcome);
+    }
+
+    let annotation = annotate.then_some((&analysis, proposed.version.as_ref()));
+    create_tags(&run, &target.branch, &release, annotation)?;
+    let created_tags: &[String] = if release.replace_existing {
+        &[]
+    } else {
+        &release.tags
+    };
+    hooks.run(&run, HookType::PostTagCreate)?;
+    if let Some(outcome) = run.checkpoint(created_tags) {
+        return Ok(outcome);
+    }
+
+    push_release(
+        &run,
+        &last_answers,
+        &target,
+        &release,
+        release_commit,
+        &mut hooks,
+        &step_summary,
+    )
+}
+
+/// The branch to release: `--branch`, the only configured branch present in
+/// the repository, or the one the user picks
+fn select_branch(
+    repo: &dyn Repository,
+    prompter: &dyn Prompter,
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    args: &PublishWorkflowArgs,
+    last_answers: &LastAnswers,
+) -> Result<String> {
+    let existing_branches = repo.list_branches()?;
+    // Right after `git init` there is nothing to tag yet
+    if existing_branches.is_empty() {
+        if let Some(branch) = repo.unborn_branch() {
+            return Err(fail(
+                notifier,
+                &tr(Msg::NoCommitsYet, &[("branch", &branch)]),
+            ));
+        }
+    }
+
+    let branch_to_tag = if let Some(branch) = &args.branch {
+        branch.clone()
+    } else {
+        // Get configured branches as a sorted vector
+        let mut configured_branches: Vec<String> = cfg.branches.keys().cloned().collect();
+        configured_branches.sort();
+        state::put_first(&mut configured_branches, last_answers.branch.as_deref());
+        if configured_branches.is_empty() {
+            return Err(fail(notifier, text(Msg::NoBranchesConfigured)));
+        }
+
+        // Skip the prompt when only one configured branch exists in this repository
+        let present: Vec<&String> = configured_branches
+            .iter()
+            .filter(|branch| existing_branches.contains(branch))
+            .collect();
+        if let [branch] = present.as_slice() {
+            notifier.status(&tr(Msg::AutoSelectedBranch, &[("branch", branch)]));
+            branch.to_string()
+        } else {
+            prompter.select_branch(&configured_branches)?
+        }
+    };
+
+    // Verify the selected branch exists in config
+    if !cfg.branches.contains_key(&branch_to_tag) {
+        return Err(fail(
+            notifier,
+            &tr(Msg::BranchNotConfigured, &[("branch", &branch_to_tag)]),
+        ));
+    }
+
+    // Fail early on a branch that doesn't exist, rather than deep inside the analysis
+    if !existing_branches.contains(&branch_to_tag) {
+        return Err(fail(
+            notifier,
+            &tr(
+                Msg::BranchNotFound,
+                &[
+                    ("branch", &branch_to_tag),
+                    ("branches", &existing_branches.join(", ")),
+                ],
+            ),
+        ));
+    }
+    Ok(branch_to_tag)
+}
+
+/// Select the remote, check that it can be reached and fetch from it
+fn connect_remote(run: &Run, last_answers: &LastAnswers, branch: String) -> Step<Target> {
+    let Run {
+        repo,
+        prompter,
+        notifier,
+        cfg,
+        args,
+        ..
+    } = *run;
+    let remote = select_remote(repo, prompter, notifier, cfg, args, last_answers)?;
+
+    // Probe the remote before doing any work, so connectivity and credential
+    // problems show up before walking through the whole flow
+    notifier.status(&tr(Msg::CheckingRemote, &[("remote", &remote)]));
+    let mut offline = false;
+    if let Err(e) = repo.check_remote_connection(&remote, cfg.remote_overrides.get(&remote)) {
+        let kind = e
+            .downcast_ref::<GitPublishError>()
+            .and_then(|err| err.remote_failure_kind())
+            .unwrap_or(RemoteFailureKind::Other);
+        let warning = BoundaryWarning::RemoteConnectionFailed {
+            remote: remote.clone(),
+            kind,
+            detail: e.to_string(),
+        };
+        if run.warn(&warning)? && !prompter.confirm(text(Msg::ContinueOffline))? {
+            notifier.message(text(Msg::OperationCancelled));
+            return Ok(ControlFlow::Break(PublishOutcome::Cancelled));
+        }
+        offline = true;
+    }
+
+    // Fetch latest from remote to ensure we have the latest tags and commits
+    if offline {
+        notifier.status(&tr(Msg::WorkingOffline, &[("remote", &remote)]));
+    } else if args.already_fetched {
+        notifier.status(&tr(Msg::AlreadyFetched, &[("remote", &remote)]));
+    } else if let Some(outcome) =
+        fetch_remote(repo, prompter, notifier, cfg, args, &remote, &branch)?
+    {
+        return Ok(ControlFlow::Break(outcome));
+    }
+    Ok(ControlFlow::Continue(Target {
+        branch,
+        remote,
+        offline,
+    }))
+}
+
+/// Find the latest tag and analyze the commits since it
+fn analyze_commits(run: &Run, target: &Target) -> Step<Analysis> {
+    let Run {
+        repo,
+        prompter,
+        notifier,
+        cfg,
+        args,
+        ..
+    } = *run;
+    let branch_to_tag = &target.branch;
+
+    // Get the tag pattern for this branch from config
+    let tag_pattern = cfg.branches.get(branch_to_tag).map(|s| s.as_str());
+
+    // Get the latest tag on the selected branch, checking both local and remote-tracking branches
+    let latest_tag = match &args.from_tag {
+        // --from-tag replaces the lookup, e.g. when a hotfix tag would be found
+        Some(tag) if repo.tag_exists(tag) => {
+            notifier.status(&tr(Msg::AnalyzingSinceTag, &[("tag", tag)]));
+            Some(tag.clone())
+        }
+        Some(tag) => {
+            return Err(fail(notifier, &tr(Msg::FromTagNotFound, &[("tag", tag)])));
+        }
+        None => match timings::measure(Phase::TagScan, || {
+            repo.get_latest_tag_on_branch_with_remote(
+                branch_to_tag,
+                Some(&target.remote),
+                tag_pattern,
+            )
+        }) {
+            Ok(tag) => tag,
+            Err(e) => {
+                return Err(fail(
+                    notifier,
+                    &tr(
+                        Msg::LatestTagFailed,
+                        &[("branch", branch_to_tag), ("error", &e)],
+                    ),
+                ));
+            }
+        },
+    };
+
+    // Metadata recorded in the previous release's annotated tag
+    let previous_metadata = latest_tag
+        .as_deref()
+        .filter(|_| cfg.behavior.read_tag_metadata)
+        .and_then(|tag| repo.tag_message(tag).ok().flatten())
+        .map(|message| {
+            TagMetadata::parse(
+                &message,
+                &cfg.conventional_commits.breaking_change_indicators,
+            )
+        });
+    if let (Some(tag), Some(metadata)) = (&latest_tag, &previous_metadata) {
+        if let (Ok(tag_version), Some(recorded)) = (Version::parse(tag), &metadata.version) {
+            if tag_version != *recorded {
+                let warning = BoundaryWarning::TagMetadataMismatch {
+                    tag: tag.clone(),
+                    tag_version: tag_version.to_string(),
+                    recorded_version: recorded.to_string(),
+                };
+                run.warn(&warning)?;
+            }
+        }
+        if !metadata.breaking_changes.is_empty() {
+            notifier.status(&tr(
+                Msg::PreviousBreakingChanges,
+                &[("tag", tag), ("count", &metadata.breaking_changes.len())],
+            ));
+            for change in &metadata.breaking_changes {
+                notifier.message(&format!("  - {}", change));
+            }
+        }
+    }
+
+    // A tag outside the branch history would count released commits again
+    if let Some(tag) = &latest_tag {
+        let reachable =
+            timings::measure(Phase::Revwalk, || repo.is_tag_reachable(branch_to_tag, tag));
+        if matches!(reachable, Ok(false)) {
+            let warning = BoundaryWarning::TagUnreachable {
+                tag: tag.clone(),
+                branch: branch_to_tag.clone(),
+            };
+            if run.warn(&warning)? && !prompter.confirm(text(Msg::ContinueWithUnreachableTag))? {
+                notifier.message(text(Msg::OperationCancelled));
+                return Ok(ControlFlow::Break(PublishOutcome::Cancelled));
+            }
+        }
+    }
+
+    // Get the messages of the commits since the latest tag
+    // Analyze the commits as the walk yields them (newest first), keeping
+    // only the messages within max_commits_analyzed: years of untagged history
+    // would otherwise take minutes and a lot of memory
+    let limit = cfg.behavior.max_commits_analyzed;
+    let mut commit_messages: Vec<String> = Vec::new();
+    let mut commit_count = 0;
+    let mut bump_analysis = ChannelAnalysis::default();
+    let base = match &args.merge_base {
+        Some(other) => match repo.merge_base(branch_to_tag, other) {
+            Ok(base) => {
+                let short_hash = &base[..base.len().min(7)];
+                notifier.status(&tr(
+                    Msg::AnalyzingSinceMergeBase,
+                    &[("branch", other), ("commit", &short_hash)],
+                ));
+                Some(base)
+            }
+            Err(e) => {
+                return Err(fail(
+                    notifier,
+                    &tr(
+                        Msg::MergeBaseNotFound,
+                        &[("branch", branch_to_tag), ("other", other), ("error", &e)],
+                    ),
+                ));
+            }
+        },
+        None => match &args.since {
+            Some(since) => match since_base(repo, branch_to_tag, since) {
+                Ok(base) => {
+                    notifier.status(&tr(Msg::AnalyzingSince, &[("since", since)]));
+                    base
+                }
+                Err(e) => {
+                    return Err(fail(
+                        notifier,
+                        &tr(Msg::SinceNotFound, &[("since", since), ("error", &e)]),
+                    ));
+                }
+            },
+            None => None,
+        },
+    };
+    // --since replaces the latest tag as the start of the range, so it can
+    // also reach past it; the version is still bumped from the latest tag
+    let range_tag = match &args.since {
+        Some(_) => None,
+        None => latest_tag.as_deref(),
+    };
+    let walk = timings::measure(Phase::Revwalk, || {
+        let mut visit = |message: &str| {
+            commit_count += 1;
+            if limit == 0 || commit_messages.len() < limit {
+                // Once settled, further commits can't change the bump
+                if !bump_analysis.is_settled() {
+                    timings::measure(Phase::Analysis, || {
+                        bump_analysis.add(message, &cfg.conventional_commits, &cfg.channels)
+                    });
+                }
+                commit_messages.push(message.to_string());
+            }
+            // Every commit is still counted and listed, for the release
+            // decision, the breaking changes and the non-conforming commits
+            ControlFlow::Continue(())
+        };
+        match &base {
+            Some(base) => {
+                repo.visit_commit_messages_since_base(branch_to_tag, range_tag, base, &mut visit)
+            }
+            None => repo.visit_commit_messages_since_tag(branch_to_tag, range_tag, &mut visit),
+        }
+    });
+    if let Err(e) = walk {
+        return Err(fail(
+            notifier,
+            &tr(
+                Msg::CommitsSinceTagFailed,
+                &[("branch", branch_to_tag), ("error", &e)],
+            ),
+        ));
+    }
+    // Oldest first, like the rest of the workflow expects
+    commit_messages.reverse();
+
+    if commit_count > commit_messages.len() {
+        let warning = BoundaryWarning::CommitLimitExceeded {
+            count: commit_count,
+            limit,
+        };
+        run.warn(&warning)?;
+    }
+
+    // Releases should be attributable; squash tools and bots sometimes
+    // leave commits without an author
+    let anonymous = timings::measure(Phase::Revwalk, || {
+        repo.anonymous_commits(branch_to_tag, range_tag, base.as_deref(), limit)
+    });
+    if let Some(commits) = anonymous.ok().filter(|commits| !commits.is_empty()) {
+        let warning = BoundaryWarning::AnonymousCommits {
+            count: commits.len(),
+            commits,
+        };
+        run.warn(&warning)?;
+    }
+
+    // Promoting a pre-release usually comes without new commits
+    if commit_messages.is_empty() && !args.promote {
+        let head_hash = repo.get_current_head_hash()?;
+        let warning = BoundaryWarning::NoNewCommits {
+            latest_tag: latest_tag.clone().unwrap_or_else(|| "unknown".to_string()),
+            current_commit_hash: head_hash,
+        };
+
+        if run.warn(&warning)? && !prompter.confirm(text(Msg::ContinueWithNoNewCommits))? {
+            notifier.message(text(Msg::OperationCancelled));
+            return Ok(ControlFlow::Break(PublishOutcome::Cancelled));
+        }
+    }
+
+    // Display commit analysis
+    notifier.commit_analysis(&commit_messages, branch_to_tag);
+
+    let non_conforming = timings::measure(Phase::Analysis, || {
+        crate::domain::commit::find_non_conforming_commits(
+            &commit_messages,
+            &cfg.conventional_commits,
+        )
+    });
+    if !non_conforming.is_empty() {
+        notifier.non_conforming_commits(&non_conforming);
+    }
+
+    // Determined while walking the commits
+    let mut version_bump = bump_analysis.bump();
+    // Only commits of channel scopes since the latest tag: a pre-release of
+    // that channel rather than a stable release
+    let mut channel = bump_analysis.prerelease().cloned();
+    if let Some(identifier) = &channel {
+        notifier.status(&tr(Msg::ChannelPreReleaseOnly, &[("channel", identifier)]));
+    }
+    // --bump replaces the analysis, which stays on screen for reference
+    if let Some(bump) = args.bump {
+        notifier.status(&tr(
+            Msg::BumpOverridden,
+            &[
+                ("bump", &bump),
+                ("analyzed", &format!("{:?}", version_bump).to_lowercase()),
+            ],
+        ));
+        version_bump = bump.bump();
+        channel = None;
+    }
+
+    Ok(ControlFlow::Continue(Analysis {
+        latest_tag,
+        previous_metadata,
+        commit_messages,
+        commit_count,
+        bump: version_bump,
+        channel,
+    }))
+}
+
+/// Compute the new tag from the current version (the latest tag's or the
+/// manifest's) and let the user confirm or edit it
+fn compute_tag(run: &Run, target: &Target, analysis: &Analysis) -> Step<ProposedTag> {
+    let Run {
+        repo,
+        prompter,
+        notifier,
+        cfg,
+        args,
+        ..
+    } = *run;
+    let latest_tag = &analysis.latest_tag;
+    let version_bump = analysis.bump;
+    let tag_pattern = cfg.branches.get(&target.branch).map(|s| s.as_str());
+
+    // Format the new tag using the configured pattern
+    let new_tag_pattern = cfg
+        .branches
+        .tag_pattern(&target.branch)
+        .cloned()
+        .unwrap_or_else(|| TagPattern::new("v{version}"));
+    // With version_source = "manifest", the manifest's version is the one to bump
+    let mut version_source = cfg.behavior.version_source;
+    let manifest = match repo.workdir().and_then(Manifest::find) {
+        Some(manifest) => match manifest.read_version() {
+            Ok(version) => Some((manifest, version)),
+            Err(e) if version_source == VersionSource::Manifest => {
+                return Err(fail(
+                    notifier,
+                    &tr(
+                        Msg::ManifestReadFailed,
+                        &[("path", &manifest.kind.file_name()), ("error", &e)],
+                    ),
+                ));
+            }
+            // Not the version source, so an unreadable manifest doesn't matter
+            Err(_) => None,
+        },
+        None if version_source == VersionSource::Manifest => {
+            return Err(fail(notifier, text(Msg::ManifestNotFound)));
+        }
+        None => None,
+    };
+    // Read through the pattern first, for prefixes longer than a letter (`api-v`)
+    let tag_version = latest_tag.as_ref().map(|tag| {
+        match new_tag_pattern
+            .extract_version(tag)
+            .and_then(|version| Version::parse(&version).ok())
+        {
+            Some(version) => Ok(version),
+            None => Version::parse(tag).map_err(|_| tag.clone()),
+        }
+    });
+
+    // The manifest and the latest tag should agree; if not, ask which one to trust
+    if let (Some((manifest, manifest_version)), Some(Ok(tag_version)), Some(tag)) =
+        (&manifest, &tag_version, latest_tag)
+    {
+        if manifest_version != tag_version {
+            let warning = BoundaryWarning::ManifestVersionMismatch {
+                manifest: manifest.kind.file_name().to_string(),
+                manifest_version: manifest_version.to_string(),
+                tag: tag.clone(),
+                tag_version: tag_version.to_string(),
+            };
+            if run.warn(&warning)? {
+                version_source = prompter.select_version_source(
+                    (tag, tag_version),
+                    (manifest.kind.file_name(), manifest_version),
+                    version_source,
+                )?;
+            }
+        }
+    }
+    let current_version = match (&manifest, version_source) {
+        (Some((_, version)), VersionSource::Manifest) => Some(Ok(version.clone())),
+        _ => tag_version,
+    };
+
+    let previous_version = current_version
+        .as_ref()
+        .and_then(|version| version.as_ref().ok())
+        .cloned();
+
+    // Context for typing a custom tag
+    let recent_tags = || {
+        repo.list_tags(tag_pattern)
+            .map(|tags| new_tag_pattern.recent_tags(&tags, RECENT_TAGS_SHOWN))
+            .unwrap_or_default()
+    };
+
+    if args.promote
+        && !matches!(&current_version, Some(Ok(version)) if version.prerelease.is_some())
+    {
+        let tag = latest_tag.as_deref().unwrap_or("-");
+        return Err(fail(notifier, &tr(Msg::NothingToPromote, &[("tag", &tag)])));
+    }
+
+    // Recommended tag and new major version when the commits call for a major bump
+    let mut major_bump: Option<(String, u32)> = None;
+    let final_tag = match current_version {
+        Some(current_version) => match current_version {
+            Ok(current_version) => {
+                let identifier = match (&args.prerelease, args.bump) {
+                    (Some(identifier), _) => Some(identifier.clone()),
+                    (None, Some(BumpOverride::PreRelease)) => Some(prerelease_identifier(
+                        &current_version,
+                        &cfg.prerelease.default_identifier,
+                    )),
+                    _ => analysis.channel.clone(),
+                };
+                let candidate_versions = match &identifier {
+                    _ if args.promote => vec![current_version.release()],
+                    Some(identifier) => {
+                        vec![current_version.next_prerelease(&version_bump, identifier)]
+                    }
+                    None => current_version.bump_options(&version_bump),
+                };
+                let candidate_tags: Vec<String> = candidate_versions
+                    .into_iter()
+                    .map(|version| new_tag_pattern.format(&version.to_string()))
+                    .collect();
+                let recommended_tag = candidate_tags
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| new_tag_pattern.format("0.1.0"));
+                // Asked for explicitly, so no gate
+                if version_bump == VersionBump::Major
+                    && identifier.is_none()
+                    && args.bump.is_none()
+                    && !args.promote
+                {
+                    major_bump = Some((recommended_tag.clone(), current_version.major + 1));
+                }
+
+                notifier.proposed_tag(latest_tag.as_deref(), &recommended_tag);
+
+                if !args.force && !args.dry_run {
+                    prompt_valid_tag(notifier, cfg, &new_tag_pattern, || {
+                        prompter.select_tag_from_candidates(
+                            &recommended_tag,
+                            &candidate_tags,
+                            &recent_tags(),
+                        )
+                    })?
+                } else {
+                    recommended_tag
+                }
+            }
+            Err(tag) => {
+                let warning = BoundaryWarning::UnparsableTag {
+                    tag,
+                    reason: text(Msg::UnrecognizedVersionFormat).to_string(),
+                };
+                if run.warn(&warning)? && !prompter.confirm(text(Msg::UseInitialVersion))? {
+                    notifier.message(text(Msg::OperationCancelled));
+                    return Ok(ControlFlow::Break(PublishOutcome::Cancelled));
+                }
+
+                let new_version = Version::new(0, 1, 0);
+                let new_tag = new_tag_pattern.format(&new_version.to_string());
+                notifier.proposed_tag(latest_tag.as_deref(), &new_tag);
+
+                if !args.force && !args.dry_run {
+                    prompt_valid_tag(notifier, cfg, &new_tag_pattern, || {
+                        prompter.select_or_customize_tag(
+                            &new_tag,
+                            new_tag_pattern.as_str(),
+                            &recent_tags(),
+                        )
+                    })?
+                } else {
+                    new_tag
+                }
+            }
+        },
+        None => {
+            let new_version = Version::new(0, 1, 0);
+            let new_tag = new_tag_pattern.format(&new_version.to_string());
+            notifier.proposed_tag(latest_tag.as_deref(), &new_tag);
+
+            if !args.force && !args.dry_run {
+                prompt_valid_tag(notifier, cfg, &new_tag_pattern, || {
+                    prompter.select_or_customize_tag(
+                        &new_tag,
+                        new_tag_pattern.as_str(),
+                        &recent_tags(),
+                    )
+                })?
+            } else {
+                new_tag
+            }
+        }
+    };
+
+    // An edited tag may go backwards, e.g. v1.2.3 typed after v1.10.0
+    let chosen_version = new_tag_pattern
+        .extract_version(&final_tag)
+        .and_then(|version| Version::parse(&version).ok());
+    if let (Some(chosen), Some(previous)) = (&chosen_version, &previous_version) {
+        if chosen <= previous {
+            let warning = BoundaryWarning::VersionNotIncreased {
+                tag: final_tag.clone(),
+                version: chosen.to_string(),
+                current_version: previous.to_string(),
+            };
+            let ask = run.warn(&warning)?;
+            match cfg.behavior.on_version_downgrade {
+                DowngradePolicy::Block => {
+                    return Err(fail(notifier, text(Msg::VersionDowngradeBlocked)));
+                }
+                DowngradePolicy::Warn => {
+                    if ask && !prompter.confirm(text(Msg::ContinueWithOlderVersion))? {
+                        notifier.message(text(Msg::TagCreationCancelled));
+                        return Ok(ControlFlow::Break(PublishOutcome::Cancelled));
+                    }
+                }
+            }
+        }
+    }
+
+    // Breaking-change gate: a major bump needs --accept-breaking or the new major typed back
+    if let Some((_, new_major)) = major_bump.filter(|(tag, _)| *tag == final_tag) {
+        notifier.breaking_changes(&crate::domain::commit::breaking_commits(
+            &analysis.commit_messages,
+            &cfg.conventional_commits.breaking_change_indicators,
+        ));
+
+        if !args.accept_breaking {
+            if args.dry_run {
+                notifier.status(text(Msg::BreakingNeedsAcceptance));
+            } else if args.force {
+                return Err(fail(notifier, text(Msg::BreakingForceWithoutAccept)));
+            } else if !prompter.confirm_major_version(new_major)? {
+                notifier.message(text(Msg::TagCreationCancelled));
+                return Ok(ControlFlow::Break(PublishOutcome::Cancelled));
+            }
+        }
+    }
+
+    Ok(ControlFlow::Continue(ProposedTag {
+        pattern: new_tag_pattern,
+        tag: final_tag,
+        version: chosen_version,
+        manifest,
+    }))
+}
+
+/// Settle the tags of the release: apply `on_existing_tag` to a tag that
+/// exists already, add the `--also-tag` tags and check them against the
+/// tag policy
+fn resolve_collisions(run: &Run, target: &Target, proposed: &ProposedTag) -> Step<ReleaseTags> {
+    let Run {
+        repo,
+        prompter,
+        notifier,
+        cfg,
+        args,
+        ..
+    } = *run;
+    let selected_remote = &target.remote;
+    let new_tag_pattern = &proposed.pattern;
+    let final_tag = proposed.tag.clone();
+
+    // The tag may already exist (e.g. a custom tag, or a release that was
+    // tagged by hand), locally or only on the remote
+    let remote_tags = if target.offline {
+        Vec::new()
+    } else {
+        repo.remote_tags(selected_remote, cfg.remote_overrides.get(selected_remote))
+            .unwrap_or_default()
+    };
+    let tag_taken = |tag: &str| repo.tag_exists(tag) || remote_tags.iter().any(|t| t == tag);
+    let mut replace_existing = false;
+    let final_tag = if tag_taken(&final_tag) {
+        match cfg.behavior.on_existing_tag {
+            ExistingTagPolicy::Abort => {
+                return Err(fail(
+                    notifier,
+                    &tr(Msg::TagAlreadyExists, &[("tag", &final_tag)]),
+                ));
+            }
+            ExistingTagPolicy::Skip => {
+                notifier.status(&tr(Msg::ExistingTagSkipped, &[("tag", &final_tag)]));
+                return Ok(ControlFlow::Break(PublishOutcome::Skipped {
+                    tag: final_tag,
+                }));
+            }
+            ExistingTagPolicy::Increment => {
+                let next = new_tag_pattern.next_available(&final_tag, tag_taken);
+                let Some(next) = next else {
+                    return Err(fail(
+                        notifier,
+                        &tr(Msg::TagAlreadyExists, &[("tag", &final_tag)]),
+                    ));
+                };
+                notifier.status(&tr(
+                    Msg::ExistingTagIncremented,
+                    &[("tag", &final_tag), ("next", &next)],
+                ));
+                next
+            }
+            ExistingTagPolicy::Replace => {
+                notifier.warning(&tr(Msg::ExistingTagReplaced, &[("tag", &final_tag)]));
+                replace_existing = true;
+                final_tag
+            }
+        }
+    } else {
+        final_tag
+    };
+    let version = new_tag_pattern
+        .extract_version(&final_tag)
+        .unwrap_or_else(|| final_tag.clone());
+
+    // The same version under the patterns of the --also-tag branches
+    let mut release_tags = vec![final_tag.clone()];
+    for branch in &args.also_tag {
+        let Some(pattern) = cfg.branches.tag_pattern(branch) else {
+            continue;
+        };
+        let tag = pattern.format(&version);
+        if release_tags.contains(&tag) {
+            continue;
+        }
+        if tag_taken(&tag) && !replace_existing {
+            return Err(fail(notifier, &tr(Msg::TagAlreadyExists, &[("tag", &tag)])));
+        }
+        release_tags.push(tag);
+    }
+    if let [_, also_tags @ ..] = release_tags.as_slice() {
+        if !also_tags.is_empty() {
+            notifier.status(&tr(Msg::AlsoTagging, &[("tags", &also_tags.join(", "))]));
+        }
+    }
+
+    // Moving a published tag rewrites what others already fetched: name
+    // every ref that changes and ask, even with --force
+    if replace_existing {
+        let moved: Vec<String> = release_tags
+            .iter()
+            .filter(|tag| tag_taken(tag))
+            .map(|tag| {
+                let mut places = Vec::new();
+                if repo.tag_exists(tag) {
+                    places.push(text(Msg::ReplacedTagLocal).to_string());
+                }
+                if remote_tags.contains(tag) {
+                    places.push(selected_remote.clone());
+                }
+                format!("  refs/tags/{} ({})", tag, places.join(", "))
+            })
+            .collect();
+        notifier.warning(&tr(Msg::ReplacingTags, &[("count", &moved.len())]));
+        for line in &moved {
+            notifier.message(line);
+        }
+        if args.dry_run || args.replace_tags {
+            // Nothing is moved in a dry run; --replace-tags is the answer
+        } else if args.force {
+            return Err(fail(notifier, text(Msg::ReplaceTagsNeedsFlag)));
+        } else if !prompter.confirm(&tr(Msg::ConfirmReplaceTags, &[("remote", selected_remote)]))? {
+            notifier.message(text(Msg::TagCreationCancelled));
+            return Ok(ControlFlow::Break(PublishOutcome::Cancelled));
+        }
+    }
+
+    // Organization policy applies to every tag, also custom-entered ones and with --force
+    for tag in &release_tags {
+        if let Err(e) =
+            crate::ui::validate_tag_policy(tag, cfg.behavior.tag_validation_regex.as_deref())
+        {
+            return Err(fail(notifier, &e.to_string()));
+        }
+    }
+
+    // Confirm tag use (checks format and gets user confirmation)
+    if !args.force
+        && !args.dry_run
+        && !prompter.confirm_tag_use(&final_tag, new_tag_pattern.as_str())?
+    {
+        notifier.message(text(Msg::TagCreationCancelled));
+        return Ok(ControlFlow::Break(PublishOutcome::Cancelled));
+    }
+
+    Ok(ControlFlow::Continue(ReleaseTags {
+        tag: final_tag,
+        version,
+        tags: release_tags,
+        replace_existing,
+    }))
+}
+
+/// The release summary for the step summary file, with the changelog that
+/// also goes into the changelog file
+fn release_summary(
+    run: &Run,
+    target: &Target,
+    analysis: &Analysis,
+    release: &ReleaseTags,
+) -> StepSummary {
+    let Run { repo, cfg, .. } = *run;
+    // Pull request and compare links need the repository's web URL
+    let repo_url = repo
+        .remote_url(&target.remote)
+        .ok()
+        .flatten()
+        .and_then(|url| web_url(&url));
+    let cherry_picks = match cfg.changelog.cherry_picks {
+        CherryPicks::Keep => HashMap::new(),
+        CherryPicks::Annotate | CherryPicks::Exclude => timings::measure(Phase::Analysis, || {
+            released_cherry_picks(
+                repo,
+                cfg,
+                &target.branch,
+                &target.remote,
+                analysis.latest_tag.as_deref(),
+            )
+        }),
+    };
+    let mut changelog = timings::measure(Phase::Analysis, || {
+        Changelog::from_commits_with_cherry_picks(
+            &analysis.commit_messages,
+            &cfg.conventional_commits.breaking_change_indicators,
+            &cherry_picks,
+            cfg.changelog.cherry_picks,
+        )
+        .with_layout(cfg.changelog.layout)
+    });
+    if let Some(template) = repo_url
+        .as_deref()
+        .and_then(|url| cfg.changelog.pull_request_link_template(url))
+    {
+        changelog = changelog.with_pull_request_links(template);
+    }
+    StepSummary {
+        tag: release.tag.clone(),
+        previous_tag: analysis.latest_tag.clone(),
+        branch: target.branch.clone(),
+        remote: target.remote.clone(),
+        bump: analysis.bump,
+        commit_count: analysis.commit_messages.len(),
+        changelog,
+        repo_url,
+        previous_notes: analysis
+            .previous_metadata
+            .as_ref()
+            .map(|metadata| metadata.notes.clone())
+            .filter(|notes| !notes.is_empty()),
+    }
+}
+
+/// The files to commit before tagging: the manifest with the new version
+/// (with `version_source = "manifest"`) and the changelog file
+fn release_files<'a>(
+    run: &Run<'a>,
+    manifest: Option<(Manifest, Version)>,
+    release: &ReleaseTags,
+) -> Result<ReleaseFiles<'a>> {
+    let Run {
+        repo,
+        notifier,
+        cfg,
+        args,
+        ..
+    } = *run;
+    // Keep the manifest in sync: the new version is committed before tagging
+    let manifest = manifest
+        .filter(|_| cfg.behavior.version_source == VersionSource::Manifest)
+        .and_then(
+            |(manifest, current_version)| match Version::parse(&release.version) {
+                Ok(version) => (version != current_version).then_some((manifest, version)),
+                Err(_) => {
+                    notifier.warning(&tr(
+                        Msg::ManifestNotUpdated,
+                        &[("tag", &release.tag), ("path", &manifest.kind.file_name())],
+                    ));
+                    None
+                }
+            },
+        );
+
+    // Release notes go into the changelog file, committed along with the manifest
+    let changelog = if args.changelog.unwrap_or(cfg.changelog.file.is_some()) {
+        let file = cfg
+            .changelog
+            .file
+            .as_deref()
+            .unwrap_or(crate::changelog::DEFAULT_FILE);
+        match repo.workdir() {
+            Some(root) => Some((ChangelogFile::in_root(root, file), file)),
+            None => {
+                return Err(fail(
+                    notifier,
+                    &tr(
+                        Msg::ChangelogUpdateFailed,
+                        &[("path", &file), ("error", &text(Msg::NoWorkingTree))],
+                    ),
+                ));
+            }
+        }
+    } else {
+        None
+    };
+    Ok(ReleaseFiles {
+        manifest,
+        changelog,
+    })
+}
+
+/// Report what the release would change, without changing anything
+fn report_dry_run(
+    run: &Run,
+    target: &Target,
+    release: &ReleaseTags,
+    files: &ReleaseFiles,
+    hooks: &ReleaseHooks,
+) -> PublishOutcome {
+    let Run {
+        repo,
+        notifier,
+        args,
+        ..
+    } = *run;
+    notifier.status(text(Msg::DryRunHeader));
+    if let Some((manifest, version)) = &files.manifest {
+        notifier.success(&tr(
+            Msg::DryRunUpdateManifest,
+            &[("path", &manifest.kind.file_name()), ("version", version)],
+        ));
+    }
+    if let Some((_, file)) = &files.changelog {
+        notifier.success(&tr(Msg::DryRunUpdateChangelog, &[("path", file)]));
+    }
+    for tag in &release.tags {
+        notifier.success(&tr(Msg::DryRunCreateTag, &[("tag", tag)]));
+    }
+    if args.tag_only {
+        notifier.success(text(Msg::DryRunTagOnly));
+    } else {
+        notifier.success(text(Msg::DryRunAskPush));
+        notifier.success(&tr(
+            Msg::DryRunPush,
+            &[
+                ("tag", &release.tags.join(", ")),
+                ("remote", &target.remote),
+            ],
+        ));
+    }
+    let planned_hooks: Vec<(&str, Vec<String>)> = HookType::ALL
+        .iter()
+        .map(|&hook_type| {
+            let commands = resolve_scripts(&hooks.config, repo.workdir(), hook_type)
+                .iter()
+                .map(HookExecutor::command_line)
+                .collect();
+            (hook_type.name(), commands)
+        })
+        .collect();
+    notifier.hook_dry_run(&planned_hooks, &masked_env(&hooks.context.to_env_vars()));
+    PublishOutcome::DryRun {
+        tag: release.tag.clone(),
+    }
+}
+
+/// Write the new version to the manifest and the release notes to the
+/// changelog file, and commit them as `chore(release): <tag>`
+///
+/// The files are put back as they were if the commit can't be made.
+fn commit_release(
+    run: &Run,
+    branch: &str,
+    release: &ReleaseTags,
+    files: &ReleaseFiles,
+    step_summary: &StepSummary,
+) -> Result<()> {
+    let Run {
+        repo,
+        notifier,
+        cfg,
+        ..
+    } = *run;
+    // Files of the release commit, put back as they were if it can't be made
+    let mut release_files: Vec<(PathBuf, String)> = Vec::new();
+    let mut manifest_written = None;
+    let mut changelog_original = None;
+    let restore =
+        |manifest_written: &Option<ManifestUpdate>,
+         changelog_original: &Option<(&ChangelogFile, Option<String>)>| {
+            if let Some(update) = manifest_written {
+                let _ = update.restore();
+            }
+            if let Some((changelog_file, original)) = changelog_original {
+                let _ = match original {
+                    Some(content) => std::fs::write(&changelog_file.path, content),
+                    None => std::fs::remove_file(&changelog_file.path),
+                };
+            }
+        };
+    if let Some((manifest, version)) = &files.manifest {
+        let path = manifest.kind.file_name();
+        notifier.status(&tr(
+            Msg::UpdatingManifest,
+            &[("path", &path), ("version", version)],
+        ));
+        match manifest.write_version(version) {
+            Ok(update) => {
+                let root = repo.workdir().unwrap_or(Path::new(""));
+                release_files.extend(update.paths().map(|path| {
+                    let name = path.strip_prefix(root).unwrap_or(path);
+                    (path.to_path_buf(), name.display().to_string())
+                }));
+                manifest_written = Some(update);
+            }
+            Err(e) => {
+                return Err(fail(
+                    notifier,
+                    &tr(Msg::ManifestUpdateFailed, &[("path", &path), ("error", &e)]),
+                ));
+            }
+        }
+    }
+    if let Some((changelog_file, file)) = &files.changelog {
+        notifier.status(&tr(Msg::UpdatingChangelog, &[("path", file)]));
+        changelog_original = Some((
+            changelog_file,
+            std::fs::read_to_string(&changelog_file.path).ok(),
+        ));
+        let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let header = cfg
+            .changelog
+            .release_header(&release.tag, &release.version, &date);
+        if let Err(e) = changelog_file.add_release(&header, &step_summary.changelog.to_markdown()) {
+            restore(&manifest_written, &changelog_original);
+            return Err(fail(
+                notifier,
+                &tr(Msg::ChangelogUpdateFailed, &[("path", file), ("error", &e)]),
+            ));
+        }
+        release_files.push((changelog_file.path.clone(), file.to_string()));
+    }
+    if release_files.is_empty() {
+        return Ok(());
+    }
+
+    let paths: Vec<&Path> = release_files
+        .iter()
+        .map(|(path, _)| path.as_path())
+        .collect();
+    let names = release_files
+        .iter()
+        .map(|(_, name)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    match repo.commit_files(branch, &paths, &format!("chore(release): {}", release.tag)) {
+        Ok(commit) => {
+            notifier.success(&tr(
+                Msg::ReleaseCommitted,
+                &[
+                    ("paths", &names),
+                    ("commit", &commit.get(..7).unwrap_or(&commit)),
+                ],
+            ));
+            Ok(())
+        }
+        Err(e) => {
+            restore(&manifest_written, &changelog_original);
+            Err(fail(
+                notifier,
+                &tr(
+                    Msg::ReleaseCommitFailed,
+                    &[("paths", &names), ("error", &e)],
+                ),
+            ))
+        }
+    }
+}
+
+/// Create the release's tags on `branch` (not on the current HEAD),
+/// annotated with a summary of `annotation`'s analysis when it is set
+///
+/// A failure removes the tags created before it, so no half of a compound
+/// release is left behind.
+fn create_tags(
+    run: &Run,
+    branch: &str,
+    release: &ReleaseTags,
+    annotation: Option<(&Analysis, Option<&Version>)>,
+) -> Result<()> {
+    let Run {
+        repo,
+        notifier,
+        cfg,
+        ..
+    } = *run;
+    // Annotated tags summarize the release in their message
+    let breaking_changes: Vec<String> = annotation
+        .map(|(analysis, _)| {
+            analysis
+                .commit_messages
+                .iter()
+                .map(|message| {
+                    ParsedCommit::parse_with_indicators(
+                        message,
+                        &cfg.conventional_commits.breaking_change_indicators,
+                    )
+                })
+                .filter(|commit| commit.is_breaking_change)
+                .map(|commit| commit.subject_description().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    let tag_annotation = |tag: &str| {
+        annotation.map(|(analysis, version)| TagAnnotation {
+            message: ReleaseNote {
+                tag,
+                version,
+                previous_tag: analysis.latest_tag.as_deref(),
+                bump: analysis.bump,
+                commit_count: analysis.commit_count,
+                breaking_changes: &breaking_changes,
+            }
+            .message(),
+            signed: cfg.tags.signed,
+        })
+    };
+
+    for tag in &release.tags {
+        notifier.status(&tr(Msg::CreatingTag, &[("tag", tag)]));
+        if let Err(e) = repo.write_tag(
+            tag,
+            branch,
+            release.replace_existing,
+            tag_annotation(tag).as_ref(),
+        ) {
+            // Don't leave half of a compound release behind
+            if !release.replace_existing {
+                for created in release.tags.iter().take_while(|created| *created != tag) {
+                    let _ = repo.delete_tag(created);
+                }
+            }
+            interrupt::release_tag();
+            return Err(fail(
+                notifier,
+                &tr(Msg::CreateTagFailed, &[("tag", tag), ("error", &e)]),
+            ));
+        }
+        // A replaced tag existed before this run, so an interrupt must not delete it
+        if !release.replace_existing {
+            interrupt::track_tag(repo.git_dir(), tag);
+        }
+        notifier.success(&tr(Msg::CreatedTag, &[("tag", tag)]));
+    }
+    Ok(())
+}
+
+/// Ask whether to push the tags (unless `force` or `tag_only` decides), then
+/// push the release commit and the tags, or queue them when the remote is
+/// unreachable
+fn push_release(
+    run: &Run,
+    last_answers: &LastAnswers,
+    target: &Target,
+    release: &ReleaseTags,
+    release_commit: bool,
+    hooks: &mut ReleaseHooks,
+    step_summary: &StepSummary,
+) -> Result<PublishOutcome> {
+    let Run {
+        repo,
+        prompter,
+        notifier,
+        cfg,
+        args,
+        ..
+    } = *run;
+    let (branch_to_tag, selected_remote) = (&target.branch, &target.remote);
+    let tag_list = release.tags.join(", ");
+    let created_tags: &[String] = if release.replace_existing {
+        &[]
+    } else {
+        &release.tags
+    };
+
+    // Step 2: Ask user whether to push the tag
+    let should_push = if args.tag_only {
+        // Pushed by a later stage, e.g. with --push-only
+        false
+    } else if !args.force {
+        let push = prompter.confirm_push_tag(
+            &tag_list,
+            selected_remote,
+            last_answers.push.unwrap_or(true),
+        )?;
+        let answers = LastAnswers {
+            branch: Some(branch_to_tag.clone()),
+            remote: Some(selected_remote.clone()),
+            push: Some(push),
+        };
+        // Only a convenience for the next run
+        let _ = answers.save(repo.git_dir());
+        push
+    } else {
+        true // In force mode, push automatically
+    };
+
+    // Step 3: Push if user confirmed (or in force mode)
+    if !should_push {
+        // Tag created locally, but not pushed
+        interrupt::release_tag();
+        notifier.manual_push_instruction(
+            &cfg.behavior
+                .manual_push_command_for(&release.tags.join(" "), selected_remote),
+        );
+        if release_commit {
+            notifier.warning(&tr(
+                Msg::ReleaseCommitNotPushed,
+                &[("branch", branch_to_tag), ("remote", selected_remote)],
+            ));
+        }
+        notifier.hook_summary(&hooks.runs);
+        write_step_summary(notifier, args, step_summary, SummaryStatus::CreatedLocally);
+
+        notifier.finished(&tr(
+            Msg::CreatedTagLocally,
+            &[("tag", &release.tag), ("branch", branch_to_tag)],
+        ));
+        return Ok(PublishOutcome::CreatedLocally {
+            tag: release.tag.clone(),
+        });
+    }
+    if let Some(outcome) = run.checkpoint(created_tags) {
+        return Ok(outcome);
+    }
+
+    // Offline (the probe failed): queue the push right away instead of trying
+    let queue_push = target.offline || {
+        // The release commit goes first, so the tag never points at an unpushed commit
+        let pushed_branch = if release_commit {
+            notifier.status(&tr(
+                Msg::PushingBranch,
+                &[("branch", branch_to_tag), ("remote", selected_remote)],
+            ));
+            repo.push_branch(branch_to_tag, selected_remote)
+        } else {
+            Ok(())
+        };
+        let pushed = pushed_branch.and_then(|()| {
+            notifier.status(&tr(
+                Msg::PushingTag,
+                &[("tag", &tag_list), ("remote", selected_remote)],
+            ));
+            let remote_override = cfg.remote_overrides.get(selected_remote);
+            let tags: Vec<&str> = release.tags.iter().map(String::as_str).collect();
+            repo.push_tags_with_options(
+                &tags,
+                selected_remote,
+                remote_override,
+                release.replace_existing,
+            )
+        });
+        match pushed {
+            Ok(()) => false,
+            Err(e) => {
+                let unreachable = e
+                    .downcast_ref::<GitPublishError>()
+                    .and_then(|err| err.remote_failure_kind())
+                    == Some(RemoteFailureKind::NetworkUnreachable);
+                if !unreachable {
+                    return Err(fail(
+                        notifier,
+                        &tr(Msg::PushTagFailed, &[("tag", &tag_list), ("error", &e)]),
+                    ));
+                }
+                true
+            }
+        }
+    };
+    if queue_push {
+        // Remote unreachable or offline: keep the tag and queue the push for `git-publish flush`
+        let outbox = Outbox::in_git_dir(repo.git_dir());
+        for tag in &release.tags {
+            outbox.enqueue(selected_remote, tag)?;
+        }
+        interrupt::release_tag();
+        notifier.status(&tr(
+            Msg::PushQueued,
+            &[("remote", selected_remote), ("tag", &tag_list)],
+        ));
+        if release_commit {
+            notifier.warning(&tr(
+                Msg::ReleaseCommitNotPushed,
+                &[("branch", branch_to_tag), ("remote", selected_remote)],
+            ));
+        }
+        notifier.hook_summary(&hooks.runs);
+        write_step_summary(notifier, args, step_summary, SummaryStatus::Queued);
+        notifier.finished(&tr(
+            Msg::CreatedTagPushQueued,
+            &[("tag", &release.tag), ("branch", branch_to_tag)],
+        ));
+        return Ok(PublishOutcome::Queued {
+            tag: release.tag.clone(),
+        });
+    }
+    interrupt::release_tag();
+    for tag in &release.tags {
+        notifier.success(&tr(Msg::PushedTag, &[("tag", tag)]));
+    }
+    hooks.run(run, HookType::PostPush)?;
+    notifier.hook_summary(&hooks.runs);
+    write_step_summary(notifier, args, step_summary, SummaryStatus::Published);
+
+    notifier.finished(&tr(
+        Msg::PublishedTag,
+        &[("tag", &release.tag), ("branch", branch_to_tag)],
+    ));
+    Ok(PublishOutcome::Published {
+        tag: release.tag.clone(),
+    })
+}
+
+/// Push an existing local tag (`--push-only`), e.g. in a CI stage after
+/// another one created it with `--tag-only`.
+///
+/// The tag's branch is `--branch` or the configured branch whose pattern the
+/// tag matches; its post-push hooks run once the tag is pushed. An
+/// unreachable remote queues the push like [`publish`] does.
+fn push_existing_tag(
+    repo: &dyn Repository,
+    prompter: &dyn Prompter,
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    args: &PublishWorkflowArgs,
+    last_answers: &LastAnswers,
+    tag: &str,
+) -> Result<PublishOutcome> {
+    if !repo.tag_exists(tag) {
+        return Err(fail(
+            notifier,
+            &tr(Msg::TagNotFoundLocally, &[("tag", &tag)]),
+        ));
+    }
+    let mut branches: Vec<&String> = cfg.branches.keys().collect();
+    branches.sort();
+    let matched = branches.into_iter().find_map(|branch| {
+        let version = cfg.branches.tag_pattern(branch)?.extract_version(tag)?;
+        Some((branch.clone(), version))
+    });
+    let (branch, version) = match (&args.branch, matched) {
+        (Some(branch), _) => {
+            let version = cfg
+                .branches
+                .tag_pattern(branch)
+                .and_then(|pattern| pattern.extract_version(tag))
+                .unwrap_or_else(|| tag.to_string());
+            (branch.clone(), version)
+        }
+        (None, Some(matched)) => matched,
+        (None, None) => {
+            return Err(fail(notifier, &tr(Msg::TagBranchUnknown, &[("tag", &tag)])));
+        }
+    };
+    let selected_remote = select_remote(repo, prompter, notifier, cfg, args, last_answers)?;
+
+    if args.dry_run {
+        notifier.status(text(Msg::DryRunHeader));
+        notifier.success(&tr(
+            Msg::DryRunPush,
+            &[("tag", &tag), ("remote", &selected_remote)],
+        ));
+        return Ok(PublishOutcome::DryRun {
+            tag: tag.to_string(),
+        });
+    }
+
+    notifier.status(&tr(
+        Msg::PushingTag,
+        &[("tag", &tag), ("remote", &selected_remote)],
+    ));
+    if let Err(e) = repo.push_tags_with_options(
+        &[tag],
+        &selected_remote,
+        cfg.remote_overrides.get(&selected_remote),
+        false,
+    ) {
+        let unreachable = e
+            .downcast_ref::<GitPublishError>()
+            .and_then(|err| err.remote_failure_kind())
+            == Some(RemoteFailureKind::NetworkUnreachable);
+        if !unreachable {
+            return Err(fail(
+                notifier,
+                &tr(Msg::PushTagFailed, &[("tag", &tag), ("error", &e)]),
+            ));
+        }
+        Outbox::in_git_dir(repo.git_dir()).enqueue(&selected_remote, tag)?;
+        notifier.status(&tr(
+            Msg::PushQueued,
+            &[("remote", &selected_remote), ("tag", &tag)],
+        ));
+        return Ok(PublishOutcome::Queued {
+            tag: tag.to_string(),
+        });
+    }
+    notifier.success(&tr(Msg::PushedTag, &[("tag", &tag)]));
+
+    let hooks = cfg.hooks_for(&branch);
+    let hook_context = HookContext {
+        hook_type: HookType::PostPush,
+        branch: branch.clone(),
+        tag: tag.to_string(),
+        version,
+        remote: selected_remote,
+        previous_tag: None,
+        version_bump: None,
+        commit_count: None,
+        commit_types: None,
+        env: hooks.hook_env(),
+        repo_root: repo.workdir().map(|root| root.components().collect()),
+    };
+    let mut hook_runs = Vec::new();
+    run_hook(
+        notifier,
+        &hooks,
+        repo.workdir(),
+        HookType::PostPush,
+        &hook_context,
+        cfg.behavior.hook_echo,
+        &mut hook_runs,
+    )?;
+    notifier.hook_summary(&hook_runs);
+
+    notifier.finished(&tr(
+        Msg::PublishedTag,
+        &[("tag", &tag), ("branch", &branch)],
+    ));
+    Ok(PublishOutcome::Published {
+        tag: tag.to_string(),
+    })
+}
+
+/// The remote to fetch from and push to: `--remote` (checked to exist), the
+/// only remote with `skip_remote_selection`, or else the user's pick
+/// Fetch `remote`, updating `branch` and fast-forwarding the other configured
+/// branches from the same fetch
+///
+/// A failed fetch is a warning: the release goes on with local data.
+///
+/// # Returns
+/// * `Ok(None)` - Fetched, or going on with local data
+/// * `Ok(Some(PublishOutcome::Cancelled))` - The user declined to go on with local data
+fn fetch_remote(
+    repo: &dyn Repository,
+    prompter: &dyn Prompter,
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    args: &PublishWorkflowArgs,
+    remote: &str,
+    branch: &str,
+) -> Result<Option<PublishOutcome>> {
+    let mut other_branches: Vec<String> = cfg
+        .branches
+        .keys()
+        .filter(|other| other.as_str() != branch)
+        .cloned()
+        .collect();
+    other_branches.sort();
+
+    notifier.status(&tr(Msg::Fetching, &[("remote", &remote)]));
+    match timings::measure(Phase::Fetch, || {
+        // A dry run leaves the local branches where they are
+        if args.dry_run {
+            repo.fetch_remote_refs(remote)
+        } else {
+            repo.fetch_from_remote_with_branches(remote, branch, &other_branches)
+        }
+    }) {
+        Ok(_) => {
+            notifier.success(&tr(Msg::Fetched, &[("remote", &remote)]));
+        }
+        Err(e) => {
+            let warning = e
+                .downcast_ref::<GitPublishError>()
+                .and_then(|err| err.remote_failure_kind())
+                .and_then(|kind| BoundaryWarning::from_fetch_failure(remote, kind));
+
+            if let Some(warning) = warning {
+                if report_warning(notifier, cfg, args, &warning)?
+                    && !prompter.confirm(text(Msg::ContinueWithLocalData))?
+                {
+                    notifier.message(text(Msg::OperationCancelled));
+                    return Ok(Some(PublishOutcome::Cancelled));
+                }
+            } else {
+                // Unclassified errors are still warnings
+                notifier.status(&tr(
+                    Msg::FetchFailedUsingLocal,
+                    &[("remote", &remote), ("error", &e)],
+                ));
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn select_remote(
+    repo: &dyn Repository,
+    prompter: &dyn Prompter,
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    args: &PublishWorkflowArgs,
+    last_answers: &LastAnswers,
+) -> Result<String> {
+    // Validate specified remote if provided
+    if let Some(ref specified_remote) = args.remote {
+        if !repo
+            .remote_exists(specified_remote)
+            .context(text(Msg::ValidateRemoteFailed))?
+        {
+            let available = repo.list_remotes()?;
+            anyhow::bail!(
+                "{}",
+                tr(
+                    Msg::RemoteNotFound,
+                    &[
+                        ("remote", specified_remote),
+                        ("available", &available.join(", "))
+                    ],
+                )
+            );
+        }
+    }
+
+    // Get available remotes for selection
+    let available_remotes = match repo.list_remotes() {
+        Ok(mut remotes) => {
+            if remotes.is_empty() {
+                return Err(fail(notifier, text(Msg::NoRemotesConfigured)));
+            }
+            state::put_first(&mut remotes, last_answers.remote.as_deref());
+            remotes
+        }
+        Err(e) => {
+            return Err(fail(
+                notifier,
+                &tr(Msg::ListRemotesFailed, &[("error", &e)]),
+            ));
+        }
+    };
+
+    // Determine which remote to use with three-tier precedence:
+    // 1. CLI flag (--remote) - takes absolute precedence if provided
+    // 2. Config option (skip_remote_selection) - applies only to single-remote case
+    //    - If true and single remote exists: auto-select without prompting
+    //    - If false (default): always prompt user even for single remote
+    // 3. Interactive prompt - used for multiple remotes or when no CLI flag
+    let selected_remote = if let Some(ref cli_remote) = args.remote {
+        // CLI flag takes precedence
+        cli_remote.clone()
+    } else {
+        // Check available remotes
+        if available_remotes.len() == 1 {
+            // Single remote case
+            let should_skip = cfg.behavior.skip_remote_selection;
+            if should_skip {
+                // Auto-select the single remote
+                available_remotes[0].clone()
+            } else {
+                // Prompt even though there's only one
+                prompter.select_remote(&available_remotes)?
+            }
+        } else {
+            // Multiple remotes - always prompt (config only applies to single remote case)
+            prompter.select_remote(&available_remotes)?
+        }
+    };
+    Ok(selected_remote)
+}
+
+/// Identifier of the pre-release `--bump prerelease` makes after `current`:
+/// that of the current pre-release, so `1.3.0-beta.1` goes on to `beta.2`,
+/// or else `default_identifier`
+fn prerelease_identifier(current: &Version, default_identifier: &str) -> PreReleaseType {
+    match &current.prerelease {
+        Some(prerelease) => prerelease.identifier.clone(),
+        None => default_identifier.parse().unwrap_or(PreReleaseType::Alpha),
+    }
+}
+
+/// How many existing tags are shown when asking for a custom tag
+const RECENT_TAGS_SHOWN: usize = 5;
+
+/// How many invalid tags are accepted before the run fails
+const MAX_TAG_ATTEMPTS: usize = 5;
+
+/// Ask for the tag with `ask` until it is not empty and passes the pattern
+/// and `tag_validation_regex` checks, reporting why a tag was rejected
+/// before asking again
+///
+/// Gives up with the last error after [`MAX_TAG_ATTEMPTS`] tries, so a
+/// prompter that always answers the same doesn't loop forever.
+fn prompt_valid_tag(
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    pattern: &TagPattern,
+    mut ask: impl FnMut() -> Result<String>,
+) -> Result<String> {
+    let mut attempts = 0;
+    loop {
+        let tag = ask()?;
+        let checked = if tag.is_empty() {
+            Err(anyhow::anyhow!(text(Msg::EmptyTag)))
+        } else {
+            crate::ui::validate_tag_format(&tag, pattern.as_str()).and_then(|()| {
+                crate::ui::validate_tag_policy(&tag, cfg.behavior.tag_validation_regex.as_deref())
+            })
+        };
+        let Err(e) = checked else {
+            return Ok(tag);
+        };
+        attempts += 1;
+        if attempts >= MAX_TAG_ATTEMPTS {
+            return Err(fail(notifier, &e.to_string()));
+        }
+        notifier.warning(&tr(Msg::TagRejectedTryAgain, &[("error", &e)]));
+    }
+}
+
+/// Report a boundary warning and apply its `[warnings]` policy.
+///
+/// # Returns
+/// * `Ok(true)` - Ask the user whether to go on: the policy is `prompt` and
+///   the run is interactive
+/// * `Ok(false)` - Go on without asking
+/// * `Err` - The policy is `fail`
+fn report_warning(
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    args: &PublishWorkflowArgs,
+    warning: &BoundaryWarning,
+) -> Result<bool> {
+    notifier.boundary_warning(warning);
+    match cfg.warnings.policy(warning) {
+        WarningPolicy::Prompt => Ok(!args.force && !args.dry_run),
+        WarningPolicy::Ignore => Ok(false),
+        WarningPolicy::Fail => Err(fail(
+            notifier,
+            &tr(Msg::WarningIsFatal, &[("key", &warning.config_key())]),
+        )),
+    }
+}
+
+/// The commit `--since` starts the analysis after: the commit a revision
+/// points at, or for a date (`YYYY-MM-DD`, UTC) the last commit of the branch
+/// before it; `None` when the whole branch is newer than the date
+fn since_base(repo: &dyn Repository, branch: &str, since: &str) -> Result<Option<String>> {
+    match NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        Ok(date) => {
+            let time = date.and_time(NaiveTime::MIN).and_utc().timestamp();
+            repo.last_commit_before(branch, time)
+        }
+        Err(_) => repo.resolve_commit(since).map(Some),
+    }
+}
+
+/// Commits since `latest_tag` whose change the latest tag of another
+/// configured branch already released, mapped to that tag.
+///
+/// Branches that don't exist locally or have no tag are skipped, as is a tag
+/// the branch shares.
+fn released_cherry_picks(
+    repo: &dyn Repository,
+    cfg: &Config,
+    branch: &str,
+    remote: &str,
+    latest_tag: Option<&str>,
+) -> HashMap<String, String> {
+    let mut others: Vec<(&String, &String)> = cfg
+        .branches
+        .iter()
+        .filter(|(other, _)| other.as_str() != branch)
+        .collect();
+    others.sort();
+
+    let mut cherry_picks = HashMap::new();
+    for (other, pattern) in others {
+        let Ok(Some(released_tag)) =
+            repo.get_latest_tag_on_branch_with_remote(other, Some(remote), Some(pattern))
+        else {
+            continue;
+        };
+        if latest_tag == Some(released_tag.as_str()) {
+            continue;
+        }
+        let Ok(messages) = repo.released_cherry_picks(branch, latest_tag, &released_tag) else {
+            continue;
+        };
+        for message in messages {
+            cherry_picks
+                .entry(message)
+                .or_insert_with(|| released_tag.clone());
+        }
+    }
+    cherry_picks
+}
+
+/// Append the release to `args.step_summary`, if set; failing to do so only warns
+fn write_step_summary(
+    notifier: &dyn Notifier,
+    args: &PublishWorkflowArgs,
+    summary: &StepSummary,
+    status: SummaryStatus,
+) {
+    let Some(path) = &args.step_summary else {
+        return;
+    };
+    if let Err(e) = summary.append_to(path, status) {
+        notifier.warning(&tr(
+            Msg::StepSummaryFailed,
+            &[("path", &path.display()), ("error", &e)],
+        ));
+    }
+}
+
+/// Stop if `token` was cancelled, removing `created_tags` (the unpushed tags
+/// created by this run) unless `keep_tag_on_interrupt` is set
+fn checkpoint(
+    token: &CancellationToken,
+    repo: &dyn Repository,
+    notifier: &dyn Notifier,
+    cfg: &Config,
+    created_tags: &[String],
+) -> Option<PublishOutcome> {
+    if !token.is_cancelled() {
+        return None;
+    }
+    notifier.warning(text(Msg::PublishCancelled));
+
+    let mut removed_tag = None;
+    if !created_tags.is_empty() {
+        interrupt::release_tag();
+    }
+    for tag in created_tags {
+        if cfg.behavior.keep_tag_on_interrupt {
+            notifier.status(&tr(Msg::InterruptKeptTag, &[("tag", tag)]));
+        } else {
+            match repo.delete_tag(tag) {
+                Ok(()) => {
+                    notifier.status(&tr(Msg::InterruptRolledBack, &[("tag", tag)]));
+                    // The main tag comes first
+                    removed_tag.get_or_insert_with(|| tag.clone());
+                }
+                Err(e) => notifier.error(&tr(Msg::InterruptRollbackFailed, &[("error", &e)])),
+            }
+        }
+    }
+    Some(PublishOutcome::Aborted { removed_tag })
+}
+
+/// Run the scripts for a hook in order: the configured ones, then any in
+/// `.gitpublish/hooks/`, recording each outcome in `runs`. With `echo`, each
+/// script's command line is reported before it runs.
+///
+/// A failing script with the abort policy (the default for pre-tag-create and
+/// post-tag-create) stops the workflow after reporting the hook summary.
+fn run_hook(
+    notifier: &dyn Notifier,
+    hooks: &HooksConfig,
+    repo_root: Option<&Path>,
+    hook_type: HookType,
+    context: &HookContext,
+    echo: bool,
+    runs: &mut Vec<HookRun>,
+) -> Result<()> {
+    let context = HookContext {
+        hook_type,
+        ..context.clone()
+    };
+
+    for command in resolve_scripts(hooks, repo_root, hook_type) {
+        notifier.status(&tr(
+            Msg::RunningHook,
+            &[("hook", &hook_type.name()), ("script", &command.script)],
+        ));
+        if echo {
+            notifier.message(&format!(
+                "  {}",
+                tr(
+                    Msg::HookCommandLine,
+                    &[("command", &HookExecutor::command_line(&command))]
+                )
+            ));
+        }
+        let run = HookExecutor::run(&command, &context);
+        let mut failure = None;
+        match &run.error {
+            None => notifier.success(&tr(Msg::HookSucceeded, &[("hook", &hook_type.name())])),
+            Some(error) if !run.aborts() => notifier.warning(&tr(
+                Msg::HookWarning,
+                &[("hook", &hook_type.name()), ("error", error)],
+            )),
+            Some(error) => {
+                let message = tr(
+                    Msg::HookFailed,
+                    &[("hook", &hook_type.name()), ("error", error)],
+                );
+                notifier.error(&message);
+                failure = Some(message);
+            }
+        }
+        if !run.succeeded() {
+            for line in run.output.lines() {
+                notifier.message(&format!("    {}", line));
+            }
+        }
+
+        runs.push(run);
+        if let Some(message) = failure {
+            notifier.hook_summary(runs);
+            return Err(ReportedFailure(message).into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    use serial_test::serial;
+
+    use crate::config::RemoteOverride;
+    use crate::domain::commit::NonConformingCommit;
+    use crate::git_ops::TagDetails;
+
+    /// [`super::publish`], with a failed run as its error
+    fn publish(
+        repo: &dyn Repository,
+        prompter: &dyn Prompter,
+        notifier: &dyn Notifier,
+        cfg: &Config,
+        args: &PublishWorkflowArgs,
+    ) -> Result<PublishOutcome> {
+        super::publish(repo, prompter, notifier, cfg, args).into_outcome()
+    }
+
+    /// [`super::publish_with_cancellation`], with a failed run as its error
+    fn publish_with_cancellation(
+        repo: &dyn Repository,
+        prompter: &dyn Prompter,
+        notifier: &dyn Notifier,
+        cfg: &Config,
+        args: &PublishWorkflowArgs,
+        token: &CancellationToken,
+    ) -> Result<PublishOutcome> {
+        super::publish_with_cancellation(repo, prompter, notifier, cfg, args, token).into_outcome()
+    }
+
+    /// Repository with a `main` branch tagged `v1.2.0` and a feature commit on top
+    struct FakeRepo {
+        git_dir: PathBuf,
+        tags: RefCell<Vec<String>>,
+        pushed: RefCell<Vec<String>>,
+        /// Cancelled as soon as a tag is written
+        cancel_on_tag: Option<CancellationToken>,
+        /// Messages of annotated tags
+        tag_messages: RefCell<HashMap<String, String>>,
+        /// The branch history was rewritten after the latest tag
+        rewritten: bool,
+        /// Messages of the commits since the latest tag, oldest first
+        commits: Vec<String>,
+        /// Messages of the commits up to the latest tag, oldest first; only
+        /// walked without a tag
+        released: Vec<String>,
+        /// How many of the oldest `commits` are also on the `release` branch
+        shared_with_release: usize,
+        /// `commits` cherry-picked from the `maintenance` branch, tagged `v1.1.5`
+        cherry_picked: Vec<String>,
+        /// Working tree; none by default, like a bare repository
+        workdir: Option<PathBuf>,
+        /// Messages of the commits made on the branch
+        release_commits: RefCell<Vec<String>>,
+        /// Files of the commits made on the branch
+        release_paths: RefCell<Vec<PathBuf>>,
+        /// Commits on the branch fail
+        release_commit_fails: bool,
+        /// Short hashes of the commits without an author
+        anonymous: Vec<String>,
+        /// `user.name` and `user.email` are set
+        identity: bool,
+        /// Latest tag of `main`
+        latest_tag: RefCell<String>,
+        /// Freshly initialized: `main` has no commit yet
+        unborn: bool,
+        /// Branches updated by each fetch, the fetched branch first; none for
+        /// a fetch leaving the branches alone
+        fetches: RefCell<Vec<Vec<String>>>,
+        /// Tags on the remote
+        remote_tags: Vec<String>,
+    }
+
+    impl FakeRepo {
+        fn new() -> Self {
+            FakeRepo {
+                git_dir: PathBuf::from("/nonexistent/.git"),
+                tags: RefCell::new(vec!["v1.2.0".to_string()]),
+                pushed: RefCell::new(Vec::new()),
+                cancel_on_tag: None,
+                tag_messages: RefCell::new(HashMap::new()),
+                rewritten: false,
+                commits: vec!["feat: add export (#12)".to_string()],
+                released: Vec::new(),
+                shared_with_release: 0,
+                cherry_picked: Vec::new(),
+                workdir: None,
+                release_commits: RefCell::new(Vec::new()),
+                release_paths: RefCell::new(Vec::new()),
+                release_commit_fails: false,
+                anonymous: Vec::new(),
+                identity: true,
+                latest_tag: RefCell::new("v1.2.0".to_string()),
+                unborn: false,
+                fetches: RefCell::new(Vec::new()),
+                remote_tags: Vec::new(),
+            }
+        }
+    }
+
+    impl Repository for FakeRepo {
+        fn list_branches(&self) -> Result<Vec<String>> {
+            if self.unborn {
+                return Ok(Vec::new());
+            }
+            Ok(vec!["main".to_string()])
+        }
+
+        fn unborn_branch(&self) -> Option<String> {
+            self.unborn.then(|| "main".to_string())
+        }
+
+        fn list_remotes(&self) -> Result<Vec<String>> {
+            Ok(vec!["origin".to_string()])
+        }
+
+        fn remote_url(&self, _: &str) -> Result<Option<String>> {
+            Ok(Some("git@github.com:team/app.git".to_string()))
+        }
+
+        fn remote_exists(&self, remote_name: &str) -> Result<bool> {
+            Ok(remote_name == "origin")
+        }
+
+        fn check_remote_connection(&self, _: &str, _: Option<&RemoteOverride>) -> Result<()> {
+            Ok(())
+        }
+
+        fn remote_tags(&self, _: &str, _: Option<&RemoteOverride>) -> Result<Vec<String>> {
+            Ok(self.remote_tags.clone())
+        }
+
+        fn fetch_from_remote(&self, _: &str, _: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn fetch_remote_refs(&self, _: &str) -> Result<()> {
+            self.fetches.borrow_mut().push(Vec::new());
+            Ok(())
+        }
+
+        fn fetch_from_remote_with_branches(
+            &self,
+            _: &str,
+            branch_name: &str,
+            other_branches: &[String],
+        ) -> Result<()> {
+            let mut branches = vec![branch_name.to_string()];
+            branches.extend_from_slice(other_branches);
+            self.fetches.borrow_mut().push(branches);
+            Ok(())
+        }
+
+        fn get_latest_tag_on_branch_with_remote(
+            &self,
+            branch_name: &str,
+            _: Option<&str>,
+            _: Option<&str>,
+        ) -> Result<Option<String>> {
+            match branch_name {
+                "maintenance" => Ok(Some("v1.1.5".to_string())),
+                _ => Ok(Some(self.latest_tag.borrow().clone())),
+            }
+        }
+
+        fn commit_messages_since_tag(&self, _: &str, tag: Option<&str>) -> Result<Vec<String>> {
+            match tag {
+                Some(_) => Ok(self.commits.clone()),
+                None => Ok([self.released.clone(), self.commits.clone()].concat()),
+            }
+        }
+
+        /// Revisions are commit messages here
+        fn resolve_commit(&self, revision: &str) -> Result<String> {
+            Ok(revision.to_string())
+        }
+
+        fn merge_base(&self, _: &str, other: &str) -> Result<String> {
+            match other {
+                "release" => Ok("fedcba9876543210".to_string()),
+                _ => Err(anyhow::anyhow!("Branch '{}' not found", other)),
+            }
+        }
+
+        fn visit_commit_messages_since_base(
+            &self,
+            branch_name: &str,
+            tag: Option<&str>,
+            base: &str,
+            visit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+        ) -> Result<()> {
+            let history = self.commit_messages_since_tag(branch_name, tag)?;
+            // The merge base with `release`, otherwise the commit with that message
+            let start = match base {
+                "fedcba9876543210" => history.len() - self.commits.len() + self.shared_with_release,
+                _ => history
+                    .iter()
+                    .position(|message| message == base)
+                    .map_or(0, |index| index + 1),
+            };
+            for message in history[start..].iter().rev() {
+                if visit(message).is_break() {
+                    break;
+                }
+            }
+            Ok(())
+        }
+
+        fn released_cherry_picks(
+            &self,
+            _: &str,
+            _: Option<&str>,
+            released_tag: &str,
+        ) -> Result<Vec<String>> {
+            match released_tag {
+                "v1.1.5" => Ok(self.cherry_picked.clone()),
+                _ => Ok(Vec::new()),
+            }
+        }
+
+        fn is_tag_reachable(&self, _: &str, _: &str) -> Result<bool> {
+            Ok(!self.rewritten)
+        }
+
+        fn anonymous_commits(
+            &self,
+            _: &str,
+            _: Option<&str>,
+            _: Option<&str>,
+            _: usize,
+        ) -> Result<Vec<String>> {
+            Ok(self.anonymous.clone())
+        }
+
+        fn has_identity(&self) -> bool {
+            self.identity
+        }
+
+        fn get_current_head_hash(&self) -> Result<String> {
+            Ok("0123456789abcdef".to_string())
+        }
+
+        fn workdir(&self) -> Option<&Path> {
+            self.workdir.as_deref()
+        }
+
+        fn git_dir(&self) -> &Path {
+            &self.git_dir
+        }
+
+        fn tag_message(&self, tag_name: &str) -> Result<Option<String>> {
+            Ok(self.tag_messages.borrow().get(tag_name).cloned())
+        }
+
+        fn list_tags(&self, _: Option<&str>) -> Result<Vec<String>> {
+            Ok(self.tags.borrow().clone())
+        }
+
+        fn tag_exists(&self, tag_name: &str) -> bool {
+            self.tags.borrow().iter().any(|tag| tag == tag_name)
+        }
+
+        fn write_tag(
+            &self,
+            tag_name: &str,
+            _: &str,
+            _: bool,
+            annotation: Option<&TagAnnotation>,
+        ) -> Result<()> {
+            self.tags.borrow_mut().push(tag_name.to_string());
+            if let Some(annotation) = annotation {
+                self.tag_messages
+                    .borrow_mut()
+                    .insert(tag_name.to_string(), annotation.message.clone());
+            }
+            if let Some(token) = &self.cancel_on_tag {
+                token.cancel();
+            }
+            Ok(())
+        }
+
+        fn write_tag_on_tag(
+            &self,
+            tag_name: &str,
+            _: &str,
+            _: bool,
+            annotation: &TagAnnotation,
+        ) -> Result<()> {
+            self.tags.borrow_mut().push(tag_name.to_string());
+            self.tag_messages
+                .borrow_mut()
+                .insert(tag_name.to_string(), annotation.message.clone());
+            Ok(())
+        }
+
+        fn delete_tag(&self, tag_name: &str) -> Result<()> {
+            self.tags.borrow_mut().retain(|tag| tag != tag_name);
+            Ok(())
+        }
+
+        fn list_tag_details(&self, _: Option<&str>) -> Result<Vec<TagDetails>> {
+            Ok(self
+                .tags
+                .borrow()
+                .iter()
+                .map(|tag| TagDetails {
+                    name: tag.clone(),
+                    target: "0".repeat(40),
+                    time: 0,
+                    annotated: self.tag_messages.borrow().contains_key(tag),
+                })
+                .collect())
+        }
+
+        fn delete_remote_tag(
+            &self,
+            tag_name: &str,
+            _: &str,
+            _: Option<&RemoteOverride>,
+        ) -> Result<()> {
+            // Recorded as the refspec that deletes it
+            self.pushed.borrow_mut().push(format!(":{}", tag_name));
+            Ok(())
+        }
+
+        fn commit_files(&self, _: &str, paths: &[&Path], message: &str) -> Result<String> {
+            if self.release_commit_fails {
+                anyhow::bail!("index is locked");
+            }
+            self.release_commits.borrow_mut().push(message.to_string());
+            self.release_paths
+                .borrow_mut()
+                .extend(paths.iter().map(|path| path.to_path_buf()));
+            Ok("0123456789abcdef".to_string())
+        }
+
+        fn push_branch(&self, branch_name: &str, _: &str) -> Result<()> {
+            self.pushed.borrow_mut().push(branch_name.to_string());
+            Ok(())
+        }
+
+        fn push_tag_with_options(
+            &self,
+            tag_name: &str,
+            _: &str,
+            _: Option<&RemoteOverride>,
+            _: bool,
+        ) -> Result<()> {
+            self.pushed.borrow_mut().push(tag_name.to_string());
+            Ok(())
+        }
+    }
+
+    /// Prompter for forced and dry runs, where nothing may be asked
+    struct NoPrompts;
+
+    impl Prompter for NoPrompts {
+        fn select_branch(&self, _: &[String]) -> Result<String> {
+            unreachable!()
+        }
+
+        fn select_remote(&self, _: &[String]) -> Result<String> {
+            unreachable!()
+        }
+
+        fn confirm(&self, prompt: &str) -> Result<bool> {
+            unreachable!("unexpected prompt: {}", prompt)
+        }
+
+        fn select_version_source(
+            &self,
+            _: (&str, &Version),
+            _: (&str, &Version),
+            _: VersionSource,
+        ) -> Result<VersionSource> {
+            unreachable!()
+        }
+
+        fn select_tag_from_candidates(
+            &self,
+            _: &str,
+            _: &[String],
+            _: &[String],
+        ) -> Result<String> {
+            unreachable!()
+        }
+
+        fn select_or_customize_tag(&self, _: &str, _: &str, _: &[String]) -> Result<String> {
+            unreachable!()
+        }
+
+        fn confirm_major_version(&self, _: u32) -> Result<bool> {
+            unreachable!()
+        }
+
+        fn confirm_tag_use(&self, _: &str, _: &str) -> Result<bool> {
+            unreachable!()
+        }
+
+        fn confirm_push_tag(&self, _: &str, _: &str, _: bool) -> Result<bool> {
+            unreachable!()
+        }
+    }
+
+    /// Interactive user who types `tags` in turn (the last one again once
+    /// they run out) instead of the recommended tag, and answers every yes/no
+    /// question with `answer`
+    struct EditsTag {
+        tags: &'static [&'static str],
+        asked: std::cell::Cell<usize>,
+        answer: bool,
+        /// Defaults offered when asked to push
+        push_defaults: RefCell<Vec<bool>>,
+    }
+
+    impl EditsTag {
+        fn new(tags: &'static [&'static str], answer: bool) -> Self {
+            EditsTag {
+                tags,
+                asked: std::cell::Cell::new(0),
+                answer,
+                push_defaults: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn next_tag(&self) -> Result<String> {
+            let asked = self.asked.get();
+            self.asked.set(asked + 1);
+            Ok(self.tags[asked.min(self.tags.len() - 1)].to_string())
+        }
+    }
+
+    impl Prompter for EditsTag {
+        fn select_branch(&self, _: &[String]) -> Result<String> {
+            unreachable!()
+        }
+
+        fn select_remote(&self, _: &[String]) -> Result<String> {
+            unreachable!()
+        }
+
+        fn confirm(&self, _: &str) -> Result<bool> {
+            Ok(self.answer)
+        }
+
+        fn select_version_source(
+            &self,
+            _: (&str, &Version),
+            _: (&str, &Version),
+            _: VersionSource,
+        ) -> Result<VersionSource> {
+            unreachable!()
+        }
+
+        fn select_tag_from_candidates(
+            &self,
+            _: &str,
+            _: &[String],
+            _: &[String],
+        ) -> Result<String> {
+            self.next_tag()
+        }
+
+        fn select_or_customize_tag(&self, _: &str, _: &str, _: &[String]) -> Result<String> {
+            self.next_tag()
+        }
+
+        fn confirm_major_version(&self, _: u32) -> Result<bool> {
+            Ok(self.answer)
+        }
+
+        fn confirm_tag_use(&self, _: &str, _: &str) -> Result<bool> {
+            Ok(self.answer)
+        }
+
+        fn confirm_push_tag(&self, _: &str, _: &str, default: bool) -> Result<bool> {
+            self.push_defaults.borrow_mut().push(default);
+            Ok(self.answer)
+        }
+    }
+
+    /// Notifier that keeps the reported statuses, errors, warnings, decision
+    /// and the hook environment of a dry run
+    #[derive(Default)]
+    struct Recorder {
+        statuses: RefCell<Vec<String>>,
+        errors: RefCell<Vec<String>>,
+        warnings: RefCell<Vec<BoundaryWarning>>,
+        decision: RefCell<Option<ReleaseDecision>>,
+        hook_env: RefCell<Vec<(String, String)>>,
+        result: RefCell<Option<WorkflowResult>>,
+    }
+
+    impl Notifier for Recorder {
+        fn status(&self, message: &str) {
+            self.statuses.borrow_mut().push(message.to_string());
+        }
+        fn success(&self, _: &str) {}
+        fn warning(&self, _: &str) {}
+        fn error(&self, message: &str) {
+            self.errors.borrow_mut().push(message.to_string());
+        }
+        fn message(&self, _: &str) {}
+        fn finished(&self, _: &str) {}
+        fn boundary_warning(&self, warning: &BoundaryWarning) {
+            self.warnings.borrow_mut().push(warning.clone());
+        }
+        fn commit_analysis(&self, _: &[String], _: &str) {}
+        fn non_conforming_commits(&self, _: &[NonConformingCommit]) {}
+        fn proposed_tag(&self, _: Option<&str>, _: &str) {}
+        fn breaking_changes(&self, _: &[String]) {}
+        fn hook_dry_run(&self, _: &[(&str, Vec<String>)], env: &[(String, String)]) {
+            *self.hook_env.borrow_mut() = env.to_vec();
+        }
+        fn hook_summary(&self, _: &[HookRun]) {}
+        fn manual_push_instruction(&self, _: &str) {}
+        fn release_decision(&self, decision: &ReleaseDecision) {
+            *self.decision.borrow_mut() = Some(decision.clone());
+        }
+        fn workflow_result(&self, result: &WorkflowResult) {
+            *self.result.borrow_mut() = Some(result.clone());
+        }
+    }
+
+    fn config(extra: &str) -> Config {
+        toml::from_str(&format!("[branches]\nmain = \"v{{version}}\"\n{}", extra)).unwrap()
+    }
+
+    fn forced() -> PublishWorkflowArgs {
+        PublishWorkflowArgs {
+            remote: Some("origin".to_string()),
+            force: true,
+            ..PublishWorkflowArgs::default()
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_creates_and_pushes_tag() {
+        let repo = FakeRepo::new();
+        let outcome = publish(
+            &repo,
+            &NoPrompts,
+            &Recorder::default(),
+            &config(""),
+            &forced(),
+        );
+
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.3.0".to_string()
+            }
+        );
+        assert!(repo.tag_exists("v1.3.0"));
+        assert_eq!(*repo.pushed.borrow(), vec!["v1.3.0".to_string()]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_package() {
+        let repo = FakeRepo::new();
+        repo.tags.borrow_mut().push("api-v0.4.0".to_string());
+        *repo.latest_tag.borrow_mut() = "api-v0.4.0".to_string();
+        let cfg =
+            config("[packages]\napi = { path = \"services/api/**\", tag = \"api-v{version}\" }\n");
+        let args = PublishWorkflowArgs {
+            package: Some("api".to_string()),
+            ..forced()
+        };
+        let outcome = publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &args);
+
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "api-v0.5.0".to_string()
+            }
+        );
+
+        let unknown = PublishWorkflowArgs {
+            package: Some("web".to_string()),
+            ..forced()
+        };
+        let notifier = Recorder::default();
+        assert!(publish(&repo, &NoPrompts, &notifier, &cfg, &unknown).is_err());
+        assert!(notifier.errors.borrow()[0].contains("'web'"));
+    }
+
+    #[test]
+    fn test_publish_without_commits() {
+        let repo = FakeRepo {
+            unborn: true,
+            ..FakeRepo::new()
+        };
+        let notifier = Recorder::default();
+        let outcome = publish(&repo, &NoPrompts, &notifier, &config(""), &forced());
+
+        assert!(outcome.unwrap_err().is::<ReportedFailure>());
+        assert!(notifier.errors.borrow()[0].contains("no commits yet"));
+        assert!(repo.pushed.borrow().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_guards_against_version_downgrade() {
+        let args = PublishWorkflowArgs {
+            remote: Some("origin".to_string()),
+            ..PublishWorkflowArgs::default()
+        };
+
+        // Warned and declined
+        let repo = FakeRepo::new();
+        let recorder = Recorder::default();
+        let prompter = EditsTag::new(&["v1.1.9"], false);
+        let outcome = publish(&repo, &prompter, &recorder, &config(""), &args);
+        assert_eq!(outcome.unwrap(), PublishOutcome::Cancelled);
+        assert!(matches!(
+            recorder.warnings.borrow().last(),
+            Some(BoundaryWarning::VersionNotIncreased { current_version, .. })
+                if current_version == "1.2.0"
+        ));
+
+        // Blocked outright, even when the user would go on
+        let repo = FakeRepo::new();
+        let prompter = EditsTag::new(&["v1.1.9"], true);
+        let cfg = config("[behavior]\non_version_downgrade = \"block\"");
+        let outcome = publish(&repo, &prompter, &Recorder::default(), &cfg, &args);
+        assert!(outcome.is_err());
+        assert!(!repo.tag_exists("v1.1.9"));
+
+        // A higher custom tag is fine
+        let repo = FakeRepo::new();
+        let prompter = EditsTag::new(&["v1.10.0"], true);
+        let recorder = Recorder::default();
+        let outcome = publish(&repo, &prompter, &recorder, &cfg, &args);
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.10.0".to_string()
+            }
+        );
+        assert!(recorder.warnings.borrow().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_remembers_last_answers() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let args = PublishWorkflowArgs {
+            remote: Some("origin".to_string()),
+            ..PublishWorkflowArgs::default()
+        };
+
+        let repo = FakeRepo {
+            git_dir: dir.path().to_path_buf(),
+            ..FakeRepo::new()
+        };
+        let prompter = EditsTag::new(&["v1.3.0"], true);
+        publish(&repo, &prompter, &Recorder::default(), &config(""), &args).unwrap();
+        assert_eq!(*prompter.push_defaults.borrow(), vec![true]);
+        assert_eq!(
+            LastAnswers::load(dir.path()),
+            LastAnswers {
+                branch: Some("main".to_string()),
+                remote: Some("origin".to_string()),
+                push: Some(true),
+            }
+        );
+
+        // A declined push makes "no" the default next time
+        let prompter = EditsTag::new(&["v1.3.0"], true);
+        let repo = FakeRepo {
+            git_dir: dir.path().to_path_buf(),
+            ..FakeRepo::new()
+        };
+        LastAnswers {
+            push: Some(false),
+            ..LastAnswers::load(dir.path())
+        }
+        .save(dir.path())
+        .unwrap();
+        publish(&repo, &prompter, &Recorder::default(), &config(""), &args).unwrap();
+        assert_eq!(*prompter.push_defaults.borrow(), vec![false]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_asks_again_for_invalid_tag() {
+        let args = PublishWorkflowArgs {
+            remote: Some("origin".to_string()),
+            ..PublishWorkflowArgs::default()
+        };
+        let cfg = config("[behavior]\ntag_validation_regex = \"^.{1,6}$\"");
+
+        let repo = FakeRepo::new();
+        let recorder = Recorder::default();
+        let prompter = EditsTag::new(&["", "1.4.0", "v10.0.0", "v1.4.0"], true);
+        let outcome = publish(&repo, &prompter, &recorder, &cfg, &args);
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.4.0".to_string()
+            }
+        );
+        assert_eq!(prompter.asked.get(), 4);
+
+        // A prompter that keeps answering the same gives up eventually
+        let repo = FakeRepo::new();
+        let recorder = Recorder::default();
+        let prompter = EditsTag::new(&["v10.0.0"], true);
+        assert!(publish(&repo, &prompter, &recorder, &cfg, &args).is_err());
+        assert_eq!(prompter.asked.get(), MAX_TAG_ATTEMPTS);
+        assert!(recorder.errors.borrow()[0].contains("tag_validation_regex"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_enforces_tag_validation_regex() {
+        let repo = FakeRepo::new();
+        let recorder = Recorder::default();
+        let cfg = config("[behavior]\ntag_validation_regex = \"^release-\"");
+        let outcome = publish(&repo, &NoPrompts, &recorder, &cfg, &forced());
+
+        assert!(outcome.is_err());
+        assert!(recorder.errors.borrow()[0].contains("tag_validation_regex"));
+        assert!(!repo.tag_exists("v1.3.0"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_also_tags_other_environments() {
+        let repo = FakeRepo::new();
+        let args = PublishWorkflowArgs {
+            branch: Some("main".to_string()),
+            also_tag: vec!["gray".to_string()],
+            ..forced()
+        };
+        let outcome = publish(
+            &repo,
+            &NoPrompts,
+            &Recorder::default(),
+            &config("gray = \"g{version}\""),
+            &args,
+        );
+
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.3.0".to_string()
+            }
+        );
+        assert!(repo.tag_exists("g1.3.0"));
+        assert_eq!(
+            *repo.pushed.borrow(),
+            vec!["v1.3.0".to_string(), "g1.3.0".to_string()]
+        );
+
+        // Only configured branches lend their pattern
+        let repo = FakeRepo::new();
+        let recorder = Recorder::default();
+        let args = PublishWorkflowArgs {
+            also_tag: vec!["staging".to_string()],
+            ..args
+        };
+        assert!(publish(&repo, &NoPrompts, &recorder, &config(""), &args).is_err());
+        assert!(recorder.errors.borrow()[0].contains("staging"));
+        assert!(!repo.tag_exists("v1.3.0"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_appends_step_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.md");
+        let args = PublishWorkflowArgs {
+            step_summary: Some(path.clone()),
+            ..forced()
+        };
+        publish(
+            &FakeRepo::new(),
+            &NoPrompts,
+            &Recorder::default(),
+            &config(""),
+            &args,
+        )
+        .unwrap();
+
+        let summary = std::fs::read_to_string(&path).unwrap();
+        assert!(summary.starts_with("## Released `v1.3.0`\n"));
+        assert!(summary.contains(
+            "| Previous tag | [`v1.2.0`](https://github.com/team/app/compare/v1.2.0...v1.3.0) |"
+        ));
+        assert!(summary.contains("| Bump | minor |"));
+        assert!(summary.contains("- add export ([#12](https://github.com/team/app/pull/12))\n"));
+
+        // Without links the reference stays as text
+        let cfg = config("[changelog]\npull_request_links = false\n");
+        std::fs::remove_file(&path).unwrap();
+        publish(
+            &FakeRepo::new(),
+            &NoPrompts,
+            &Recorder::default(),
+            &cfg,
+            &args,
+        )
+        .unwrap();
+        let summary = std::fs::read_to_string(&path).unwrap();
+        assert!(summary.contains("- add export (#12)\n"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_annotates_cherry_picks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.md");
+        let args = PublishWorkflowArgs {
+            branch: Some("main".to_string()),
+            step_summary: Some(path.clone()),
+            ..forced()
+        };
+        let repo = || FakeRepo {
+            commits: vec![
+                "fix: close files".to_string(),
+                "feat: add export".to_string(),
+            ],
+            cherry_picked: vec!["fix: close files".to_string()],
+            ..FakeRepo::new()
+        };
+        let branches = "maintenance = \"v{version}\"\n";
+
+        let cfg = config(&format!(
+            "{}[changelog]\ncherry_picks = \"annotate\"\n",
+            branches
+        ));
+        publish(&repo(), &NoPrompts, &Recorder::default(), &cfg, &args).unwrap();
+        let summary = std::fs::read_to_string(&path).unwrap();
+        assert!(summary.contains("- close files (also released in v1.1.5)\n"));
+
+        let cfg = config(&format!(
+            "{}[changelog]\ncherry_picks = \"exclude\"\n",
+            branches
+        ));
+        std::fs::remove_file(&path).unwrap();
+        publish(&repo(), &NoPrompts, &Recorder::default(), &cfg, &args).unwrap();
+        let summary = std::fs::read_to_string(&path).unwrap();
+        assert!(!summary.contains("close files"));
+        assert!(summary.contains("- add export\n"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_updates_changelog_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("CHANGELOG.md");
+        std::fs::write(&path, "# Changelog\n\n## v1.2.0 (2024-01-02)\n\n- first\n").unwrap();
+        let repo = FakeRepo {
+            workdir: Some(dir.path().to_path_buf()),
+            ..FakeRepo::new()
+        };
+        let cfg =
+            config("[changelog]\nfile = \"CHANGELOG.md\"\nrelease_header = \"## {version}\"\n");
+        publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &forced()).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "# Changelog\n\n## 1.3.0\n\n### Features\n\n\
+             - add export ([#12](https://github.com/team/app/pull/12))\n\n\
+             ## v1.2.0 (2024-01-02)\n\n- first\n"
+        );
+        assert_eq!(
+            *repo.release_commits.borrow(),
+            vec!["chore(release): v1.3.0"]
+        );
+        // The release commit is pushed before the tag
+        assert_eq!(*repo.pushed.borrow(), vec!["main", "v1.3.0"]);
+
+        // --no-changelog wins over the configuration
+        let repo = FakeRepo {
+            workdir: Some(dir.path().to_path_buf()),
+            ..FakeRepo::new()
+        };
+        let args = PublishWorkflowArgs {
+            changelog: Some(false),
+            ..forced()
+        };
+        publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &args).unwrap();
+        assert!(repo.release_commits.borrow().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_commits_manifest_and_lock_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        let lock = dir.path().join("Cargo.lock");
+        let manifest_content = "[package]\nname = \"app\"\nversion = \"1.2.0\"\n";
+        let lock_content = "version = 3\n\n[[package]]\nname = \"app\"\nversion = \"1.2.0\"\n\n\
+                            [[package]]\nname = \"serde\"\nversion = \"1.2.0\"\n\
+                            source = \"registry+https://github.com/rust-lang/crates.io-index\"\n";
+        std::fs::write(&manifest, manifest_content).unwrap();
+        std::fs::write(&lock, lock_content).unwrap();
+        let cfg = config("[behavior]\nversion_source = \"manifest\"\n");
+
+        // A failed release commit leaves the files as they were
+        let repo = FakeRepo {
+            workdir: Some(dir.path().to_path_buf()),
+            release_commit_fails: true,
+            ..FakeRepo::new()
+        };
+        let recorder = Recorder::default();
+        assert!(publish(&repo, &NoPrompts, &recorder, &cfg, &forced()).is_err());
+        assert!(recorder.errors.borrow()[0].contains("Cargo.toml, Cargo.lock"));
+        assert_eq!(
+            std::fs::read_to_string(&manifest).unwrap(),
+            manifest_content
+        );
+        assert_eq!(std::fs::read_to_string(&lock).unwrap(), lock_content);
+        assert!(repo.tags.borrow().iter().all(|tag| tag != "v1.3.0"));
+
+        let repo = FakeRepo {
+            workdir: Some(dir.path().to_path_buf()),
+            ..FakeRepo::new()
+        };
+        publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &forced()).unwrap();
+        assert_eq!(
+            *repo.release_paths.borrow(),
+            vec![manifest.clone(), lock.clone()]
+        );
+        assert_eq!(
+            std::fs::read_to_string(&manifest).unwrap(),
+            "[package]\nname = \"app\"\nversion = \"1.3.0\"\n"
+        );
+        // Only the package's own entry follows the manifest
+        let lock_content = std::fs::read_to_string(&lock).unwrap();
+        assert!(lock_content.contains("name = \"app\"\nversion = \"1.3.0\""));
+        assert!(lock_content.contains("name = \"serde\"\nversion = \"1.2.0\""));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_returns_and_renders_its_result() {
+        let recorder = Recorder::default();
+        let result = super::publish(
+            &FakeRepo::new(),
+            &NoPrompts,
+            &recorder,
+            &config(""),
+            &forced(),
+        );
+        assert_eq!(result.status, "published");
+        assert!(result.pushed);
+        assert_eq!(
+            result.release.as_ref().map(|release| release.tag.as_str()),
+            Some("v1.3.0")
+        );
+        assert_eq!(recorder.result.borrow().as_ref(), Some(&result));
+
+        let recorder = Recorder::default();
+        let args = PublishWorkflowArgs {
+            branch: Some("dev".to_string()),
+            ..forced()
+        };
+        let result = super::publish(&FakeRepo::new(), &NoPrompts, &recorder, &config(""), &args);
+        assert!(result.is_failure());
+        assert_eq!(result.status, "failed");
+        assert_eq!(result.error.as_ref(), recorder.errors.borrow().last());
+        assert_eq!(recorder.result.borrow().as_ref(), Some(&result));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_annotates_tags() {
+        let repo = FakeRepo::new();
+        let cfg = config("[tags]\nannotated = true\n");
+        publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &forced()).unwrap();
+
+        assert_eq!(
+            repo.tag_messages.borrow().get("v1.3.0").map(String::as_str),
+            Some("Release v1.3.0\n\nminor bump, 1 commit since v1.2.0\n\nVersion: 1.3.0\n")
+        );
+
+        // Lightweight by default
+        let repo = FakeRepo::new();
+        publish(
+            &repo,
+            &NoPrompts,
+            &Recorder::default(),
+            &config(""),
+            &forced(),
+        )
+        .unwrap();
+        assert!(repo.tag_messages.borrow().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_warns_about_missing_identities() {
+        let repo = FakeRepo {
+            anonymous: vec!["a1b2c3d".to_string()],
+            identity: false,
+            ..FakeRepo::new()
+        };
+        let notifier = Recorder::default();
+        let cfg = config("[tags]\nannotated = true\n");
+        publish(&repo, &NoPrompts, &notifier, &cfg, &forced()).unwrap();
+
+        assert_eq!(
+            *notifier.warnings.borrow(),
+            vec![
+                BoundaryWarning::AnonymousCommits {
+                    count: 1,
+                    commits: vec!["a1b2c3d".to_string()],
+                },
+                BoundaryWarning::MissingGitIdentity,
+            ]
+        );
+        // Tagged without a tagger
+        assert!(repo.tags.borrow().contains(&"v1.3.0".to_string()));
+        assert!(repo.tag_messages.borrow().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_reads_previous_tag_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.md");
+        let args = PublishWorkflowArgs {
+            step_summary: Some(path.clone()),
+            ..forced()
+        };
+        let repo = FakeRepo::new();
+        repo.tag_messages.borrow_mut().insert(
+            "v1.2.0".to_string(),
+            "Release 1.1.9\n\nBREAKING CHANGE: new config format".to_string(),
+        );
+        let notifier = Recorder::default();
+        let cfg = config("[behavior]\nread_tag_metadata = true\n");
+        publish(&repo, &NoPrompts, &notifier, &cfg, &args).unwrap();
+
+        assert_eq!(
+            *notifier.warnings.borrow(),
+            vec![BoundaryWarning::TagMetadataMismatch {
+                tag: "v1.2.0".to_string(),
+                tag_version: "1.2.0".to_string(),
+                recorded_version: "1.1.9".to_string(),
+            }]
+        );
+        let summary = std::fs::read_to_string(&path).unwrap();
+        assert!(summary.contains(
+            "<details><summary>Previous release notes (<code>v1.2.0</code>)</summary>\n\n\
+             Release 1.1.9\n\nBREAKING CHANGE: new config format\n\n</details>\n"
+        ));
+
+        // Off by default
+        let notifier = Recorder::default();
+        publish(
+            &FakeRepo::new(),
+            &NoPrompts,
+            &notifier,
+            &config(""),
+            &forced(),
+        )
+        .unwrap();
+        assert!(notifier.warnings.borrow().is_empty());
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_warns_about_unreachable_tag() {
+        let repo = FakeRepo {
+            rewritten: true,
+            ..FakeRepo::new()
+        };
+        let notifier = Recorder::default();
+        let outcome = publish(&repo, &NoPrompts, &notifier, &config(""), &forced());
+
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.3.0".to_string()
+            }
+        );
+        assert_eq!(
+            *notifier.warnings.borrow(),
+            vec![BoundaryWarning::TagUnreachable {
+                tag: "v1.2.0".to_string(),
+                branch: "main".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_fails_on_fatal_warning() {
+        let repo = FakeRepo {
+            rewritten: true,
+            ..FakeRepo::new()
+        };
+        let notifier = Recorder::default();
+        let cfg = config("[warnings]\ntag_unreachable = \"fail\"\n");
+        // --force doesn't get past a fatal warning
+        let outcome = publish(&repo, &NoPrompts, &notifier, &cfg, &forced());
+
+        assert!(outcome.is_err());
+        assert_eq!(notifier.warnings.borrow().len(), 1);
+        assert_eq!(notifier.errors.borrow().len(), 1);
+        assert!(notifier.errors.borrow()[0].contains("warnings.tag_unreachable"));
+        assert!(repo.tags.borrow().iter().all(|tag| tag == "v1.2.0"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_caps_analyzed_commits() {
+        let repo = FakeRepo {
+            commits: vec![
+                "feat!: drop the v1 API".to_string(),
+                "fix: close files".to_string(),
+                "fix: handle timeouts".to_string(),
+            ],
+            ..FakeRepo::new()
+        };
+        let notifier = Recorder::default();
+        let cfg = config("[behavior]\nmax_commits_analyzed = 2\n");
+        let outcome = publish(&repo, &NoPrompts, &notifier, &cfg, &forced());
+
+        // The breaking change is older than the two analyzed commits
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.2.1".to_string()
+            }
+        );
+        assert_eq!(
+            *notifier.warnings.borrow(),
+            vec![BoundaryWarning::CommitLimitExceeded { count: 3, limit: 2 }]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_hooks_get_the_commit_count_beyond_the_analyzed_commits() {
+        let repo = FakeRepo {
+            commits: vec![
+                "fix: close files".to_string(),
+                "fix: handle timeouts".to_string(),
+                "fix: retry pushes".to_string(),
+            ],
+            ..FakeRepo::new()
+        };
+        let notifier = Recorder::default();
+        let cfg = config("[behavior]\nmax_commits_analyzed = 2\n");
+        let args = PublishWorkflowArgs {
+            dry_run: true,
+            ..forced()
+        };
+        publish(&repo, &NoPrompts, &notifier, &cfg, &args).unwrap();
+
+        assert!(notifier
+            .hook_env
+            .borrow()
+            .contains(&("GITPUBLISH_COMMIT_COUNT".to_string(), "3".to_string())));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_analyzes_commits_since_merge_base() {
+        let repo = FakeRepo {
+            commits: vec![
+                "feat!: drop the v1 API".to_string(),
+                "fix: close files".to_string(),
+            ],
+            shared_with_release: 1,
+            ..FakeRepo::new()
+        };
+        let args = PublishWorkflowArgs {
+            merge_base: Some("release".to_string()),
+            ..forced()
+        };
+        let outcome = publish(&repo, &NoPrompts, &Recorder::default(), &config(""), &args);
+        // The breaking change is also on the release branch
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.2.1".to_string()
+            }
+        );
+
+        let args = PublishWorkflowArgs {
+            merge_base: Some("nowhere".to_string()),
+            ..forced()
+        };
+        let recorder = Recorder::default();
+        let outcome = publish(&FakeRepo::new(), &NoPrompts, &recorder, &config(""), &args);
+        assert!(outcome.is_err());
+        assert!(recorder.errors.borrow()[0].contains("nowhere"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_from_tag_overrides_latest_tag() {
+        let repo = FakeRepo::new();
+        repo.tags.borrow_mut().push("v1.0.4".to_string());
+        let args = PublishWorkflowArgs {
+            from_tag: Some("v1.0.4".to_string()),
+            ..forced()
+        };
+        let outcome = publish(&repo, &NoPrompts, &Recorder::default(), &config(""), &args);
+        // Bumped from the given tag rather than v1.2.0
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.1.0".to_string()
+            }
+        );
+
+        let args = PublishWorkflowArgs {
+            from_tag: Some("v0.9.0".to_string()),
+            ..forced()
+        };
+        let recorder = Recorder::default();
+        let outcome = publish(&FakeRepo::new(), &NoPrompts, &recorder, &config(""), &args);
+        assert!(outcome.is_err());
+        assert!(recorder.errors.borrow()[0].contains("v0.9.0"));
+    }
+
+    #[test]
+    fn test_publish_since_reaches_past_the_latest_tag() {
+        let repo = FakeRepo {
+            released: vec![
+                "chore: initial commit".to_string(),
+                "feat: add search".to_string(),
+            ],
+            commits: vec!["fix: typo".to_string()],
+            ..FakeRepo::new()
+        };
+        let outcome = publish(
+            &repo,
+            &NoPrompts,
+            &Recorder::default(),
+            &config(""),
+            &forced(),
+        );
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.2.1".to_string()
+            }
+        );
+
+        // The feature before v1.2.0 counts, and v1.2.0 is still what gets bumped
+        let repo = FakeRepo {
+            tags: RefCell::new(vec!["v1.2.0".to_string()]),
+            ..repo
+        };
+        let args = PublishWorkflowArgs {
+            since: Some("chore: initial commit".to_string()),
+            ..forced()
+        };
+        let outcome = publish(&repo, &NoPrompts, &Recorder::default(), &config(""), &args);
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.3.0".to_string()
+            }
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_branches_goes_on_after_a_failure() {
+        let repo = FakeRepo::new();
+        let recorder = Recorder::default();
+        let cfg = config("develop = \"d{version}\"\n");
+        let branches = ["develop".to_string(), "main".to_string()];
+        let outcomes = publish_branches(&repo, &NoPrompts, &recorder, &cfg, &forced(), &branches);
+
+        // develop doesn't exist in the repository
+        assert_eq!(outcomes[0].branch, "develop");
+        assert!(outcomes[0].is_failure());
+        assert!(recorder.errors.borrow()[0].contains("develop"));
+        assert_eq!(outcomes[1].branch, "main");
+        assert_eq!(
+            outcomes[1].outcome.as_ref().unwrap(),
+            &PublishOutcome::Published {
+                tag: "v1.3.0".to_string()
+            }
+        );
+        assert_eq!(*repo.pushed.borrow(), vec!["v1.3.0".to_string()]);
+        // One fetch for the batch, updating every configured branch
+        assert_eq!(
+            *repo.fetches.borrow(),
+            vec![vec!["develop".to_string(), "main".to_string()]]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_fetches_other_configured_branches() {
+        let repo = FakeRepo::new();
+        let cfg = config("develop = \"d{version}\"\ngray = \"g{version}\"\n");
+        let args = PublishWorkflowArgs {
+            branch: Some("main".to_string()),
+            ..forced()
+        };
+        publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &args).unwrap();
+        assert_eq!(
+            *repo.fetches.borrow(),
+            vec![vec![
+                "main".to_string(),
+                "develop".to_string(),
+                "gray".to_string()
+            ]]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_dry_run_fetch_leaves_local_branches_alone() {
+        let repo = FakeRepo::new();
+        let cfg = config("develop = \"d{version}\"\n");
+        let args = PublishWorkflowArgs {
+            branch: Some("main".to_string()),
+            dry_run: true,
+            ..forced()
+        };
+        publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &args).unwrap();
+        assert_eq!(*repo.fetches.borrow(), vec![Vec::<String>::new()]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_tag_only_then_push_only() {
+        let repo = FakeRepo::new();
+        let tag_only = PublishWorkflowArgs {
+            tag_only: true,
+            ..forced()
+        };
+        let outcome = publish(
+            &repo,
+            &NoPrompts,
+            &Recorder::default(),
+            &config(""),
+            &tag_only,
+        );
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::CreatedLocally {
+                tag: "v1.3.0".to_string()
+            }
+        );
+        assert!(repo.pushed.borrow().is_empty());
+
+        // The result tells how to push the tag by hand, for `--json`
+        let result = super::publish(
+            &FakeRepo::new(),
+            &NoPrompts,
+            &Recorder::default(),
+            &config(""),
+            &tag_only,
+        );
+        assert_eq!(
+            result.manual_push_command.as_deref(),
+            Some("git push origin v1.3.0")
+        );
+
+        let push_only = |tag: &str| PublishWorkflowArgs {
+            push_only: Some(tag.to_string()),
+            ..forced()
+        };
+        let outcome = publish(
+            &repo,
+            &NoPrompts,
+            &Recorder::default(),
+            &config(""),
+            &push_only("v1.3.0"),
+        );
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.3.0".to_string()
+            }
+        );
+        assert_eq!(*repo.pushed.borrow(), vec!["v1.3.0".to_string()]);
+        // No new tag: nothing was analyzed or created
+        assert_eq!(repo.tags.borrow().len(), 2);
+
+        let notifier = Recorder::default();
+        let outcome = publish(
+            &repo,
+            &NoPrompts,
+            &notifier,
+            &config(""),
+            &push_only("v9.9.9"),
+        );
+        assert!(outcome.is_err());
+        assert!(notifier.errors.borrow()[0].contains("v9.9.9"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_bump_override() {
+        let tag = |bump| {
+            let args = PublishWorkflowArgs {
+                bump: Some(bump),
+                ..forced()
+            };
+            match publish(
+                &FakeRepo::new(),
+                &NoPrompts,
+                &Recorder::default(),
+                &config(""),
+                &args,
+            ) {
+                Ok(PublishOutcome::Published { tag }) => tag,
+                outcome => panic!("unexpected outcome {:?}", outcome),
+            }
+        };
+
+        // The commits call for a minor bump
+        assert_eq!(tag(BumpOverride::Release(VersionBump::Patch)), "v1.2.1");
+        // Asked for explicitly, so --accept-breaking isn't needed
+        assert_eq!(tag(BumpOverride::Release(VersionBump::Major)), "v2.0.0");
+        assert_eq!(tag(BumpOverride::PreRelease), "v1.2.1-alpha.1");
+        // A pre-release goes on with its own identifier
+        let beta = Version::parse("v1.3.0-beta.1").unwrap();
+        assert_eq!(prerelease_identifier(&beta, "alpha"), PreReleaseType::Beta);
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_prerelease_then_promote() {
+        let cfg = config("[prerelease]\nenabled = true\n");
+        let repo = FakeRepo::new();
+        let publish_with = |args: &PublishWorkflowArgs| match publish(
+            &repo,
+            &NoPrompts,
+            &Recorder::default(),
+            &cfg,
+            args,
+        ) {
+            Ok(PublishOutcome::Published { tag }) => {
+                *repo.latest_tag.borrow_mut() = tag.clone();
+                tag
+            }
+            outcome => panic!("unexpected outcome {:?}", outcome),
+        };
+        let beta = PublishWorkflowArgs {
+            prerelease: Some(PreReleaseType::Beta),
+            ..forced()
+        };
+
+        assert_eq!(publish_with(&beta), "v1.3.0-beta.1");
+        // The latest tag is a beta already: next iteration
+        assert_eq!(publish_with(&beta), "v1.3.0-beta.2");
+        let promote = PublishWorkflowArgs {
+            promote: true,
+            ..forced()
+        };
+        assert_eq!(publish_with(&promote), "v1.3.0");
+
+        // Nothing left to promote
+        let recorder = Recorder::default();
+        assert!(publish(&repo, &NoPrompts, &recorder, &cfg, &promote).is_err());
+        assert!(recorder.errors.borrow()[0].contains("v1.3.0"));
+
+        // Pre-releases must be enabled
+        let recorder = Recorder::default();
+        let outcome = publish(&FakeRepo::new(), &NoPrompts, &recorder, &config(""), &beta);
+        assert!(outcome.is_err());
+        assert!(recorder.errors.borrow()[0].contains("[prerelease] enabled"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_channel_commits_make_a_prerelease() {
+        let cfg = config("[channels]\nexperimental = \"beta\"\n");
+        let channel_commits = || {
+            vec![
+                "feat(experimental)!: new planner".to_string(),
+                "fix(experimental): typo".to_string(),
+            ]
+        };
+
+        // Only channel commits: a beta of the next major, without the breaking-change gate
+        let repo = FakeRepo {
+            commits: channel_commits(),
+            ..FakeRepo::new()
+        };
+        let outcome = publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &forced());
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v2.0.0-beta.1".to_string()
+            }
+        );
+
+        // A stable commit among them: a stable release, ignoring the channel bump
+        let mut commits = channel_commits();
+        commits.push("fix(io): close files".to_string());
+        let repo = FakeRepo {
+            commits,
+            ..FakeRepo::new()
+        };
+        let outcome = publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &forced());
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.2.1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_publish_reports_release_decision() {
+        let repo = FakeRepo::new();
+        let notifier = Recorder::default();
+        let args = PublishWorkflowArgs {
+            dry_run: true,
+            ..forced()
+        };
+        publish(&repo, &NoPrompts, &notifier, &config(""), &args).unwrap();
+
+        assert_eq!(
+            *notifier.decision.borrow(),
+            Some(ReleaseDecision {
+                branch: "main".to_string(),
+                remote: "origin".to_string(),
+                previous_tag: Some("v1.2.0".to_string()),
+                version: "1.3.0".to_string(),
+                bump: VersionBump::Minor,
+                commit_count: 1,
+                commits_analyzed: 1,
+                tag: "v1.3.0".to_string(),
+                tags: vec!["v1.3.0".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_publish_counts_the_commits_behind_a_breaking_change() {
+        let repo = FakeRepo {
+            commits: vec![
+                "fix: old fix".to_string(),
+                "feat!: drop the v1 API".to_string(),
+                "feat!: drop the v2 API".to_string(),
+                "fix: new fix".to_string(),
+            ],
+            ..FakeRepo::new()
+        };
+        let args = PublishWorkflowArgs {
+            dry_run: true,
+            accept_breaking: true,
+            ..forced()
+        };
+        let notifier = Recorder::default();
+        publish(&repo, &NoPrompts, &notifier, &config(""), &args).unwrap();
+        let decision = notifier.decision.borrow().clone().unwrap();
+        assert_eq!(decision.bump, VersionBump::Major);
+        // The bump is settled at the newest breaking change, but older
+        // commits are still counted
+        assert_eq!(decision.commit_count, 4);
+        assert_eq!(decision.commits_analyzed, 4);
+    }
+
+    #[test]
+    fn test_publish_dry_run_changes_nothing() {
+        let repo = FakeRepo::new();
+        let args = PublishWorkflowArgs {
+            force: false,
+            dry_run: true,
+            ..forced()
+        };
+        let outcome = publish(&repo, &NoPrompts, &Recorder::default(), &config(""), &args);
+
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::DryRun {
+                tag: "v1.3.0".to_string()
+            }
+        );
+        assert!(!repo.tag_exists("v1.3.0"));
+        assert!(repo.pushed.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_publish_skips_existing_tag() {
+        let repo = FakeRepo::new();
+        repo.tags.borrow_mut().push("v1.3.0".to_string());
+        let cfg = config("[behavior]\non_existing_tag = \"skip\"\n");
+        let outcome = publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &forced());
+
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Skipped {
+                tag: "v1.3.0".to_string()
+            }
+        );
+        assert!(repo.pushed.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_publish_sees_tags_only_on_the_remote() {
+        let repo = FakeRepo {
+            remote_tags: vec!["v1.3.0".to_string()],
+            ..FakeRepo::new()
+        };
+        let recorder = Recorder::default();
+        let outcome = publish(&repo, &NoPrompts, &recorder, &config(""), &forced());
+        assert!(outcome.is_err());
+        assert!(recorder.errors.borrow()[0].contains("v1.3.0"));
+
+        let cfg = config("[behavior]\non_existing_tag = \"increment\"\n");
+        let outcome = publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &forced());
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.3.1".to_string()
+            }
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_publish_replaces_tags_only_when_confirmed() {
+        let cfg = config("[behavior]\non_existing_tag = \"replace\"\n");
+        let existing = || FakeRepo {
+            remote_tags: vec!["v1.3.0".to_string()],
+            ..FakeRepo::new()
+        };
+
+        // --force can't confirm moving a published tag
+        let repo = existing();
+        let recorder = Recorder::default();
+        let outcome = publish(&repo, &NoPrompts, &recorder, &cfg, &forced());
+        assert!(outcome.is_err());
+        assert!(recorder.errors.borrow()[0].contains("--replace-tags"));
+        assert!(repo.pushed.borrow().is_empty());
+
+        let args = PublishWorkflowArgs {
+            replace_tags: true,
+            ..forced()
+        };
+        let repo = existing();
+        let outcome = publish(&repo, &NoPrompts, &Recorder::default(), &cfg, &args);
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Published {
+                tag: "v1.3.0".to_string()
+            }
+        );
+        assert_eq!(*repo.pushed.borrow(), ["v1.3.0"]);
+
+        // Asked, and declined
+        let args = PublishWorkflowArgs {
+            remote: Some("origin".to_string()),
+            ..PublishWorkflowArgs::default()
+        };
+        let repo = existing();
+        let prompter = EditsTag::new(&["v1.3.0"], false);
+        let outcome = publish(&repo, &prompter, &Recorder::default(), &cfg, &args);
+        assert_eq!(outcome.unwrap(), PublishOutcome::Cancelled);
+        assert!(!repo.tag_exists("v1.3.0"));
+    }
+
+    #[test]
+    fn test_publish_reports_unconfigured_branch() {
+        let notifier = Recorder::default();
+        let args = PublishWorkflowArgs {
+            branch: Some("release".to_string()),
+            ..forced()
+        };
+        let err = publish(&FakeRepo::new(), &NoPrompts, &notifier, &config(""), &args).unwrap_err();
+
+        assert!(err.is::<ReportedFailure>());
+        assert_eq!(notifier.errors.borrow().len(), 1);
+        assert!(notifier.errors.borrow()[0].contains("release"));
+    }
+
+    #[test]
+    fn test_cancelled_before_start_does_nothing() {
+        let repo = FakeRepo::new();
+        let token = CancellationToken::new();
+        token.cancel();
+        let outcome = publish_with_cancellation(
+            &repo,
+            &NoPrompts,
+            &Recorder::default(),
+            &config(""),
+            &forced(),
+            &token,
+        );
+
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Aborted { removed_tag: None }
+        );
+        assert_eq!(*repo.tags.borrow(), vec!["v1.2.0".to_string()]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_cancel_after_tagging_removes_unpushed_tag() {
+        let token = CancellationToken::new();
+        let repo = FakeRepo {
+            cancel_on_tag: Some(token.clone()),
+            ..FakeRepo::new()
+        };
+        let outcome = publish_with_cancellation(
+            &repo,
+            &NoPrompts,
+            &Recorder::default(),
+            &config(""),
+            &forced(),
+            &token,
+        );
+
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Aborted {
+                removed_tag: Some("v1.3.0".to_string())
+            }
+        );
+        assert!(!repo.tag_exists("v1.3.0"));
+        assert!(repo.pushed.borrow().is_empty());
+
+        // With keep_tag_on_interrupt the tag stays, still unpushed
+        let token = CancellationToken::new();
+        let repo = FakeRepo {
+            cancel_on_tag: Some(token.clone()),
+            ..FakeRepo::new()
+        };
+        let cfg = config("[behavior]\nkeep_tag_on_interrupt = true\n");
+        let outcome = publish_with_cancellation(
+            &repo,
+            &NoPrompts,
+            &Recorder::default(),
+            &cfg,
+            &forced(),
+            &token,
+        );
+
+        assert_eq!(
+            outcome.unwrap(),
+            PublishOutcome::Aborted { removed_tag: None }
+        );
+        assert!(repo.tag_exists("v1.3.0"));
+        assert!(repo.pushed.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_layer_tag_over_release() {
+        let repo = FakeRepo::new();
+        let args = LayerTagArgs {
+            target_tag: "v1.2.0".to_string(),
+            tag: "v1.2.0-signed".to_string(),
+            message: Some("Approved by QA".to_string()),
+            push: true,
+            ..LayerTagArgs::default()
+        };
+        layer_tag(&repo, &Recorder::default(), &config(""), &args).unwrap();
+
+        assert!(repo.tag_exists("v1.2.0-signed"));
+        assert_eq!(
+            repo.tag_message("v1.2.0-signed").unwrap().as_deref(),
+            Some("Approved by QA")
+        );
+        assert_eq!(*repo.pushed.borrow(), vec!["v1.2.0-signed"]);
+
+        // The new tag exists now, and the target must exist
+        let notifier = Recorder::default();
+        assert!(layer_tag(&repo, &notifier, &config(""), &args).is_err());
+        let missing = LayerTagArgs {
+            target_tag: "v9.9.9".to_string(),
+            ..args
+        };
+        assert!(layer_tag(&repo, &notifier, &config(""), &missing).is_err());
+        assert_eq!(notifier.errors.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_rollback_latest_tag() {
+        let repo = FakeRepo::new();
+        repo.tags.borrow_mut().push("v1.3.0".to_string());
+        let args = RollbackArgs {
+            yes: true,
+            ..RollbackArgs::default()
+        };
+        let notifier = Recorder::default();
+        let deleted = rollback(&repo, &notifier, &NoPrompts, &config(""), &args);
+
+        assert_eq!(deleted.unwrap().as_deref(), Some("v1.3.0"));
+        assert_eq!(*repo.tags.borrow(), vec!["v1.2.0"]);
+        assert_eq!(*repo.pushed.borrow(), vec![":v1.3.0"]);
+        // --yes skips the question, not the list of deleted refs
+        assert_eq!(
+            *notifier.statuses.borrow(),
+            vec![
+                "To delete: refs/tags/v1.3.0 on remote 'origin'",
+                "To delete: local refs/tags/v1.3.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rollback_older_tag_needs_force() {
+        let repo = FakeRepo::new();
+        repo.tags.borrow_mut().push("v1.3.0".to_string());
+        let args = RollbackArgs {
+            tag: Some("v1.2.0".to_string()),
+            scope: RollbackScope::LocalOnly,
+            ..RollbackArgs::default()
+        };
+        let notifier = Recorder::default();
+        assert!(rollback(&repo, &notifier, &NoPrompts, &config(""), &args).is_err());
+        assert_eq!(notifier.errors.borrow().len(), 1);
+
+        // Declining keeps the tag
+        let forced = RollbackArgs {
+            force: true,
+            ..args
+        };
+        let declined = rollback(
+            &repo,
+            &notifier,
+            &EditsTag::new(&[""], false),
+            &config(""),
+            &forced,
+        );
+        assert_eq!(declined.unwrap(), None);
+        assert!(repo.tag_exists("v1.2.0"));
+
+        let deleted = rollback(
+            &repo,
+            &notifier,
+            &EditsTag::new(&[""], true),
+            &config(""),
+            &forced,
+        );
+        assert_eq!(deleted.unwrap().as_deref(), Some("v1.2.0"));
+        assert!(!repo.tag_exists("v1.2.0"));
+        assert!(repo.pushed.borrow().is_empty());
+    }
+}
@@ -0,0 +1,558 @@
+//! The git operations the publish workflow depends on.
+//!
+//! [`GitRepo`] is the real implementation; tests and embedders can provide
+//! their own to run the workflow against something other than a local clone.
+
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::config::RemoteOverride;
+use crate::git_ops::{GitRepo, TagAnnotation, TagDetails, TagTarget};
+
+/// Repository access used by [`publish`](super::publish)
+///
+/// Methods mirror the ones on [`GitRepo`]; see there for details.
+pub trait Repository {
+    /// Local and remote-tracking branch names, without the remote prefix
+    fn list_branches(&self) -> Result<Vec<String>>;
+
+    /// The branch HEAD points at when it has no commit yet
+    fn unborn_branch(&self) -> Option<String> {
+        None
+    }
+
+    /// Configured remotes, `origin` first
+    fn list_remotes(&self) -> Result<Vec<String>>;
+
+    /// Whether a remote with this name is configured
+    fn remote_exists(&self, remote_name: &str) -> Result<bool>;
+
+    /// Fetch URL of the remote, with `insteadOf` rules applied
+    fn remote_url(&self, remote_name: &str) -> Result<Option<String>>;
+
+    /// Connect to the remote without transferring anything
+    fn check_remote_connection(
+        &self,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+    ) -> Result<()>;
+
+    /// Tags on the remote that tags are pushed to
+    ///
+    /// The default refuses: implementations that can list remote refs should
+    /// override it.
+    fn remote_tags(
+        &self,
+        remote_name: &str,
+        _remote_override: Option<&RemoteOverride>,
+    ) -> Result<Vec<String>> {
+        anyhow::bail!(
+            "Listing the tags of '{}' is not supported by this repository",
+            remote_name
+        )
+    }
+
+    /// Fetch the branch and tags from the remote
+    fn fetch_from_remote(&self, remote_name: &str, branch_name: &str) -> Result<()>;
+
+    /// Fetch the remote's branches and tags, leaving every local branch alone
+    fn fetch_remote_refs(&self, remote_name: &str) -> Result<()> {
+        anyhow::bail!(
+            "Fetching from '{}' without updating branches is not supported by this repository",
+            remote_name
+        )
+    }
+
+    /// Fetch like [`fetch_from_remote`](Self::fetch_from_remote), also
+    /// fast-forwarding the `other_branches` that exist locally
+    ///
+    /// The default only updates `branch_name`; implementations should bring
+    /// the other branches up to date from the same fetch.
+    fn fetch_from_remote_with_branches(
+        &self,
+        remote_name: &str,
+        branch_name: &str,
+        _other_branches: &[String],
+    ) -> Result<()> {
+        self.fetch_from_remote(remote_name, branch_name)
+    }
+
+    /// Latest tag matching the pattern on the branch (or its remote-tracking branch)
+    fn get_latest_tag_on_branch_with_remote(
+        &self,
+        branch_name: &str,
+        remote_name: Option<&str>,
+        tag_pattern: Option<&str>,
+    ) -> Result<Option<String>>;
+
+    /// Messages of the commits on the branch since the tag, oldest first
+    fn commit_messages_since_tag(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+    ) -> Result<Vec<String>>;
+
+    /// Pass the messages of the commits on the branch since the tag to
+    /// `visit`, newest first, until it returns `ControlFlow::Break`
+    ///
+    /// The default collects [`commit_messages_since_tag`](Self::commit_messages_since_tag)
+    /// first; implementations should stream instead when ranges can be large.
+    fn visit_commit_messages_since_tag(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        visit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> Result<()> {
+        for message in self
+            .commit_messages_since_tag(branch_name, tag_name)?
+            .iter()
+            .rev()
+        {
+            if visit(message).is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Hash of the best common ancestor of the branch and `other`
+    fn merge_base(&self, branch_name: &str, other: &str) -> Result<String>;
+
+    /// Full hash of the commit a revision (a SHA, `HEAD~2`, a tag...) points at
+    ///
+    /// The default refuses: implementations that can parse revisions should
+    /// override it.
+    fn resolve_commit(&self, revision: &str) -> Result<String> {
+        anyhow::bail!(
+            "Resolving the revision '{}' is not supported by this repository",
+            revision
+        )
+    }
+
+    /// Hash of the newest first-parent commit of the branch made before
+    /// `time` (seconds since the Unix epoch); `None` when there is none
+    ///
+    /// The default refuses: implementations that can read commit times
+    /// should override it.
+    fn last_commit_before(&self, branch_name: &str, _time: i64) -> Result<Option<String>> {
+        anyhow::bail!(
+            "Finding commits of '{}' by date is not supported by this repository",
+            branch_name
+        )
+    }
+
+    /// Like [`visit_commit_messages_since_tag`](Self::visit_commit_messages_since_tag),
+    /// also leaving out the commits reachable from the `base` commit
+    fn visit_commit_messages_since_base(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        base: &str,
+        visit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> Result<()>;
+
+    /// Messages of the commits on the branch since the tag whose change was
+    /// already released by `released_tag` from another branch
+    fn released_cherry_picks(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        released_tag: &str,
+    ) -> Result<Vec<String>>;
+
+    /// Short hashes of the commits on the branch since the tag (and not
+    /// reachable from `base`) without an author name or email, checking at
+    /// most `limit` commits (0 for all)
+    fn anonymous_commits(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        base: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<String>>;
+
+    /// Whether a git identity is configured for tagging
+    fn has_identity(&self) -> bool;
+
+    /// Whether the tag's commit is in the history of the branch
+    fn is_tag_reachable(&self, branch_name: &str, tag_name: &str) -> Result<bool>;
+
+    /// Hash of the current HEAD commit
+    fn get_current_head_hash(&self) -> Result<String>;
+
+    /// Files changed by the commit a revision points at, relative to the
+    /// repository root
+    ///
+    /// The default refuses: implementations that can diff commits should
+    /// override it.
+    fn changed_files(&self, revision: &str) -> Result<Vec<PathBuf>> {
+        anyhow::bail!(
+            "Listing the files changed by '{}' is not supported by this repository",
+            revision
+        )
+    }
+
+    /// Root of the working tree, or `None` for a bare repository
+    fn workdir(&self) -> Option<&Path>;
+
+    /// Path of the git directory
+    fn git_dir(&self) -> &Path;
+
+    /// Whether a local tag with this name exists
+    fn tag_exists(&self, tag_name: &str) -> bool;
+
+    /// Local tags matching the pattern, in no particular order
+    fn list_tags(&self, tag_pattern: Option<&str>) -> Result<Vec<String>>;
+
+    /// Local tags matching the pattern with their target commit, time and
+    /// kind, in no particular order
+    ///
+    /// The default refuses: implementations that can read tag objects
+    /// should override it.
+    fn list_tag_details(&self, _tag_pattern: Option<&str>) -> Result<Vec<TagDetails>> {
+        anyhow::bail!("Listing tag details is not supported by this repository")
+    }
+
+    /// Message of an annotated tag, `None` for a lightweight tag
+    fn tag_message(&self, tag_name: &str) -> Result<Option<String>>;
+
+    /// What the tag points at: a commit for a lightweight tag, a tag object
+    /// for an annotated one
+    ///
+    /// The default tells them apart by [`tag_message`](Self::tag_message);
+    /// implementations should look at the object itself.
+    fn tag_target(&self, tag_name: &str) -> Result<TagTarget> {
+        Ok(match self.tag_message(tag_name)? {
+            Some(_) => TagTarget::Tag,
+            None => TagTarget::Commit,
+        })
+    }
+
+    /// Create an annotated tag pointing at the existing `target_tag` (its tag
+    /// object, or its commit when it is lightweight), moving an existing tag
+    /// when `replace` is set
+    ///
+    /// The default refuses: implementations that can write tag objects
+    /// pointing at tags should override it.
+    fn write_tag_on_tag(
+        &self,
+        tag_name: &str,
+        target_tag: &str,
+        _replace: bool,
+        _annotation: &TagAnnotation,
+    ) -> Result<()> {
+        anyhow::bail!(
+            "Tagging the tag '{}' as '{}' is not supported by this repository",
+            target_tag,
+            tag_name
+        )
+    }
+
+    /// Tag the branch head, moving an existing tag when `replace` is set;
+    /// lightweight unless an annotation is given
+    fn write_tag(
+        &self,
+        tag_name: &str,
+        branch_name: &str,
+        replace: bool,
+        annotation: Option<&TagAnnotation>,
+    ) -> Result<()>;
+
+    /// Delete a local tag
+    fn delete_tag(&self, tag_name: &str) -> Result<()>;
+
+    /// Delete a tag on the remote
+    ///
+    /// The default refuses: implementations that can push should override it.
+    fn delete_remote_tag(
+        &self,
+        tag_name: &str,
+        remote_name: &str,
+        _remote_override: Option<&RemoteOverride>,
+    ) -> Result<()> {
+        anyhow::bail!(
+            "Deleting the tag '{}' on '{}' is not supported by this repository",
+            tag_name,
+            remote_name
+        )
+    }
+
+    /// Commit some files on top of the checked-out branch
+    fn commit_files(&self, branch_name: &str, paths: &[&Path], message: &str) -> Result<String>;
+
+    /// Push the branch to the remote branch of the same name
+    fn push_branch(&self, branch_name: &str, remote_name: &str) -> Result<()>;
+
+    /// Push a tag, force-updating the remote tag when `force` is set
+    fn push_tag_with_options(
+        &self,
+        tag_name: &str,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+        force: bool,
+    ) -> Result<()>;
+
+    /// Push several tags together
+    ///
+    /// The default pushes them one by one; implementations should use a single
+    /// push so the remote receives all of them or none.
+    fn push_tags_with_options(
+        &self,
+        tag_names: &[&str],
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+        force: bool,
+    ) -> Result<()> {
+        for tag_name in tag_names {
+            self.push_tag_with_options(tag_name, remote_name, remote_override, force)?;
+        }
+        Ok(())
+    }
+}
+
+impl Repository for GitRepo {
+    fn list_branches(&self) -> Result<Vec<String>> {
+        GitRepo::list_branches(self)
+    }
+
+    fn unborn_branch(&self) -> Option<String> {
+        GitRepo::unborn_branch(self)
+    }
+
+    fn list_remotes(&self) -> Result<Vec<String>> {
+        GitRepo::list_remotes(self)
+    }
+
+    fn remote_exists(&self, remote_name: &str) -> Result<bool> {
+        GitRepo::remote_exists(self, remote_name)
+    }
+
+    fn remote_url(&self, remote_name: &str) -> Result<Option<String>> {
+        GitRepo::remote_url(self, remote_name)
+    }
+
+    fn check_remote_connection(
+        &self,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+    ) -> Result<()> {
+        GitRepo::check_remote_connection(self, remote_name, remote_override)
+    }
+
+    fn remote_tags(
+        &self,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+    ) -> Result<Vec<String>> {
+        GitRepo::remote_tags(self, remote_name, remote_override)
+    }
+
+    fn fetch_from_remote(&self, remote_name: &str, branch_name: &str) -> Result<()> {
+        GitRepo::fetch_from_remote(self, remote_name, branch_name)
+    }
+
+    fn fetch_remote_refs(&self, remote_name: &str) -> Result<()> {
+        GitRepo::fetch_remote_refs(self, remote_name)
+    }
+
+    fn fetch_from_remote_with_branches(
+        &self,
+        remote_name: &str,
+        branch_name: &str,
+        other_branches: &[String],
+    ) -> Result<()> {
+        GitRepo::fetch_from_remote_with_branches(self, remote_name, branch_name, other_branches)
+    }
+
+    fn get_latest_tag_on_branch_with_remote(
+        &self,
+        branch_name: &str,
+        remote_name: Option<&str>,
+        tag_pattern: Option<&str>,
+    ) -> Result<Option<String>> {
+        GitRepo::get_latest_tag_on_branch_with_remote(self, branch_name, remote_name, tag_pattern)
+    }
+
+    fn commit_messages_since_tag(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+    ) -> Result<Vec<String>> {
+        Ok(self
+            .get_commits_since_tag(branch_name, tag_name)?
+            .iter()
+            .filter_map(|commit| commit.message().map(str::to_string))
+            .collect())
+    }
+
+    fn visit_commit_messages_since_tag(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        visit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> Result<()> {
+        self.walk_commits_since_tag(branch_name, tag_name, |commit| {
+            commit
+                .message()
+                .map_or(ControlFlow::Continue(()), &mut *visit)
+        })
+    }
+
+    fn merge_base(&self, branch_name: &str, other: &str) -> Result<String> {
+        GitRepo::merge_base(self, branch_name, other)
+    }
+
+    fn resolve_commit(&self, revision: &str) -> Result<String> {
+        GitRepo::resolve_commit(self, revision)
+    }
+
+    fn last_commit_before(&self, branch_name: &str, time: i64) -> Result<Option<String>> {
+        GitRepo::last_commit_before(self, branch_name, time)
+    }
+
+    fn visit_commit_messages_since_base(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        base: &str,
+        visit: &mut dyn FnMut(&str) -> ControlFlow<()>,
+    ) -> Result<()> {
+        self.walk_commits_since(branch_name, tag_name, Some(base), |commit| {
+            commit
+                .message()
+                .map_or(ControlFlow::Continue(()), &mut *visit)
+        })
+    }
+
+    fn released_cherry_picks(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        released_tag: &str,
+    ) -> Result<Vec<String>> {
+        GitRepo::released_cherry_picks(self, branch_name, tag_name, released_tag)
+    }
+
+    fn anonymous_commits(
+        &self,
+        branch_name: &str,
+        tag_name: Option<&str>,
+        base: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<String>> {
+        GitRepo::anonymous_commits(self, branch_name, tag_name, base, limit)
+    }
+
+    fn has_identity(&self) -> bool {
+        GitRepo::has_identity(self)
+    }
+
+    fn is_tag_reachable(&self, branch_name: &str, tag_name: &str) -> Result<bool> {
+        GitRepo::is_tag_reachable(self, branch_name, tag_name)
+    }
+
+    fn get_current_head_hash(&self) -> Result<String> {
+        GitRepo::get_current_head_hash(self)
+    }
+
+    fn changed_files(&self, revision: &str) -> Result<Vec<PathBuf>> {
+        GitRepo::changed_files(self, revision)
+    }
+
+    fn workdir(&self) -> Option<&Path> {
+        GitRepo::workdir(self)
+    }
+
+    fn git_dir(&self) -> &Path {
+        GitRepo::git_dir(self)
+    }
+
+    fn tag_exists(&self, tag_name: &str) -> bool {
+        GitRepo::tag_exists(self, tag_name)
+    }
+
+    fn tag_message(&self, tag_name: &str) -> Result<Option<String>> {
+        GitRepo::tag_message(self, tag_name)
+    }
+
+    fn list_tags(&self, tag_pattern: Option<&str>) -> Result<Vec<String>> {
+        GitRepo::list_tags(self, tag_pattern)
+    }
+
+    fn list_tag_details(&self, tag_pattern: Option<&str>) -> Result<Vec<TagDetails>> {
+        GitRepo::list_tag_details(self, tag_pattern)
+    }
+
+    fn write_tag(
+        &self,
+        tag_name: &str,
+        branch_name: &str,
+        replace: bool,
+        annotation: Option<&TagAnnotation>,
+    ) -> Result<()> {
+        match annotation {
+            Some(annotation) => {
+                self.create_annotated_tag(tag_name, Some(branch_name), annotation, replace)
+            }
+            None if replace => self.replace_tag(tag_name, Some(branch_name)),
+            None => self.create_tag(tag_name, Some(branch_name)),
+        }
+    }
+
+    fn tag_target(&self, tag_name: &str) -> Result<TagTarget> {
+        GitRepo::tag_target(self, tag_name)
+    }
+
+    fn write_tag_on_tag(
+        &self,
+        tag_name: &str,
+        target_tag: &str,
+        replace: bool,
+        annotation: &TagAnnotation,
+    ) -> Result<()> {
+        self.create_annotated_tag_on_tag(tag_name, target_tag, annotation, replace)
+    }
+
+    fn delete_tag(&self, tag_name: &str) -> Result<()> {
+        GitRepo::delete_tag(self, tag_name)
+    }
+
+    fn delete_remote_tag(
+        &self,
+        tag_name: &str,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+    ) -> Result<()> {
+        GitRepo::delete_remote_tag(self, tag_name, remote_name, remote_override)
+    }
+
+    fn commit_files(&self, branch_name: &str, paths: &[&Path], message: &str) -> Result<String> {
+        GitRepo::commit_files(self, branch_name, paths, message)
+    }
+
+    fn push_branch(&self, branch_name: &str, remote_name: &str) -> Result<()> {
+        GitRepo::push_branch(self, branch_name, remote_name)
+    }
+
+    fn push_tag_with_options(
+        &self,
+        tag_name: &str,
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+        force: bool,
+    ) -> Result<()> {
+        GitRepo::push_tag_with_options(self, tag_name, remote_name, remote_override, force)
+    }
+
+    fn push_tags_with_options(
+        &self,
+        tag_names: &[&str],
+        remote_name: &str,
+        remote_override: Option<&RemoteOverride>,
+        force: bool,
+    ) -> Result<()> {
+        GitRepo::push_tags_with_options(self, tag_names, remote_name, remote_override, force)
+    }
+}
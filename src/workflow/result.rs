@@ -0,0 +1,290 @@
+//! Everything a publish run did, in one serializable struct.
+//!
+//! [`WorkflowResult`] combines the [`PublishOutcome`] with what was reported
+//! on the way: the release decision, the hooks that ran and the warnings.
+//! [`publish`](super::publish) returns it and hands it to the notifier; it is
+//! what `--json` prints.
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::boundary::BoundaryWarning;
+use crate::domain::commit::NonConformingCommit;
+use crate::hooks::HookRun;
+use crate::workflow::{Notifier, PublishOutcome, ReleaseDecision, ReportedFailure};
+
+/// A warning raised during the run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkflowWarning {
+    /// Key of a boundary warning in the `[warnings]` table
+    pub key: Option<&'static str>,
+    pub message: String,
+}
+
+/// What happened during a publish run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkflowResult {
+    /// How the run ended; `None` when it failed
+    #[serde(skip)]
+    pub outcome: Option<PublishOutcome>,
+    /// `published`, `queued`, `created_locally`, `dry_run`, `skipped`,
+    /// `cancelled`, `aborted` or `failed`
+    pub status: &'static str,
+    /// Whether the tags reached the remote
+    pub pushed: bool,
+    /// Command for pushing the tags by hand, when they were created but not
+    /// pushed (e.g. with `--tag-only`)
+    pub manual_push_command: Option<String>,
+    /// What was (or would be) released; `None` when the run ended before
+    /// the tag was settled
+    pub release: Option<ReleaseDecision>,
+    /// Every hook script that ran, in order
+    pub hooks: Vec<HookRun>,
+    pub warnings: Vec<WorkflowWarning>,
+    pub error: Option<String>,
+    /// Wall-clock run time in milliseconds
+    pub duration_ms: u64,
+}
+
+impl WorkflowResult {
+    /// The result of a run that ended with `outcome`; `error` is the failure
+    /// reported through the notifier, if any
+    pub fn new(outcome: &Result<PublishOutcome>, error: Option<String>) -> Self {
+        let status = match outcome {
+            Ok(outcome) => outcome.status(),
+            Err(_) => "failed",
+        };
+        let error = match outcome {
+            // Reported failures were recorded as they happened
+            Err(e) if !e.is::<ReportedFailure>() => Some(e.to_string()),
+            _ => error,
+        };
+        WorkflowResult {
+            outcome: outcome.as_ref().ok().cloned(),
+            status,
+            pushed: matches!(outcome, Ok(PublishOutcome::Published { .. })),
+            manual_push_command: None,
+            release: None,
+            hooks: Vec::new(),
+            warnings: Vec::new(),
+            error,
+            duration_ms: 0,
+        }
+    }
+
+    /// Whether the run failed
+    pub fn is_failure(&self) -> bool {
+        self.outcome.is_none()
+    }
+
+    /// How the run ended, a failure as the [`ReportedFailure`] it was
+    pub fn into_outcome(self) -> Result<PublishOutcome> {
+        self.outcome
+            .ok_or_else(|| ReportedFailure(self.error.unwrap_or_default()).into())
+    }
+
+    /// Render the result as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Notifier passing everything on to another one, recording what goes into
+/// the [`WorkflowResult`] on the way
+pub(super) struct Recording<'a> {
+    notifier: &'a dyn Notifier,
+    decision: RefCell<Option<ReleaseDecision>>,
+    hooks: RefCell<Vec<HookRun>>,
+    warnings: RefCell<Vec<WorkflowWarning>>,
+    error: RefCell<Option<String>>,
+    manual_push_command: RefCell<Option<String>>,
+}
+
+impl<'a> Recording<'a> {
+    pub(super) fn new(notifier: &'a dyn Notifier) -> Self {
+        Recording {
+            notifier,
+            decision: RefCell::new(None),
+            hooks: RefCell::new(Vec::new()),
+            warnings: RefCell::new(Vec::new()),
+            error: RefCell::new(None),
+            manual_push_command: RefCell::new(None),
+        }
+    }
+
+    /// The result of the run that ended with `outcome` after `duration`
+    pub(super) fn into_result(
+        self,
+        outcome: &Result<PublishOutcome>,
+        duration: Duration,
+    ) -> WorkflowResult {
+        WorkflowResult {
+            manual_push_command: self.manual_push_command.into_inner(),
+            release: self.decision.into_inner(),
+            hooks: self.hooks.into_inner(),
+            warnings: self.warnings.into_inner(),
+            duration_ms: duration.as_millis() as u64,
+            ..WorkflowResult::new(outcome, self.error.into_inner())
+        }
+    }
+}
+
+impl Notifier for Recording<'_> {
+    fn status(&self, message: &str) {
+        self.notifier.status(message);
+    }
+
+    fn success(&self, message: &str) {
+        self.notifier.success(message);
+    }
+
+    fn warning(&self, message: &str) {
+        self.notifier.warning(message);
+        self.warnings.borrow_mut().push(WorkflowWarning {
+            key: None,
+            message: message.to_string(),
+        });
+    }
+
+    fn error(&self, message: &str) {
+        self.notifier.error(message);
+        *self.error.borrow_mut() = Some(message.to_string());
+    }
+
+    fn message(&self, message: &str) {
+        self.notifier.message(message);
+    }
+
+    fn finished(&self, message: &str) {
+        self.notifier.finished(message);
+    }
+
+    fn boundary_warning(&self, warning: &BoundaryWarning) {
+        self.notifier.boundary_warning(warning);
+        self.warnings.borrow_mut().push(WorkflowWarning {
+            key: Some(warning.config_key()),
+            message: warning.to_string(),
+        });
+    }
+
+    fn commit_analysis(&self, commit_messages: &[String], branch: &str) {
+        self.notifier.commit_analysis(commit_messages, branch);
+    }
+
+    fn non_conforming_commits(&self, commits: &[NonConformingCommit]) {
+        self.notifier.non_conforming_commits(commits);
+    }
+
+    fn proposed_tag(&self, old_tag: Option<&str>, new_tag: &str) {
+        self.notifier.proposed_tag(old_tag, new_tag);
+    }
+
+    fn breaking_changes(&self, subjects: &[String]) {
+        self.notifier.breaking_changes(subjects);
+    }
+
+    fn hook_dry_run(&self, hooks: &[(&str, Vec<String>)], env: &[(String, String)]) {
+        self.notifier.hook_dry_run(hooks, env);
+    }
+
+    fn hook_summary(&self, runs: &[HookRun]) {
+        self.notifier.hook_summary(runs);
+        *self.hooks.borrow_mut() = runs.to_vec();
+    }
+
+    fn manual_push_instruction(&self, command: &str) {
+        self.notifier.manual_push_instruction(command);
+        *self.manual_push_command.borrow_mut() = Some(command.to_string());
+    }
+
+    fn release_decision(&self, decision: &ReleaseDecision) {
+        self.notifier.release_decision(decision);
+        *self.decision.borrow_mut() = Some(decision.clone());
+    }
+
+    fn workflow_result(&self, result: &WorkflowResult) {
+        self.notifier.workflow_result(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::VersionBump;
+    use crate::ui::json::JsonNotifier;
+
+    fn decision() -> ReleaseDecision {
+        ReleaseDecision {
+            branch: "main".to_string(),
+            remote: "origin".to_string(),
+            previous_tag: Some("v1.2.0".to_string()),
+            version: "1.3.0".to_string(),
+            bump: VersionBump::Minor,
+            commit_count: 3,
+            commits_analyzed: 2,
+            tag: "v1.3.0".to_string(),
+            tags: vec!["v1.3.0".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_result_of_published_release() {
+        let recording = Recording::new(&JsonNotifier);
+        recording.release_decision(&decision());
+        recording.boundary_warning(&BoundaryWarning::CommitLimitExceeded { count: 3, limit: 2 });
+        let outcome = Ok(PublishOutcome::Published {
+            tag: "v1.3.0".to_string(),
+        });
+        let result = recording.into_result(&outcome, Duration::from_millis(1500));
+        assert!(!result.is_failure());
+
+        let json: serde_json::Value = serde_json::from_str(&result.to_json().unwrap()).unwrap();
+        assert_eq!(json["status"], "published");
+        assert_eq!(json["pushed"], true);
+        assert_eq!(json["release"]["previous_tag"], "v1.2.0");
+        assert_eq!(json["release"]["version"], "1.3.0");
+        assert_eq!(json["release"]["bump"], "minor");
+        assert_eq!(json["release"]["commit_count"], 3);
+        assert_eq!(json["release"]["commits_analyzed"], 2);
+        assert_eq!(json["release"]["remote"], "origin");
+        assert_eq!(json["hooks"], serde_json::json!([]));
+        assert_eq!(json["warnings"][0]["key"], "commit_limit_exceeded");
+        assert!(json["error"].is_null());
+        assert!(json["manual_push_command"].is_null());
+        assert_eq!(json["duration_ms"], 1500);
+        assert!(json.get("outcome").is_none());
+    }
+
+    #[test]
+    fn test_recorded_failure() {
+        let recording = Recording::new(&JsonNotifier);
+        recording.error("Branch 'dev' not found");
+        let outcome = Err(ReportedFailure("Branch 'dev' not found".to_string()).into());
+
+        let result = recording.into_result(&outcome, Duration::ZERO);
+        assert_eq!(result.status, "failed");
+        assert_eq!(result.release, None);
+        assert_eq!(result.error.as_deref(), Some("Branch 'dev' not found"));
+        let err = result.into_outcome().unwrap_err();
+        assert!(err.is::<ReportedFailure>());
+    }
+
+    #[test]
+    fn test_result_of_failed_run() {
+        let reported: Result<PublishOutcome> =
+            Err(ReportedFailure("Branch 'dev' not found".to_string()).into());
+        let result = WorkflowResult::new(&reported, Some("Branch 'dev' not found".to_string()));
+        assert_eq!(result.status, "failed");
+        assert!(!result.pushed);
+        assert_eq!(result.error.as_deref(), Some("Branch 'dev' not found"));
+
+        let unreported = Err(anyhow::anyhow!("disk full"));
+        assert_eq!(
+            WorkflowResult::new(&unreported, None).error.as_deref(),
+            Some("disk full")
+        );
+    }
+}
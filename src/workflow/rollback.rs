@@ -0,0 +1,173 @@
+//! Deleting a release tag again, for `git-publish rollback`.
+//!
+//! Without a tag, the latest one (by version) is deleted. Deleting an older
+//! tag usually means a mistake, as the releases after it were built on top
+//! of it, so it needs `--force`. The refs to delete are always listed
+//! before the confirmation.
+
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::domain::TagPattern;
+use crate::i18n::{text, tr, Msg};
+use crate::tag_list::{tag_version, TagList};
+use crate::workflow::{fail, Notifier, Prompter, Repository};
+
+/// Where to delete the tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RollbackScope {
+    /// The local tag and the remote one
+    #[default]
+    Everywhere,
+    /// Only the local tag
+    LocalOnly,
+    /// Only the remote tag, keeping the local one
+    RemoteOnly,
+}
+
+impl RollbackScope {
+    fn local(self) -> bool {
+        self != RollbackScope::RemoteOnly
+    }
+
+    fn remote(self) -> bool {
+        self != RollbackScope::LocalOnly
+    }
+}
+
+/// Which tag to delete, and where
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RollbackArgs {
+    /// Tag to delete; the latest tag without one
+    pub tag: Option<String>,
+    /// Only consider tags following this branch's tag pattern
+    pub branch: Option<String>,
+    pub scope: RollbackScope,
+    /// Delete the tag even when a newer tag exists
+    pub force: bool,
+    /// Don't ask for confirmation
+    pub yes: bool,
+    /// Remote to delete from; the first configured remote (`origin` first) without one
+    pub remote: Option<String>,
+}
+
+/// Delete `args.tag`, or the latest tag, locally and/or on the remote
+///
+/// # Returns
+/// * `Ok(Some(tag))` - The deleted tag
+/// * `Ok(None)` - The user declined
+pub fn rollback(
+    repo: &dyn Repository,
+    notifier: &dyn Notifier,
+    prompter: &dyn Prompter,
+    cfg: &Config,
+    args: &RollbackArgs,
+) -> Result<Option<String>> {
+    let pattern = args
+        .branch
+        .as_ref()
+        .and_then(|branch| cfg.branches.get(branch))
+        .map(TagPattern::new);
+    let tags = TagList::new(
+        repo.list_tag_details(pattern.as_ref().map(|p| p.pattern.as_str()))?,
+        pattern.as_ref(),
+    );
+    let latest = tags.tags.first();
+
+    let tag = match (&args.tag, latest) {
+        (Some(tag), _) => tag.clone(),
+        (None, Some(latest)) => latest.tag.clone(),
+        (None, None) => return Err(fail(notifier, text(Msg::RollbackNoTags))),
+    };
+    // Without the local tag, only the remote one can be deleted
+    if args.scope.local() && !repo.tag_exists(&tag) {
+        return Err(fail(
+            notifier,
+            &tr(Msg::RollbackTagNotFound, &[("tag", &tag)]),
+        ));
+    }
+
+    if let Some(latest) = latest.filter(|latest| latest.tag != tag) {
+        let latest_version = tag_version(&latest.tag, pattern.as_ref());
+        let older = match (tag_version(&tag, pattern.as_ref()), latest_version) {
+            (Some(version), Some(latest_version)) => version <= latest_version,
+            (None, Some(_)) => true,
+            (_, None) => false,
+        };
+        if older && !args.force {
+            return Err(fail(
+                notifier,
+                &tr(
+                    Msg::RollbackNotLatest,
+                    &[("tag", &tag), ("latest", &latest.tag)],
+                ),
+            ));
+        }
+    }
+
+    let remote = if args.scope.remote() {
+        match &args.remote {
+            Some(remote) => Some(remote.clone()),
+            None => match repo.list_remotes()?.into_iter().next() {
+                Some(remote) => Some(remote),
+                None => return Err(fail(notifier, text(Msg::NoRemotesConfigured))),
+            },
+        }
+    } else {
+        None
+    };
+
+    // The exact refs are listed even with --yes, so the log shows what went
+    let tag_ref = format!("refs/tags/{}", tag);
+    if let Some(remote) = &remote {
+        notifier.status(&tr(
+            Msg::RollbackRefRemote,
+            &[("ref", &tag_ref), ("remote", remote)],
+        ));
+    }
+    if args.scope.local() {
+        notifier.status(&tr(Msg::RollbackRefLocal, &[("ref", &tag_ref)]));
+    }
+
+    if !args.yes {
+        let prompt = match (&remote, args.scope.local()) {
+            (Some(remote), true) => tr(Msg::RollbackConfirm, &[("tag", &tag), ("remote", remote)]),
+            (Some(remote), false) => tr(
+                Msg::RollbackConfirmRemote,
+                &[("tag", &tag), ("remote", remote)],
+            ),
+            (None, _) => tr(Msg::RollbackConfirmLocal, &[("tag", &tag)]),
+        };
+        if !prompter.confirm(&prompt)? {
+            notifier.status(text(Msg::RollbackCancelled));
+            return Ok(None);
+        }
+    }
+
+    // The remote first: if it fails, the local tag is still there to retry with
+    if let Some(remote) = &remote {
+        if let Err(e) = repo.delete_remote_tag(&tag, remote, cfg.remote_overrides.get(remote)) {
+            return Err(fail(
+                notifier,
+                &tr(
+                    Msg::RollbackRemoteFailed,
+                    &[("tag", &tag), ("remote", remote), ("error", &e)],
+                ),
+            ));
+        }
+        notifier.success(&tr(
+            Msg::RollbackRemoteDeleted,
+            &[("tag", &tag), ("remote", remote)],
+        ));
+    }
+    if args.scope.local() {
+        if let Err(e) = repo.delete_tag(&tag) {
+            return Err(fail(
+                notifier,
+                &tr(Msg::RollbackLocalFailed, &[("tag", &tag), ("error", &e)]),
+            ));
+        }
+        notifier.success(&tr(Msg::RollbackLocalDeleted, &[("tag", &tag)]));
+    }
+    Ok(Some(tag))
+}
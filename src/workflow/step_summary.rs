@@ -0,0 +1,171 @@
+//! Markdown release summary for the GitHub Actions run page.
+//!
+//! GitHub sets `GITHUB_STEP_SUMMARY` to a file that each step may append
+//! Markdown to; its contents are shown on the run's summary page.
+
+use std::fmt::Write as _;
+use std::fs::OpenOptions;
+use std::io::{self, Write as _};
+use std::path::Path;
+
+use crate::domain::{Changelog, VersionBump};
+use crate::i18n::{text, tr, Msg};
+
+/// Environment variable GitHub Actions points at the step summary file
+pub const STEP_SUMMARY_VAR: &str = "GITHUB_STEP_SUMMARY";
+
+/// What a release run did with its tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SummaryStatus {
+    /// Created and pushed
+    Published,
+    /// Created, push queued until the remote is reachable
+    Queued,
+    /// Created but not pushed
+    CreatedLocally,
+}
+
+/// The details of a release shown in the step summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepSummary {
+    pub tag: String,
+    pub previous_tag: Option<String>,
+    pub branch: String,
+    pub remote: String,
+    pub bump: VersionBump,
+    pub commit_count: usize,
+    pub changelog: Changelog,
+    /// Web URL of the repository, for the link comparing the two tags
+    pub repo_url: Option<String>,
+    /// Notes from the previous release's annotated tag
+    pub previous_notes: Option<String>,
+}
+
+impl StepSummary {
+    /// Render the summary as Markdown
+    pub fn to_markdown(&self, status: SummaryStatus) -> String {
+        let mut out = String::new();
+        let heading = match status {
+            SummaryStatus::Published => Msg::SummaryReleased,
+            SummaryStatus::Queued => Msg::SummaryQueued,
+            SummaryStatus::CreatedLocally => Msg::SummaryCreatedLocally,
+        };
+        let heading = tr(heading, &[("tag", &self.tag)]);
+        let _ = writeln!(out, "## {}\n", heading);
+
+        let _ = writeln!(out, "| | |");
+        let _ = writeln!(out, "|---|---|");
+        let _ = writeln!(out, "| {} | `{}` |", text(Msg::ColumnBranch), self.branch);
+        let _ = writeln!(out, "| {} | `{}` |", text(Msg::ColumnRemote), self.remote);
+        match (&self.previous_tag, &self.repo_url) {
+            (Some(previous), Some(repo_url)) => {
+                let _ = writeln!(
+                    out,
+                    "| {label} | [`{previous}`]({repo_url}/compare/{previous}...{tag}) |",
+                    label = text(Msg::ColumnPreviousTag),
+                    previous = previous,
+                    repo_url = repo_url,
+                    tag = self.tag
+                );
+            }
+            (Some(previous), None) => {
+                let _ = writeln!(out, "| {} | `{}` |", text(Msg::ColumnPreviousTag), previous);
+            }
+            (None, _) => {}
+        }
+        let _ = writeln!(
+            out,
+            "| {} | {} |",
+            text(Msg::ColumnBump),
+            format!("{:?}", self.bump).to_lowercase()
+        );
+        let _ = writeln!(
+            out,
+            "| {} | {} |",
+            text(Msg::ColumnCommits),
+            self.commit_count
+        );
+        out.push('\n');
+
+        out.push_str(&self.changelog.to_markdown());
+
+        if let (Some(previous), Some(notes)) = (&self.previous_tag, &self.previous_notes) {
+            let _ = writeln!(
+                out,
+                "<details><summary>{} (<code>{}</code>)</summary>\n\n{}\n\n</details>\n",
+                text(Msg::SummaryPreviousNotes),
+                previous,
+                notes
+            );
+        }
+        out
+    }
+
+    /// Append the rendered summary to `path`, creating the file if needed
+    pub fn append_to(&self, path: &Path, status: SummaryStatus) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(self.to_markdown(status).as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary() -> StepSummary {
+        StepSummary {
+            tag: "v1.3.0".to_string(),
+            previous_tag: Some("v1.2.0".to_string()),
+            branch: "main".to_string(),
+            remote: "origin".to_string(),
+            bump: VersionBump::Minor,
+            commit_count: 2,
+            changelog: Changelog::from_commits(&[
+                "feat: add export".to_string(),
+                "fix(io): close files".to_string(),
+            ]),
+            repo_url: None,
+            previous_notes: None,
+        }
+    }
+
+    #[test]
+    fn test_step_summary_markdown() {
+        assert_eq!(
+            summary().to_markdown(SummaryStatus::Published),
+            "## Released `v1.3.0`\n\n\
+             | | |\n|---|---|\n\
+             | Branch | `main` |\n\
+             | Remote | `origin` |\n\
+             | Previous tag | `v1.2.0` |\n\
+             | Bump | minor |\n\
+             | Commits | 2 |\n\n\
+             ### Features\n\n- add export\n\n\
+             ### Bug Fixes\n\n- **io:** close files\n\n"
+        );
+        let linked = StepSummary {
+            repo_url: Some("https://github.com/team/app".to_string()),
+            ..summary()
+        };
+        assert!(linked.to_markdown(SummaryStatus::Published).contains(
+            "| Previous tag | [`v1.2.0`](https://github.com/team/app/compare/v1.2.0...v1.3.0) |"
+        ));
+        assert!(summary()
+            .to_markdown(SummaryStatus::Queued)
+            .starts_with("## Tagged `v1.3.0` (push queued)\n"));
+    }
+
+    #[test]
+    fn test_step_summary_appends() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.md");
+        std::fs::write(&path, "## Build\n\n").unwrap();
+
+        summary()
+            .append_to(&path, SummaryStatus::CreatedLocally)
+            .unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("## Build\n\n## Tagged `v1.3.0` (not pushed)\n"));
+    }
+}
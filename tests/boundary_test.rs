@@ -1,4 +1,5 @@
 use git_publish::boundary::BoundaryWarning;
+use git_publish::error::RemoteFailureKind;
 use git_publish::ui;
 
 // ============================================================================
@@ -74,6 +75,181 @@ fn test_boundary_warning_fetch_auth_failed_display() {
     );
 }
 
+#[test]
+fn test_boundary_warning_network_unreachable_display() {
+    let warning = BoundaryWarning::FetchNetworkUnreachable {
+        remote: "upstream".to_string(),
+    };
+
+    let display_msg = warning.to_string();
+    assert!(
+        display_msg.contains("unreachable"),
+        "Message should mention unreachable remote, got: {}",
+        display_msg
+    );
+    assert!(display_msg.contains("upstream"));
+    assert!(warning.remediation_hint().is_some());
+}
+
+#[test]
+fn test_boundary_warning_host_key_failed_display() {
+    let warning = BoundaryWarning::FetchHostKeyVerificationFailed {
+        remote: "origin".to_string(),
+    };
+
+    let display_msg = warning.to_string();
+    assert!(
+        display_msg.contains("Host key verification failed"),
+        "Message should mention host key verification, got: {}",
+        display_msg
+    );
+    assert!(warning.remediation_hint().unwrap().contains("known_hosts"));
+}
+
+#[test]
+fn test_boundary_warning_from_fetch_failure() {
+    assert_eq!(
+        BoundaryWarning::from_fetch_failure("origin", RemoteFailureKind::Authentication),
+        Some(BoundaryWarning::FetchAuthenticationFailed {
+            remote: "origin".to_string()
+        })
+    );
+    assert_eq!(
+        BoundaryWarning::from_fetch_failure("origin", RemoteFailureKind::NetworkUnreachable),
+        Some(BoundaryWarning::FetchNetworkUnreachable {
+            remote: "origin".to_string()
+        })
+    );
+    assert_eq!(
+        BoundaryWarning::from_fetch_failure("origin", RemoteFailureKind::HostKeyVerification),
+        Some(BoundaryWarning::FetchHostKeyVerificationFailed {
+            remote: "origin".to_string()
+        })
+    );
+    assert_eq!(
+        BoundaryWarning::from_fetch_failure("origin", RemoteFailureKind::Other),
+        None
+    );
+}
+
+#[test]
+fn test_boundary_warning_remote_connection_failed() {
+    let warning = BoundaryWarning::RemoteConnectionFailed {
+        remote: "origin".to_string(),
+        kind: RemoteFailureKind::Authentication,
+        detail: "Failed to connect: no credentials".to_string(),
+    };
+
+    let display_msg = warning.to_string();
+    assert!(display_msg.contains("origin"));
+    assert!(display_msg.contains("no credentials"));
+    assert!(warning.remediation_hint().unwrap().contains("ssh-add"));
+
+    let other = BoundaryWarning::RemoteConnectionFailed {
+        remote: "origin".to_string(),
+        kind: RemoteFailureKind::Other,
+        detail: "boom".to_string(),
+    };
+    assert_eq!(other.remediation_hint(), None);
+}
+
+#[test]
+fn test_boundary_warning_manifest_version_mismatch() {
+    let warning = BoundaryWarning::ManifestVersionMismatch {
+        manifest: "Cargo.toml".to_string(),
+        manifest_version: "1.3.0".to_string(),
+        tag: "v1.2.0".to_string(),
+        tag_version: "1.2.0".to_string(),
+    };
+
+    let display_msg = warning.to_string();
+    assert!(display_msg.contains("Cargo.toml"));
+    assert!(display_msg.contains("1.3.0"));
+    assert!(display_msg.contains("v1.2.0"));
+    assert!(warning.remediation_hint().is_some());
+}
+
+#[test]
+fn test_boundary_warning_tag_metadata_mismatch() {
+    let warning = BoundaryWarning::TagMetadataMismatch {
+        tag: "v1.2.0".to_string(),
+        tag_version: "1.2.0".to_string(),
+        recorded_version: "1.1.9".to_string(),
+    };
+
+    let display_msg = warning.to_string();
+    assert!(display_msg.contains("v1.2.0"));
+    assert!(display_msg.contains("1.1.9"));
+    assert!(warning.remediation_hint().is_some());
+}
+
+#[test]
+fn test_boundary_warning_tag_unreachable() {
+    let warning = BoundaryWarning::TagUnreachable {
+        tag: "v1.2.0".to_string(),
+        branch: "main".to_string(),
+    };
+
+    let display_msg = warning.to_string();
+    assert!(display_msg.contains("v1.2.0"));
+    assert!(display_msg.contains("main"));
+    assert!(warning.remediation_hint().is_some());
+}
+
+#[test]
+fn test_boundary_warning_commit_limit_exceeded() {
+    let warning = BoundaryWarning::CommitLimitExceeded {
+        count: 12000,
+        limit: 5000,
+    };
+
+    let display_msg = warning.to_string();
+    assert!(display_msg.contains("12000"));
+    assert!(display_msg.contains("5000"));
+    assert!(warning
+        .remediation_hint()
+        .is_some_and(|hint| hint.contains("max_commits_analyzed")));
+}
+
+#[test]
+fn test_boundary_warning_version_not_increased() {
+    let warning = BoundaryWarning::VersionNotIncreased {
+        tag: "v1.2.3".to_string(),
+        version: "1.2.3".to_string(),
+        current_version: "1.10.0".to_string(),
+    };
+
+    let display_msg = warning.to_string();
+    assert!(display_msg.contains("v1.2.3"));
+    assert!(display_msg.contains("1.10.0"));
+    assert!(warning
+        .remediation_hint()
+        .is_some_and(|hint| hint.contains("on_version_downgrade")));
+}
+
+#[test]
+fn test_boundary_warning_anonymous_commits() {
+    let warning = BoundaryWarning::AnonymousCommits {
+        count: 2,
+        commits: vec!["a1b2c3d".to_string(), "e4f5a6b".to_string()],
+    };
+
+    let display_msg = warning.to_string();
+    assert!(display_msg.contains("a1b2c3d, e4f5a6b"));
+    assert_eq!(warning.config_key(), "anonymous_commits");
+    assert!(warning.remediation_hint().is_some());
+}
+
+#[test]
+fn test_boundary_warning_missing_git_identity() {
+    let warning = BoundaryWarning::MissingGitIdentity;
+
+    assert!(warning.to_string().contains("user.email"));
+    assert!(warning
+        .remediation_hint()
+        .is_some_and(|hint| hint.contains("git config user.name")));
+}
+
 // ============================================================================
 // Tag Format Validation Tests
 // ============================================================================
@@ -1,5 +1,5 @@
 // tests/config_test.rs
-use git_publish::config::{load_config, Config};
+use git_publish::config::{load_config, Config, HookCommand};
 use std::io::Write;
 use tempfile::NamedTempFile;
 
@@ -70,12 +70,42 @@ fn test_default_values() {
 #[test]
 fn test_behavior_config_defaults() {
     let config = Config::default();
-    assert_eq!(config.behavior.skip_remote_selection, false);
+    assert!(!config.behavior.skip_remote_selection);
 }
 
 #[test]
 fn test_behavior_config_skip_remote_selection_from_file() {
     let config = load_config(Some("tests/fixtures/config_with_behavior.toml"))
         .expect("Failed to load test config");
-    assert_eq!(config.behavior.skip_remote_selection, true);
+    assert!(config.behavior.skip_remote_selection);
+}
+
+#[test]
+fn test_load_config_with_hooks() {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    let toml_content = r#"
+[branches]
+"main" = "v{version}"
+
+[hooks]
+pre-tag-create = "./hooks/pre-tag-create.sh"
+post-tag-create = "./hooks/post-tag-create.sh"
+post-push = "./hooks/post-push.sh"
+"#;
+    temp_file.write_all(toml_content.as_bytes()).unwrap();
+    temp_file.flush().unwrap();
+
+    let config = load_config(Some(temp_file.path().to_str().unwrap())).unwrap();
+    assert_eq!(
+        config.hooks.pre_tag_create,
+        vec![HookCommand::new("./hooks/pre-tag-create.sh")]
+    );
+    assert_eq!(
+        config.hooks.post_tag_create,
+        vec![HookCommand::new("./hooks/post-tag-create.sh")]
+    );
+    assert_eq!(
+        config.hooks.post_push,
+        vec![HookCommand::new("./hooks/post-push.sh")]
+    );
 }
@@ -7,7 +7,7 @@ use std::process::Command;
 #[serial]
 fn test_git_publish_help() {
     let output = Command::new("cargo")
-        .args(&["run", "--bin", "git-publish", "--", "--help"])
+        .args(["run", "--bin", "git-publish", "--", "--help"])
         .output()
         .expect("Failed to execute command");
 
@@ -21,7 +21,7 @@ fn test_git_publish_help() {
 #[serial]
 fn test_git_publish_version() {
     let output = Command::new("cargo")
-        .args(&["run", "--bin", "git-publish", "--", "--version"])
+        .args(["run", "--bin", "git-publish", "--", "--version"])
         .output()
         .expect("Failed to execute command");
 
@@ -97,18 +97,18 @@ fn test_conventional_commit_parsing() {
     assert_eq!(parsed.r#type, "feat");
     assert_eq!(parsed.scope, Some("auth".to_string()));
     assert_eq!(parsed.description, "add new login system");
-    assert_eq!(parsed.is_breaking_change, false);
+    assert!(!parsed.is_breaking_change);
 
     // Test breaking change with ! syntax
     let parsed_breaking = ParsedCommit::parse("feat!: remove deprecated API");
     assert_eq!(parsed_breaking.r#type, "feat");
-    assert_eq!(parsed_breaking.is_breaking_change, true);
+    assert!(parsed_breaking.is_breaking_change);
 
     // Test breaking change in footer
     let breaking_with_footer = "feat: new feature\n\nBREAKING CHANGE: This changes the API";
     let parsed_footer = ParsedCommit::parse(breaking_with_footer);
     assert_eq!(parsed_footer.r#type, "feat");
-    assert_eq!(parsed_footer.is_breaking_change, true);
+    assert!(parsed_footer.is_breaking_change);
 
     // Test non-conventional commit (should default to chore)
     let parsed_non_conv = ParsedCommit::parse("Update README");
@@ -219,6 +219,784 @@ mod git_operations_tests {
         env::set_current_dir(original_dir).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_get_tag_releases_on_branch() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+
+        // Third commit by another author, released with an annotated tag
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        let alice = git2::Signature::now("Alice", "alice@example.com").unwrap();
+        let commit_id = repo
+            .commit(
+                Some("HEAD"),
+                &alice,
+                &alice,
+                "fix: patch",
+                &parent.tree().unwrap(),
+                &[&parent],
+            )
+            .expect("Could not create commit");
+        repo.tag(
+            "v1.1.0",
+            &repo.find_object(commit_id, None).unwrap(),
+            &alice,
+            "Release v1.1.0",
+            false,
+        )
+        .expect("Could not create annotated tag");
+        repo.tag_lightweight(
+            "other-tag",
+            &repo.find_object(commit_id, None).unwrap(),
+            false,
+        )
+        .expect("Could not create tag");
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let releases = git_repo
+            .get_tag_releases_on_branch("master", Some("v{version}"))
+            .expect("Should list releases");
+        let messages = git_repo
+            .get_release_commit_messages("v1.1.0", Some("v1.0.0"))
+            .expect("Should list release commits");
+        let latest = git_repo
+            .get_latest_version_tag_on_branch("master")
+            .expect("Should find a tag");
+        let annotated = git_repo.tag_message("v1.1.0").expect("Should read tag");
+        let lightweight = git_repo.tag_message("other-tag").expect("Should read tag");
+        let missing = git_repo.tag_message("v9.9.9");
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(annotated.as_deref().map(str::trim), Some("Release v1.1.0"));
+        assert_eq!(lightweight, None);
+        assert!(missing.is_err());
+
+        let tags: Vec<&str> = releases.iter().map(|r| r.tag.as_str()).collect();
+        assert_eq!(tags, vec!["v1.0.0", "v1.1.0"]);
+        assert_eq!(releases[0].commit_count, 1);
+        assert_eq!(releases[1].commit_count, 2);
+        assert_eq!(releases[1].authors, vec!["Alice", "Test User"]);
+        let subjects: Vec<&str> = messages.iter().map(|m| m.trim()).collect();
+        assert_eq!(subjects.last(), Some(&"fix: patch"));
+        assert_eq!(subjects.len(), 2);
+        // other-tag is on the same commit but has no version
+        assert_eq!(latest.as_deref(), Some("v1.1.0"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_annotated_tag_on_tag() {
+        use git_publish::git_ops::{TagAnnotation, TagTarget};
+
+        let temp_dir = setup_test_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let annotation = |message: &str| TagAnnotation {
+            message: message.to_string(),
+            signed: false,
+        };
+        git_repo
+            .create_annotated_tag("v2.0.0", None, &annotation("Release v2.0.0"), false)
+            .expect("Should create the release tag");
+        git_repo
+            .create_annotated_tag_on_tag("v2.0.0-signed", "v2.0.0", &annotation("Approved"), false)
+            .expect("Should tag the tag");
+        let targets = (
+            git_repo.tag_target("v1.0.0").unwrap(),
+            git_repo.tag_target("v2.0.0-signed").unwrap(),
+        );
+        let message = git_repo.tag_message("v2.0.0-signed").unwrap();
+        let missing = git_repo.create_annotated_tag_on_tag("x", "v9.9.9", &annotation("x"), false);
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(targets, (TagTarget::Commit, TagTarget::Tag));
+        assert_eq!(message.as_deref().map(str::trim), Some("Approved"));
+        assert!(missing.is_err());
+
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let release = repo.find_reference("refs/tags/v2.0.0").unwrap();
+        let layered = repo
+            .find_reference("refs/tags/v2.0.0-signed")
+            .unwrap()
+            .peel_to_tag()
+            .unwrap();
+        assert_eq!(layered.target_id(), release.target().unwrap());
+        assert_eq!(layered.target_type(), Some(git2::ObjectType::Tag));
+        assert_eq!(
+            release.peel_to_commit().unwrap().id(),
+            repo.head().unwrap().peel_to_commit().unwrap().id()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_unborn_branch() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let mut options = git2::RepositoryInitOptions::new();
+        options.initial_head("main");
+        Repository::init_opts(temp_dir.path(), &options).expect("Failed to init repo");
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change dir");
+
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Failed to create GitRepo");
+        let unborn = git_repo.unborn_branch();
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(unborn.as_deref(), Some("main"));
+
+        let temp_dir = setup_test_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change dir");
+        let unborn = git_publish::git_ops::GitRepo::new()
+            .unwrap()
+            .unborn_branch();
+        env::set_current_dir(original_dir).unwrap();
+        assert_eq!(unborn, None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_list_tag_details() {
+        use git_publish::git_ops::TagAnnotation;
+
+        let temp_dir = setup_test_repo();
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let annotation = TagAnnotation {
+            message: "Release v2.0.0".to_string(),
+            signed: false,
+        };
+        git_repo
+            .create_annotated_tag("v2.0.0", None, &annotation, false)
+            .expect("Should create the release tag");
+        let mut details = git_repo.list_tag_details(None).unwrap();
+        details.sort_by(|a, b| a.name.cmp(&b.name));
+
+        env::set_current_dir(original_dir).unwrap();
+
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let names: Vec<&str> = details.iter().map(|tag| tag.name.as_str()).collect();
+        assert_eq!(names, ["v1.0.0", "v2.0.0"]);
+        assert!(!details[0].annotated);
+        assert!(details[1].annotated);
+        assert_eq!(details[1].target, head.id().to_string());
+        // A lightweight tag takes the commit's time
+        let first = repo
+            .find_reference("refs/tags/v1.0.0")
+            .unwrap()
+            .peel_to_commit()
+            .unwrap();
+        assert_eq!(details[0].target, first.id().to_string());
+        assert_eq!(details[0].time, first.time().seconds());
+    }
+
+    #[test]
+    #[serial]
+    fn test_simulate_at_past_commit() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let sig = repo.signature().unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        let fix = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "fix(api)!: drop v1",
+                &parent.tree().unwrap(),
+                &[&parent],
+            )
+            .unwrap();
+        repo.tag_lightweight("v2.0.0", &repo.find_object(fix, None).unwrap(), false)
+            .unwrap();
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let config = git_publish::config::Config::default();
+        let past = git_publish::simulation::simulate(&git_repo, &config, "master", "HEAD~1");
+        let head = git_publish::simulation::simulate(&git_repo, &config, "master", "HEAD");
+        env::set_current_dir(original_dir).unwrap();
+
+        let past = past.expect("Should simulate the past commit");
+        assert_eq!(past.commit, parent.id().to_string());
+        assert_eq!(past.subject, "feat: add new feature");
+        assert_eq!(past.baseline_tag.as_deref(), Some("v1.0.0"));
+        assert_eq!(past.commit_count, 1);
+        assert_eq!(past.bump, Some(git_publish::VersionBump::Minor));
+        assert_eq!(past.next_tag.as_deref(), Some("v1.1.0"));
+
+        let head = head.expect("Should simulate HEAD");
+        assert_eq!(head.baseline_tag.as_deref(), Some("v2.0.0"));
+        assert_eq!(head.commit_count, 0);
+        assert_eq!(head.next_tag, None);
+    }
+
+    #[test]
+    #[serial]
+    fn test_last_commit_before_date() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        // A day after the other commits, so the dates can't tie
+        let later = git2::Signature::new(
+            "Test User",
+            "test@example.com",
+            &git2::Time::new(parent.time().seconds() + 86_400, 0),
+        )
+        .unwrap();
+        let head = repo
+            .commit(
+                Some("HEAD"),
+                &later,
+                &later,
+                "fix: handle empty input",
+                &parent.tree().unwrap(),
+                &[&parent],
+            )
+            .unwrap();
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let before_head = git_repo.last_commit_before("master", later.when().seconds());
+        let after_head = git_repo.last_commit_before("master", later.when().seconds() + 1);
+        let before_all = git_repo.last_commit_before("master", 0);
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(before_head.unwrap(), Some(parent.id().to_string()));
+        assert_eq!(after_head.unwrap(), Some(head.to_string()));
+        assert_eq!(before_all.unwrap(), None);
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(unix)]
+    fn test_create_annotated_and_signed_tags() {
+        use git_publish::git_ops::TagAnnotation;
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = setup_test_repo();
+        // Stands in for gpg: prints a detached signature
+        let gpg = temp_dir.path().join("fake-gpg");
+        fs::write(
+            &gpg,
+            "#!/bin/sh\ncat > /dev/null\necho '-----BEGIN PGP SIGNATURE-----'\necho\necho 'ZmFrZQ=='\necho '-----END PGP SIGNATURE-----'\n",
+        )
+        .unwrap();
+        fs::set_permissions(&gpg, fs::Permissions::from_mode(0o755)).unwrap();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        repo.config()
+            .unwrap()
+            .set_str("gpg.program", gpg.to_str().unwrap())
+            .unwrap();
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let annotation = |signed| TagAnnotation {
+            message: "Release v1.1.0\n\nVersion: 1.1.0\n".to_string(),
+            signed,
+        };
+        let annotated =
+            git_repo.create_annotated_tag("v1.1.0", Some("master"), &annotation(false), false);
+        let signed =
+            git_repo.create_annotated_tag("v1.1.0-rc.1", Some("master"), &annotation(true), false);
+        let existing =
+            git_repo.create_annotated_tag("v1.1.0", Some("master"), &annotation(true), false);
+        let annotated_message = git_repo.tag_message("v1.1.0").unwrap();
+        let signed_message = git_repo.tag_message("v1.1.0-rc.1").unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("gpg.program", "/nonexistent/gpg")
+            .unwrap();
+        let unsigned = git_repo.create_annotated_tag("v1.2.0", None, &annotation(true), false);
+        env::set_current_dir(original_dir).unwrap();
+
+        annotated.expect("Should create an annotated tag");
+        signed.expect("Should create a signed tag");
+        assert!(existing.is_err());
+        assert!(unsigned.is_err());
+        assert_eq!(
+            annotated_message.as_deref(),
+            Some("Release v1.1.0\n\nVersion: 1.1.0\n")
+        );
+        let signed_message = signed_message.expect("Signed tags are annotated");
+        assert!(signed_message.starts_with("Release v1.1.0\n\nVersion: 1.1.0\n-----BEGIN PGP"));
+
+        // git reads the signed tag object back
+        let tag = repo
+            .find_reference("refs/tags/v1.1.0-rc.1")
+            .unwrap()
+            .peel_to_tag()
+            .unwrap();
+        assert_eq!(tag.tagger().unwrap().name(), Some("Test User"));
+        assert_eq!(tag.target_id(), repo.head().unwrap().target().unwrap());
+    }
+
+    /// Commit the current tree on top of `parents` without moving any branch
+    fn commit_with_parents(repo: &Repository, parents: &[git2::Oid], message: &str) -> git2::Oid {
+        let sig = repo.signature().unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        let parents: Vec<git2::Commit> = parents
+            .iter()
+            .map(|oid| repo.find_commit(*oid).unwrap())
+            .collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(None, &sig, &sig, message, &tree, &parents)
+            .expect("Could not create commit")
+    }
+
+    /// Messages of the commits on master since the tag, sorted
+    fn messages_since(temp_dir: &TempDir, tag: &str) -> Vec<String> {
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let commits = git_repo.get_commits_since_tag("master", Some(tag));
+        env::set_current_dir(original_dir).unwrap();
+
+        let mut messages: Vec<String> = commits
+            .expect("Should list commits")
+            .iter()
+            .map(|commit| commit.message().unwrap().to_string())
+            .collect();
+        messages.sort();
+        messages
+    }
+
+    #[test]
+    #[serial]
+    fn test_commits_since_tag_on_merged_release_branch() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let initial = repo.revparse_single("v1.0.0").unwrap().id();
+        let feature = repo.head().unwrap().target().unwrap();
+
+        // v1.1.0 is released from a branch off v1.0.0, then merged back into master
+        let release = commit_with_parents(&repo, &[initial], "fix: release fix");
+        let sig = repo.signature().unwrap();
+        repo.tag(
+            "v1.1.0",
+            &repo.find_object(release, None).unwrap(),
+            &sig,
+            "Release v1.1.0",
+            false,
+        )
+        .expect("Could not create annotated tag");
+        let merge = commit_with_parents(&repo, &[feature, release], "Merge branch 'release'");
+        let after = commit_with_parents(&repo, &[merge], "fix: after release");
+        repo.reference("refs/heads/master", after, true, "advance master")
+            .unwrap();
+
+        // The feature never made it into v1.1.0; the release branch did
+        assert_eq!(
+            messages_since(&temp_dir, "v1.1.0"),
+            vec![
+                "Merge branch 'release'",
+                "feat: add new feature",
+                "fix: after release"
+            ]
+        );
+
+        // Streaming visits newest first and stops when asked to
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let mut visited = Vec::new();
+        let walk = git_repo.walk_commits_since_tag("master", Some("v1.1.0"), |commit| {
+            visited.push(commit.message().unwrap().to_string());
+            std::ops::ControlFlow::Break(())
+        });
+        env::set_current_dir(original_dir).unwrap();
+
+        walk.expect("Should walk commits");
+        assert_eq!(visited, vec!["fix: after release"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_commits_since_tag_through_octopus_merge() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let initial = repo.revparse_single("v1.0.0").unwrap().id();
+        let feature = repo.head().unwrap().target().unwrap();
+
+        // The tag sits behind the third parent of an octopus merge
+        let topic = commit_with_parents(&repo, &[initial], "feat: topic");
+        let tagged = commit_with_parents(&repo, &[initial], "fix: tagged");
+        let released = commit_with_parents(&repo, &[tagged], "fix: released");
+        repo.tag_lightweight("v1.1.0", &repo.find_object(released, None).unwrap(), false)
+            .unwrap();
+        let octopus = commit_with_parents(&repo, &[feature, topic, released], "Merge octopus");
+        repo.reference("refs/heads/master", octopus, true, "advance master")
+            .unwrap();
+
+        assert_eq!(
+            messages_since(&temp_dir, "v1.1.0"),
+            vec!["Merge octopus", "feat: add new feature", "feat: topic"]
+        );
+        // The tag's own history up to v1.0.0 is the only thing left out
+        assert_eq!(
+            messages_since(&temp_dir, "v1.0.0"),
+            vec![
+                "Merge octopus",
+                "feat: add new feature",
+                "feat: topic",
+                "fix: released",
+                "fix: tagged"
+            ]
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_commits_since_merge_base() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let feature = repo.head().unwrap().target().unwrap();
+
+        // master and topic both continue from the feature commit
+        let topic = commit_with_parents(&repo, &[feature], "feat: topic work");
+        repo.reference("refs/heads/topic", topic, true, "create topic")
+            .unwrap();
+        let fix = commit_with_parents(&repo, &[feature], "fix: master only");
+        repo.reference("refs/heads/master", fix, true, "advance master")
+            .unwrap();
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let base = git_repo.merge_base("master", "topic");
+        let missing = git_repo.merge_base("master", "nowhere");
+        let mut visited = Vec::new();
+        if let Ok(base) = &base {
+            git_repo
+                .walk_commits_since("master", Some("v1.0.0"), Some(base), |commit| {
+                    visited.push(commit.message().unwrap().to_string());
+                    std::ops::ControlFlow::Continue(())
+                })
+                .expect("Should walk commits");
+        }
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(
+            base.expect("Should find the merge base"),
+            feature.to_string()
+        );
+        assert!(missing.is_err());
+        assert_eq!(visited, vec!["fix: master only"]);
+    }
+
+    /// Commit on top of `parent` that writes `content` to `file`
+    fn commit_file(
+        repo: &Repository,
+        parent: git2::Oid,
+        file: &str,
+        content: &str,
+        message: &str,
+    ) -> git2::Oid {
+        let sig = repo.signature().unwrap();
+        let parent = repo.find_commit(parent).unwrap();
+        let blob = repo.blob(content.as_bytes()).unwrap();
+        let mut builder = repo.treebuilder(Some(&parent.tree().unwrap())).unwrap();
+        builder.insert(file, blob, 0o100644).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        repo.commit(None, &sig, &sig, message, &tree, &[&parent])
+            .expect("Could not create commit")
+    }
+
+    #[test]
+    #[serial]
+    fn test_released_cherry_picks() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let released = repo.revparse_single("v1.0.0").unwrap().id();
+        let feature = repo.head().unwrap().target().unwrap();
+
+        // The fix is released from a maintenance branch, then picked into master
+        let original = commit_file(&repo, released, "fix.txt", "fixed\n", "fix: close files");
+        // Rewriting a file unchanged makes an empty commit
+        let bump = commit_file(&repo, original, "fix.txt", "fixed\n", "chore: release");
+        repo.tag_lightweight("v1.0.1", &repo.find_object(bump, None).unwrap(), false)
+            .unwrap();
+        let picked = commit_file(
+            &repo,
+            feature,
+            "fix.txt",
+            "fixed\n",
+            "fix: close files\n\n(cherry picked from commit abc)",
+        );
+        let other = commit_file(&repo, picked, "other.txt", "other\n", "feat: other");
+        let empty = commit_file(&repo, other, "other.txt", "other\n", "chore: empty");
+        repo.reference("refs/heads/master", empty, true, "advance master")
+            .unwrap();
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let cherry_picks = git_repo.released_cherry_picks("master", Some("v1.0.0"), "v1.0.1");
+        let missing = git_repo.released_cherry_picks("master", Some("v1.0.0"), "v9.9.9");
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(
+            cherry_picks.expect("Should compare the branches"),
+            vec!["fix: close files\n\n(cherry picked from commit abc)"]
+        );
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_anonymous_commits() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+
+        // libgit2 refuses empty identities, so write the bot's commit by hand
+        let raw = format!(
+            "tree {}\nparent {}\nauthor  <> 1700000000 +0000\ncommitter  <> 1700000000 +0000\n\nchore: squash",
+            head.tree_id(),
+            head.id()
+        );
+        let empty = repo
+            .odb()
+            .unwrap()
+            .write(git2::ObjectType::Commit, raw.as_bytes())
+            .unwrap();
+        let unknown_sig = git2::Signature::now("unknown", "bot@example.com").unwrap();
+        let unknown = repo
+            .commit(
+                None,
+                &unknown_sig,
+                &unknown_sig,
+                "fix: bot",
+                &head.tree().unwrap(),
+                &[&repo.find_commit(empty).unwrap()],
+            )
+            .unwrap();
+        let named = commit_file(&repo, unknown, "other.txt", "other\n", "feat: other");
+        repo.reference("refs/heads/master", named, true, "advance master")
+            .unwrap();
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let all = git_repo.anonymous_commits("master", Some("v1.0.0"), None, 0);
+        let recent = git_repo.anonymous_commits("master", Some("v1.0.0"), None, 2);
+        let has_identity = git_repo.has_identity();
+        env::set_current_dir(original_dir).unwrap();
+
+        let short = |oid: git2::Oid| oid.to_string()[..7].to_string();
+        assert_eq!(
+            all.expect("Should walk the branch"),
+            vec![short(unknown), short(empty)]
+        );
+        assert_eq!(recent.unwrap(), vec![short(unknown)]);
+        assert!(has_identity);
+    }
+
+    #[test]
+    #[serial]
+    fn test_commits_only_changing_ignored_paths_are_skipped() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let head = repo.head().unwrap().target().unwrap();
+        let generated = commit_file(&repo, head, "schema.gen.rs", "v2\n", "feat: regenerate");
+        let fix = commit_file(&repo, generated, "api.rs", "fixed\n", "fix: api");
+        repo.reference("refs/heads/master", fix, true, "advance master")
+            .unwrap();
+
+        let all = messages_since(&temp_dir, "v1.0.0");
+        fs::write(
+            temp_dir.path().join(".gitpublishignore"),
+            "# generated code\n*.gen.rs\n",
+        )
+        .unwrap();
+        let counted = messages_since(&temp_dir, "v1.0.0");
+
+        assert!(all.contains(&"feat: regenerate".to_string()));
+        assert!(!counted.contains(&"feat: regenerate".to_string()));
+        assert!(counted.contains(&"fix: api".to_string()));
+        assert_eq!(counted.len(), all.len() - 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_path_scope_only_counts_package_commits() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let sig = repo.signature().unwrap();
+        for (file, message) in [
+            ("services/api/main.rs", "feat(api): add endpoint"),
+            ("services/web/app.js", "fix(web): layout"),
+        ] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, message).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(file)).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+                .unwrap();
+        }
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let mut git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        git_repo.set_path_scope(git_publish::path_ignore::PathIgnore::parse(
+            "services/api/**",
+        ));
+        let commits = git_repo.get_commits_since_tag("master", Some("v1.0.0"));
+        env::set_current_dir(original_dir).unwrap();
+
+        let messages: Vec<String> = commits
+            .expect("Should list commits")
+            .iter()
+            .map(|commit| commit.message().unwrap().to_string())
+            .collect();
+        assert_eq!(messages, vec!["feat(api): add endpoint"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_configured_ignore_paths() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let sig = repo.signature().unwrap();
+        for (file, message) in [
+            ("docs/guide.md", "feat: document export"),
+            ("src/export.rs", "fix: export"),
+        ] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, message).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(file)).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+                .unwrap();
+        }
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let mut git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        git_repo.add_ignored_paths(&["docs/**".to_string(), "*.md".to_string()]);
+        let changed = git_repo.changed_files("HEAD~1");
+        let commits = git_repo.get_commits_since_tag("master", Some("v1.0.0"));
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(
+            changed.expect("Should list changed files"),
+            vec![Path::new("docs/guide.md").to_path_buf()]
+        );
+        let messages: Vec<String> = commits
+            .expect("Should list commits")
+            .iter()
+            .map(|commit| commit.message().unwrap().to_string())
+            .collect();
+        assert_eq!(messages, vec!["fix: export"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_ignored_paths_keep_commits_out_of_the_bump() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+        let sig = repo.signature().unwrap();
+        for (files, message) in [
+            (["docs/guide.md", "README.md"], "feat!: rewrite the guide"),
+            (["docs/export.md", "src/export.rs"], "feat: add export"),
+        ] {
+            let mut index = repo.index().unwrap();
+            for file in files {
+                let path = temp_dir.path().join(file);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, message).unwrap();
+                index.add_path(Path::new(file)).unwrap();
+            }
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+                .unwrap();
+        }
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+        let mut git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        git_repo.add_ignored_paths(&["docs/**".to_string(), "*.md".to_string()]);
+        let commits = git_repo.get_commits_since_tag("master", Some("v1.0.0"));
+        env::set_current_dir(original_dir).unwrap();
+
+        // Only documentation changed in the breaking commit, so it is left out;
+        // the export commit also changes code and counts
+        let messages: Vec<String> = commits
+            .expect("Should list commits")
+            .iter()
+            .map(|commit| commit.message().unwrap().to_string())
+            .collect();
+        assert_eq!(messages, vec!["feat: add export"]);
+        assert_eq!(
+            git_publish::domain::commit::analyze_version_bump(
+                &messages,
+                &git_publish::config::ConventionalCommitsConfig::default()
+            ),
+            git_publish::VersionBump::Minor
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_tag_unreachable_after_history_rewrite() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).expect("Could not open repo");
+
+        // Release the feature commit, then amend it away
+        let released = repo.head().unwrap().peel_to_commit().unwrap();
+        let sig = repo.signature().unwrap();
+        repo.tag(
+            "v1.1.0",
+            released.as_object(),
+            &sig,
+            "Release v1.1.0",
+            false,
+        )
+        .expect("Could not create annotated tag");
+        released
+            .amend(Some("HEAD"), None, None, None, Some("feat: reworded"), None)
+            .expect("Could not amend commit");
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change to temp dir");
+
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let rewritten = git_repo.is_tag_reachable("master", "v1.1.0");
+        let ancestor = git_repo.is_tag_reachable("master", "v1.0.0");
+        let missing = git_repo.is_tag_reachable("master", "v9.9.9");
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert!(!rewritten.expect("Should check reachability"));
+        assert!(ancestor.expect("Should check reachability"));
+        assert!(missing.is_err());
+    }
+
     #[test]
     #[serial]
     fn test_get_latest_lightweight_tag_on_branch() {
@@ -499,6 +1277,87 @@ mod git_operations_tests {
         env::set_current_dir(original_dir).unwrap();
     }
 
+    #[test]
+    #[serial]
+    fn test_list_branches_includes_remote_tracking_branches() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let local = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        for name in ["origin/release", "origin/HEAD"] {
+            repo.reference(&format!("refs/remotes/{}", name), head.id(), true, "test")
+                .unwrap();
+        }
+        repo.reference(
+            &format!("refs/remotes/origin/{}", local),
+            head.id(),
+            true,
+            "test",
+        )
+        .unwrap();
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Failed to create GitRepo");
+
+        let mut expected = vec![local.clone(), "release".to_string()];
+        expected.sort();
+        assert_eq!(git_repo.list_branches().unwrap(), expected);
+        assert!(git_repo.branch_exists("release").unwrap());
+        assert!(git_repo.branch_exists(&local).unwrap());
+        assert!(!git_repo.branch_exists("foo").unwrap());
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
+    #[test]
+    #[serial]
+    fn test_commit_file_commits_only_that_file() {
+        let temp_dir = setup_test_repo();
+        let repo = Repository::open(temp_dir.path()).unwrap();
+        let local = repo.head().unwrap().shorthand().unwrap().to_string();
+        let manifest = temp_dir.path().join("Cargo.toml");
+        fs::write(&manifest, "[package]\nversion = \"1.1.0\"\n").unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "draft\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("notes.txt")).unwrap();
+        index.write().unwrap();
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Failed to create GitRepo");
+
+        // Other staged changes must not end up in the release commit
+        let err = git_repo
+            .commit_file(&local, &manifest, "chore(release): v1.1.0")
+            .unwrap_err();
+        assert!(err.to_string().contains("notes.txt"));
+
+        index.remove_path(Path::new("notes.txt")).unwrap();
+        index.write().unwrap();
+        assert!(git_repo
+            .commit_file("other", &manifest, "chore(release): v1.1.0")
+            .is_err());
+        // An ignored lock file stays out of the commit
+        let lock = temp_dir.path().join("Cargo.lock");
+        fs::write(&lock, "version = 3\n").unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "Cargo.lock\n").unwrap();
+        let hash = git_repo
+            .commit_files(&local, &[&manifest, &lock], "chore(release): v1.1.0")
+            .unwrap();
+
+        let commit = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(commit.id().to_string(), hash);
+        assert_eq!(commit.summary(), Some("chore(release): v1.1.0"));
+        assert!(commit.tree().unwrap().get_name("Cargo.toml").is_some());
+        assert!(commit.tree().unwrap().get_name("notes.txt").is_none());
+        assert!(commit.tree().unwrap().get_name("Cargo.lock").is_none());
+
+        env::set_current_dir(original_dir).unwrap();
+    }
+
     #[test]
     #[serial]
     fn test_get_latest_tag_on_remote_branch() {
@@ -893,6 +1752,131 @@ mod fetch_refspec_tests {
 
         env::set_current_dir(original_dir).unwrap();
     }
+
+    #[test]
+    #[serial]
+    fn test_fetch_fast_forwards_other_local_branches() {
+        let origin_dir = TempDir::new().expect("Could not create origin dir");
+        let origin_repo = Repository::init(origin_dir.path()).expect("Could not init origin");
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = origin_repo.index().unwrap().write_tree().unwrap();
+        let tree = origin_repo.find_tree(tree_id).unwrap();
+        let initial = origin_repo
+            .commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+        let initial = origin_repo.find_commit(initial).unwrap();
+        origin_repo.branch("develop", &initial, false).unwrap();
+        origin_repo.branch("gray", &initial, false).unwrap();
+
+        let work_dir = TempDir::new().expect("Could not create work dir");
+        let work_repo = Repository::clone(origin_dir.path().to_str().unwrap(), work_dir.path())
+            .expect("Could not clone repo");
+        let cloned_branch = work_repo.head().unwrap().shorthand().unwrap().to_string();
+        work_repo
+            .branch(
+                "develop",
+                &work_repo.find_commit(initial.id()).unwrap(),
+                false,
+            )
+            .unwrap();
+
+        // New commits on origin's develop and gray after the clone
+        let mut heads = Vec::new();
+        for branch in ["develop", "gray"] {
+            let head = origin_repo
+                .commit(
+                    Some(&format!("refs/heads/{}", branch)),
+                    &sig,
+                    &sig,
+                    &format!("fix: on {}", branch),
+                    &tree,
+                    &[&initial],
+                )
+                .unwrap();
+            heads.push(head);
+        }
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(work_dir.path()).expect("Could not change to work dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+        let fetch_result = git_repo.fetch_from_remote_with_branches(
+            "origin",
+            &cloned_branch,
+            &["develop".to_string(), "gray".to_string()],
+        );
+        env::set_current_dir(original_dir).unwrap();
+
+        fetch_result.expect("Fetch should succeed");
+        let develop = work_repo
+            .find_branch("develop", git2::BranchType::Local)
+            .unwrap();
+        assert_eq!(develop.get().target(), Some(heads[0]));
+        // Other branches are only updated, never created
+        assert!(work_repo
+            .find_branch("gray", git2::BranchType::Local)
+            .is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_fetch_moves_the_checked_out_branch_with_its_working_tree() {
+        let origin_dir = TempDir::new().expect("Could not create origin dir");
+        let origin_repo = Repository::init(origin_dir.path()).expect("Could not init origin");
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let commit_file = |content: &str, parents: &[&git2::Commit]| {
+            let blob = origin_repo.blob(content.as_bytes()).unwrap();
+            let mut builder = origin_repo.treebuilder(None).unwrap();
+            builder.insert("f", blob, 0o100644).unwrap();
+            let tree = origin_repo.find_tree(builder.write().unwrap()).unwrap();
+            let oid = origin_repo
+                .commit(Some("HEAD"), &sig, &sig, "fix: change f", &tree, parents)
+                .unwrap();
+            origin_repo.find_commit(oid).unwrap()
+        };
+        let initial = commit_file("one\n", &[]);
+
+        let work_dir = TempDir::new().expect("Could not create work dir");
+        let work_repo = Repository::clone(origin_dir.path().to_str().unwrap(), work_dir.path())
+            .expect("Could not clone repo");
+        let checked_out = work_repo.head().unwrap().shorthand().unwrap().to_string();
+        let others = [checked_out.clone()];
+        let head = commit_file("two\n", &[&initial]);
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(work_dir.path()).expect("Could not change to work dir");
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Could not create GitRepo");
+
+        // A dry run's fetch leaves the branch alone
+        git_repo.fetch_remote_refs("origin").unwrap();
+        let branch_target = |repo: &Repository| {
+            repo.find_branch(&checked_out, git2::BranchType::Local)
+                .unwrap()
+                .get()
+                .target()
+        };
+        assert_eq!(branch_target(&work_repo), Some(initial.id()));
+
+        // With local changes, the checked-out branch isn't moved
+        fs::write(work_dir.path().join("f"), "local\n").unwrap();
+        git_repo
+            .fetch_from_remote_with_branches("origin", "develop", &others)
+            .unwrap();
+        assert_eq!(branch_target(&work_repo), Some(initial.id()));
+
+        // A clean one moves along with its index and working tree
+        fs::write(work_dir.path().join("f"), "one\n").unwrap();
+        git_repo
+            .fetch_from_remote_with_branches("origin", "develop", &others)
+            .unwrap();
+        env::set_current_dir(original_dir).unwrap();
+
+        assert_eq!(branch_target(&work_repo), Some(head.id()));
+        assert_eq!(
+            fs::read_to_string(work_dir.path().join("f")).unwrap(),
+            "two\n"
+        );
+        assert!(work_repo.statuses(None).unwrap().is_empty());
+    }
 }
 
 #[cfg(test)]
@@ -1043,6 +2027,221 @@ mod remote_selection_tests {
             "Push will fail with fake remote, which is expected"
         );
     }
+
+    #[test]
+    #[serial]
+    fn test_push_tag_with_override_uses_push_url_and_refspec() {
+        use git_publish::config::RemoteOverride;
+
+        let bare_dir = TempDir::new().expect("Failed to create bare dir");
+        let bare_repo = Repository::init_bare(bare_dir.path()).expect("Failed to init bare repo");
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo = Repository::init(temp_dir.path()).expect("Failed to init repo");
+
+        // The configured URL is unreachable; the override must be used instead
+        repo.remote("origin", "https://invalid.example.invalid/repo.git")
+            .expect("Failed to add remote");
+
+        let sig = repo.signature().expect("Could not get signature");
+        let tree_id = repo
+            .index()
+            .expect("Could not get index")
+            .write_tree()
+            .expect("Could not write tree");
+        let tree = repo.find_tree(tree_id).expect("Could not find tree");
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .expect("Could not create commit");
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change dir");
+
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Failed to create GitRepo");
+        git_repo
+            .create_tag("v1.0.0", None)
+            .expect("Failed to create tag");
+
+        let remote_override = RemoteOverride {
+            push_url: Some(bare_dir.path().to_str().unwrap().to_string()),
+            tag_refspec: "refs/tags/{tag}:refs/releases/{tag}".to_string(),
+        };
+        let result = git_repo.push_tag_with_override("v1.0.0", "origin", Some(&remote_override));
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok(), "Push with override failed: {:?}", result);
+        assert!(bare_repo.find_reference("refs/releases/v1.0.0").is_ok());
+        assert!(bare_repo.find_reference("refs/tags/v1.0.0").is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_push_tags_pushes_compound_release_together() {
+        let bare_dir = TempDir::new().expect("Failed to create bare dir");
+        let bare_repo = Repository::init_bare(bare_dir.path()).expect("Failed to init bare repo");
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo = Repository::init(temp_dir.path()).expect("Failed to init repo");
+        repo.remote("origin", bare_dir.path().to_str().unwrap())
+            .expect("Failed to add remote");
+
+        let sig = repo.signature().expect("Could not get signature");
+        let tree_id = repo
+            .index()
+            .expect("Could not get index")
+            .write_tree()
+            .expect("Could not write tree");
+        let tree = repo.find_tree(tree_id).expect("Could not find tree");
+        let commit = repo
+            .commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .expect("Could not create commit");
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change dir");
+
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Failed to create GitRepo");
+        git_repo
+            .create_tag("v1.0.0", None)
+            .expect("Failed to create tag");
+        git_repo
+            .create_tag("g1.0.0", None)
+            .expect("Failed to create tag");
+        let result = git_repo.push_tags_with_options(&["v1.0.0", "g1.0.0"], "origin", None, false);
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert!(result.is_ok(), "Push of both tags failed: {:?}", result);
+        for tag in ["refs/tags/v1.0.0", "refs/tags/g1.0.0"] {
+            let pushed = bare_repo
+                .find_reference(tag)
+                .and_then(|reference| reference.peel_to_commit())
+                .expect("Tag missing on the remote");
+            assert_eq!(pushed.id(), commit);
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_delete_remote_tag() {
+        let bare_dir = TempDir::new().expect("Failed to create bare dir");
+        let bare_repo = Repository::init_bare(bare_dir.path()).expect("Failed to init bare repo");
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo = Repository::init(temp_dir.path()).expect("Failed to init repo");
+        repo.remote("origin", bare_dir.path().to_str().unwrap())
+            .expect("Failed to add remote");
+
+        let sig = repo.signature().expect("Could not get signature");
+        let tree_id = repo
+            .index()
+            .expect("Could not get index")
+            .write_tree()
+            .expect("Could not write tree");
+        let tree = repo.find_tree(tree_id).expect("Could not find tree");
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .expect("Could not create commit");
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change dir");
+
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Failed to create GitRepo");
+        git_repo
+            .create_tag("v1.0.0", None)
+            .expect("Failed to create tag");
+        git_repo
+            .push_tag_with_options("v1.0.0", "origin", None, false)
+            .expect("Failed to push tag");
+        let pushed = bare_repo.find_reference("refs/tags/v1.0.0").is_ok();
+        let result = git_repo.delete_remote_tag("v1.0.0", "origin", None);
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert!(pushed);
+        assert!(result.is_ok(), "Deleting the tag failed: {:?}", result);
+        assert!(bare_repo.find_reference("refs/tags/v1.0.0").is_err());
+        // The local tag is kept
+        assert!(repo.find_reference("refs/tags/v1.0.0").is_ok());
+    }
+
+    #[test]
+    #[serial]
+    fn test_push_tag_applies_insteadof_rewrite() {
+        let remote_root = TempDir::new().expect("Failed to create remote root");
+        let bare_path = remote_root.path().join("app.git");
+        let bare_repo = Repository::init_bare(&bare_path).expect("Failed to init bare repo");
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo = Repository::init(temp_dir.path()).expect("Failed to init repo");
+        repo.remote("origin", "fake:app.git")
+            .expect("Failed to add remote");
+        {
+            let mut config = repo.config().expect("Could not get config");
+            config
+                .set_str(
+                    &format!("url.{}/.insteadOf", remote_root.path().display()),
+                    "fake:",
+                )
+                .expect("Could not set insteadOf");
+        }
+
+        let sig = repo.signature().expect("Could not get signature");
+        let tree_id = repo
+            .index()
+            .expect("Could not get index")
+            .write_tree()
+            .expect("Could not write tree");
+        let tree = repo.find_tree(tree_id).expect("Could not find tree");
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .expect("Could not create commit");
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change dir");
+
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Failed to create GitRepo");
+        git_repo
+            .create_tag("v1.0.0", None)
+            .expect("Failed to create tag");
+        let result = git_repo.push_tag("v1.0.0", "origin");
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert!(
+            result.is_ok(),
+            "Push through insteadOf failed: {:?}",
+            result
+        );
+        assert!(bare_repo.find_reference("refs/tags/v1.0.0").is_ok());
+    }
+
+    #[test]
+    #[serial]
+    fn test_check_remote_connection() {
+        let remote_root = TempDir::new().expect("Failed to create remote root");
+        let bare_path = remote_root.path().join("app.git");
+        Repository::init_bare(&bare_path).expect("Failed to init bare repo");
+
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let repo = Repository::init(temp_dir.path()).expect("Failed to init repo");
+        repo.remote("origin", bare_path.to_str().unwrap())
+            .expect("Failed to add remote");
+        repo.remote(
+            "broken",
+            remote_root.path().join("missing.git").to_str().unwrap(),
+        )
+        .expect("Failed to add remote");
+
+        let original_dir = env::current_dir().unwrap();
+        env::set_current_dir(temp_dir.path()).expect("Could not change dir");
+
+        let git_repo = git_publish::git_ops::GitRepo::new().expect("Failed to create GitRepo");
+        let ok = git_repo.check_remote_connection("origin", None);
+        let broken = git_repo.check_remote_connection("broken", None);
+
+        env::set_current_dir(original_dir).unwrap();
+
+        assert!(ok.is_ok(), "Connection to bare repo failed: {:?}", ok);
+        assert!(broken.is_err(), "Connection to missing repo should fail");
+    }
 }
 
 #[cfg(test)]
@@ -1053,7 +2252,7 @@ mod cli_remote_flag_tests {
     #[serial]
     fn test_cli_accepts_remote_flag() {
         let output = std::process::Command::new("cargo")
-            .args(&["run", "--", "--help"])
+            .args(["run", "--", "--help"])
             .output()
             .expect("Failed to run help");
 
@@ -1074,7 +2273,7 @@ mod cli_remote_flag_tests {
         // Test that the --remote flag is properly parsed by clap
         // We verify the flag appears in help and can be parsed
         let output = std::process::Command::new("cargo")
-            .args(&["run", "--", "--help"])
+            .args(["run", "--", "--help"])
             .output()
             .expect("Failed to run help");
 
@@ -1095,6 +2294,7 @@ mod cli_remote_flag_tests {
 }
 
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants)]
 mod remote_selection_precedence_tests {
     #[test]
     fn test_cli_remote_takes_precedence_over_config() {